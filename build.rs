@@ -0,0 +1,15 @@
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    #[cfg(feature = "ffi-header")]
+    {
+        println!("cargo:rerun-if-changed=cbindgen.toml");
+        println!("cargo:rerun-if-changed=src/ffi.rs");
+        let crate_dir = std::env::var("CARGO_MANIFEST_DIR").unwrap();
+        let out_dir = std::path::PathBuf::from(std::env::var("OUT_DIR").unwrap());
+        let config = cbindgen::Config::from_file(format!("{crate_dir}/cbindgen.toml"))
+            .expect("unable to read cbindgen.toml");
+        cbindgen::generate_with_config(&crate_dir, config)
+            .expect("unable to generate C header")
+            .write_to_file(out_dir.join("fp.h"));
+    }
+}
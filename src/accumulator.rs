@@ -0,0 +1,78 @@
+use core::marker::PhantomData;
+
+use crate::raw::signed_bits;
+use crate::{Num, RangeError, I128};
+
+/// Running total of up to `2^CAPACITY` values of `T`, e.g. a long-running integrator or
+/// block average, which can never overflow.
+///
+/// The total is kept in an `i128` accumulator, and has `CAPACITY` more bits than `T`
+/// (plus a sign bit if `T` is unsigned).  It is a compile error to instantiate an
+/// `Accumulator` whose total could overflow `i128`.  Adding more than `2^CAPACITY` values
+/// is a runtime error, which costs only the comparison of a counter.
+#[derive(Clone, Debug)]
+pub struct Accumulator<T: Num, const CAPACITY: u32> {
+    _type: PhantomData<T>,
+    count: u128,
+    total: i128,
+}
+
+impl<T: Num, const CAPACITY: u32> Accumulator<T, CAPACITY>
+where
+    T::Raw: Into<i128>,
+{
+    /// Maximum number of values.  Evaluating this constant also checks at compile time
+    /// that the total fits in `i128`.
+    pub const MAX_COUNT: u128 = {
+        assert!(
+            signed_bits::<T>() + CAPACITY <= i128::BITS,
+            "too many values or bits for Accumulator"
+        );
+        1 << CAPACITY
+    };
+    /// Create an empty accumulator, whose total is 0.
+    pub fn new() -> Self {
+        let _ = Self::MAX_COUNT; // force the compile-time check of the accumulator width
+        Self {
+            _type: PhantomData,
+            count: 0,
+            total: 0,
+        }
+    }
+    /// Return the number of values added so far.
+    pub fn count(&self) -> u128 {
+        self.count
+    }
+    /// Return the number of values which can still be added.
+    pub fn remaining(&self) -> u128 {
+        Self::MAX_COUNT - self.count
+    }
+    /// Add `val` to the total.  Return `RangeError::TooLarge`, without adding the value, if
+    /// `2^CAPACITY` values have already been added.
+    pub fn add(&mut self, val: T) -> Result<(), RangeError> {
+        if self.count >= Self::MAX_COUNT {
+            return Err(RangeError::TooLarge);
+        }
+        self.count += 1;
+        self.total += val.raw().into();
+        Ok(())
+    }
+    /// Return the total, which has the same shift as `T`.
+    pub fn total(&self) -> I128<{ signed_bits::<T>() + CAPACITY }, { T::SHIFT }> {
+        unsafe { I128::new_unchecked(self.total) }
+    }
+    /// Reset the total and the count to 0.
+    pub fn reset(&mut self) {
+        self.count = 0;
+        self.total = 0;
+    }
+}
+
+impl<T: Num, const CAPACITY: u32> Default for Accumulator<T, CAPACITY>
+where
+    T::Raw: Into<i128>,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
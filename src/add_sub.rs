@@ -12,6 +12,26 @@ pub const fn max(a: u32, b: u32) -> u32 {
     }
 }
 
+/// Needed for const-generic support, because the built-in
+/// methods to compute minimum of two values are not const.
+pub const fn min(a: u32, b: u32) -> u32 {
+    if a < b {
+        a
+    } else {
+        b
+    }
+}
+
+/// Needed for const-generic support: the number of extra bits for a sum of `n` terms,
+/// i.e. `log2(n)` rounded up.
+pub const fn ceil_log2(n: usize) -> u32 {
+    if n > 1 {
+        (n - 1).ilog2() + 1
+    } else {
+        0
+    }
+}
+
 macro_rules! fp_impl {
     ($Name:ident, $Iname:ident) => {
         use crate::$Name;
@@ -52,6 +72,50 @@ macro_rules! fp_impl {
                 }
             }
         }
+        impl<const B: u32, const S: i32> $Name<B, S> {
+            /// Return the midpoint `(self + other) / 2`, rounded toward negative infinity.
+            /// This never overflows, and the result has the same type as the inputs, since
+            /// it lies between them.
+            pub fn midpoint(self, other: Self) -> Self {
+                let (a, b) = (self.raw(), other.raw());
+                unsafe { Self::new_unchecked((a & b) + ((a ^ b) >> 1)) }
+            }
+        }
+        impl<const B0: u32, const S: i32> $Name<B0, S> {
+            /// Return the lesser of `self` and `other`, which may have different bits but
+            /// the same shift, unlike `Ord::min`.  The result has the bits of the wider of
+            /// the two inputs.
+            pub fn min_fp<const B1: u32>(self, other: $Name<B1, S>) -> $Name<{ max(B0, B1) }, S>
+            where
+                [(); max(B0, B1) as usize]:,
+            {
+                unsafe { $Name::new_unchecked(self.raw().min(other.raw())) }
+            }
+            /// Return the greater of `self` and `other`, which may have different bits but
+            /// the same shift, unlike `Ord::max`.  The result has the bits of the wider of
+            /// the two inputs.
+            pub fn max_fp<const B1: u32>(self, other: $Name<B1, S>) -> $Name<{ max(B0, B1) }, S>
+            where
+                [(); max(B0, B1) as usize]:,
+            {
+                unsafe { $Name::new_unchecked(self.raw().max(other.raw())) }
+            }
+            /// Restrict `self` to the interval `[low, high]`, whose bounds may have different
+            /// bits but the same shift, e.g. to limit a wide intermediate to a narrow output
+            /// range.  The result has the bits of the wider bound, regardless of the bits of
+            /// `self`.  Panics if `low > high`, like `Ord::clamp`, which requires the same
+            /// type for all three.
+            pub fn clamp_fp<const B1: u32, const B2: u32>(
+                self,
+                low: $Name<B1, S>,
+                high: $Name<B2, S>,
+            ) -> $Name<{ max(B1, B2) }, S>
+            where
+                [(); max(B1, B2) as usize]:,
+            {
+                unsafe { $Name::new_unchecked(self.raw().clamp(low.raw(), high.raw())) }
+            }
+        }
         /// Any fixed-point integer may be negated.  The result of negation is always
         /// signed.  Negation adds a bit: unsigned values gain a sign bit; signed values
         /// can overflow from `MIN` to `-MIN = MAX + 1`.  The shift is unchanged.
@@ -79,3 +143,203 @@ fp_impl!(U128, I128);
 fp_impl!(I128, I128);
 fp_impl!(Usize, Isize);
 fp_impl!(Isize, Isize);
+
+macro_rules! fp_signed_unsigned_impl {
+    ($Uname:ident, $Iname:ident) => {
+        impl<const B0: u32, const S: i32> $Iname<B0, S> {
+            /// Return the absolute difference `|self - other|` as unsigned, like `abs_diff`
+            /// on the primitive integers.  The result has as many bits as the wider input.
+            pub fn abs_diff<const B1: u32>(self, other: $Iname<B1, S>) -> $Uname<{ max(B0, B1) }, S>
+            where
+                [(); max(B0, B1) as usize]:,
+            {
+                unsafe { $Uname::new_unchecked(self.raw().abs_diff(other.raw())) }
+            }
+        }
+        impl<const B0: u32, const S: i32> $Uname<B0, S> {
+            /// Return the absolute difference `|self - other|`, like `abs_diff` on the
+            /// primitive integers.  The result has as many bits as the wider input.
+            pub fn abs_diff<const B1: u32>(self, other: $Uname<B1, S>) -> $Uname<{ max(B0, B1) }, S>
+            where
+                [(); max(B0, B1) as usize]:,
+            {
+                unsafe { $Uname::new_unchecked(self.raw().abs_diff(other.raw())) }
+            }
+        }
+        /// A signed and an unsigned fixed-point integer with the same raw width and the
+        /// same shift may be added together.  The result is signed, with the same shift.
+        /// The unsigned input counts as one bit wider (for its sign bit), and the result
+        /// has 1 more bit than the wider of the two inputs.
+        impl<const B0: u32, const B1: u32, const S: i32> Add<$Uname<B1, S>> for $Iname<B0, S>
+        where
+            [(); (max(B0, B1 + 1) + 1) as usize]:,
+        {
+            type Output = $Iname<{ max(B0, B1 + 1) + 1 }, S>;
+            fn add(self: $Iname<B0, S>, other: $Uname<B1, S>) -> Self::Output {
+                let other_raw = other.raw() as <Self::Output as Num>::Raw;
+                unsafe { Self::Output::new_unchecked(self.raw().unchecked_add(other_raw)) }
+            }
+        }
+        impl<const B0: u32, const B1: u32, const S: i32> Add<$Iname<B1, S>> for $Uname<B0, S>
+        where
+            [(); (max(B0 + 1, B1) + 1) as usize]:,
+        {
+            type Output = $Iname<{ max(B0 + 1, B1) + 1 }, S>;
+            fn add(self: $Uname<B0, S>, other: $Iname<B1, S>) -> Self::Output {
+                let self_raw = self.raw() as <Self::Output as Num>::Raw;
+                unsafe { Self::Output::new_unchecked(self_raw.unchecked_add(other.raw())) }
+            }
+        }
+        /// A signed and an unsigned fixed-point integer with the same raw width and the
+        /// same shift may be subtracted, in either order, with the same result type as
+        /// their sum.
+        impl<const B0: u32, const B1: u32, const S: i32> Sub<$Uname<B1, S>> for $Iname<B0, S>
+        where
+            [(); (max(B0, B1 + 1) + 1) as usize]:,
+        {
+            type Output = $Iname<{ max(B0, B1 + 1) + 1 }, S>;
+            fn sub(self: $Iname<B0, S>, other: $Uname<B1, S>) -> Self::Output {
+                let other_raw = other.raw() as <Self::Output as Num>::Raw;
+                unsafe { Self::Output::new_unchecked(self.raw().unchecked_sub(other_raw)) }
+            }
+        }
+        impl<const B0: u32, const B1: u32, const S: i32> Sub<$Iname<B1, S>> for $Uname<B0, S>
+        where
+            [(); (max(B0 + 1, B1) + 1) as usize]:,
+        {
+            type Output = $Iname<{ max(B0 + 1, B1) + 1 }, S>;
+            fn sub(self: $Uname<B0, S>, other: $Iname<B1, S>) -> Self::Output {
+                let self_raw = self.raw() as <Self::Output as Num>::Raw;
+                unsafe { Self::Output::new_unchecked(self_raw.unchecked_sub(other.raw())) }
+            }
+        }
+    };
+}
+
+fp_signed_unsigned_impl!(U8, I8);
+fp_signed_unsigned_impl!(U16, I16);
+fp_signed_unsigned_impl!(U32, I32);
+fp_signed_unsigned_impl!(U64, I64);
+fp_signed_unsigned_impl!(U128, I128);
+fp_signed_unsigned_impl!(Usize, Isize);
+
+macro_rules! fp_cross_impl {
+    ($Narrow:ident, $Wide:ident, $Iwide:ident) => {
+        /// Fixed-point integers with different raw types and the same signedness and shift
+        /// may be added together.  The narrower input is widened, and the result has the
+        /// wider raw type, with 1 more bit than the wider of the two inputs.
+        impl<const B0: u32, const B1: u32, const S: i32> Add<$Wide<B1, S>> for $Narrow<B0, S>
+        where
+            [(); (max(B0, B1) + 1) as usize]:,
+        {
+            type Output = $Wide<{ max(B0, B1) + 1 }, S>;
+            fn add(self: $Narrow<B0, S>, other: $Wide<B1, S>) -> Self::Output {
+                let self_raw = self.raw() as <Self::Output as Num>::Raw;
+                unsafe { Self::Output::new_unchecked(self_raw.unchecked_add(other.raw())) }
+            }
+        }
+        impl<const B0: u32, const B1: u32, const S: i32> Add<$Narrow<B1, S>> for $Wide<B0, S>
+        where
+            [(); (max(B0, B1) + 1) as usize]:,
+        {
+            type Output = $Wide<{ max(B0, B1) + 1 }, S>;
+            fn add(self: $Wide<B0, S>, other: $Narrow<B1, S>) -> Self::Output {
+                let other_raw = other.raw() as <Self::Output as Num>::Raw;
+                unsafe { Self::Output::new_unchecked(self.raw().unchecked_add(other_raw)) }
+            }
+        }
+        /// Fixed-point integers with different raw types and the same signedness and shift
+        /// may be subtracted.  The result is always signed, with the wider raw type, and 1
+        /// more bit than the wider of the two inputs.
+        impl<const B0: u32, const B1: u32, const S: i32> Sub<$Wide<B1, S>> for $Narrow<B0, S>
+        where
+            [(); (max(B0, B1) + 1) as usize]:,
+        {
+            type Output = $Iwide<{ max(B0, B1) + 1 }, S>;
+            fn sub(self: $Narrow<B0, S>, other: $Wide<B1, S>) -> Self::Output {
+                let self_raw = self.raw() as <Self::Output as Num>::Raw;
+                let other_raw = other.raw() as <Self::Output as Num>::Raw;
+                unsafe { Self::Output::new_unchecked(self_raw.unchecked_sub(other_raw)) }
+            }
+        }
+        impl<const B0: u32, const B1: u32, const S: i32> Sub<$Narrow<B1, S>> for $Wide<B0, S>
+        where
+            [(); (max(B0, B1) + 1) as usize]:,
+        {
+            type Output = $Iwide<{ max(B0, B1) + 1 }, S>;
+            fn sub(self: $Wide<B0, S>, other: $Narrow<B1, S>) -> Self::Output {
+                let self_raw = self.raw() as <Self::Output as Num>::Raw;
+                let other_raw = other.raw() as <Self::Output as Num>::Raw;
+                unsafe { Self::Output::new_unchecked(self_raw.unchecked_sub(other_raw)) }
+            }
+        }
+    };
+}
+
+fp_cross_impl!(U8, U16, I16);
+fp_cross_impl!(I8, I16, I16);
+fp_cross_impl!(U8, U32, I32);
+fp_cross_impl!(I8, I32, I32);
+fp_cross_impl!(U8, U64, I64);
+fp_cross_impl!(I8, I64, I64);
+fp_cross_impl!(U8, U128, I128);
+fp_cross_impl!(I8, I128, I128);
+fp_cross_impl!(U16, U32, I32);
+fp_cross_impl!(I16, I32, I32);
+fp_cross_impl!(U16, U64, I64);
+fp_cross_impl!(I16, I64, I64);
+fp_cross_impl!(U16, U128, I128);
+fp_cross_impl!(I16, I128, I128);
+fp_cross_impl!(U32, U64, I64);
+fp_cross_impl!(I32, I64, I64);
+fp_cross_impl!(U32, U128, I128);
+fp_cross_impl!(I32, I128, I128);
+fp_cross_impl!(U64, U128, I128);
+fp_cross_impl!(I64, I128, I128);
+
+macro_rules! fp_sign_impl {
+    ($Name:ident) => {
+        impl<const B: u32, const S: i32> $Name<B, S> {
+            /// Return the absolute value.  Like negation, this adds a bit, so that `MIN` has
+            /// an absolute value `-MIN = MAX + 1`.  The shift is unchanged.
+            pub fn abs(self) -> $Name<{ B + 1 }, S>
+            where
+                [(); (B + 1) as usize]:,
+            {
+                unsafe { $Name::new_unchecked(self.raw().abs()) }
+            }
+            /// Return the absolute value with the same number of bits, saturating `MIN` to
+            /// `MAX`.
+            pub fn saturating_abs(self) -> Self {
+                if self == Self::MIN {
+                    Self::MAX
+                } else {
+                    unsafe { Self::new_unchecked(self.raw().abs()) }
+                }
+            }
+            /// Return -1, 0 or 1 for a negative, zero or positive value, as an integer.
+            pub fn signum(self) -> $Name<2, 0> {
+                unsafe { $Name::new_unchecked(self.raw().signum()) }
+            }
+            /// Return a value with the magnitude of `self` and the sign of `other`, where zero
+            /// counts as positive.  Like `abs`, this adds a bit for the magnitude of `MIN`.
+            pub fn copysign<const B1: u32, const S1: i32>(
+                self,
+                other: $Name<B1, S1>,
+            ) -> $Name<{ B + 1 }, S>
+            where
+                [(); (B + 1) as usize]:,
+            {
+                let abs = self.raw().abs();
+                unsafe { $Name::new_unchecked(if other.raw() < 0 { -abs } else { abs }) }
+            }
+        }
+    };
+}
+
+fp_sign_impl!(I8);
+fp_sign_impl!(I16);
+fp_sign_impl!(I32);
+fp_sign_impl!(I64);
+fp_sign_impl!(I128);
+fp_sign_impl!(Isize);
@@ -0,0 +1,297 @@
+//! Range analysis of fixed-point expressions, to find the minimal `BITS` needed at each
+//! step of a computation.
+//!
+//! The types in this crate guarantee that no operation can overflow, by giving every
+//! result enough bits for the worst case of its input types.  When the actual inputs
+//! occupy only part of the range of their types, the worst case is pessimistic, and some
+//! of those bits are wasted headroom.  The [`fp_analyze!`](crate::fp_analyze) macro
+//! propagates declared input ranges through an expression, and reports for every node both
+//! the `BITS` of the type which this crate assigns to it and the minimal `BITS` which would
+//! hold the values which can actually occur there.
+//!
+//! The analysis runs in a const context, so it can be used to generate a constant report,
+//! or to fail compilation when the headroom at any node exceeds a budget (see
+//! [`Analysis::assert_headroom_at_most`]).
+
+use core::fmt;
+
+use crate::num_impl::runtime_limits;
+use crate::Num;
+
+/// The range of values at one node of an analyzed expression.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Node {
+    /// The source text of the node.
+    pub expr: &'static str,
+    /// The smallest raw value which can occur at this node.
+    pub min: i128,
+    /// The largest raw value which can occur at this node.
+    pub max: i128,
+    /// The shift of the type at this node.
+    pub shift: i32,
+    /// Whether the type at this node is signed.
+    pub signed: bool,
+    /// The `BITS` of the type at this node: the declared type of an input, or the output
+    /// type of an operation.
+    pub bits: u32,
+}
+
+/// Return `2^exp` as a float, for `|exp| <= 1022`.
+const fn pow2(exp: i32) -> f64 {
+    assert!(exp.abs() <= 1022, "shift out of range for analysis");
+    f64::from_bits(((exp + 1023) as u64) << 52)
+}
+
+impl Node {
+    /// An input of type `T` whose logical values lie in `[min, max]`.  Panics if the
+    /// declared range does not fit in `T`.
+    pub const fn input<T: Num>(expr: &'static str, min: f64, max: f64) -> Self {
+        assert!(min <= max, "empty input range");
+        assert!(T::BITS < 128, "input type is too wide for analysis");
+        let scale = pow2(T::SHIFT);
+        let (lo, hi) = runtime_limits(T::BITS, T::SIGNED);
+        let (min, max) = ((min * scale).floor(), (max * scale).ceil());
+        assert!(
+            min >= lo as f64 && max <= hi as f64,
+            "declared range does not fit the input type"
+        );
+        Self {
+            expr,
+            min: min as i128,
+            max: max as i128,
+            shift: T::SHIFT,
+            signed: T::SIGNED,
+            bits: T::BITS,
+        }
+    }
+    /// Return the minimal `BITS` for a type with this signedness which holds every value
+    /// which can occur at this node.
+    pub const fn needed_bits(&self) -> u32 {
+        if self.signed {
+            let magnitude = if self.max > -self.min - 1 {
+                self.max
+            } else {
+                -self.min - 1
+            };
+            i128::BITS - magnitude.leading_zeros() + 1
+        } else {
+            i128::BITS - self.max.leading_zeros()
+        }
+    }
+    /// Return the number of bits of the type at this node which can never be used.
+    pub const fn headroom(&self) -> u32 {
+        self.bits.saturating_sub(self.needed_bits())
+    }
+    /// Return the logical range `(min, max)` at this node.
+    pub const fn range(&self) -> (f64, f64) {
+        let scale = pow2(-self.shift);
+        (self.min as f64 * scale, self.max as f64 * scale)
+    }
+    const fn check_compatible(&self, other: &Self) {
+        assert!(
+            self.signed == other.signed,
+            "operands must have the same signedness"
+        );
+    }
+    /// The sum `self + other`, which has 1 more bit than the wider operand.
+    pub const fn add(self, other: Self, expr: &'static str) -> Self {
+        self.check_compatible(&other);
+        assert!(
+            self.shift == other.shift,
+            "operands must have the same shift"
+        );
+        Self {
+            expr,
+            min: self.min + other.min,
+            max: self.max + other.max,
+            shift: self.shift,
+            signed: self.signed,
+            bits: crate::add_sub::max(self.bits, other.bits) + 1,
+        }
+    }
+    /// The difference `self - other`, which is signed, and has 1 more bit than the wider
+    /// operand.
+    pub const fn sub(self, other: Self, expr: &'static str) -> Self {
+        self.check_compatible(&other);
+        assert!(
+            self.shift == other.shift,
+            "operands must have the same shift"
+        );
+        Self {
+            expr,
+            min: self.min - other.max,
+            max: self.max - other.min,
+            shift: self.shift,
+            signed: true,
+            bits: crate::add_sub::max(self.bits, other.bits) + 1,
+        }
+    }
+    /// The negation `-self`, which is signed, and has 1 more bit than the operand.
+    pub const fn neg(self, expr: &'static str) -> Self {
+        Self {
+            expr,
+            min: -self.max,
+            max: -self.min,
+            shift: self.shift,
+            signed: true,
+            bits: self.bits + 1,
+        }
+    }
+    /// The product `self * other`, which has the sum of the bits and the sum of the shifts
+    /// of the operands.
+    pub const fn mul(self, other: Self, expr: &'static str) -> Self {
+        self.check_compatible(&other);
+        let products = [
+            self.min * other.min,
+            self.min * other.max,
+            self.max * other.min,
+            self.max * other.max,
+        ];
+        let (mut min, mut max) = (products[0], products[0]);
+        let mut i = 1;
+        while i < products.len() {
+            if products[i] < min {
+                min = products[i];
+            }
+            if products[i] > max {
+                max = products[i];
+            }
+            i += 1;
+        }
+        Self {
+            expr,
+            min,
+            max,
+            shift: self.shift + other.shift,
+            signed: self.signed,
+            bits: self.bits + other.bits,
+        }
+    }
+}
+
+/// Maximum number of nodes (inputs and operations) in an analyzed expression.
+pub const MAX_NODES: usize = 32;
+
+/// The result of [`fp_analyze!`](crate::fp_analyze): the inputs, followed by every
+/// operation of the expression in evaluation order.  The last node is the result.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Analysis {
+    nodes: [Node; MAX_NODES],
+    len: usize,
+}
+
+impl Default for Analysis {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Analysis {
+    /// An empty analysis.
+    pub const fn new() -> Self {
+        const EMPTY: Node = Node {
+            expr: "",
+            min: 0,
+            max: 0,
+            shift: 0,
+            signed: false,
+            bits: 0,
+        };
+        Self {
+            nodes: [EMPTY; MAX_NODES],
+            len: 0,
+        }
+    }
+    /// Append a node, and return it.
+    pub const fn push(&mut self, node: Node) -> Node {
+        assert!(
+            self.len < MAX_NODES,
+            "too many nodes in analyzed expression"
+        );
+        self.nodes[self.len] = node;
+        self.len += 1;
+        node
+    }
+    /// Return the nodes of the analysis.
+    pub const fn nodes(&self) -> &[Node] {
+        self.nodes.split_at(self.len).0
+    }
+    /// Panics (at compile time, in a const context) if any node has more than `bits` bits
+    /// of headroom.  The panic message is the source text of the first such node.
+    pub const fn assert_headroom_at_most(&self, bits: u32) {
+        let mut i = 0;
+        while i < self.len {
+            if self.nodes[i].headroom() > bits {
+                panic!("{}", self.nodes[i].expr);
+            }
+            i += 1;
+        }
+    }
+}
+
+/// Formats the analysis as a table, with one line per node.
+impl fmt::Display for Analysis {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for node in self.nodes() {
+            let (min, max) = node.range();
+            writeln!(
+                f,
+                "{}: BITS = {}, needed = {}, headroom = {}, range = [{min}, {max}]",
+                node.expr,
+                node.bits,
+                node.needed_bits(),
+                node.headroom(),
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Analyze the ranges of the nodes of a fixed-point expression, given the types and ranges
+/// of its inputs.  Returns an [`Analysis`](crate::analyze::Analysis); see the
+/// [`analyze`](crate::analyze) module.
+///
+/// Inputs are declared as `name: Type = min..=max`, separated by commas, where `min` and
+/// `max` are numeric literals giving the logical range.  The expression follows after a
+/// semicolon, and may use `+`, `-`, `*` and unary `-`.  There is no operator precedence:
+/// every operand must be an input or a parenthesized expression, e.g. `(x * y) + z`.
+#[macro_export]
+macro_rules! fp_analyze {
+    ($($x:ident : $T:ty = $min:literal ..= $max:literal),+ ; $($expr:tt)+) => {{
+        let mut analysis = $crate::analyze::Analysis::new();
+        $(
+            let $x = analysis.push($crate::analyze::Node::input::<$T>(
+                stringify!($x),
+                $min as f64,
+                $max as f64,
+            ));
+        )+
+        $crate::fp_analyze!(@node analysis; $($expr)+);
+        analysis
+    }};
+    (@node $a:ident; ($($inner:tt)+)) => {
+        $crate::fp_analyze!(@node $a; $($inner)+)
+    };
+    (@node $a:ident; $x:tt + $y:tt) => {{
+        let x = $crate::fp_analyze!(@node $a; $x);
+        let y = $crate::fp_analyze!(@node $a; $y);
+        $a.push(x.add(y, stringify!($x + $y)))
+    }};
+    (@node $a:ident; $x:tt - $y:tt) => {{
+        let x = $crate::fp_analyze!(@node $a; $x);
+        let y = $crate::fp_analyze!(@node $a; $y);
+        $a.push(x.sub(y, stringify!($x - $y)))
+    }};
+    (@node $a:ident; $x:tt * $y:tt) => {{
+        let x = $crate::fp_analyze!(@node $a; $x);
+        let y = $crate::fp_analyze!(@node $a; $y);
+        $a.push(x.mul(y, stringify!($x * $y)))
+    }};
+    (@node $a:ident; - $x:tt) => {{
+        let x = $crate::fp_analyze!(@node $a; $x);
+        $a.push(x.neg(stringify!(-$x)))
+    }};
+    (@node $a:ident; $x:ident) => {
+        $x
+    };
+}
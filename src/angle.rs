@@ -0,0 +1,195 @@
+use core::ops::{Add, Neg, Sub};
+
+use crate::cordic::{FRAC, PI};
+use crate::raw::{from_raw, shift_round};
+use crate::{Num, RangeError, I16, I32, I64, I8};
+
+/// Number of fractional bits of the internal representation of turns, which is wide
+/// enough for every `Angle`.
+const TURN_BITS: u32 = 64;
+
+/// Return `raw * 2^-shift` turns, modulo one turn, with `TURN_BITS` fractional bits and
+/// rounded to nearest.
+fn turns_from_turns(raw: i128, shift: i32) -> u64 {
+    match TURN_BITS as i32 - shift {
+        k if k >= TURN_BITS as i32 => 0,
+        k if k >= 0 => (raw as u64) << k,
+        k => shift_round(raw, k).unwrap() as u64,
+    }
+}
+
+/// Return `raw * 2^-shift` radians, modulo one turn, with `TURN_BITS` fractional bits and
+/// rounded to nearest.  Panics if the magnitude of the angle is 2^66 radians or more.
+fn turns_from_radians(raw: i128, shift: i32) -> u64 {
+    let radians = shift_round(raw, FRAC as i32 - shift).expect("angle out of range");
+    // The remainder is less than 2^63, so shifting it by `TURN_BITS` fits in `u128`.
+    let remainder = radians.rem_euclid(2 * PI) as u128;
+    let divisor = 2 * PI as u128;
+    (((remainder << TURN_BITS) + divisor / 2) / divisor) as u64
+}
+
+/// Return `raw * 2^-shift` degrees, modulo one turn, with `TURN_BITS` fractional bits and
+/// rounded to nearest.  The reduction modulo 360 degrees is exact.
+fn turns_from_degrees(raw: i128, shift: i32) -> u64 {
+    if shift < 0 {
+        // The angle is an integer number of degrees: reduce it modulo 360 with shift 0.
+        let mut scale = 1;
+        for _ in 0..-shift {
+            scale = scale * 2 % 360;
+        }
+        return turns_from_degrees(raw.rem_euclid(360) * scale % 360, 0);
+    }
+    if shift > i128::BITS as i32 - 10 {
+        // Less than 360 degrees in magnitude, so the reduction modulo 360 is unnecessary.
+        return turns_from_turns(raw / 360, shift);
+    }
+    let remainder = raw.rem_euclid(360 << shift) as u128;
+    let (num, den) = match TURN_BITS as i32 - shift {
+        k if k >= 0 => (remainder << k, 360),
+        k => (remainder, 360 << -k),
+    };
+    ((num + den / 2) / den) as u64
+}
+
+/// Return the signed angle, in (-1/2, 1/2] turns, of `turns` with `TURN_BITS` fractional
+/// bits.
+fn signed_turns(turns: u64) -> i128 {
+    match turns as i64 {
+        i64::MIN => -i128::from(i64::MIN),
+        turns => turns.into(),
+    }
+}
+
+/// Return `val * 2^-shift` as a value with shift `out_shift`, rounded to nearest.
+fn round(val: i128, shift: i32, out_shift: i32) -> Result<i128, RangeError> {
+    let err = if val < 0 {
+        RangeError::TooSmall
+    } else {
+        RangeError::TooLarge
+    };
+    shift_round(val, out_shift - shift).ok_or(err)
+}
+
+macro_rules! angle_impl {
+    ($T:ty, $Iname:ident) => {
+        impl Angle<$T> {
+            /// The zero angle.
+            pub const ZERO: Self = Self(0);
+            /// Half a turn.
+            pub const HALF_TURN: Self = Self(1 << (<$T>::BITS - 1));
+            /// Create an angle of `raw * 2^-BITS` turns, where `BITS` is the width of the
+            /// raw type.
+            pub const fn new(raw: $T) -> Self {
+                Self(raw)
+            }
+            /// Return the raw value, which is the angle in turns times `2^BITS`.
+            pub const fn raw(self) -> $T {
+                self.0
+            }
+            /// Return the angle from its value in `TURN_BITS` fractional bits, rounded to
+            /// nearest.
+            fn from_turns_internal(turns: u64) -> Self {
+                let shift = TURN_BITS - <$T>::BITS;
+                if shift == 0 {
+                    Self(turns as $T)
+                } else {
+                    Self((turns.wrapping_add(1 << (shift - 1)) >> shift) as $T)
+                }
+            }
+            /// Return the angle with `TURN_BITS` fractional bits.
+            fn turns_internal(self) -> u64 {
+                u64::from(self.0) << (TURN_BITS - <$T>::BITS)
+            }
+            /// Return the angle `x` in turns, modulo one turn and rounded to nearest.  Any
+            /// input is accepted.
+            pub fn from_turns<T: Num>(x: T) -> Self
+            where
+                T::Raw: Into<i128>,
+            {
+                Self::from_turns_internal(turns_from_turns(x.raw().into(), T::SHIFT))
+            }
+            /// Return the angle `x` in radians, modulo one turn and rounded to nearest.
+            /// Panics if the magnitude of `x` is 2^66 or more.
+            pub fn from_radians<T: Num>(x: T) -> Self
+            where
+                T::Raw: Into<i128>,
+            {
+                Self::from_turns_internal(turns_from_radians(x.raw().into(), T::SHIFT))
+            }
+            /// Return the angle `x` in degrees, modulo one turn and rounded to nearest.  Any
+            /// input is accepted.
+            pub fn from_degrees<T: Num>(x: T) -> Self
+            where
+                T::Raw: Into<i128>,
+            {
+                Self::from_turns_internal(turns_from_degrees(x.raw().into(), T::SHIFT))
+            }
+            /// Return the angle in turns, in the range (-1/2, 1/2], rounded to nearest.
+            pub fn to_turns<Out: Num>(self) -> Result<Out, RangeError>
+            where
+                Out::Raw: TryFrom<i128>,
+            {
+                let turns = signed_turns(self.turns_internal());
+                from_raw(round(turns, TURN_BITS as i32, Out::SHIFT))
+            }
+            /// Return the angle in radians, in the range (-pi, pi], rounded to nearest.
+            pub fn to_radians<Out: Num>(self) -> Result<Out, RangeError>
+            where
+                Out::Raw: TryFrom<i128>,
+            {
+                let radians = signed_turns(self.turns_internal()) * (2 * PI);
+                from_raw(round(radians, (TURN_BITS + FRAC) as i32, Out::SHIFT))
+            }
+            /// Return the angle in degrees, in the range (-180, 180], rounded to nearest.
+            pub fn to_degrees<Out: Num>(self) -> Result<Out, RangeError>
+            where
+                Out::Raw: TryFrom<i128>,
+            {
+                let degrees = signed_turns(self.turns_internal()) * 360;
+                from_raw(round(degrees, TURN_BITS as i32, Out::SHIFT))
+            }
+            /// Return the shortest signed difference `self - other` in turns, in the range
+            /// [-1/2, 1/2), i.e. the rotation which takes `other` to `self`.
+            pub fn diff(self, other: Self) -> $Iname<{ <$T>::BITS }, { <$T>::BITS as i32 }> {
+                unsafe { $Iname::new_unchecked(self.0.wrapping_sub(other.0) as _) }
+            }
+        }
+        /// Angles are added modulo one turn.
+        impl Add for Angle<$T> {
+            type Output = Self;
+            fn add(self, other: Self) -> Self {
+                Self(self.0.wrapping_add(other.0))
+            }
+        }
+        /// Angles are subtracted modulo one turn.
+        impl Sub for Angle<$T> {
+            type Output = Self;
+            fn sub(self, other: Self) -> Self {
+                Self(self.0.wrapping_sub(other.0))
+            }
+        }
+        /// Angles are negated modulo one turn.
+        impl Neg for Angle<$T> {
+            type Output = Self;
+            fn neg(self) -> Self {
+                Self(self.0.wrapping_neg())
+            }
+        }
+    };
+}
+
+/// An angle, represented as a fraction of a turn in the full range of the unsigned raw
+/// type, e.g. `Angle<u16>` has a resolution of 2^-16 turns.
+///
+/// Unlike the fixed-point types, whose arithmetic never wraps, angles are added and
+/// subtracted modulo one turn, which suits phase accumulators, headings and the positions
+/// of rotating shafts.  `diff` returns the shortest signed difference between two angles.
+/// Conversions from turns, radians and degrees accept any angle and reduce it modulo one
+/// turn; conversions to them return a signed angle, i.e. at most half a turn in magnitude.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Angle<T>(T);
+
+angle_impl!(u8, I8);
+angle_impl!(u16, I16);
+angle_impl!(u32, I32);
+angle_impl!(u64, I64);
@@ -0,0 +1,190 @@
+//! Support for the [`auto_bits!`](crate::auto_bits) macro.
+
+use crate::*;
+
+/// Selects a fixed-point type from the width (in bits) and signedness of its raw type.
+pub struct Raw<const WIDTH: u32, const SIGNED: bool>;
+
+/// The fixed-point type with a given raw type, for each `Raw<WIDTH, SIGNED>`.
+pub trait Select {
+    type Fixed<const B: u32, const S: i32>;
+}
+
+macro_rules! select_impl {
+    ($Name:ident, $T:ty) => {
+        impl Select for Raw<{ <$T>::BITS }, { <$T>::MIN != 0 }> {
+            type Fixed<const B: u32, const S: i32> = $Name<B, S>;
+        }
+    };
+}
+
+select_impl!(I8, i8);
+select_impl!(U8, u8);
+select_impl!(I16, i16);
+select_impl!(U16, u16);
+select_impl!(I32, i32);
+select_impl!(U32, u32);
+select_impl!(I64, i64);
+select_impl!(U64, u64);
+select_impl!(I128, i128);
+select_impl!(U128, u128);
+
+impl Select for Raw<256, true> {
+    type Fixed<const B: u32, const S: i32> = I256<B, S>;
+}
+
+impl Select for Raw<256, false> {
+    type Fixed<const B: u32, const S: i32> = U256<B, S>;
+}
+
+/// The signed fixed-point type with `BITS` bits and shift `SHIFT`, stored in the narrowest
+/// raw type with at least `BITS` bits, e.g. `Fix<12, 4>` is `I16<12, 4>`.  This allows code
+/// to be written in terms of bit budgets rather than raw types.
+pub type Fix<const BITS: u32, const SHIFT: i32> =
+    <Raw<{ raw_width(BITS) }, true> as Select>::Fixed<BITS, SHIFT>;
+
+/// The unsigned fixed-point type with `BITS` bits and shift `SHIFT`, stored in the narrowest
+/// raw type with at least `BITS` bits, e.g. `UFix<12, 4>` is `U16<12, 4>`.
+pub type UFix<const BITS: u32, const SHIFT: i32> =
+    <Raw<{ raw_width(BITS) }, false> as Select>::Fixed<BITS, SHIFT>;
+
+/// Return the raw value of `val` with the given shift.  Panics unless it is an integer
+/// with magnitude less than 2^53, so that it is exactly representable in any case.
+pub const fn raw(val: f64, shift: i32) -> i128 {
+    assert!(shift.abs() <= 1022, "shift out of range");
+    let raw = val * f64::from_bits(((shift + 1023) as u64) << 52);
+    assert!(
+        raw.fract() == 0. && raw.abs() < (1u64 << 53) as f64,
+        "literal is not exactly representable with this shift"
+    );
+    raw as i128
+}
+
+/// Return the value of the integer literal whose source text is `literal`, or `None` if it
+/// is a float literal.  The literal has already been checked by the compiler, so any
+/// letters after the digits (other than hex digits) are its type suffix.
+const fn parse_int(literal: &str) -> Option<i128> {
+    let bytes = literal.as_bytes();
+    let negative = bytes[0] == b'-';
+    let i = negative as usize;
+    let (radix, mut i) = if bytes.len() > i + 1 && bytes[i] == b'0' {
+        match bytes[i + 1] {
+            b'x' => (16, i + 2),
+            b'o' => (8, i + 2),
+            b'b' => (2, i + 2),
+            _ => (10, i),
+        }
+    } else {
+        (10, i)
+    };
+    let mut magnitude: u128 = 0;
+    while i < bytes.len() {
+        let digit = match bytes[i] {
+            b'_' => {
+                i += 1;
+                continue;
+            }
+            b'.' | b'e' | b'E' | b'f' if radix == 10 => return None,
+            b'0'..=b'9' => bytes[i] - b'0',
+            b'a'..=b'f' if radix == 16 => bytes[i] - b'a' + 10,
+            b'A'..=b'F' if radix == 16 => bytes[i] - b'A' + 10,
+            // the type suffix
+            _ => break,
+        };
+        let Some(next) = magnitude.checked_mul(radix) else {
+            panic!("literal out of range");
+        };
+        let Some(next) = next.checked_add(digit as u128) else {
+            panic!("literal out of range");
+        };
+        magnitude = next;
+        i += 1;
+    }
+    if negative {
+        assert!(magnitude <= i128::MIN.unsigned_abs(), "literal out of range");
+        Some((magnitude as i128).wrapping_neg())
+    } else {
+        assert!(magnitude <= i128::MAX as u128, "literal out of range");
+        Some(magnitude as i128)
+    }
+}
+
+/// Return the raw value of the numeric literal whose source text is `literal` and whose
+/// value is `val`, with the given shift.  Integer literals are converted exactly, so they
+/// may have any magnitude which fits in `i128`; float literals are converted by `raw`.
+/// Panics unless the raw value is an integer.
+pub const fn literal_raw(literal: &str, val: f64, shift: i32) -> i128 {
+    let Some(int) = parse_int(literal) else {
+        return raw(val, shift);
+    };
+    let exact = if shift >= 0 {
+        if int == 0 {
+            Some(0)
+        } else if shift < 128 && (int << shift) >> shift == int {
+            Some(int << shift)
+        } else {
+            None
+        }
+    } else if shift > -128 {
+        let k = shift.unsigned_abs();
+        if int & !(-1 << k) == 0 {
+            Some(int >> k)
+        } else {
+            None
+        }
+    } else if int == 0 {
+        Some(0)
+    } else {
+        None
+    };
+    match exact {
+        Some(raw) => raw,
+        None => panic!("literal is not exactly representable with this shift"),
+    }
+}
+
+/// Return the minimal `BITS` of a fixed-point type which holds `raw`.
+pub const fn bits(raw: i128) -> u32 {
+    if raw < 0 {
+        i128::BITS - (!raw).leading_zeros() + 1
+    } else {
+        i128::BITS - raw.leading_zeros()
+    }
+}
+
+/// Return the width of the narrowest raw type with at least `bits` bits.
+pub const fn raw_width(bits: u32) -> u32 {
+    if bits <= 8 {
+        8
+    } else {
+        bits.next_power_of_two()
+    }
+}
+
+/// Return the width of the narrowest primitive integer type which holds `raw`.
+pub const fn width(raw: i128) -> u32 {
+    raw_width(bits(raw))
+}
+
+/// Construct a constant of the narrowest fixed-point type which holds a numeric literal
+/// with the given shift, e.g. `auto_bits!(100, shift = 4)` has the type `U16<11, 4>`.
+///
+/// `BITS` is the minimal number of bits for the raw value (the literal times `2^shift`),
+/// and the raw type is the narrowest primitive integer type with at least `BITS` bits:
+/// unsigned for non-negative literals and signed for negative ones.  The raw value must be
+/// an integer (checked at compile time).  Integer literals are converted exactly, so their
+/// raw value may be anything that fits in `i128`, while that of a float literal must have
+/// magnitude less than `2^53`.
+#[macro_export]
+macro_rules! auto_bits {
+    ($val:literal, shift = $shift:literal) => {{
+        const RAW: i128 =
+            $crate::auto_bits::literal_raw(stringify!($val), $val as f64, $shift);
+        type Fixed = <$crate::auto_bits::Raw<
+                { $crate::auto_bits::width(RAW) },
+                { RAW < 0 },
+            > as $crate::auto_bits::Select>::Fixed<{ $crate::auto_bits::bits(RAW) }, $shift>;
+        const VAL: Fixed = Fixed::new_const(RAW as _);
+        VAL
+    }};
+}
@@ -0,0 +1,256 @@
+use core::ops::{Add, Mul, Neg, Sub};
+
+use crate::add_sub::max;
+use crate::checked::SameShift;
+use crate::{Num, RangeError, I128};
+
+/// Return the raw value of the minimum of `T` as an `i128`.
+pub const fn raw_min<T: Num>() -> i128 {
+    if T::SIGNED && T::BITS > 0 {
+        -raw_max::<T>() - 1
+    } else {
+        0
+    }
+}
+
+/// Return the raw value of the maximum of `T` as an `i128`.
+pub const fn raw_max<T: Num>() -> i128 {
+    let bits = T::BITS - T::SIGNED as u32;
+    if bits > 0 {
+        ((1 << (bits - 1)) - 1) * 2 + 1
+    } else {
+        0
+    }
+}
+
+/// Needed for const-generic support: the number of bits of a signed type which holds every
+/// raw value in `lo..=hi`.
+pub const fn range_bits(lo: i128, hi: i128) -> u32 {
+    let lo_bits = if lo < 0 {
+        i128::BITS + 1 - lo.leading_ones()
+    } else {
+        1
+    };
+    let hi_bits = if hi > 0 {
+        i128::BITS + 1 - hi.leading_zeros()
+    } else {
+        1
+    };
+    max(lo_bits, hi_bits)
+}
+
+/// Needed for const-generic support: the bounds of a sum, which panics (at compile time) if
+/// they overflow `i128`.
+pub const fn sum_bound(a: i128, b: i128) -> i128 {
+    match a.checked_add(b) {
+        Some(bound) => bound,
+        None => panic!("bounds overflow i128"),
+    }
+}
+
+/// Needed for const-generic support: the bounds of a difference, which panics (at compile
+/// time) if they overflow `i128`.
+pub const fn difference_bound(a: i128, b: i128) -> i128 {
+    match a.checked_sub(b) {
+        Some(bound) => bound,
+        None => panic!("bounds overflow i128"),
+    }
+}
+
+/// Needed for const-generic support: the lower (if `lower`) or upper bound of the product
+/// of values in `a_lo..=a_hi` and `b_lo..=b_hi`, which panics (at compile time) if it
+/// overflows `i128`.
+pub const fn product_bound(a_lo: i128, a_hi: i128, b_lo: i128, b_hi: i128, lower: bool) -> i128 {
+    let corners = [
+        a_lo.checked_mul(b_lo),
+        a_lo.checked_mul(b_hi),
+        a_hi.checked_mul(b_lo),
+        a_hi.checked_mul(b_hi),
+    ];
+    let mut bound = if lower { i128::MAX } else { i128::MIN };
+    let mut i = 0;
+    while i < corners.len() {
+        match corners[i] {
+            Some(corner) if lower && corner < bound => bound = corner,
+            Some(corner) if !lower && corner > bound => bound = corner,
+            Some(_) => (),
+            None => panic!("bounds overflow i128"),
+        }
+        i += 1;
+    }
+    bound
+}
+
+/// A fixed-point value of type `T` whose raw value is known to lie in `LO..=HI`.
+///
+/// Arithmetic on bounded values tracks the exact range of the raw result, rather than a
+/// power-of-two number of bits, so the result of a long chain of operations needs less
+/// headroom than the same chain on plain fixed-point values.  For example, squaring a value
+/// in `-5..=5` gives a result in `0..=25`, which needs 6 bits (including a sign bit), whereas
+/// squaring an `I8<4, 0>` gives an 8-bit result.  The result of each operation is stored in
+/// an `I128` with just enough bits for its range, and may be converted to a narrower type
+/// with `cast`, which is checked at compile time.
+///
+/// It is a compile error if `LO > HI`, if `T` cannot represent every raw value in
+/// `LO..=HI`, or if the bounds of a result overflow `i128`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Bounded<T: Num, const LO: i128, const HI: i128>(T);
+
+impl<T: Num, const LO: i128, const HI: i128> Bounded<T, LO, HI>
+where
+    T::Raw: Into<i128> + TryFrom<i128>,
+{
+    /// Compile-time check that the bounds are ordered and representable by `T`.
+    const CHECK: () = assert!(
+        raw_min::<T>() <= LO && LO <= HI && HI <= raw_max::<T>(),
+        "bounds are not representable by the fixed-point type"
+    );
+    /// Wrap `val`, or return a `RangeError` if its raw value is not in `LO..=HI`.
+    pub fn new(val: T) -> Result<Self, RangeError> {
+        let () = Self::CHECK;
+        match val.raw().into() {
+            raw if raw < LO => Err(RangeError::TooSmall),
+            raw if raw > HI => Err(RangeError::TooLarge),
+            _ => Ok(Self(val)),
+        }
+    }
+    /// Wrap `val` without checking its range.
+    ///
+    /// # Safety
+    ///
+    /// The raw value of `val` must be in `LO..=HI`.
+    pub unsafe fn new_unchecked(val: T) -> Self {
+        let () = Self::CHECK;
+        Self(val)
+    }
+    /// Return the wrapped value.
+    pub fn get(self) -> T {
+        self.0
+    }
+    /// Return the raw value as an `i128`.
+    pub fn raw(self) -> i128 {
+        self.0.raw().into()
+    }
+    /// Convert to another fixed-point type with the same shift, which is infallible because
+    /// it is a compile error unless `U` can represent every raw value in `LO..=HI`.
+    pub fn cast<U: Num>(self) -> Bounded<U, LO, HI>
+    where
+        U::Raw: Into<i128> + TryFrom<i128>,
+    {
+        let () = SameShift::<T, U>::CHECK;
+        let () = Bounded::<U, LO, HI>::CHECK;
+        let raw = self.raw().try_into().unwrap_or_else(|_| unreachable!());
+        Bounded(unsafe { U::new_unchecked(raw) })
+    }
+    /// Loosen the bounds to `LO1..=HI1`, which must contain `LO..=HI` (checked at compile
+    /// time).
+    pub fn widen<const LO1: i128, const HI1: i128>(self) -> Bounded<T, LO1, HI1> {
+        let () = Widen::<LO, HI, LO1, HI1>::CHECK;
+        let () = Bounded::<T, LO1, HI1>::CHECK;
+        Bounded(self.0)
+    }
+}
+
+struct Widen<const LO: i128, const HI: i128, const LO1: i128, const HI1: i128>;
+
+impl<const LO: i128, const HI: i128, const LO1: i128, const HI1: i128> Widen<LO, HI, LO1, HI1> {
+    /// Compile-time check that `LO1..=HI1` contains `LO..=HI`.
+    const CHECK: () = assert!(
+        LO1 <= LO && HI <= HI1,
+        "bounds must contain the original bounds"
+    );
+}
+
+/// Bounded values with the same shift may be added.  The bounds of the result are the sums
+/// of the bounds of the inputs.
+impl<T0: Num, T1: Num, const LO0: i128, const HI0: i128, const LO1: i128, const HI1: i128>
+    Add<Bounded<T1, LO1, HI1>> for Bounded<T0, LO0, HI0>
+where
+    T0::Raw: Into<i128> + TryFrom<i128>,
+    T1::Raw: Into<i128> + TryFrom<i128>,
+    [(); range_bits(sum_bound(LO0, LO1), sum_bound(HI0, HI1)) as usize]:,
+    [(); T0::SHIFT as usize]:,
+{
+    type Output = Bounded<
+        I128<{ range_bits(sum_bound(LO0, LO1), sum_bound(HI0, HI1)) }, { T0::SHIFT }>,
+        { sum_bound(LO0, LO1) },
+        { sum_bound(HI0, HI1) },
+    >;
+    fn add(self, other: Bounded<T1, LO1, HI1>) -> Self::Output {
+        let () = SameShift::<T0, T1>::CHECK;
+        let raw = self.raw() + other.raw();
+        Bounded(unsafe { I128::new_unchecked(raw) })
+    }
+}
+
+/// Bounded values with the same shift may be subtracted.  The lower bound of the result is
+/// `LO0 - HI1` and the upper bound is `HI0 - LO1`.
+impl<T0: Num, T1: Num, const LO0: i128, const HI0: i128, const LO1: i128, const HI1: i128>
+    Sub<Bounded<T1, LO1, HI1>> for Bounded<T0, LO0, HI0>
+where
+    T0::Raw: Into<i128> + TryFrom<i128>,
+    T1::Raw: Into<i128> + TryFrom<i128>,
+    [(); range_bits(difference_bound(LO0, HI1), difference_bound(HI0, LO1)) as usize]:,
+    [(); T0::SHIFT as usize]:,
+{
+    type Output = Bounded<
+        I128<{ range_bits(difference_bound(LO0, HI1), difference_bound(HI0, LO1)) }, { T0::SHIFT }>,
+        { difference_bound(LO0, HI1) },
+        { difference_bound(HI0, LO1) },
+    >;
+    fn sub(self, other: Bounded<T1, LO1, HI1>) -> Self::Output {
+        let () = SameShift::<T0, T1>::CHECK;
+        let raw = self.raw() - other.raw();
+        Bounded(unsafe { I128::new_unchecked(raw) })
+    }
+}
+
+/// Bounded values may be multiplied.  The bounds of the result are the extremes of the
+/// products of the bounds of the inputs, and its shift is the sum of their shifts.
+impl<T0: Num, T1: Num, const LO0: i128, const HI0: i128, const LO1: i128, const HI1: i128>
+    Mul<Bounded<T1, LO1, HI1>> for Bounded<T0, LO0, HI0>
+where
+    T0::Raw: Into<i128> + TryFrom<i128>,
+    T1::Raw: Into<i128> + TryFrom<i128>,
+    [(); range_bits(
+        product_bound(LO0, HI0, LO1, HI1, true),
+        product_bound(LO0, HI0, LO1, HI1, false),
+    ) as usize]:,
+    [(); (T0::SHIFT + T1::SHIFT) as usize]:,
+{
+    type Output = Bounded<
+        I128<
+            {
+                range_bits(
+                    product_bound(LO0, HI0, LO1, HI1, true),
+                    product_bound(LO0, HI0, LO1, HI1, false),
+                )
+            },
+            { T0::SHIFT + T1::SHIFT },
+        >,
+        { product_bound(LO0, HI0, LO1, HI1, true) },
+        { product_bound(LO0, HI0, LO1, HI1, false) },
+    >;
+    fn mul(self, other: Bounded<T1, LO1, HI1>) -> Self::Output {
+        let raw = self.raw() * other.raw();
+        Bounded(unsafe { I128::new_unchecked(raw) })
+    }
+}
+
+/// A bounded value may be negated.  The bounds of the result are `-HI..=-LO`.
+impl<T: Num, const LO: i128, const HI: i128> Neg for Bounded<T, LO, HI>
+where
+    T::Raw: Into<i128> + TryFrom<i128>,
+    [(); range_bits(difference_bound(0, HI), difference_bound(0, LO)) as usize]:,
+    [(); T::SHIFT as usize]:,
+{
+    type Output = Bounded<
+        I128<{ range_bits(difference_bound(0, HI), difference_bound(0, LO)) }, { T::SHIFT }>,
+        { difference_bound(0, HI) },
+        { difference_bound(0, LO) },
+    >;
+    fn neg(self) -> Self::Output {
+        let raw = -self.raw();
+        Bounded(unsafe { I128::new_unchecked(raw) })
+    }
+}
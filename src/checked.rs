@@ -0,0 +1,116 @@
+use core::marker::PhantomData;
+
+use crate::{i256, Num};
+
+// Raw arithmetic for the checked, wrapping and overflowing operations of `Num`, which keep
+// the type of their operands.  The checked functions return `None` if the result overflows
+// `i128`; the caller then checks that it fits in the operand type.
+
+/// Apply the sign to a magnitude, or return `None` if the result overflows.
+fn signed(negative: bool, magnitude: u128) -> Option<i128> {
+    if negative {
+        0i128.checked_sub_unsigned(magnitude)
+    } else {
+        magnitude.try_into().ok()
+    }
+}
+
+/// Return `a * b / 2^shift`, rounded toward negative infinity.
+pub(crate) fn mul(a: i128, b: i128, shift: i32) -> Option<i128> {
+    let negative = (a < 0) != (b < 0);
+    let product = a.unsigned_abs().checked_mul(b.unsigned_abs())?;
+    let magnitude = if shift < 0 {
+        if product.leading_zeros() < shift.unsigned_abs() {
+            return None;
+        }
+        product << -shift
+    } else if shift as u32 >= u128::BITS {
+        (negative && product != 0) as u128
+    } else {
+        let floor = product >> shift;
+        // A negative product rounds away from zero.
+        floor + (negative && floor << shift != product) as u128
+    };
+    signed(negative, magnitude)
+}
+
+/// Return `a * 2^shift / b`, rounded toward zero, or `None` if `b` is zero.
+pub(crate) fn div(a: i128, b: i128, shift: i32) -> Option<i128> {
+    if b == 0 {
+        return None;
+    }
+    let negative = (a < 0) != (b < 0);
+    let (num, mut den) = (a.unsigned_abs(), b.unsigned_abs());
+    if shift < 0 {
+        if den.leading_zeros() < shift.unsigned_abs() {
+            // The divisor exceeds the dividend.
+            return Some(0);
+        }
+        den <<= -shift;
+    }
+    let (mut quotient, mut remainder) = (num / den, num % den);
+    // Long division for the bits below the binary point of `num / den`.
+    for _ in 0..shift.max(0) {
+        quotient = quotient.checked_mul(2)?;
+        // `remainder < den`, so doubling it can only overflow if `den > 2^127`.
+        let (doubled, overflow) = remainder.overflowing_mul(2);
+        if overflow || doubled >= den {
+            quotient += 1;
+            remainder = doubled.wrapping_sub(den);
+        } else {
+            remainder = doubled;
+        }
+    }
+    signed(negative, quotient)
+}
+
+pub(crate) struct SameShift<A, B>(PhantomData<(A, B)>);
+
+impl<A: Num, B: Num> SameShift<A, B> {
+    /// Compile-time check that the two types have the same shift.
+    pub(crate) const CHECK: () = assert!(A::SHIFT == B::SHIFT, "shifts must be the same");
+}
+
+/// Return the multiple of `step` nearest to `val`, with ties toward positive infinity, or
+/// `None` if it overflows.  `step` must be nonzero.
+pub(crate) fn round_to_multiple(val: i128, step: i128) -> Option<i128> {
+    let step = step.unsigned_abs();
+    let remainder = match val.unsigned_abs() % step {
+        rem if val < 0 && rem != 0 => step - rem,
+        rem => rem,
+    };
+    let floor = val.checked_sub_unsigned(remainder)?;
+    if remainder >= step - remainder {
+        floor.checked_add_unsigned(step)
+    } else {
+        Some(floor)
+    }
+}
+
+/// Return the low `bits` bits of `val`, sign-extended if `signed`, i.e. `val` wrapped to
+/// the range of a type with `bits` bits.
+pub(crate) fn wrap(val: u128, bits: u32, signed: bool) -> i128 {
+    if bits == 0 {
+        return 0;
+    }
+    let unused = u128::BITS - bits;
+    if signed {
+        (val << unused) as i128 >> unused
+    } else {
+        ((val << unused) >> unused) as i128
+    }
+}
+
+/// Return the low 128 bits of `a * b / 2^shift`, rounded toward negative infinity.
+pub(crate) fn wrapping_mul(a: i128, b: i128, shift: i32) -> u128 {
+    // The exact product fits in 255 bits.
+    let product = i256::from(a) * i256::from(b);
+    let product = if shift >= 0 {
+        product >> shift.unsigned_abs().min(255)
+    } else if shift > -256 {
+        product << shift.unsigned_abs()
+    } else {
+        i256::ZERO
+    };
+    product.limbs().1
+}
@@ -0,0 +1,120 @@
+use core::ops::{Add, Mul, Neg, Sub};
+
+use crate::mul_div;
+use crate::{Num, I128, I16, I32, I64, I8, U128, U16, U32, U64, U8};
+
+/// A complex number with real and imaginary parts of type `N` (usually a signed fixed-point
+/// number), e.g. a sample of an I/Q signal or an FFT twiddle factor.
+///
+/// The arithmetic operators act on the parts with the operators of `N`, so the bits of the
+/// result grow exactly as for the parts, and an overflow of either part is a compile error
+/// rather than a wrapped value.  Addition and subtraction add 1 bit, and multiplication adds
+/// the bits of the inputs plus 1, for the sum of two products in each part.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Complex<N> {
+    re: N,
+    im: N,
+}
+
+impl<N> Complex<N> {
+    /// Create the complex number `re + im * i`.
+    pub const fn new(re: N, im: N) -> Self {
+        Self { re, im }
+    }
+}
+
+impl<N: Copy> Complex<N> {
+    /// Return the real part.
+    pub fn re(self) -> N {
+        self.re
+    }
+    /// Return the imaginary part.
+    pub fn im(self) -> N {
+        self.im
+    }
+}
+
+/// Complex numbers may be added, if their parts may be added.
+impl<T0: Add<T1>, T1> Add<Complex<T1>> for Complex<T0> {
+    type Output = Complex<T0::Output>;
+    fn add(self, other: Complex<T1>) -> Self::Output {
+        Complex::new(self.re + other.re, self.im + other.im)
+    }
+}
+
+/// Complex numbers may be subtracted, if their parts may be subtracted.
+impl<T0: Sub<T1>, T1> Sub<Complex<T1>> for Complex<T0> {
+    type Output = Complex<T0::Output>;
+    fn sub(self, other: Complex<T1>) -> Self::Output {
+        Complex::new(self.re - other.re, self.im - other.im)
+    }
+}
+
+/// A complex number may be negated, if its parts may be negated.
+impl<T: Neg> Neg for Complex<T> {
+    type Output = Complex<T::Output>;
+    fn neg(self) -> Self::Output {
+        Complex::new(-self.re, -self.im)
+    }
+}
+
+/// Complex numbers may be multiplied, if their parts may be multiplied and the products
+/// may be both added and subtracted with the same output type, which is the case for
+/// signed fixed-point numbers.  Each part of the result is the sum or difference of two
+/// products, so it has 1 more bit than a product of the parts.
+impl<T0, T1, P> Mul<Complex<T1>> for Complex<T0>
+where
+    T0: Mul<T1, Output = P> + Copy,
+    T1: Copy,
+    P: Add<P> + Sub<P, Output = <P as Add<P>>::Output>,
+{
+    type Output = Complex<<P as Add<P>>::Output>;
+    fn mul(self, other: Complex<T1>) -> Self::Output {
+        Complex::new(
+            self.re * other.re - self.im * other.im,
+            self.re * other.im + self.im * other.re,
+        )
+    }
+}
+
+macro_rules! complex_impl {
+    ($Uname:ident, $Iname:ident) => {
+        impl<const B: u32, const S: i32> Complex<$Iname<B, S>> {
+            /// Return the complex conjugate `re - im * i`.  The parts have 1 more bit, like
+            /// negation, since the imaginary part may be `MIN`.
+            pub fn conj(self) -> Complex<$Iname<{ B + 1 }, S>>
+            where
+                [(); (B + 1) as usize]:,
+            {
+                let re = unsafe { $Iname::new_unchecked(self.re.raw()) };
+                Complex::new(re, -self.im)
+            }
+            /// Return the squared magnitude `re^2 + im^2`, which is exact and never negative.
+            /// It has twice the bits and shift of the parts, like a product, and it is a
+            /// compile error if this does not fit in the raw type.
+            pub fn norm_sqr(self) -> $Uname<{ 2 * B }, { 2 * S }>
+            where
+                [(); (2 * B) as usize]:,
+                [(); (2 * S) as usize]:,
+            {
+                let (re, im) = (self.re.raw().unsigned_abs(), self.im.raw().unsigned_abs());
+                unsafe { $Uname::new_unchecked(re * re + im * im) }
+            }
+            /// Return the magnitude `sqrt(re^2 + im^2)`, rounded down, by an integer square
+            /// root of the exact squared magnitude.  It has the same shift as the parts and 1
+            /// more bit, since it may exceed the magnitude of either part by `sqrt(2)`.
+            pub fn abs(self) -> $Iname<{ B + 1 }, S>
+            where
+                [(); (B + 1) as usize]:,
+            {
+                mul_div::hypot(self.re, self.im)
+            }
+        }
+    };
+}
+
+complex_impl!(U8, I8);
+complex_impl!(U16, I16);
+complex_impl!(U32, I32);
+complex_impl!(U64, I64);
+complex_impl!(U128, I128);
@@ -0,0 +1,213 @@
+use core::marker::PhantomData;
+
+use crate::prob::lerp_raw;
+use crate::raw::{from_raw, shift_round, signed_bits};
+use crate::{Num, Prob, RangeError};
+
+/// Return the shift of the state of an `Integrator<K, X, Y>`, which is fine enough for both
+/// the exact products `K * X` and the values of `Y`.
+const fn state_shift<K: Num, X: Num, Y: Num>() -> i32 {
+    let product = K::SHIFT + X::SHIFT;
+    if product > Y::SHIFT {
+        product
+    } else {
+        Y::SHIFT
+    }
+}
+
+struct IntegratorState<K, X, Y>(PhantomData<(K, X, Y)>);
+
+impl<K: Num, X: Num, Y: Num> IntegratorState<K, X, Y> {
+    /// Compile-time check that the sum of the state (within the range of `Y`) and one
+    /// product `K * X`, at the shift of `state_shift`, fits in `i128`.
+    const CHECK: () = {
+        let shift = state_shift::<K, X, Y>();
+        let product =
+            signed_bits::<K>() as i32 + signed_bits::<X>() as i32 + shift - (K::SHIFT + X::SHIFT);
+        let state = signed_bits::<Y>() as i32 + shift - Y::SHIFT;
+        let widest = if product > state { product } else { state };
+        assert!(widest < i128::BITS as i32, "too many bits for integrator");
+    };
+}
+
+struct Tracking<G, E>(PhantomData<(G, E)>);
+
+impl<G: Num, E: Num> Tracking<G, E> {
+    /// Compile-time check that the products `G * E` fit in `i128`.
+    const CHECK: () = assert!(
+        signed_bits::<G>() + signed_bits::<E>() <= i128::BITS,
+        "too many bits for back-calculation"
+    );
+}
+
+/// A discrete integrator `y += k x` with anti-windup, e.g. the integral term of a custom
+/// controller, where the gain `k` includes the sample period.
+///
+/// The state is kept exactly, at the shift of the products `K * X` (or of `Y`, if that is
+/// finer), and with enough bits for the range of `Y`: it is a compile error if this does
+/// not fit in `i128`.  The output is the state rounded to nearest in `Y`.
+///
+/// Two anti-windup schemes are supported, and may be combined:
+///
+/// - Clamping: the state is limited to `[min, max]` after every update, so that it never
+///   winds up beyond the range the controller can use.  Set the limits to `Y::MIN` and
+///   `Y::MAX` to only prevent overflow.
+/// - Back-calculation: after the controller output has been saturated, `back_calculate`
+///   feeds the excess `saturated - unsaturated` back into the state with a tracking gain,
+///   so that the integrator unwinds as soon as the output leaves saturation.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Integrator<K, X, Y> {
+    gain: K,
+    // The limits and the state, with the shift of `state_shift`.
+    min: i128,
+    max: i128,
+    state: i128,
+    _types: PhantomData<(X, Y)>,
+}
+
+impl<K: Num, X: Num, Y: Num> Integrator<K, X, Y>
+where
+    K::Raw: Into<i128>,
+    X::Raw: Into<i128>,
+    Y::Raw: Into<i128> + TryFrom<i128>,
+{
+    /// Create an integrator with the gain `k` and the clamping limits `min` and `max`,
+    /// with the initial state 0 (or the nearest limit).  Panics if `min > max`.
+    pub fn new(k: K, min: Y, max: Y) -> Self {
+        let () = IntegratorState::<K, X, Y>::CHECK;
+        assert!(min <= max, "integrator limits must be in order");
+        let shift = state_shift::<K, X, Y>() - Y::SHIFT;
+        let (min, max) = (min.raw().into() << shift, max.raw().into() << shift);
+        Self {
+            gain: k,
+            min,
+            max,
+            state: 0.clamp(min, max),
+            _types: PhantomData,
+        }
+    }
+    /// Return the output, i.e. the state rounded to nearest.
+    pub fn output(&self) -> Y {
+        let shift = Y::SHIFT - state_shift::<K, X, Y>();
+        from_raw(Ok(shift_round(self.state, shift).unwrap())).unwrap()
+    }
+    /// Set the state to `y`, clamped to the limits, e.g. for bumpless transfer from manual
+    /// control.
+    pub fn set(&mut self, y: Y) {
+        let shift = state_shift::<K, X, Y>() - Y::SHIFT;
+        self.state = (y.raw().into() << shift).clamp(self.min, self.max);
+    }
+    /// Add `k x` to the state, clamp it to the limits, and return the new output.
+    pub fn update(&mut self, x: X) -> Y {
+        let shift = state_shift::<K, X, Y>() - K::SHIFT - X::SHIFT;
+        let product = (self.gain.raw().into() * x.raw().into()) << shift;
+        self.state = (self.state + product).clamp(self.min, self.max);
+        self.output()
+    }
+    /// Back-calculation anti-windup: add `gain * excess`, rounded to nearest, to the state
+    /// and clamp it to the limits, where `excess` is the difference `saturated -
+    /// unsaturated` of the controller output.  The tracking gain is typically `1 / T_t`
+    /// times the sample period, for a tracking time constant `T_t`.
+    pub fn back_calculate<G: Num, E: Num>(&mut self, gain: G, excess: E)
+    where
+        G::Raw: Into<i128>,
+        E::Raw: Into<i128>,
+    {
+        let () = Tracking::<G, E>::CHECK;
+        let product = gain.raw().into() * excess.raw().into();
+        let shift = state_shift::<K, X, Y>() - G::SHIFT - E::SHIFT;
+        self.state = match shift_round(product, shift) {
+            Some(delta) => (self.state + delta).clamp(self.min, self.max),
+            // A correction too large to represent saturates the state.
+            None if product < 0 => self.min,
+            None => self.max,
+        };
+    }
+}
+
+struct DifferentiatorState<K, X, A>(PhantomData<(K, X, A)>);
+
+impl<K: Num, X: Num, A: Num> DifferentiatorState<K, X, A> {
+    /// Compile-time check that the filtered derivative, with the fractional bits of the
+    /// smoothing coefficient, fits in `i128`.
+    const CHECK: () = assert!(
+        signed_bits::<K>() as i32 + signed_bits::<X>() as i32 + 2 + A::SHIFT < i128::BITS as i32,
+        "too many bits for differentiator"
+    );
+}
+
+/// A discrete differentiator `y = k (x[n] - x[n-1])`, with an optional first-order lowpass
+/// filter, e.g. the derivative term of a custom controller, where the gain `k` includes the
+/// reciprocal of the sample period.
+///
+/// The difference and its product with `k` are exact.  With a smoothing coefficient
+/// `alpha < 1`, the derivative is filtered by `y += alpha (k Δx - y)`, which limits its
+/// high-frequency gain (and the amplification of noise) to `k`; the filter state is kept
+/// at the shift of the products `K * X`, rounded to nearest.  It is a compile error if the
+/// state does not fit in `i128`.
+///
+/// The first update after creating or resetting the differentiator only records the input
+/// and returns 0, which avoids a derivative kick from the initial value.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Differentiator<K: Num, X, A: Num> {
+    gain: K,
+    smoothing: Prob<A>,
+    // The previous input, and the filtered derivative with the shift of `K * X`.
+    prev: Option<i128>,
+    state: i128,
+    _input: PhantomData<X>,
+}
+
+impl<K: Num, X: Num, A: Num> Differentiator<K, X, A>
+where
+    K::Raw: Into<i128>,
+    X::Raw: Into<i128>,
+    A::Raw: Into<i128> + TryFrom<i128>,
+{
+    /// Create a differentiator with the gain `k` and the smoothing coefficient `alpha`,
+    /// which is 1 for an unfiltered derivative, or e.g. `one_pole_alpha` for a time
+    /// constant.
+    pub fn new(k: K, alpha: Prob<A>) -> Self {
+        let () = DifferentiatorState::<K, X, A>::CHECK;
+        Self {
+            gain: k,
+            smoothing: alpha,
+            prev: None,
+            state: 0,
+            _input: PhantomData,
+        }
+    }
+    /// Forget the previous input, and reset the filtered derivative to 0.
+    pub fn reset(&mut self) {
+        self.prev = None;
+        self.state = 0;
+    }
+    /// Return the (filtered) derivative, rounded to the nearest value of the type `Y`, or
+    /// a `RangeError` if it does not fit.
+    pub fn output<Y: Num>(&self) -> Result<Y, RangeError>
+    where
+        Y::Raw: TryFrom<i128>,
+    {
+        let shift = Y::SHIFT - K::SHIFT - X::SHIFT;
+        let err = if self.state < 0 {
+            RangeError::TooSmall
+        } else {
+            RangeError::TooLarge
+        };
+        from_raw(shift_round(self.state, shift).ok_or(err))
+    }
+    /// Record the input `x`, and return the new (filtered) derivative like `output`.
+    pub fn update<Y: Num>(&mut self, x: X) -> Result<Y, RangeError>
+    where
+        Y::Raw: TryFrom<i128>,
+    {
+        let x = x.raw().into();
+        if let Some(prev) = self.prev.replace(x) {
+            let derivative = self.gain.raw().into() * (x - prev);
+            let alpha = self.smoothing.get().raw().into();
+            let filtered = lerp_raw(self.state, derivative, alpha, A::SHIFT);
+            self.state = shift_round(filtered, -A::SHIFT).unwrap();
+        }
+        self.output()
+    }
+}
@@ -0,0 +1,142 @@
+use core::marker::PhantomData;
+
+use crate::raw::signed_bits;
+use crate::{Num, I64};
+
+/// Number of bits of the accumulator for an `N`×`N` kernel of type `K` applied to pixels of
+/// type `P`: each product needs the bits of both operands, and the sum of `N²` products
+/// needs `ceil(log2(N²))` more.
+pub const fn conv2d_bits<P: Num, K: Num, const N: usize>() -> u32 {
+    let area = (N * N) as u32;
+    signed_bits::<P>() + signed_bits::<K>() + (u32::BITS - (area - 1).leading_zeros())
+}
+
+/// Handling of the pixels beyond the edges of the image, which a kernel centered near the
+/// edge would read.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Edge {
+    /// Pixels beyond the edge are zero.
+    Zero,
+    /// Pixels beyond the edge repeat the nearest edge pixel (`aaa|abc`).
+    Replicate,
+    /// Pixels beyond the edge are mirrored about the edge pixel (`cb|abc`).
+    Reflect,
+    /// The image wraps around, as if it were tiled (`bc|abc`).
+    Wrap,
+}
+
+impl Edge {
+    /// Map the coordinate `i` to a coordinate in `0..len`, or `None` for a zero pixel.
+    /// For `Reflect`, `i` must lie less than `len` beyond either end of `0..len`.
+    fn index(self, i: isize, len: usize) -> Option<usize> {
+        let len = len as isize;
+        if (0..len).contains(&i) {
+            return Some(i as usize);
+        }
+        let i = match self {
+            Edge::Zero => return None,
+            Edge::Replicate => i.clamp(0, len - 1),
+            // A single pixel reflects to itself.
+            Edge::Reflect if len == 1 => 0,
+            Edge::Reflect if i < 0 => -i,
+            Edge::Reflect => 2 * (len - 1) - i,
+            Edge::Wrap => i.rem_euclid(len),
+        };
+        Some(i as usize)
+    }
+}
+
+/// A square convolution kernel of size `N`×`N` (e.g. 3×3 or 5×5), with weights of type `K`.
+///
+/// Applying the kernel to an image with `conv2d` produces exact results: every output pixel
+/// is an `I64` accumulator with `conv2d_bits::<P, K, N>()` bits, which is enough for the
+/// worst-case sum of `N²` products.  It is a compile error if `N` is even or if the
+/// accumulator would need more than 64 bits.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Kernel<K: Num, const N: usize> {
+    weights: [[K; N]; N],
+}
+
+impl<K: Num, const N: usize> Kernel<K, N>
+where
+    K::Raw: Into<i64>,
+{
+    /// Compile-time check that the kernel has a center.
+    const CHECK: () = assert!(N % 2 == 1, "kernel size must be odd");
+    /// Create a kernel from its weights, as rows from top to bottom.
+    pub const fn new(weights: [[K; N]; N]) -> Self {
+        let () = Self::CHECK;
+        Self { weights }
+    }
+    /// Return the weights, as rows from top to bottom.
+    pub const fn weights(&self) -> &[[K; N]; N] {
+        &self.weights
+    }
+    /// Apply the kernel to an image stored as row-major pixels, `width` pixels per row,
+    /// and store the result for each pixel in `output`.  Pixels beyond the edges of the
+    /// image are handled according to `edge`.
+    ///
+    /// Like most image-processing libraries (and unlike the mathematical definition of
+    /// convolution), the kernel is not flipped: `output[y][x]` is the sum of
+    /// `weights[j][i] * input[y + j - N/2][x + i - N/2]`.
+    ///
+    /// Panics unless `input.len()` is a multiple of `width` and `output.len() ==
+    /// input.len()`, or with `Edge::Reflect` if the image is too small to be mirrored
+    /// (i.e. if its width or height is between 2 and `N / 2`).
+    pub fn conv2d<P: Num>(
+        &self,
+        input: &[P],
+        width: usize,
+        edge: Edge,
+        output: &mut [I64<{ conv2d_bits::<P, K, N>() }, { P::SHIFT + K::SHIFT }>],
+    ) where
+        P::Raw: Into<i64>,
+    {
+        let () = Accumulator::<P, K, N>::CHECK;
+        assert!(
+            width > 0 && input.len().is_multiple_of(width),
+            "image must consist of whole rows"
+        );
+        assert_eq!(
+            output.len(),
+            input.len(),
+            "output must match the image size"
+        );
+        let height = input.len() / width;
+        if edge == Edge::Reflect {
+            let fits = |len: usize| len > N / 2 || len <= 1;
+            assert!(
+                fits(width) && fits(height),
+                "image is too small to reflect the kernel"
+            );
+        }
+        let half = (N / 2) as isize;
+        for y in 0..height {
+            for x in 0..width {
+                let mut sum = 0i64;
+                for (j, row) in self.weights.iter().enumerate() {
+                    let Some(yy) = edge.index(y as isize + j as isize - half, height) else {
+                        continue;
+                    };
+                    for (i, weight) in row.iter().enumerate() {
+                        let Some(xx) = edge.index(x as isize + i as isize - half, width) else {
+                            continue;
+                        };
+                        sum += input[yy * width + xx].raw().into() * (*weight).raw().into();
+                    }
+                }
+                output[y * width + x] = unsafe { I64::new_unchecked(sum) };
+            }
+        }
+    }
+}
+
+struct Accumulator<P, K, const N: usize>(PhantomData<(P, K)>);
+
+impl<P: Num, K: Num, const N: usize> Accumulator<P, K, N> {
+    /// Compile-time check that the accumulator fits in `i64`.
+    const CHECK: () = assert!(
+        conv2d_bits::<P, K, N>() <= i64::BITS,
+        "too many bits for conv2d accumulator"
+    );
+}
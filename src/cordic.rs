@@ -0,0 +1,525 @@
+//! Elementary functions computed with the CORDIC algorithm, using only integer
+//! shifts and additions.
+//!
+//! All functions take any fixed-point input type whose raw type converts losslessly
+//! into `i128` (i.e. anything except `u128`), and return the caller's choice of
+//! fixed-point output type.  Internally, CORDIC iterations are carried out with 60
+//! fractional bits, so the result is accurate to within about 2^-52 before it is rounded
+//! to the nearest value of the output type.  A `RangeError` is returned if the result
+//! does not fit in the output type.
+
+use core::marker::PhantomData;
+
+use crate::raw::{from_raw, shift_round};
+use crate::{Num, RangeError};
+
+/// Number of fractional bits used for all internal calculations.
+pub(crate) const FRAC: u32 = 60;
+const ONE: i128 = 1 << FRAC;
+/// ln(2) = 2 atanh(1/3), with `FRAC` fractional bits.
+const LN_2: i128 = {
+    // Taylor series for atanh(1/3), with 60 guard bits.
+    let mut sum: u128 = 0;
+    let mut pow3: u128 = 3;
+    let mut k = 1;
+    while pow3 < 1 << 120 {
+        sum += (1 << 120) / pow3 / k;
+        pow3 *= 9;
+        k += 2;
+    }
+    ((sum + (1 << 58)) >> 59) as i128
+};
+
+/// Number of iterations, which is enough to reach full precision with `FRAC` bits.
+const ITERATIONS: usize = FRAC as usize;
+
+/// `atanh(2^-i)` for each hyperbolic iteration `i` (starting from 1).
+const ATANH: [i128; ITERATIONS] = {
+    let mut table = [0; ITERATIONS];
+    let mut i = 0;
+    while i < ITERATIONS {
+        table[i] = atanh_pow2(i as u32 + 1, FRAC);
+        i += 1;
+    }
+    table
+};
+
+/// Reciprocal of the gain of the hyperbolic iterations, i.e. `1 / prod(sqrt(1 - 2^-2i))`,
+/// including the repeated iterations.
+const HYPERBOLIC_INV_GAIN: i128 = {
+    let mut gain = ONE as u128;
+    let mut i = 1;
+    while i <= ITERATIONS as u32 {
+        // sqrt(1 - 2^-2i), with `FRAC` fractional bits
+        let factor = ((1u128 << (2 * FRAC)) - (1u128 << (2 * FRAC - 2 * i))).isqrt();
+        gain = (gain * factor) >> FRAC;
+        if is_repeated(i) {
+            gain = (gain * factor) >> FRAC;
+        }
+        i += 1;
+    }
+    ((1u128 << (2 * FRAC)) / gain) as i128
+};
+
+/// Return `atanh(2^-i)` with `frac` fractional bits, computed from the Taylor series
+/// `atanh(t) = t + t^3/3 + t^5/5 + ...`.  Requires `i >= 1`.
+const fn atanh_pow2(i: u32, frac: u32) -> i128 {
+    // Extra guard bits, so that the truncation of each term is insignificant.
+    const GUARD: u32 = 64;
+    let mut sum: u128 = 0;
+    let mut k = 0;
+    while i * (2 * k + 1) <= frac + GUARD {
+        sum += (1u128 << (frac + GUARD - i * (2 * k + 1))) / (2 * k as u128 + 1);
+        k += 1;
+    }
+    ((sum + (1 << (GUARD - 1))) >> GUARD) as i128
+}
+
+/// Iterations 4, 13, 40, ... must be repeated for the hyperbolic iterations to converge.
+const fn is_repeated(i: u32) -> bool {
+    let mut k = 4;
+    while k < i {
+        k = 3 * k + 1;
+    }
+    k == i
+}
+
+/// Hyperbolic CORDIC in rotation mode: rotate `(x, y)` through the hyperbolic angle `z`,
+/// which must satisfy `|z| <= 1.118`.  The result is scaled by the hyperbolic gain.
+/// This is a const fn (hence the `while` loops), so that it can be evaluated at compile time.
+const fn hyperbolic_rotate(mut x: i128, mut y: i128, mut z: i128) -> (i128, i128) {
+    let mut i = 1;
+    while i <= ITERATIONS as u32 {
+        let mut repeat = 0;
+        while repeat <= is_repeated(i) as u32 {
+            let (dx, dy) = (y >> i, x >> i);
+            if z >= 0 {
+                (x, y, z) = (x + dx, y + dy, z - ATANH[i as usize - 1]);
+            } else {
+                (x, y, z) = (x - dx, y - dy, z + ATANH[i as usize - 1]);
+            }
+            repeat += 1;
+        }
+        i += 1;
+    }
+    (x, y)
+}
+
+/// Hyperbolic CORDIC in vectoring mode: return `atanh(y / x)`, which requires `x > 0`
+/// and `|y / x| <= 0.806`.
+const fn hyperbolic_vector(mut x: i128, mut y: i128) -> i128 {
+    let mut z = 0;
+    let mut i = 1;
+    while i <= ITERATIONS as u32 {
+        let mut repeat = 0;
+        while repeat <= is_repeated(i) as u32 {
+            let (dx, dy) = (y >> i, x >> i);
+            if y < 0 {
+                (x, y, z) = (x + dx, y + dy, z - ATANH[i as usize - 1]);
+            } else {
+                (x, y, z) = (x - dx, y - dy, z + ATANH[i as usize - 1]);
+            }
+            repeat += 1;
+        }
+        i += 1;
+    }
+    z
+}
+
+/// pi = 16 atan(1/5) - 4 atan(1/239) (Machin's formula), with `FRAC` fractional bits.
+pub(crate) const PI: i128 = {
+    // Extra guard bits, so that the truncation of each term is insignificant.
+    const GUARD: u32 = 64;
+    let sum = 16 * atan_inv(5, FRAC + GUARD) - 4 * atan_inv(239, FRAC + GUARD);
+    (sum + (1 << (GUARD - 1))) >> GUARD
+};
+
+/// `atan(2^-i)` for each circular iteration `i` (starting from 0).
+const ATAN: [i128; ITERATIONS] = {
+    let mut table = [0; ITERATIONS];
+    table[0] = (PI + 2) >> 2;
+    let mut i = 1;
+    while i < ITERATIONS {
+        table[i] = atan_pow2(i as u32, FRAC);
+        i += 1;
+    }
+    table
+};
+
+/// Reciprocal of the gain of the circular iterations, i.e. `1 / prod(sqrt(1 + 2^-2i))`.
+const CIRCULAR_INV_GAIN: i128 = {
+    let mut gain = ONE as u128;
+    let mut i = 0;
+    while i < ITERATIONS as u32 {
+        // sqrt(1 + 2^-2i), with `FRAC` fractional bits
+        let factor = ((1u128 << (2 * FRAC)) + (1u128 << (2 * FRAC - 2 * i))).isqrt();
+        gain = (gain * factor) >> FRAC;
+        i += 1;
+    }
+    ((1u128 << (2 * FRAC)) / gain) as i128
+};
+
+/// Return `atan(1/n)` with `frac` fractional bits (truncated), computed from the Taylor
+/// series `atan(t) = t - t^3/3 + t^5/5 - ...`.  Requires `n >= 2` and `frac <= 124`.
+const fn atan_inv(n: i128, frac: u32) -> i128 {
+    let mut sum = 0;
+    let mut pow = (1 << frac) / n;
+    let mut k = 0;
+    while pow > 0 {
+        let term = pow / (2 * k + 1);
+        sum += if k % 2 == 0 { term } else { -term };
+        pow /= n * n;
+        k += 1;
+    }
+    sum
+}
+
+/// Return `atan(2^-i)` with `frac` fractional bits, computed from the Taylor series.
+/// Requires `i >= 1`.
+const fn atan_pow2(i: u32, frac: u32) -> i128 {
+    // Extra guard bits, so that the truncation of each term is insignificant.
+    const GUARD: u32 = 64;
+    let mut sum: i128 = 0;
+    let mut k = 0;
+    while i * (2 * k + 1) <= frac + GUARD {
+        let term = (1i128 << (frac + GUARD - i * (2 * k + 1))) / (2 * k as i128 + 1);
+        sum += if k % 2 == 0 { term } else { -term };
+        k += 1;
+    }
+    (sum + (1 << (GUARD - 1))) >> GUARD
+}
+
+/// Circular CORDIC in rotation mode: return `(cos(z), sin(z))` for `|z| <= pi / 2`.
+/// This is a const fn, so that tables can be computed at compile time.
+const fn circular_rotate(z: i128) -> (i128, i128) {
+    let (mut x, mut y, mut z) = (CIRCULAR_INV_GAIN, 0, z);
+    let mut i = 0;
+    while i < ITERATIONS {
+        let (dx, dy) = (y >> i, x >> i);
+        if z >= 0 {
+            (x, y, z) = (x - dx, y + dy, z - ATAN[i]);
+        } else {
+            (x, y, z) = (x + dx, y - dy, z + ATAN[i]);
+        }
+        i += 1;
+    }
+    (x, y)
+}
+
+/// Circular CORDIC in vectoring mode: return `atan(y / x)`, which requires `x >= 0`.
+fn circular_vector(mut x: i128, mut y: i128) -> i128 {
+    let mut z = 0;
+    for (i, &atan) in ATAN.iter().enumerate() {
+        let (dx, dy) = (y >> i, x >> i);
+        if y < 0 {
+            (x, y, z) = (x - dx, y + dy, z - atan);
+        } else {
+            (x, y, z) = (x + dx, y - dy, z + atan);
+        }
+    }
+    z
+}
+
+/// The angle of the vector `(x, y)` in turns, in (-1/2, 1/2], with `FRAC` fractional bits.
+/// The angle of the zero vector is zero.
+pub(crate) fn atan2_turns_internal(y: i128, x: i128) -> i128 {
+    if x == 0 && y == 0 {
+        return 0;
+    }
+    // Normalize the larger component to 61 significant bits, leaving headroom for the
+    // CORDIC gain.
+    let bits = 128 - x.unsigned_abs().max(y.unsigned_abs()).leading_zeros() as i32;
+    let (x, y) = if bits > 61 {
+        (x >> (bits - 61), y >> (bits - 61))
+    } else {
+        (x << (61 - bits), y << (61 - bits))
+    };
+    // Rotate into the right half-plane by a quarter turn if necessary.
+    let (x, y, quarters) = if x >= 0 {
+        (x, y, 0)
+    } else if y >= 0 {
+        (y, -x, 1)
+    } else {
+        (-y, x, -1)
+    };
+    let radians = circular_vector(x, y);
+    (quarters << (FRAC - 2)) + (radians << FRAC) / (2 * PI)
+}
+
+/// `(cos(2 pi x), sin(2 pi x))` for an angle `x` in turns (with the given shift), with
+/// `FRAC` fractional bits.  Only the fractional part of `x` matters, and the reduction is
+/// exact.
+pub(crate) const fn cos_sin_turns_internal(x: i128, shift: i32) -> (i128, i128) {
+    // The fraction of a turn, with `FRAC` fractional bits.
+    let turns = if shift <= 0 {
+        0
+    } else if shift < i128::BITS as i32 {
+        let frac = x & ((1 << shift) - 1);
+        shift_round(frac, FRAC as i32 - shift).unwrap() & (ONE - 1)
+    } else {
+        shift_round(x, FRAC as i32 - shift).unwrap() & (ONE - 1)
+    };
+    // Reduce to a quarter turn.
+    let quadrant = turns >> (FRAC - 2);
+    let rest = turns & ((ONE >> 2) - 1);
+    let (c, s) = circular_rotate((rest * 2 * PI) >> FRAC);
+    match quadrant {
+        0 => (c, s),
+        1 => (-s, c),
+        2 => (-c, -s),
+        _ => (s, -c),
+    }
+}
+
+/// Normalize a positive value with `FRAC` fractional bits to `(ln(m), exp)`, where the
+/// value is `m * 2^exp` with `m` in [0.5, 1).
+const fn ln_mantissa(val: i128) -> (i128, i32) {
+    let exp = (i128::BITS - val.leading_zeros()) as i32 - FRAC as i32;
+    let mantissa = if exp >= 0 { val >> exp } else { val << -exp };
+    // ln(m) = 2 * atanh((m - 1) / (m + 1)), where |(m - 1) / (m + 1)| <= 1/3.
+    (2 * hyperbolic_vector(mantissa + ONE, mantissa - ONE), exp)
+}
+
+/// Natural logarithm of a positive value with `FRAC` fractional bits.
+pub(crate) const fn ln_internal(val: i128) -> i128 {
+    let (ln_m, exp) = ln_mantissa(val);
+    ln_m + exp as i128 * LN_2
+}
+
+/// Base-2 logarithm of a positive value with `FRAC` fractional bits.
+fn log2_internal(val: i128) -> i128 {
+    let (ln_m, exp) = ln_mantissa(val);
+    (ln_m << FRAC) / LN_2 + ((exp as i128) << FRAC)
+}
+
+/// `e^val` for `|val| <= ln(2) / 2 + 2^-FRAC`, with `FRAC` fractional bits.
+const fn exp_reduced(val: i128) -> i128 {
+    let (cosh, sinh) = hyperbolic_rotate(HYPERBOLIC_INV_GAIN, 0, val);
+    cosh + sinh
+}
+
+/// Convert a value with `FRAC` fractional bits to a raw value with the given shift, rounding
+/// to nearest.
+fn round_internal(val: i128, shift: i32) -> Result<i128, RangeError> {
+    let err = if val < 0 {
+        RangeError::TooSmall
+    } else {
+        RangeError::TooLarge
+    };
+    shift_round(val, shift - FRAC as i32).ok_or(err)
+}
+
+/// `e^-x` for a non-negative ratio `x = num / den`, with `shift` fractional bits and rounded
+/// to nearest.  This is a const fn, so that coefficients can be computed at compile time.
+pub(crate) const fn exp_neg_ratio(num: u64, den: u64, shift: i32) -> i128 {
+    assert!(den > 0, "division by zero");
+    assert!(shift <= 125, "too many fractional bits");
+    // e^-x underflows every supported type well before x = 2^12.
+    if num / den >= 1 << 12 {
+        return 0;
+    }
+    let x = -((((num as i128) << FRAC) + den as i128 / 2) / den as i128);
+    let k = (x + LN_2 / 2).div_euclid(LN_2);
+    let val = exp_reduced(x - k * LN_2);
+    match shift_round(val, shift - FRAC as i32 + k as i32) {
+        Some(val) => val,
+        None => unreachable!(),
+    }
+}
+
+// The functions below operate on raw values with shifts known only at runtime, so that
+// they can be shared by the generic functions and by the language bindings.  Each takes
+// the raw input and its shift, and returns the raw output with shift `out_shift`.
+
+/// Hyperbolic sine of an input in [-1, 1).
+pub(crate) fn sinh_raw(x: i128, shift: i32, out_shift: i32) -> Result<i128, RangeError> {
+    let x = shift_round(x, FRAC as i32 - shift).unwrap();
+    let (_, sinh) = hyperbolic_rotate(HYPERBOLIC_INV_GAIN, 0, x);
+    round_internal(sinh, out_shift)
+}
+
+/// Hyperbolic cosine of an input in [-1, 1).
+pub(crate) fn cosh_raw(x: i128, shift: i32, out_shift: i32) -> Result<i128, RangeError> {
+    let x = shift_round(x, FRAC as i32 - shift).unwrap();
+    let (cosh, _) = hyperbolic_rotate(HYPERBOLIC_INV_GAIN, 0, x);
+    round_internal(cosh, out_shift)
+}
+
+/// Inverse hyperbolic tangent of an input in [-1, 1).
+pub(crate) fn atanh_raw(x: i128, shift: i32, out_shift: i32) -> Result<i128, RangeError> {
+    let x = shift_round(x, FRAC as i32 - shift).unwrap();
+    if x <= -ONE {
+        Err(RangeError::TooSmall)
+    } else if 4 * x.abs() <= 3 * ONE {
+        round_internal(hyperbolic_vector(ONE, x), out_shift)
+    } else {
+        // Vectoring does not converge near ±1: use atanh(x) = (ln(1 + x) - ln(1 - x)) / 2.
+        round_internal((ln_internal(ONE + x) - ln_internal(ONE - x)) / 2, out_shift)
+    }
+}
+
+/// Exponential function of any input.
+pub(crate) fn exp_raw(x: i128, shift: i32, out_shift: i32) -> Result<i128, RangeError> {
+    // e^x overflows (or underflows to zero) every supported type well before |x| = 2^12.
+    let x = match shift_round(x, FRAC as i32 - shift) {
+        Some(val) if val.abs() < ONE << 12 => val,
+        _ if x > 0 => return Err(RangeError::TooLarge),
+        _ => return Ok(0),
+    };
+    let k = (x + LN_2 / 2).div_euclid(LN_2);
+    let r = x - k * LN_2;
+    let val = exp_reduced(r);
+    let err = if k > 0 {
+        RangeError::TooLarge
+    } else {
+        RangeError::TooSmall
+    };
+    shift_round(val, out_shift - FRAC as i32 + k as i32).ok_or(err)
+}
+
+/// Natural logarithm of a positive input.
+pub(crate) fn ln_raw(x: i128, shift: i32, out_shift: i32) -> Result<i128, RangeError> {
+    assert!(x > 0, "logarithm of a non-positive number");
+    // ln(raw * 2^-SHIFT) = ln(raw * 2^-FRAC) + (FRAC - SHIFT) ln(2), where the first term
+    // is computed with `raw` treated as a value with `FRAC` fractional bits.
+    round_internal(
+        ln_internal(x) + (FRAC as i128 - shift as i128) * LN_2,
+        out_shift,
+    )
+}
+
+/// Base-2 exponential function of any input.
+pub(crate) fn exp2_raw(x: i128, shift: i32, out_shift: i32) -> Result<i128, RangeError> {
+    let x = match shift_round(x, FRAC as i32 - shift) {
+        Some(val) if val.abs() < ONE << 12 => val,
+        _ if x > 0 => return Err(RangeError::TooLarge),
+        _ => return Ok(0),
+    };
+    // 2^x = 2^k e^(r ln(2)), with |r| <= 1/2.
+    let k = (x + ONE / 2) >> FRAC;
+    let r = x - (k << FRAC);
+    let val = exp_reduced((r * LN_2) >> FRAC);
+    let err = if k > 0 {
+        RangeError::TooLarge
+    } else {
+        RangeError::TooSmall
+    };
+    shift_round(val, out_shift - FRAC as i32 + k as i32).ok_or(err)
+}
+
+/// Base-2 logarithm of a positive input.
+pub(crate) fn log2_raw(x: i128, shift: i32, out_shift: i32) -> Result<i128, RangeError> {
+    assert!(x > 0, "logarithm of a non-positive number");
+    round_internal(
+        log2_internal(x) + ((FRAC as i128 - shift as i128) << FRAC),
+        out_shift,
+    )
+}
+
+struct UnitRange<T>(PhantomData<T>);
+
+impl<T: Num> UnitRange<T> {
+    /// Compile-time check that the type `T` can only represent values in [-1, 1).
+    const CHECK: () = assert!(
+        T::BITS as i32 - T::SIGNED as i32 - T::SHIFT <= 0,
+        "input must lie in [-1, 1)"
+    );
+}
+
+/// Hyperbolic sine.  The input type must be limited to the range [-1, 1) (checked at
+/// compile time); for larger arguments, use `exp`.
+pub fn sinh<Out: Num, T: Num>(x: T) -> Result<Out, RangeError>
+where
+    T::Raw: Into<i128>,
+    Out::Raw: TryFrom<i128>,
+{
+    let () = UnitRange::<T>::CHECK;
+    from_raw(sinh_raw(x.raw().into(), T::SHIFT, Out::SHIFT))
+}
+
+/// Hyperbolic cosine.  The input type must be limited to the range [-1, 1) (checked at
+/// compile time); for larger arguments, use `exp`.
+pub fn cosh<Out: Num, T: Num>(x: T) -> Result<Out, RangeError>
+where
+    T::Raw: Into<i128>,
+    Out::Raw: TryFrom<i128>,
+{
+    let () = UnitRange::<T>::CHECK;
+    from_raw(cosh_raw(x.raw().into(), T::SHIFT, Out::SHIFT))
+}
+
+/// Inverse hyperbolic tangent.  The input type must be limited to the range [-1, 1)
+/// (checked at compile time).  `atanh(-1)` returns `RangeError::TooSmall`.
+pub fn atanh<Out: Num, T: Num>(x: T) -> Result<Out, RangeError>
+where
+    T::Raw: Into<i128>,
+    Out::Raw: TryFrom<i128>,
+{
+    let () = UnitRange::<T>::CHECK;
+    from_raw(atanh_raw(x.raw().into(), T::SHIFT, Out::SHIFT))
+}
+
+/// Exponential function.  Any input is accepted: the argument is reduced to
+/// `x = k ln(2) + r` with `|r| <= ln(2) / 2`, and `e^r` is computed by CORDIC.
+pub fn exp<Out: Num, T: Num>(x: T) -> Result<Out, RangeError>
+where
+    T::Raw: Into<i128>,
+    Out::Raw: TryFrom<i128>,
+{
+    from_raw(exp_raw(x.raw().into(), T::SHIFT, Out::SHIFT))
+}
+
+/// Natural logarithm.  Panics unless `x` is positive.
+pub fn ln<Out: Num, T: Num>(x: T) -> Result<Out, RangeError>
+where
+    T::Raw: Into<i128>,
+    Out::Raw: TryFrom<i128>,
+{
+    from_raw(ln_raw(x.raw().into(), T::SHIFT, Out::SHIFT))
+}
+
+/// Cosine and sine of an angle in turns (i.e. `(cos(2 pi x), sin(2 pi x))`).  Any input
+/// is accepted, and only its fractional part matters, so angles from a wrapping counter or
+/// an `Encoder` can be used directly.
+pub fn cos_sin_turns<Out: Num, T: Num>(x: T) -> Result<(Out, Out), RangeError>
+where
+    T::Raw: Into<i128>,
+    Out::Raw: TryFrom<i128>,
+{
+    let (c, s) = cos_sin_turns_internal(x.raw().into(), T::SHIFT);
+    Ok((
+        from_raw(round_internal(c, Out::SHIFT))?,
+        from_raw(round_internal(s, Out::SHIFT))?,
+    ))
+}
+
+/// Four-quadrant inverse tangent: the angle of the vector `(x, y)` in turns, in the range
+/// (-1/2, 1/2].  The angle of the zero vector is zero.
+pub fn atan2_turns<Out: Num, T: Num>(y: T, x: T) -> Result<Out, RangeError>
+where
+    T::Raw: Into<i128>,
+    Out::Raw: TryFrom<i128>,
+{
+    from_raw(round_internal(
+        atan2_turns_internal(y.raw().into(), x.raw().into()),
+        Out::SHIFT,
+    ))
+}
+
+/// Base-2 exponential function.  Any input is accepted.
+pub fn exp2<Out: Num, T: Num>(x: T) -> Result<Out, RangeError>
+where
+    T::Raw: Into<i128>,
+    Out::Raw: TryFrom<i128>,
+{
+    from_raw(exp2_raw(x.raw().into(), T::SHIFT, Out::SHIFT))
+}
+
+/// Base-2 logarithm.  Panics unless `x` is positive.
+pub fn log2<Out: Num, T: Num>(x: T) -> Result<Out, RangeError>
+where
+    T::Raw: Into<i128>,
+    Out::Raw: TryFrom<i128>,
+{
+    from_raw(log2_raw(x.raw().into(), T::SHIFT, Out::SHIFT))
+}
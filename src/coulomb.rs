@@ -0,0 +1,116 @@
+use core::marker::PhantomData;
+
+use crate::raw::signed_bits;
+use crate::regression::div_scaled;
+use crate::{Num, Prob, RangeError, I128};
+
+/// Fractional bits of the internal state of charge.
+const FRAC: i32 = 62;
+
+/// Coulomb-counting battery gauge: integrates current samples `Σ I·Δt` into a charge
+/// accumulator, for up to `2^CAPACITY` samples.
+///
+/// Currents have type `I` (positive while charging, negative while discharging) and time
+/// steps have type `D`, so the accumulated charge has the shift `I::SHIFT + D::SHIFT`.  The
+/// accumulator is an `i128`, and it is a compile error to instantiate a `CoulombCounter`
+/// whose sample formats and maximum number of samples could overflow it.  The maximum
+/// runtime is `2^CAPACITY` samples; after that, `record` returns an error rather than
+/// silently wrapping, and the gauge must be recalibrated with `reset`.
+///
+/// The state of charge is the initial state of charge plus the accumulated charge as a
+/// fraction of the battery capacity, clamped to [0, 1].
+#[derive(Clone, Debug)]
+pub struct CoulombCounter<I: Num, D: Num, const CAPACITY: u32> {
+    _types: PhantomData<(I, D)>,
+    count: u64,
+    // Accumulated charge, with the shift `I::SHIFT + D::SHIFT`.
+    charge: i128,
+    // Battery capacity, with the shift `capacity_shift`.
+    capacity: i128,
+    capacity_shift: i32,
+    // State of charge at the last reset, with `FRAC` fractional bits.
+    initial: i128,
+}
+
+impl<I: Num, D: Num, const CAPACITY: u32> CoulombCounter<I, D, CAPACITY>
+where
+    I::Raw: Into<i64>,
+    D::Raw: Into<i64>,
+{
+    /// Maximum number of samples.  Evaluating this constant also checks at compile time
+    /// that the charge accumulator fits in `i128`.
+    const MAX_COUNT: u64 = {
+        assert!(
+            CAPACITY < 64 && signed_bits::<I>() + signed_bits::<D>() + CAPACITY <= i128::BITS,
+            "too many samples or bits for CoulombCounter"
+        );
+        1 << CAPACITY
+    };
+    /// Create a gauge for a battery with the given capacity (in units of the accumulated
+    /// charge, i.e. of `I·D`), starting from the state of charge `soc`.  Panics unless the
+    /// capacity is positive.
+    pub fn new<C: Num, P: Num>(capacity: C, soc: Prob<P>) -> Self
+    where
+        C::Raw: Into<i128>,
+        P::Raw: Into<i128> + TryFrom<i128>,
+    {
+        let _ = Self::MAX_COUNT; // force the compile-time check of the accumulator width
+        let capacity: i128 = capacity.raw().into();
+        assert!(capacity > 0, "battery capacity must be positive");
+        let mut counter = Self {
+            _types: PhantomData,
+            count: 0,
+            charge: 0,
+            capacity,
+            capacity_shift: C::SHIFT,
+            initial: 0,
+        };
+        counter.reset(soc);
+        counter
+    }
+    /// Recalibrate the gauge to the state of charge `soc` (e.g. when the battery is known
+    /// to be full, or from its rested open-circuit voltage), and clear the accumulator.
+    pub fn reset<P: Num>(&mut self, soc: Prob<P>)
+    where
+        P::Raw: Into<i128> + TryFrom<i128>,
+    {
+        let soc: i128 = soc.get().raw().into();
+        self.initial = soc << (FRAC - P::SHIFT);
+        self.charge = 0;
+        self.count = 0;
+    }
+    /// Return the number of samples recorded since the last reset.
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+    /// Add the charge `current * dt` to the accumulator.  Return `RangeError::TooLarge`,
+    /// without recording the sample, if `2^CAPACITY` samples have already been recorded.
+    pub fn record(&mut self, current: I, dt: D) -> Result<(), RangeError> {
+        if self.count >= Self::MAX_COUNT {
+            return Err(RangeError::TooLarge);
+        }
+        self.count += 1;
+        self.charge += current.raw().into() as i128 * dt.raw().into() as i128;
+        Ok(())
+    }
+    /// Return the charge accumulated since the last reset, which has the shift of `I * D`.
+    pub fn charge(
+        &self,
+    ) -> I128<{ signed_bits::<I>() + signed_bits::<D>() + CAPACITY }, { I::SHIFT + D::SHIFT }> {
+        unsafe { I128::new_unchecked(self.charge) }
+    }
+    /// Return the state of charge, rounded to the nearest value of the type `Out`.
+    pub fn state_of_charge<Out: Num>(&self) -> Prob<Out>
+    where
+        Out::Raw: Into<i128> + TryFrom<i128>,
+    {
+        let one = 1 << FRAC;
+        // The accumulated charge as a fraction of the capacity.  If it does not even fit in
+        // `i128`, the state of charge is certainly empty or full.
+        let shift = FRAC + self.capacity_shift - I::SHIFT - D::SHIFT;
+        let fraction = div_scaled(self.charge, self.capacity, shift)
+            .unwrap_or(if self.charge < 0 { -2 * one } else { 2 * one })
+            .clamp(-2 * one, 2 * one);
+        Prob::from_raw_shift((self.initial + fraction).clamp(0, one), FRAC)
+    }
+}
@@ -0,0 +1,170 @@
+use core::ops::{Add, Div, Mul, Sub};
+use core::time::Duration;
+
+use crate::raw::shift_round;
+use crate::{Num, RangeError, U64};
+
+/// Fractional bits of the number of seconds.
+const FRAC: u32 = 32;
+const NANOS_PER_SEC: u128 = 1_000_000_000;
+
+/// A non-negative span of time, stored as a number of seconds with 32 integer bits and 32
+/// fractional bits (i.e. `U64<64, 32>`), which covers more than 136 years with a
+/// resolution of about 0.23 ns.
+///
+/// Durations may be added, subtracted, multiplied and divided by integers, and scaled by any
+/// fixed-point factor.  Like `core::time::Duration`, the arithmetic operators panic on
+/// overflow, and the `checked_` methods return `None` instead.  Conversions to and from
+/// `Duration` and timer ticks are rounded to nearest.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct FixedDuration(u64);
+
+impl FixedDuration {
+    /// The zero duration.
+    pub const ZERO: Self = Self(0);
+    /// The maximum duration, which is just under 2^32 seconds.
+    pub const MAX: Self = Self(u64::MAX);
+    /// One second.
+    pub const SECOND: Self = Self(1 << FRAC);
+    /// Create a duration of `raw * 2^-32` seconds.
+    pub const fn from_raw(raw: u64) -> Self {
+        Self(raw)
+    }
+    /// Return the raw value, which is the number of seconds times `2^32`.
+    pub const fn raw(self) -> u64 {
+        self.0
+    }
+    /// Create a duration from a number of seconds.
+    pub const fn from_seconds(seconds: U64<64, 32>) -> Self {
+        Self(seconds.raw_const())
+    }
+    /// Return the number of seconds.
+    pub const fn seconds(self) -> U64<64, 32> {
+        U64::new_const(self.0)
+    }
+    /// Return the number of whole seconds.
+    pub const fn as_secs(self) -> u32 {
+        (self.0 >> FRAC) as u32
+    }
+    /// Return `self + other`, or `None` if it overflows.
+    pub const fn checked_add(self, other: Self) -> Option<Self> {
+        match self.0.checked_add(other.0) {
+            Some(raw) => Some(Self(raw)),
+            None => None,
+        }
+    }
+    /// Return `self - other`, or `None` if it is negative.
+    pub const fn checked_sub(self, other: Self) -> Option<Self> {
+        match self.0.checked_sub(other.0) {
+            Some(raw) => Some(Self(raw)),
+            None => None,
+        }
+    }
+    /// Return `self - other`, or zero if it is negative.
+    pub const fn saturating_sub(self, other: Self) -> Self {
+        Self(self.0.saturating_sub(other.0))
+    }
+    /// Return `self * factor`, or `None` if it overflows.
+    pub const fn checked_mul(self, factor: u32) -> Option<Self> {
+        match self.0.checked_mul(factor as u64) {
+            Some(raw) => Some(Self(raw)),
+            None => None,
+        }
+    }
+    /// Return `self / divisor`, rounded toward zero, or `None` if `divisor` is zero.
+    pub const fn checked_div(self, divisor: u32) -> Option<Self> {
+        match self.0.checked_div(divisor as u64) {
+            Some(raw) => Some(Self(raw)),
+            None => None,
+        }
+    }
+    /// Return `self * factor` for a fixed-point factor, rounded to nearest, or `None` if
+    /// the factor is negative or the result overflows.
+    pub fn checked_scale<T: Num>(self, factor: T) -> Option<Self>
+    where
+        T::Raw: Into<i128>,
+    {
+        let factor: i128 = factor.raw().into();
+        if factor < 0 {
+            return None;
+        }
+        let product = factor.checked_mul(self.0.into())?;
+        shift_round(product, -T::SHIFT)?.try_into().ok().map(Self)
+    }
+    /// Return the duration of `ticks` periods of a timer running at `hz`, rounded to
+    /// nearest, or `None` if it overflows.  Panics if `hz` is zero.
+    pub const fn from_ticks(ticks: u64, hz: u32) -> Option<Self> {
+        assert!(hz > 0, "timer frequency must be positive");
+        let raw = (((ticks as u128) << FRAC) + hz as u128 / 2) / hz as u128;
+        if raw > u64::MAX as u128 {
+            None
+        } else {
+            Some(Self(raw as u64))
+        }
+    }
+    /// Return the number of ticks of a timer running at `hz` in this duration, rounded to
+    /// nearest.
+    pub const fn to_ticks(self, hz: u32) -> u64 {
+        let ticks = (self.0 as u128 * hz as u128 + (1 << (FRAC - 1))) >> FRAC;
+        ticks as u64
+    }
+}
+
+/// Durations may be added.  Panics on overflow.
+impl Add for FixedDuration {
+    type Output = Self;
+    fn add(self, other: Self) -> Self {
+        self.checked_add(other)
+            .expect("overflow when adding durations")
+    }
+}
+
+/// Durations may be subtracted.  Panics if the result is negative.
+impl Sub for FixedDuration {
+    type Output = Self;
+    fn sub(self, other: Self) -> Self {
+        self.checked_sub(other)
+            .expect("overflow when subtracting durations")
+    }
+}
+
+/// Durations may be multiplied by an integer.  Panics on overflow.
+impl Mul<u32> for FixedDuration {
+    type Output = Self;
+    fn mul(self, factor: u32) -> Self {
+        self.checked_mul(factor)
+            .expect("overflow when multiplying duration by scalar")
+    }
+}
+
+/// Durations may be divided by an integer, rounded toward zero.  Panics if the divisor is
+/// zero.
+impl Div<u32> for FixedDuration {
+    type Output = Self;
+    fn div(self, divisor: u32) -> Self {
+        self.checked_div(divisor)
+            .expect("divide by zero error when dividing duration by scalar")
+    }
+}
+
+/// Conversion to `Duration`, rounded to the nearest nanosecond, which never fails.
+impl From<FixedDuration> for Duration {
+    fn from(val: FixedDuration) -> Duration {
+        let nanos = (u128::from(val.0) * NANOS_PER_SEC + (1 << (FRAC - 1))) >> FRAC;
+        Duration::new(
+            (nanos / NANOS_PER_SEC) as u64,
+            (nanos % NANOS_PER_SEC) as u32,
+        )
+    }
+}
+
+/// Conversion from `Duration`, rounded to nearest, which returns `RangeError::TooLarge` if
+/// the duration is 2^32 seconds or more.
+impl TryFrom<Duration> for FixedDuration {
+    type Error = RangeError;
+    fn try_from(val: Duration) -> Result<Self, RangeError> {
+        let frac = ((u128::from(val.subsec_nanos()) << FRAC) + NANOS_PER_SEC / 2) / NANOS_PER_SEC;
+        let raw = (u128::from(val.as_secs()) << FRAC) + frac;
+        raw.try_into().map(Self).map_err(|_| RangeError::TooLarge)
+    }
+}
@@ -0,0 +1,207 @@
+use crate::raw::{from_raw, shift_round};
+use crate::{Num, RangeError};
+
+/// Fractional bits of the internal velocity estimate and filter coefficient.
+const FRAC: u32 = 32;
+
+/// Position and velocity estimator for a quadrature encoder read through a wrapping
+/// hardware counter.
+///
+/// Each call to `update` takes the current value of the counter, and accumulates the
+/// change since the previous call into a 64-bit multi-turn position, so counter wrap-around
+/// is handled as long as the counter moves by less than half its range between updates.
+///
+/// Velocity is estimated by differentiating the position over a window of `WINDOW`
+/// samples, and optionally smoothing the result with a one-pole lowpass filter (see
+/// `with_filter`).  A longer window reduces quantization noise at the cost of delay.
+///
+/// Position, angle and velocity are returned in revolutions (and revolutions per second)
+/// as the caller's choice of fixed-point type, rounded to nearest.
+#[derive(Clone, Debug)]
+pub struct Encoder<const WINDOW: usize> {
+    counts_per_rev: u32,
+    sample_rate: u32,
+    counter_bits: u32,
+    last_count: Option<u32>,
+    // Accumulated position in counts.
+    position: i64,
+    // Ring buffer of the positions at the previous `WINDOW` updates.
+    history: [i64; WINDOW],
+    next: usize,
+    filled: usize,
+    // Filter coefficient, with `FRAC` fractional bits.
+    alpha: i64,
+    // Velocity in counts per sample, with `FRAC` fractional bits.
+    velocity: i64,
+}
+
+impl<const WINDOW: usize> Encoder<WINDOW> {
+    /// Create an estimator for an encoder with `counts_per_rev` counts per revolution (i.e.
+    /// four times the number of lines), read through a `counter_bits`-bit hardware counter
+    /// at `sample_rate` updates per second.  The position starts at zero.
+    pub fn new(counts_per_rev: u32, counter_bits: u32, sample_rate: u32) -> Self {
+        assert!(
+            WINDOW > 0,
+            "velocity window must contain at least one sample"
+        );
+        assert!(counts_per_rev > 0, "counts per revolution must be positive");
+        assert!(
+            (1..=32).contains(&counter_bits),
+            "counter must have between 1 and 32 bits"
+        );
+        assert!(sample_rate > 0, "sample rate must be positive");
+        Self {
+            counts_per_rev,
+            sample_rate,
+            counter_bits,
+            last_count: None,
+            position: 0,
+            history: [0; WINDOW],
+            next: 0,
+            filled: 0,
+            alpha: 1 << FRAC,
+            velocity: 0,
+        }
+    }
+    /// Smooth the velocity estimate with the one-pole lowpass filter
+    /// `v += alpha (v_window - v)`, where `alpha` is a fixed-point number in (0, 1] (e.g.
+    /// from `one_pole_alpha`).  The default `alpha = 1` applies no filtering.
+    pub fn with_filter<A: Num>(mut self, alpha: A) -> Self
+    where
+        A::Raw: Into<i128>,
+    {
+        let raw: i128 = alpha.raw().into();
+        let shift = FRAC as i32 - A::SHIFT;
+        let alpha = if shift >= 0 {
+            raw.checked_shl(shift as u32).filter(|a| a >> shift == raw)
+        } else {
+            Some(raw >> -shift)
+        };
+        match alpha {
+            Some(alpha) if alpha > 0 && alpha <= 1 << FRAC => self.alpha = alpha as i64,
+            _ => panic!("filter coefficient must lie in (0, 1]"),
+        }
+        self
+    }
+    /// Return the signed change from the previous counter value to `count`.
+    fn delta(&self, count: u32) -> i64 {
+        let unused = 32 - self.counter_bits;
+        match self.last_count {
+            Some(last) => ((count.wrapping_sub(last) << unused) as i32 >> unused) as i64,
+            None => 0,
+        }
+    }
+    /// Update the estimate with the current value of the hardware counter.  Only the
+    /// `counter_bits` least significant bits of `count` are used.  The first update only
+    /// establishes the reference count, and does not move the position.
+    pub fn update(&mut self, count: u32) {
+        self.position += self.delta(count);
+        self.last_count = Some(count);
+        // Differentiate over the window, or over the samples seen so far.
+        let (oldest, samples) = if self.filled == WINDOW {
+            (self.history[self.next], WINDOW)
+        } else {
+            (self.history[0], self.filled)
+        };
+        if samples > 0 {
+            let window = (((self.position - oldest) as i128) << FRAC) / samples as i128;
+            let error = window - self.velocity as i128;
+            self.velocity += ((error * self.alpha as i128) >> FRAC) as i64;
+        }
+        self.history[self.next] = self.position;
+        self.next = (self.next + 1) % WINDOW;
+        self.filled = (self.filled + 1).min(WINDOW);
+    }
+    /// Handle an index pulse, given the value of the hardware counter latched at the index.
+    ///
+    /// The index marks a known angle, so the position at the index should be a whole
+    /// number of revolutions.  The position (including the velocity history) is shifted by
+    /// the nearest correction which makes it so, and the correction in counts is returned:
+    /// a nonzero correction after the first index indicates missed or spurious counts.
+    pub fn index(&mut self, latched: u32) -> i64 {
+        let at_index = self.position + self.delta(latched);
+        let cpr = self.counts_per_rev as i64;
+        let mut error = at_index.rem_euclid(cpr);
+        if 2 * error >= cpr {
+            error -= cpr;
+        }
+        self.position -= error;
+        for past in self.history.iter_mut() {
+            *past -= error;
+        }
+        -error
+    }
+    /// Return the accumulated position in counts.
+    pub fn counts(&self) -> i64 {
+        self.position
+    }
+    /// Return the accumulated position in revolutions, or a `RangeError` if it does not fit
+    /// in `Out`.
+    pub fn position<Out: Num>(&self) -> Result<Out, RangeError>
+    where
+        Out::Raw: TryFrom<i128>,
+    {
+        scale(self.position as i128, 0, self.counts_per_rev as i128)
+    }
+    /// Return the angle within the current revolution, as a fraction of a revolution in
+    /// [0, 1), or a `RangeError` if it does not fit in `Out`.
+    pub fn angle<Out: Num>(&self) -> Result<Out, RangeError>
+    where
+        Out::Raw: TryFrom<i128>,
+    {
+        let cpr = self.counts_per_rev as i128;
+        let raw = scale_raw(
+            self.position.rem_euclid(cpr as i64) as i128,
+            0,
+            cpr,
+            Out::SHIFT,
+        )?;
+        // An angle which rounds up to a full revolution wraps around to zero.
+        let full = scale_raw(cpr, 0, cpr, Out::SHIFT)?;
+        from_raw(Ok(if raw == full { 0 } else { raw }))
+    }
+    /// Return the velocity in revolutions per second, or a `RangeError` if it does not fit
+    /// in `Out`.
+    pub fn velocity<Out: Num>(&self) -> Result<Out, RangeError>
+    where
+        Out::Raw: TryFrom<i128>,
+    {
+        let num = self.velocity as i128 * self.sample_rate as i128;
+        scale(num, FRAC, self.counts_per_rev as i128)
+    }
+}
+
+/// Return the raw value of `num * 2^-frac / den` with the given shift, rounded to nearest
+/// (ties away from zero).
+fn scale_raw(num: i128, frac: u32, den: i128, shift: i32) -> Result<i128, RangeError> {
+    let err = if num < 0 {
+        RangeError::TooSmall
+    } else {
+        RangeError::TooLarge
+    };
+    let exp = shift - frac as i32;
+    let (num, den) = if exp >= 0 {
+        let scaled = num.checked_shl(exp as u32).filter(|n| n >> exp == num);
+        (scaled.ok_or(err)?, den)
+    } else if -exp <= den.leading_zeros() as i32 - 2 {
+        (num, den << -exp)
+    } else {
+        // Shifting the denominator by the whole amount would overflow: shift the numerator
+        // by the rest.
+        let room = den.leading_zeros() as i32 - 2;
+        (shift_round(num, exp + room).unwrap(), den << room)
+    };
+    let half = den / 2;
+    Ok(if num >= 0 {
+        (num + half) / den
+    } else {
+        (num - half) / den
+    })
+}
+
+fn scale<Out: Num>(num: i128, frac: u32, den: i128) -> Result<Out, RangeError>
+where
+    Out::Raw: TryFrom<i128>,
+{
+    from_raw(scale_raw(num, frac, den, Out::SHIFT))
+}
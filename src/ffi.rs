@@ -0,0 +1,516 @@
+//! C ABI for exchanging fixed-point values with C code, enabled by the `ffi` feature.
+//!
+//! Every fixed-point type such as `I32<BITS, SHIFT>` is `#[repr(transparent)]` over its
+//! raw integer type.  This is a stable guarantee: fixed-point values may be passed to and
+//! from C as the corresponding `<stdint.h>` type (`int32_t` in this example), and the
+//! logical value is the raw value divided by `2^SHIFT`.
+//!
+//! C code has no access to `BITS` and `SHIFT`, so the functions in this module take them
+//! as arguments, and perform the same checks and conversions as the corresponding Rust
+//! methods.  There is one function of each kind for each raw type up to 64 bits.  Results
+//! are written through an output pointer, and the return value is an [`FpStatus`]: the
+//! output is only written if the status is `FP_STATUS_OK`.
+//!
+//! With the `ffi-header` feature, the build script generates the C header `fp.h` in
+//! Cargo's `OUT_DIR` using cbindgen.  The header can also be generated with the cbindgen
+//! command-line tool, using the configuration in `cbindgen.toml`.
+
+use crate::float::{f64_from_raw, f64_into_parts, from_parts_in};
+use crate::num_impl::runtime_limits;
+use crate::{Num, RangeError};
+
+/// Result of a checked conversion.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum FpStatus {
+    /// The conversion succeeded.
+    Ok = 0,
+    /// The value is too small to be represented (`RangeError::TooSmall`).
+    TooSmall = 1,
+    /// The value is too large to be represented (`RangeError::TooLarge`).
+    TooLarge = 2,
+    /// The value cannot be represented exactly.
+    Inexact = 3,
+    /// An argument is invalid, e.g. `bits` is larger than the raw type or a float is not
+    /// finite.
+    Invalid = 4,
+}
+
+impl From<RangeError> for FpStatus {
+    fn from(err: RangeError) -> Self {
+        match err {
+            RangeError::TooSmall => FpStatus::TooSmall,
+            RangeError::TooLarge => FpStatus::TooLarge,
+        }
+    }
+}
+
+/// Write `val` to `out`, or return the error status.
+///
+/// # Safety
+///
+/// `out` must be valid for writes.
+unsafe fn write<T>(val: Result<T, FpStatus>, out: *mut T) -> FpStatus {
+    match val {
+        Ok(val) => {
+            unsafe { out.write(val) };
+            FpStatus::Ok
+        }
+        Err(status) => status,
+    }
+}
+
+/// Return the raw limits of a `bits`-bit format with raw type `T`.
+fn limits<T: Num>(bits: u32) -> Result<(i128, i128), FpStatus> {
+    if bits <= T::BITS {
+        Ok(runtime_limits(bits, T::SIGNED))
+    } else {
+        Err(FpStatus::Invalid)
+    }
+}
+
+fn in_range<T: Num + Into<i128>>(raw: T, bits: u32) -> bool {
+    limits::<T>(bits).is_ok_and(|(min, max)| (min..=max).contains(&raw.into()))
+}
+
+fn from_f64<T: Num + TryFrom<i128>>(val: f64, bits: u32, shift: i32) -> Result<T, FpStatus> {
+    if !val.is_finite() {
+        return Err(FpStatus::Invalid);
+    }
+    let (min, max) = limits::<T>(bits)?;
+    let (negative, mantissa, exp) = f64_into_parts(val);
+    // The caller may pass any shift: a saturated exponent is equally out of range (or zero).
+    let raw = from_parts_in(negative, mantissa, exp.saturating_add(shift), min, max)?;
+    Ok(raw.try_into().ok().unwrap())
+}
+
+fn to_f64<T: Num + Into<i128>>(raw: T, shift: i32) -> Result<f64, FpStatus> {
+    let raw: i128 = raw.into();
+    if raw == 0 {
+        return Ok(0.);
+    }
+    // Remove trailing zeros, so that only the significant bits must fit in the mantissa.
+    // The caller may pass any shift, so compute the exponents in `i64`, like `rescale`.
+    let zeros = raw.trailing_zeros();
+    let (raw, shift) = (raw >> zeros, shift as i64 - zeros as i64);
+    let msb = (i128::BITS - raw.unsigned_abs().leading_zeros()) as i64 - 1;
+    if msb >= f64::MANTISSA_DIGITS as i64
+        || shift > f64::MANTISSA_DIGITS as i64 - f64::MIN_EXP as i64
+        || msb - shift >= f64::MAX_EXP as i64
+    {
+        Err(FpStatus::Inexact)
+    } else {
+        // The checks above bound the shift to the exponent range of `f64`.
+        Ok(f64_from_raw(raw, shift as i32))
+    }
+}
+
+fn rescale<T: Num + Into<i128> + TryFrom<i128>>(
+    raw: T,
+    from_shift: i32,
+    to_shift: i32,
+    bits: u32,
+) -> Result<T, FpStatus> {
+    let (min, max) = limits::<T>(bits)?;
+    let raw: i128 = raw.into();
+    let err = if raw < 0 {
+        FpStatus::TooSmall
+    } else {
+        FpStatus::TooLarge
+    };
+    let shift = to_shift as i64 - from_shift as i64;
+    let raw = if raw == 0 {
+        0
+    } else if shift >= 0 {
+        // A nonzero `raw` has at most 127 leading zeros, so this also bounds the shift.
+        if shift >= raw.unsigned_abs().leading_zeros() as i64 {
+            return Err(err);
+        }
+        raw << shift
+    } else if shift > -(i128::BITS as i64) {
+        // Round to nearest, with ties toward positive infinity.
+        ((raw >> (-shift - 1)) + 1) >> 1
+    } else {
+        0
+    };
+    if raw < min {
+        Err(FpStatus::TooSmall)
+    } else if raw > max {
+        Err(FpStatus::TooLarge)
+    } else {
+        Ok(raw.try_into().ok().unwrap())
+    }
+}
+
+// The functions below are written out for each raw type (rather than generated by a macro)
+// so that cbindgen can see them.
+
+/// Return whether `raw` is a valid raw value for a fixed-point number with `bits` bits.
+#[no_mangle]
+pub extern "C" fn fp_i8_in_range(raw: i8, bits: u32) -> bool {
+    in_range(raw, bits)
+}
+/// Return whether `raw` is a valid raw value for a fixed-point number with `bits` bits.
+#[no_mangle]
+pub extern "C" fn fp_i16_in_range(raw: i16, bits: u32) -> bool {
+    in_range(raw, bits)
+}
+/// Return whether `raw` is a valid raw value for a fixed-point number with `bits` bits.
+#[no_mangle]
+pub extern "C" fn fp_i32_in_range(raw: i32, bits: u32) -> bool {
+    in_range(raw, bits)
+}
+/// Return whether `raw` is a valid raw value for a fixed-point number with `bits` bits.
+#[no_mangle]
+pub extern "C" fn fp_i64_in_range(raw: i64, bits: u32) -> bool {
+    in_range(raw, bits)
+}
+/// Return whether `raw` is a valid raw value for a fixed-point number with `bits` bits.
+#[no_mangle]
+pub extern "C" fn fp_u8_in_range(raw: u8, bits: u32) -> bool {
+    in_range(raw, bits)
+}
+/// Return whether `raw` is a valid raw value for a fixed-point number with `bits` bits.
+#[no_mangle]
+pub extern "C" fn fp_u16_in_range(raw: u16, bits: u32) -> bool {
+    in_range(raw, bits)
+}
+/// Return whether `raw` is a valid raw value for a fixed-point number with `bits` bits.
+#[no_mangle]
+pub extern "C" fn fp_u32_in_range(raw: u32, bits: u32) -> bool {
+    in_range(raw, bits)
+}
+/// Return whether `raw` is a valid raw value for a fixed-point number with `bits` bits.
+#[no_mangle]
+pub extern "C" fn fp_u64_in_range(raw: u64, bits: u32) -> bool {
+    in_range(raw, bits)
+}
+
+/// Convert `val` to the raw value of a fixed-point number with `bits` bits and shift
+/// `shift`, rounding toward zero, like `Num::from_f64`.
+///
+/// # Safety
+///
+/// `out` must be valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn fp_i8_from_f64(val: f64, bits: u32, shift: i32, out: *mut i8) -> FpStatus {
+    unsafe { write(from_f64(val, bits, shift), out) }
+}
+/// Convert `val` to the raw value of a fixed-point number with `bits` bits and shift
+/// `shift`, rounding toward zero, like `Num::from_f64`.
+///
+/// # Safety
+///
+/// `out` must be valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn fp_i16_from_f64(
+    val: f64,
+    bits: u32,
+    shift: i32,
+    out: *mut i16,
+) -> FpStatus {
+    unsafe { write(from_f64(val, bits, shift), out) }
+}
+/// Convert `val` to the raw value of a fixed-point number with `bits` bits and shift
+/// `shift`, rounding toward zero, like `Num::from_f64`.
+///
+/// # Safety
+///
+/// `out` must be valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn fp_i32_from_f64(
+    val: f64,
+    bits: u32,
+    shift: i32,
+    out: *mut i32,
+) -> FpStatus {
+    unsafe { write(from_f64(val, bits, shift), out) }
+}
+/// Convert `val` to the raw value of a fixed-point number with `bits` bits and shift
+/// `shift`, rounding toward zero, like `Num::from_f64`.
+///
+/// # Safety
+///
+/// `out` must be valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn fp_i64_from_f64(
+    val: f64,
+    bits: u32,
+    shift: i32,
+    out: *mut i64,
+) -> FpStatus {
+    unsafe { write(from_f64(val, bits, shift), out) }
+}
+/// Convert `val` to the raw value of a fixed-point number with `bits` bits and shift
+/// `shift`, rounding toward zero, like `Num::from_f64`.
+///
+/// # Safety
+///
+/// `out` must be valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn fp_u8_from_f64(val: f64, bits: u32, shift: i32, out: *mut u8) -> FpStatus {
+    unsafe { write(from_f64(val, bits, shift), out) }
+}
+/// Convert `val` to the raw value of a fixed-point number with `bits` bits and shift
+/// `shift`, rounding toward zero, like `Num::from_f64`.
+///
+/// # Safety
+///
+/// `out` must be valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn fp_u16_from_f64(
+    val: f64,
+    bits: u32,
+    shift: i32,
+    out: *mut u16,
+) -> FpStatus {
+    unsafe { write(from_f64(val, bits, shift), out) }
+}
+/// Convert `val` to the raw value of a fixed-point number with `bits` bits and shift
+/// `shift`, rounding toward zero, like `Num::from_f64`.
+///
+/// # Safety
+///
+/// `out` must be valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn fp_u32_from_f64(
+    val: f64,
+    bits: u32,
+    shift: i32,
+    out: *mut u32,
+) -> FpStatus {
+    unsafe { write(from_f64(val, bits, shift), out) }
+}
+/// Convert `val` to the raw value of a fixed-point number with `bits` bits and shift
+/// `shift`, rounding toward zero, like `Num::from_f64`.
+///
+/// # Safety
+///
+/// `out` must be valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn fp_u64_from_f64(
+    val: f64,
+    bits: u32,
+    shift: i32,
+    out: *mut u64,
+) -> FpStatus {
+    unsafe { write(from_f64(val, bits, shift), out) }
+}
+
+/// Convert the fixed-point number with raw value `raw` and shift `shift` to `f64`.
+/// Return `FP_STATUS_INEXACT` if the value cannot be represented exactly.
+///
+/// # Safety
+///
+/// `out` must be valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn fp_i8_to_f64(raw: i8, shift: i32, out: *mut f64) -> FpStatus {
+    unsafe { write(to_f64(raw, shift), out) }
+}
+/// Convert the fixed-point number with raw value `raw` and shift `shift` to `f64`.
+/// Return `FP_STATUS_INEXACT` if the value cannot be represented exactly.
+///
+/// # Safety
+///
+/// `out` must be valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn fp_i16_to_f64(raw: i16, shift: i32, out: *mut f64) -> FpStatus {
+    unsafe { write(to_f64(raw, shift), out) }
+}
+/// Convert the fixed-point number with raw value `raw` and shift `shift` to `f64`.
+/// Return `FP_STATUS_INEXACT` if the value cannot be represented exactly.
+///
+/// # Safety
+///
+/// `out` must be valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn fp_i32_to_f64(raw: i32, shift: i32, out: *mut f64) -> FpStatus {
+    unsafe { write(to_f64(raw, shift), out) }
+}
+/// Convert the fixed-point number with raw value `raw` and shift `shift` to `f64`.
+/// Return `FP_STATUS_INEXACT` if the value cannot be represented exactly.
+///
+/// # Safety
+///
+/// `out` must be valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn fp_i64_to_f64(raw: i64, shift: i32, out: *mut f64) -> FpStatus {
+    unsafe { write(to_f64(raw, shift), out) }
+}
+/// Convert the fixed-point number with raw value `raw` and shift `shift` to `f64`.
+/// Return `FP_STATUS_INEXACT` if the value cannot be represented exactly.
+///
+/// # Safety
+///
+/// `out` must be valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn fp_u8_to_f64(raw: u8, shift: i32, out: *mut f64) -> FpStatus {
+    unsafe { write(to_f64(raw, shift), out) }
+}
+/// Convert the fixed-point number with raw value `raw` and shift `shift` to `f64`.
+/// Return `FP_STATUS_INEXACT` if the value cannot be represented exactly.
+///
+/// # Safety
+///
+/// `out` must be valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn fp_u16_to_f64(raw: u16, shift: i32, out: *mut f64) -> FpStatus {
+    unsafe { write(to_f64(raw, shift), out) }
+}
+/// Convert the fixed-point number with raw value `raw` and shift `shift` to `f64`.
+/// Return `FP_STATUS_INEXACT` if the value cannot be represented exactly.
+///
+/// # Safety
+///
+/// `out` must be valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn fp_u32_to_f64(raw: u32, shift: i32, out: *mut f64) -> FpStatus {
+    unsafe { write(to_f64(raw, shift), out) }
+}
+/// Convert the fixed-point number with raw value `raw` and shift `shift` to `f64`.
+/// Return `FP_STATUS_INEXACT` if the value cannot be represented exactly.
+///
+/// # Safety
+///
+/// `out` must be valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn fp_u64_to_f64(raw: u64, shift: i32, out: *mut f64) -> FpStatus {
+    unsafe { write(to_f64(raw, shift), out) }
+}
+
+/// Convert the raw value `raw` with shift `from_shift` to the raw value of a fixed-point
+/// number with `bits` bits and shift `to_shift`, rounding to nearest (ties toward positive
+/// infinity).
+///
+/// # Safety
+///
+/// `out` must be valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn fp_i8_rescale(
+    raw: i8,
+    from_shift: i32,
+    to_shift: i32,
+    bits: u32,
+    out: *mut i8,
+) -> FpStatus {
+    unsafe { write(rescale(raw, from_shift, to_shift, bits), out) }
+}
+/// Convert the raw value `raw` with shift `from_shift` to the raw value of a fixed-point
+/// number with `bits` bits and shift `to_shift`, rounding to nearest (ties toward positive
+/// infinity).
+///
+/// # Safety
+///
+/// `out` must be valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn fp_i16_rescale(
+    raw: i16,
+    from_shift: i32,
+    to_shift: i32,
+    bits: u32,
+    out: *mut i16,
+) -> FpStatus {
+    unsafe { write(rescale(raw, from_shift, to_shift, bits), out) }
+}
+/// Convert the raw value `raw` with shift `from_shift` to the raw value of a fixed-point
+/// number with `bits` bits and shift `to_shift`, rounding to nearest (ties toward positive
+/// infinity).
+///
+/// # Safety
+///
+/// `out` must be valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn fp_i32_rescale(
+    raw: i32,
+    from_shift: i32,
+    to_shift: i32,
+    bits: u32,
+    out: *mut i32,
+) -> FpStatus {
+    unsafe { write(rescale(raw, from_shift, to_shift, bits), out) }
+}
+/// Convert the raw value `raw` with shift `from_shift` to the raw value of a fixed-point
+/// number with `bits` bits and shift `to_shift`, rounding to nearest (ties toward positive
+/// infinity).
+///
+/// # Safety
+///
+/// `out` must be valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn fp_i64_rescale(
+    raw: i64,
+    from_shift: i32,
+    to_shift: i32,
+    bits: u32,
+    out: *mut i64,
+) -> FpStatus {
+    unsafe { write(rescale(raw, from_shift, to_shift, bits), out) }
+}
+/// Convert the raw value `raw` with shift `from_shift` to the raw value of a fixed-point
+/// number with `bits` bits and shift `to_shift`, rounding to nearest (ties toward positive
+/// infinity).
+///
+/// # Safety
+///
+/// `out` must be valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn fp_u8_rescale(
+    raw: u8,
+    from_shift: i32,
+    to_shift: i32,
+    bits: u32,
+    out: *mut u8,
+) -> FpStatus {
+    unsafe { write(rescale(raw, from_shift, to_shift, bits), out) }
+}
+/// Convert the raw value `raw` with shift `from_shift` to the raw value of a fixed-point
+/// number with `bits` bits and shift `to_shift`, rounding to nearest (ties toward positive
+/// infinity).
+///
+/// # Safety
+///
+/// `out` must be valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn fp_u16_rescale(
+    raw: u16,
+    from_shift: i32,
+    to_shift: i32,
+    bits: u32,
+    out: *mut u16,
+) -> FpStatus {
+    unsafe { write(rescale(raw, from_shift, to_shift, bits), out) }
+}
+/// Convert the raw value `raw` with shift `from_shift` to the raw value of a fixed-point
+/// number with `bits` bits and shift `to_shift`, rounding to nearest (ties toward positive
+/// infinity).
+///
+/// # Safety
+///
+/// `out` must be valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn fp_u32_rescale(
+    raw: u32,
+    from_shift: i32,
+    to_shift: i32,
+    bits: u32,
+    out: *mut u32,
+) -> FpStatus {
+    unsafe { write(rescale(raw, from_shift, to_shift, bits), out) }
+}
+/// Convert the raw value `raw` with shift `from_shift` to the raw value of a fixed-point
+/// number with `bits` bits and shift `to_shift`, rounding to nearest (ties toward positive
+/// infinity).
+///
+/// # Safety
+///
+/// `out` must be valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn fp_u64_rescale(
+    raw: u64,
+    from_shift: i32,
+    to_shift: i32,
+    bits: u32,
+    out: *mut u64,
+) -> FpStatus {
+    unsafe { write(rescale(raw, from_shift, to_shift, bits), out) }
+}
@@ -0,0 +1,152 @@
+use crate::cordic::exp_neg_ratio;
+use crate::*;
+
+// Coefficient design for first-order filters, in engineering terms.  These are const fns,
+// so coefficients are specified as a time constant and a sample rate but compiled to exact
+// fixed-point constants:
+//
+//     // 1 kHz sample rate, 20 ms time constant.
+//     const ALPHA: U16<16, 16> = U16::one_pole_alpha(1, 20);
+macro_rules! filter_impl {
+    ($Name:ident, $T:ty) => {
+        impl<const BITS: u32, const SHIFT: i32> $Name<BITS, SHIFT> {
+            /// Return the coefficient `alpha = 1 - e^(-T / tau)` of the one-pole lowpass
+            /// filter (or exponential moving average) `y += alpha (x - y)`, where `T` is the
+            /// sample period and `tau` is the time constant, rounded to nearest.
+            ///
+            /// `period` and `tau` may be given in any unit, as long as it is the same for
+            /// both.  For a sample rate `fs` in Hz and a time constant in microseconds, use
+            /// `one_pole_alpha(1_000_000, fs * tau)`.
+            pub const fn one_pole_alpha(period: u64, tau: u64) -> Self {
+                assert!(SHIFT >= 0, "coefficient must have a non-negative shift");
+                let one = 1 << SHIFT;
+                Self::from_coefficient(one - exp_neg_ratio(period, tau, SHIFT))
+            }
+            /// Return the coefficient `a = e^(-T / tau)` of the one-pole lowpass filter
+            /// `y = a y + (1 - a) x`, i.e. `1 - one_pole_alpha(period, tau)`, rounded to
+            /// nearest.
+            pub const fn one_pole_decay(period: u64, tau: u64) -> Self {
+                Self::from_coefficient(exp_neg_ratio(period, tau, SHIFT))
+            }
+            /// Return the feedback coefficients `a` unchanged if the IIR filter
+            /// `y[n] = ... - a[0] y[n-1] - a[1] y[n-2] - ...` is stable, and panic otherwise,
+            /// which is a compile-time error in a const context:
+            ///
+            /// `const A: [I32<32, 30>; 2] = I32::assert_iir_stable([a1, a2]);`
+            ///
+            /// The check is exact for the quantized coefficients (see `iir_is_stable`), so it
+            /// also catches filters which are only made unstable by quantization.
+            pub const fn assert_iir_stable<const N: usize>(a: [Self; N]) -> [Self; N] {
+                let mut raw = [0; N];
+                let mut i = 0;
+                while i < N {
+                    let val = a[i].raw_const();
+                    assert!(val as i128 as $T == val, "coefficient does not fit in i128");
+                    raw[i] = val as i128;
+                    i += 1;
+                }
+                assert!(iir_stable_raw(raw, SHIFT), "IIR filter is unstable");
+                a
+            }
+            const fn from_coefficient(raw: i128) -> Self {
+                assert!(
+                    raw as $T as i128 == raw,
+                    "coefficient does not fit in raw type"
+                );
+                Self::new_const(raw as $T)
+            }
+        }
+    };
+}
+
+/// Return whether the polynomial `z^N + a[0] z^(N-1) + ... + a[N-1]`, with coefficients
+/// given as raw values with the given shift, has all its roots strictly inside the unit
+/// circle.
+///
+/// This is the Schur-Cohn test: a polynomial `p` of degree `m` is stable iff `|p_m| < |p_0|`
+/// and the polynomial `p_0 p_i - p_m p_(m-i)` (for `i < m`) of degree `m - 1` is stable.
+/// The recursion is evaluated in exact integer arithmetic, dividing each polynomial by the
+/// greatest common divisor of its coefficients.  Panics if an intermediate product
+/// overflows `i128`, which can only happen for high orders with wide coefficients.
+const fn iir_stable_raw<const N: usize>(a: [i128; N], shift: i32) -> bool {
+    assert!(shift >= 0 && shift < 127, "coefficient shift out of range");
+    let mut p = [0; N];
+    let mut m = N;
+    let mut i = 0;
+    while i < N {
+        p[i] = a[i];
+        i += 1;
+    }
+    // The leading coefficient, which is positive throughout.
+    let mut lead: i128 = 1 << shift;
+    while m > 0 {
+        let last = p[m - 1];
+        if last.unsigned_abs() >= lead as u128 {
+            return false;
+        }
+        let Some(new_lead) = mul_sub(lead, lead, last, last) else {
+            panic!("too many bits for IIR stability check");
+        };
+        // p[i - 1] is the coefficient p_i, and p[m - 1 - i] is p_(m-i).
+        let mut next = [0; N];
+        let mut divisor = new_lead.unsigned_abs();
+        let mut i = 1;
+        while i < m {
+            let Some(val) = mul_sub(lead, p[i - 1], last, p[m - 1 - i]) else {
+                panic!("too many bits for IIR stability check");
+            };
+            next[i - 1] = val;
+            divisor = gcd(divisor, val.unsigned_abs());
+            i += 1;
+        }
+        lead = new_lead / divisor as i128;
+        let mut i = 0;
+        while i + 1 < m {
+            p[i] = next[i] / divisor as i128;
+            i += 1;
+        }
+        m -= 1;
+    }
+    true
+}
+
+/// Return `a b - c d`, or `None` on overflow.
+const fn mul_sub(a: i128, b: i128, c: i128, d: i128) -> Option<i128> {
+    match (a.checked_mul(b), c.checked_mul(d)) {
+        (Some(ab), Some(cd)) => ab.checked_sub(cd),
+        _ => None,
+    }
+}
+
+const fn gcd(mut a: u128, mut b: u128) -> u128 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// Return whether the IIR filter `y[n] = ... - a[0] y[n-1] - a[1] y[n-2] - ...` with the
+/// feedback coefficients `a` is stable, i.e. whether all poles lie strictly inside the unit
+/// circle.
+///
+/// The poles are those of the quantized coefficients, and the check is exact (with no
+/// floating point or tolerance), so a filter whose poles are pushed onto or outside the unit
+/// circle by quantization is reported as unstable.  For constant coefficients, the const fn
+/// `assert_iir_stable` of each fixed-point type performs the same check at compile time.
+pub fn iir_is_stable<T: Num, const N: usize>(a: [T; N]) -> bool
+where
+    T::Raw: Into<i128>,
+{
+    iir_stable_raw(a.map(|coef| coef.raw().into()), T::SHIFT)
+}
+
+filter_impl!(I8, i8);
+filter_impl!(U8, u8);
+filter_impl!(I16, i16);
+filter_impl!(U16, u16);
+filter_impl!(I32, i32);
+filter_impl!(U32, u32);
+filter_impl!(I64, i64);
+filter_impl!(U64, u64);
+filter_impl!(I128, i128);
+filter_impl!(U128, u128);
@@ -0,0 +1,139 @@
+// Conversions between fixed-point numbers and IEEE-754 floats, which construct and
+// deconstruct the bit pattern of the float directly.  These use only integer operations,
+// so they are fast and exact, and they do not pull in any soft-float library calls on
+// targets without an FPU.
+
+use crate::{Num, RangeError};
+
+macro_rules! float_impl {
+    ($F:ident, $Bits:ty, $from_raw:ident, $into_parts:ident) => {
+        /// Return the float with the value `raw * 2^-shift`.  The result is exact, provided
+        /// that `|raw| < 2^MANTISSA_DIGITS` and the value is within the range of the float
+        /// (including subnormals); otherwise it is unspecified.
+        pub(crate) fn $from_raw(raw: i128, shift: i32) -> $F {
+            // Number of explicitly stored mantissa bits.
+            const M: i32 = $F::MANTISSA_DIGITS as i32 - 1;
+            let sign = ((raw < 0) as $Bits) << (<$Bits>::BITS - 1);
+            let magnitude = raw.unsigned_abs();
+            let bits = if magnitude == 0 {
+                0
+            } else {
+                let msb = (u128::BITS - 1 - magnitude.leading_zeros()) as i32;
+                let biased_exp = msb - shift + $F::MAX_EXP - 1;
+                if biased_exp > 0 {
+                    // normal float: the leading 1 is implicit
+                    let mantissa = (magnitude << (M - msb)) as $Bits & ((1 << M) - 1);
+                    ((biased_exp as $Bits) << M) | mantissa
+                } else {
+                    // subnormal float, with the least significant bit worth 2^(MIN_EXP - 1 - M)
+                    (magnitude << ($F::MANTISSA_DIGITS as i32 - $F::MIN_EXP - shift)) as $Bits
+                }
+            };
+            $F::from_bits(sign | bits)
+        }
+
+        /// Decompose a finite float into `(negative, mantissa, exp)`, such that its value is
+        /// exactly `(-1)^negative * mantissa * 2^exp`.  Panics if the float is not finite.
+        pub(crate) fn $into_parts(val: $F) -> (bool, u128, i32) {
+            const M: u32 = $F::MANTISSA_DIGITS - 1;
+            const EXP_MASK: $Bits = (1 << (<$Bits>::BITS - 1 - M)) - 1;
+            let bits = val.to_bits();
+            let biased_exp = (bits >> M) & EXP_MASK;
+            assert!(biased_exp != EXP_MASK, "float is not finite");
+            let negative = bits >> (<$Bits>::BITS - 1) != 0;
+            let fraction = (bits & ((1 << M) - 1)) as u128;
+            // The least significant bit is worth 2^(MIN_EXP - 1 - M), both for subnormals
+            // (`biased_exp == 0`) and for the smallest normal exponent (`biased_exp == 1`).
+            let exp = $F::MIN_EXP - 1 - M as i32 + (biased_exp as i32 - 1).max(0);
+            if biased_exp == 0 {
+                (negative, fraction, exp)
+            } else {
+                (negative, fraction | (1 << M), exp)
+            }
+        }
+    };
+}
+
+float_impl!(f32, u32, f32_from_raw, f32_into_parts);
+float_impl!(f64, u64, f64_from_raw, f64_into_parts);
+#[cfg(feature = "f128")]
+float_impl!(f128, u128, f128_from_raw, f128_into_parts);
+
+/// Return the magnitude of `mantissa * 2^exp`, truncated to an integer, and whether any
+/// nonzero bits were truncated.  Return `None` if the result overflows `u128`.
+pub(crate) fn truncate(mantissa: u128, exp: i32) -> Option<(u128, bool)> {
+    if exp >= 0 {
+        if mantissa != 0 && exp > mantissa.leading_zeros() as i32 {
+            None
+        } else {
+            Some((mantissa.checked_shl(exp as u32).unwrap_or(0), false))
+        }
+    } else if exp > -(u128::BITS as i32) {
+        Some((mantissa >> -exp, mantissa & ((1 << -exp) - 1) != 0))
+    } else {
+        Some((0, mantissa != 0))
+    }
+}
+
+/// Return the fixed-point number of type `N` with the value `(-1)^negative * mantissa *
+/// 2^exp`, rounded toward zero, or a `RangeError` if the value lies outside the range of
+/// `N`.  Values which round to `MIN` or `MAX` but lie beyond them are out of range.
+pub(crate) fn from_parts<N: Num>(negative: bool, mantissa: u128, exp: i32) -> Result<N, RangeError>
+where
+    N::Raw: TryFrom<u128>,
+{
+    let err = if negative {
+        RangeError::TooSmall
+    } else {
+        RangeError::TooLarge
+    };
+    let (magnitude, inexact) = truncate(mantissa, exp + N::SHIFT).ok_or(err)?;
+    let limit = if negative { N::MIN } else { N::MAX };
+    let raw = if magnitude == 0 {
+        N::Raw::ZERO
+    } else if negative {
+        if !N::SIGNED {
+            return Err(err);
+        }
+        // Negate `magnitude - 1` first, because `-magnitude` may only fit as `MIN`.
+        let one: N::Raw = 1u128.try_into().ok().unwrap();
+        let raw: N::Raw = (magnitude - 1).try_into().map_err(|_| err)?;
+        N::Raw::ZERO - raw - one
+    } else {
+        magnitude.try_into().map_err(|_| err)?
+    };
+    match N::new(raw) {
+        Ok(val) if val == limit && inexact => Err(err),
+        result => result,
+    }
+}
+
+/// Return the raw value `(-1)^negative * mantissa * 2^exp`, rounded toward zero, for a
+/// format whose raw limits `min` and `max` are only known at runtime.  Return a
+/// `RangeError` under the same conditions as `from_parts`.
+#[cfg(any(feature = "ffi", feature = "python"))]
+pub(crate) fn from_parts_in(
+    negative: bool,
+    mantissa: u128,
+    exp: i32,
+    min: i128,
+    max: i128,
+) -> Result<i128, RangeError> {
+    let (magnitude, inexact) = truncate(mantissa, exp).ok_or(if negative {
+        RangeError::TooSmall
+    } else {
+        RangeError::TooLarge
+    })?;
+    let raw = if negative {
+        0i128.checked_sub_unsigned(magnitude)
+    } else {
+        i128::try_from(magnitude).ok()
+    };
+    match raw {
+        Some(raw) if raw < min || (raw == min && negative && inexact) => Err(RangeError::TooSmall),
+        Some(raw) if raw > max || (raw == max && !negative && inexact) => Err(RangeError::TooLarge),
+        Some(raw) => Ok(raw),
+        None if negative => Err(RangeError::TooSmall),
+        None => Err(RangeError::TooLarge),
+    }
+}
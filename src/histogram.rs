@@ -0,0 +1,136 @@
+use crate::{Num, RangeError};
+
+/// A histogram of fixed-point values of type `T`, sorted into `BINS` bins.
+///
+/// Bin `i` counts values `v` with `edge[i] <= v < edge[i + 1]`.  Values below the first
+/// edge are counted in a separate underflow bin, and values at or above the last edge
+/// are counted in a separate overflow bin, so no sample is ever dropped.
+///
+/// Counts saturate at `u32::MAX`.
+#[derive(Clone, Debug)]
+pub struct Histogram<T: Num, const BINS: usize> {
+    // Lower edge of each bin.  The upper edge of the last bin is stored in `upper`.
+    lower: [T; BINS],
+    upper: T,
+    counts: [u32; BINS],
+    underflow: u32,
+    overflow: u32,
+}
+
+impl<T: Num, const BINS: usize> Histogram<T, BINS> {
+    /// Create an empty histogram from the lower edge of each bin and the upper edge of
+    /// the last bin.  The edges must be strictly increasing.
+    pub fn new(lower: [T; BINS], upper: T) -> Self {
+        assert!(BINS > 0, "histogram must have at least one bin");
+        assert!(
+            lower.windows(2).all(|pair| pair[0] < pair[1]) && lower[BINS - 1] < upper,
+            "histogram edges must be strictly increasing"
+        );
+        Self {
+            lower,
+            upper,
+            counts: [0; BINS],
+            underflow: 0,
+            overflow: 0,
+        }
+    }
+    /// Create an empty histogram whose first bin starts at `lo` and whose bins each have
+    /// the (positive) width `width`.  Return a `RangeError` if the upper edge of the last
+    /// bin is too large to be represented by `T`.
+    pub fn uniform(lo: T, width: T) -> Result<Self, RangeError> {
+        assert!(BINS > 0, "histogram must have at least one bin");
+        assert!(width > T::ZERO, "histogram bin width must be positive");
+        let mut lower = [lo; BINS];
+        let mut edge = lo;
+        for lower_edge in lower.iter_mut() {
+            *lower_edge = edge;
+            // Comparing against `MAX - width` (rather than adding first) avoids overflowing
+            // the raw type when `T` uses all of the bits of its raw type.
+            if edge.raw() > T::MAX.raw() - width.raw() {
+                return Err(RangeError::TooLarge);
+            }
+            edge = unsafe { T::new_unchecked(edge.raw() + width.raw()) };
+        }
+        Ok(Self {
+            lower,
+            upper: edge,
+            counts: [0; BINS],
+            underflow: 0,
+            overflow: 0,
+        })
+    }
+    /// Add one sample to the histogram.
+    pub fn record(&mut self, val: T) {
+        if val < self.lower[0] {
+            self.underflow = self.underflow.saturating_add(1);
+        } else if val >= self.upper {
+            self.overflow = self.overflow.saturating_add(1);
+        } else {
+            // Number of lower edges <= val, which is at least 1 because of the check above.
+            let bin = self.lower.partition_point(|edge| *edge <= val) - 1;
+            self.counts[bin] = self.counts[bin].saturating_add(1);
+        }
+    }
+    /// Reset all counts to zero, keeping the bin edges.
+    pub fn clear(&mut self) {
+        self.counts = [0; BINS];
+        self.underflow = 0;
+        self.overflow = 0;
+    }
+    /// Return the count in each bin.
+    pub fn counts(&self) -> &[u32; BINS] {
+        &self.counts
+    }
+    /// Return the number of samples which were smaller than the lower edge of the first bin.
+    pub fn underflow(&self) -> u32 {
+        self.underflow
+    }
+    /// Return the number of samples which were greater than or equal to the upper edge of
+    /// the last bin.
+    pub fn overflow(&self) -> u32 {
+        self.overflow
+    }
+    /// Return the total number of samples, including the underflow and overflow bins.
+    pub fn total(&self) -> u64 {
+        self.counts.iter().map(|&c| c as u64).sum::<u64>()
+            + self.underflow as u64
+            + self.overflow as u64
+    }
+    /// Return the lower (inclusive) and upper (exclusive) edges of bin `i`.
+    pub fn bin_edges(&self, i: usize) -> (T, T) {
+        let upper = if i + 1 < BINS { self.lower[i + 1] } else { self.upper };
+        (self.lower[i], upper)
+    }
+    /// Return an upper bound for the `q` quantile of the recorded samples, where `q` is a
+    /// fixed-point number between 0 and 1 (e.g. `q = 0.95` for the 95th percentile).
+    ///
+    /// The result is the upper edge of the bin which contains the quantile, so at least
+    /// a fraction `q` of the samples are guaranteed to be smaller than the result.
+    /// If the quantile falls in the underflow or overflow bin, return
+    /// `RangeError::TooSmall` or `RangeError::TooLarge` respectively.
+    ///
+    /// Panics if the histogram is empty.
+    pub fn quantile<Q: Num<Raw = u32>>(&self, q: Q) -> Result<T, RangeError> {
+        assert!(
+            (0..=32).contains(&Q::SHIFT),
+            "quantile must have between 0 and 32 fractional bits"
+        );
+        assert!(q.raw() as u64 <= 1 << Q::SHIFT, "quantile must not exceed 1");
+        let total = self.total();
+        assert!(total > 0, "histogram is empty");
+        // Rank (1-based) of the sample at the requested quantile, rounded up.
+        let scaled = total as u128 * q.raw() as u128;
+        let rank = ((scaled + (1 << Q::SHIFT) - 1) >> Q::SHIFT).max(1) as u64;
+        let mut cumulative = self.underflow as u64;
+        if cumulative >= rank {
+            return Err(RangeError::TooSmall);
+        }
+        for (i, &count) in self.counts.iter().enumerate() {
+            cumulative += count as u64;
+            if cumulative >= rank {
+                return Ok(self.bin_edges(i).1);
+            }
+        }
+        Err(RangeError::TooLarge)
+    }
+}
@@ -0,0 +1,134 @@
+use core::ops::{Add, Mul, Neg, Sub};
+
+use crate::requantize::try_requantize;
+use crate::{Num, RangeError, Rounding};
+
+/// A closed interval `[lo, hi]` of values of type `N` (usually a fixed-point number), which
+/// contains every value that a computation may produce, e.g. a sensor reading and its
+/// worst-case error.
+///
+/// The operators act on the bounds with the operators of `N`, so the output types account
+/// for bit growth as for the underlying types, and since fixed-point addition, subtraction
+/// and multiplication are exact, so are the bounds of their results.  Conversion to a type
+/// with fewer fractional bits (`try_requantize`) rounds the lower bound down and the upper
+/// bound up, so the result still contains every value.  An interval computed alongside an
+/// algorithm in the same formats therefore bounds the error of the algorithm.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Interval<N> {
+    lo: N,
+    hi: N,
+}
+
+impl<N: Copy + Ord> Interval<N> {
+    /// Create the interval `[lo, hi]`, or return `None` if `lo > hi`.
+    pub fn new(lo: N, hi: N) -> Option<Self> {
+        (lo <= hi).then_some(Self { lo, hi })
+    }
+    /// Create the interval which contains only `val`.
+    pub fn point(val: N) -> Self {
+        Self { lo: val, hi: val }
+    }
+    /// Return the lower bound.
+    pub fn lo(self) -> N {
+        self.lo
+    }
+    /// Return the upper bound.
+    pub fn hi(self) -> N {
+        self.hi
+    }
+    /// Return whether `val` lies in the interval.
+    pub fn contains(self, val: N) -> bool {
+        self.lo <= val && val <= self.hi
+    }
+    /// Return the smallest interval which contains both `self` and `other`.
+    pub fn hull(self, other: Self) -> Self {
+        Self {
+            lo: self.lo.min(other.lo),
+            hi: self.hi.max(other.hi),
+        }
+    }
+    /// Return the intersection of `self` and `other`, or `None` if they are disjoint.
+    pub fn intersect(self, other: Self) -> Option<Self> {
+        Self::new(self.lo.max(other.lo), self.hi.min(other.hi))
+    }
+    /// Return the width `hi - lo`, with the output type of `-`.
+    pub fn width(self) -> N::Output
+    where
+        N: Sub,
+    {
+        self.hi - self.lo
+    }
+}
+
+impl<N: Num> Interval<N> {
+    /// Convert to an interval of the type `Out`, rounding the lower bound toward negative
+    /// infinity and the upper bound toward positive infinity, so that the result contains
+    /// `self`.  Return a `RangeError` if either bound does not fit in `Out`.
+    pub fn try_requantize<Out: Num>(self) -> Result<Interval<Out>, RangeError>
+    where
+        N::Raw: Into<i128>,
+        Out::Raw: TryFrom<i128>,
+    {
+        Ok(Interval {
+            lo: try_requantize(self.lo, Rounding::Floor)?,
+            hi: try_requantize(self.hi, Rounding::Ceil)?,
+        })
+    }
+}
+
+/// Intervals may be added, if their bounds may be added.
+impl<T0: Add<T1>, T1> Add<Interval<T1>> for Interval<T0> {
+    type Output = Interval<T0::Output>;
+    fn add(self, other: Interval<T1>) -> Self::Output {
+        Interval {
+            lo: self.lo + other.lo,
+            hi: self.hi + other.hi,
+        }
+    }
+}
+
+/// Intervals may be subtracted, if their bounds may be subtracted.  The lower bound of the
+/// result is `lo0 - hi1`, and the upper bound is `hi0 - lo1`.
+impl<T0: Sub<T1>, T1> Sub<Interval<T1>> for Interval<T0> {
+    type Output = Interval<T0::Output>;
+    fn sub(self, other: Interval<T1>) -> Self::Output {
+        Interval {
+            lo: self.lo - other.hi,
+            hi: self.hi - other.lo,
+        }
+    }
+}
+
+/// An interval may be negated, if its bounds may be negated.
+impl<T: Neg> Neg for Interval<T> {
+    type Output = Interval<T::Output>;
+    fn neg(self) -> Self::Output {
+        Interval {
+            lo: -self.hi,
+            hi: -self.lo,
+        }
+    }
+}
+
+/// Intervals may be multiplied, if their bounds may be multiplied.  The bounds of the
+/// result are the least and greatest of the four products of the bounds.
+impl<T0, T1, P> Mul<Interval<T1>> for Interval<T0>
+where
+    T0: Mul<T1, Output = P> + Copy,
+    T1: Copy,
+    P: Copy + Ord,
+{
+    type Output = Interval<P>;
+    fn mul(self, other: Interval<T1>) -> Self::Output {
+        let products = [
+            self.lo * other.lo,
+            self.lo * other.hi,
+            self.hi * other.lo,
+            self.hi * other.hi,
+        ];
+        Interval {
+            lo: products.into_iter().min().unwrap(),
+            hi: products.into_iter().max().unwrap(),
+        }
+    }
+}
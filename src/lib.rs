@@ -32,13 +32,24 @@
 //! It is necessary to use nightly Rust in order to enable the unstable
 //! `generic_const_exprs` feature.  Otherwise it would not be possible to specify
 //! the correct return type from most operations.
+//!
+//! The optional `f128` feature adds exact conversions to and from the unstable `f128`
+//! type, which can represent fixed-point numbers of up to 113 bits.  The optional `posit`
+//! feature adds conversions to and from 16- and 32-bit posits.  The optional `python`
+//! feature adds Python bindings (see [`python`]).  The optional `ffi` feature adds a C ABI
+//! for mixed C/Rust firmware (see [`ffi`]), and `ffi-header` also generates a C header for it.
+//! The optional `mat` feature adds import and export of MAT-file test vectors (see
+//! [`matlab`]).  The optional `tracked` feature enables the error bounds of [`Tracked`].
 
+#![allow(incomplete_features)]
 #![feature(generic_const_exprs)]
+#![cfg_attr(feature = "f128", feature(f128))]
 
+use core::cmp::Ordering;
 use core::fmt::Debug;
-use core::ops::{Shl, Shr};
+use core::ops::{Add, Shl, Shr, Sub};
 
-#[derive(Debug)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum RangeError {
     TooSmall,
     TooLarge,
@@ -50,10 +61,20 @@ pub enum RangeError {
 pub trait Num: Clone + Copy + Debug + Eq + Ord + PartialEq + PartialOrd + Sized {
     /// The underlying ("raw") representation of this fixed-point number.
     /// Typically this is a primitive integer type, e.g. `i64`.
-    type Raw: Num<Raw = Self::Raw> + Shl<u32, Output = Self::Raw> + Shr<u32, Output = Self::Raw>;
+    type Raw: Num<Raw = Self::Raw>
+        + Add<Output = Self::Raw>
+        + Sub<Output = Self::Raw>
+        + Shl<u32, Output = Self::Raw>
+        + Shr<u32, Output = Self::Raw>;
     /// The type that this fixed point number will become after `BITS` and/or `SHIFT`
     /// are changed by an operation.
     type Output<const B: u32, const S: i32>: Num<Raw = Self::Raw>;
+    /// The signed fixed-point type with the same bits and shift as `Self` (which is `Self`
+    /// if it is already signed), e.g. `I16<12, 4>` for `U16<12, 4>`.
+    type SignedFp: Num;
+    /// The unsigned fixed-point type with the same bits and shift as `Self` (which is
+    /// `Self` if it is already unsigned), e.g. `U16<12, 4>` for `I16<12, 4>`.
+    type UnsignedFp: Num;
     /// `BITS` is the number of least-significant bits which are permitted to vary.
     /// The `Raw::BITS - BITS` high-order bits must be zero (for unsigned `Raw`) or the
     /// same as the high bit of the lower `BITS` bits (for signed `Raw`).
@@ -71,10 +92,19 @@ pub trait Num: Clone + Copy + Debug + Eq + Ord + PartialEq + PartialOrd + Sized
     const MIN: Self;
     /// Maximum possible value of this type.
     const MAX: Self;
+    /// Zero, which is a valid value of every fixed-point type.
+    const ZERO: Self;
+    /// One, which is a valid value if the shift is not negative and less than the number of
+    /// bits (excluding the sign bit).  It is a compile error to use this constant otherwise;
+    /// generic code may use `try_one` instead.
+    const ONE: Self;
     /// Whether this type is signed. (If false, it's unsigned.)
     const SIGNED: bool;
     /// Interpret the provided raw value as a fixed-point number of type `Self`.
-    /// Unsafe: no bounds checking is performed; the caller must ensure that the
+    ///
+    /// # Safety
+    ///
+    /// No bounds checking is performed; the caller must ensure that the
     /// result lies between `Self::MIN` and `Self::MAX`. It is almost always better
     /// to use `.new().unwrap()` instead of this function, so that an out-of-bounds
     /// value panics with a reasonable message instead of propagating undefined
@@ -94,6 +124,94 @@ pub trait Num: Clone + Copy + Debug + Eq + Ord + PartialEq + PartialOrd + Sized
     }
     /// Return the raw value which internally represents this fixed-point number.
     fn raw(self) -> Self::Raw;
+    /// Convert this value to the signed type `SignedFp`, with 1 more bit if `Self` is
+    /// unsigned (for the sign), like the inherent `into_signed` but for generic code.
+    fn to_signed(
+        self,
+    ) -> <Self::SignedFp as Num>::Output<{ Self::BITS + !Self::SIGNED as u32 }, { Self::SHIFT }>
+    where
+        Self::Raw: Into<i128>,
+        <Self::SignedFp as Num>::Raw: TryFrom<i128>,
+        [(); (Self::BITS + !Self::SIGNED as u32) as usize]:,
+    {
+        // Every value of `Self` is in range.
+        raw::from_raw(Ok(self.raw().into())).unwrap()
+    }
+    /// Convert this value to the unsigned type `UnsignedFp`, with 1 less bit if `Self` is
+    /// signed (the sign), or return `None` if it is negative, like the inherent
+    /// `into_unsigned` but for generic code.
+    fn try_to_unsigned(
+        self,
+    ) -> Option<
+        <Self::UnsignedFp as Num>::Output<{ Self::BITS - Self::SIGNED as u32 }, { Self::SHIFT }>,
+    >
+    where
+        Self::Raw: Into<i128>,
+        <Self::UnsignedFp as Num>::Raw: TryFrom<i128>,
+        [(); (Self::BITS - Self::SIGNED as u32) as usize]:,
+    {
+        raw::from_raw(Ok(self.raw().into())).ok()
+    }
+    /// Return one, or `None` if it is not representable (like `ONE`, but without a compile
+    /// error).
+    fn try_one() -> Option<Self> {
+        let representable =
+            Self::SHIFT >= 0 && (Self::SHIFT as u32) + (Self::SIGNED as u32) < Self::BITS;
+        representable.then(|| unsafe {
+            Self::new_unchecked(<Self::Raw as Num>::ONE << Self::SHIFT as u32)
+        })
+    }
+    /// Return whether this value is zero.
+    fn is_zero(self) -> bool {
+        self == Self::ZERO
+    }
+    /// Return whether this value is greater than zero.
+    fn is_positive(self) -> bool {
+        self > Self::ZERO
+    }
+    /// Return whether this value is less than zero, which is never the case for unsigned
+    /// types.
+    fn is_negative(self) -> bool {
+        self < Self::ZERO
+    }
+    /// Compare the logical values of `self` and `other`, which may have different bits, shifts
+    /// and raw types, e.g. a 16.16 threshold and a 24.8 measurement.  The values are aligned
+    /// to the finer of the two shifts in `i128`, and it is a compile error if this could
+    /// overflow.
+    fn cmp_logical<T: Num>(self, other: T) -> Ordering
+    where
+        Self::Raw: Into<i128>,
+        T::Raw: Into<i128>,
+    {
+        requantize::cmp_logical(self, other)
+    }
+    /// Return whether the logical values of `self` and `other` are equal, like
+    /// `cmp_logical`.
+    fn eq_logical<T: Num>(self, other: T) -> bool
+    where
+        Self::Raw: Into<i128>,
+        T::Raw: Into<i128>,
+    {
+        self.cmp_logical(other) == Ordering::Equal
+    }
+    /// Return the number of leading zeros in the binary representation of the raw value,
+    /// like `leading_zeros` on the primitive integers, e.g. to normalize a value.  This
+    /// counts all the bits of `Raw`, not only the `BITS` least-significant bits.
+    fn leading_zeros(self) -> u32 {
+        self.raw().leading_zeros()
+    }
+    /// Return the number of trailing zeros in the binary representation of the raw value,
+    /// like `trailing_zeros` on the primitive integers.  This is the number of bits in `Raw`
+    /// if the value is zero.
+    fn trailing_zeros(self) -> u32 {
+        self.raw().trailing_zeros()
+    }
+    /// Return the number of ones in the binary representation of the raw value, like
+    /// `count_ones` on the primitive integers.  This counts all the bits of `Raw`, so it
+    /// includes the sign extension of a negative value.
+    fn count_ones(self) -> u32 {
+        self.raw().count_ones()
+    }
     /// Return the fixed-point number of type `Self` which has a logical value of `val`,
     /// or return a RangeError if `val` is too small or too large to be represented
     /// by `Self`.
@@ -108,6 +226,12 @@ pub trait Num: Clone + Copy + Debug + Eq + Ord + PartialEq + PartialOrd + Sized
             Ok(unsafe { Self::from_f32_unchecked(val) })
         }
     }
+    /// Return the fixed-point number of type `Self` which has a logical value of `val`.
+    ///
+    /// # Safety
+    ///
+    /// No bounds checking is performed; the caller must ensure that `val` is
+    /// finite and lies between `Self::MIN` and `Self::MAX`.
     unsafe fn from_f32_unchecked(val: f32) -> Self;
     /// Return the fixed-point number of type `Self` which has a logical value of `val`,
     /// or return a RangeError if `val` is too small or too large to be represented
@@ -123,11 +247,42 @@ pub trait Num: Clone + Copy + Debug + Eq + Ord + PartialEq + PartialOrd + Sized
             Ok(unsafe { Self::from_f64_unchecked(val) })
         }
     }
+    /// Return the fixed-point number of type `Self` which has a logical value of `val`.
+    ///
+    /// # Safety
+    ///
+    /// No bounds checking is performed; the caller must ensure that `val` is
+    /// finite and lies between `Self::MIN` and `Self::MAX`.
     unsafe fn from_f64_unchecked(val: f64) -> Self;
     /// Return the logical value of `Self` as `f32`. Return value is guaranteed to be exact.
     fn into_f32(self) -> f32;
     /// Return the logical value of `Self` as `f64`. Return value is guaranteed to be exact.
     fn into_f64(self) -> f64;
+    /// Return the fixed-point number of type `Self` which has a logical value of `val`,
+    /// or return a RangeError if `val` is too small or too large to be represented
+    /// by `Self`.
+    #[cfg(feature = "f128")]
+    fn from_f128(val: f128) -> Result<Self, RangeError> {
+        assert!(val.is_finite());
+        if val < Self::MIN.into_f128() {
+            Err(RangeError::TooSmall)
+        } else if val > Self::MAX.into_f128() {
+            Err(RangeError::TooLarge)
+        } else {
+            Ok(unsafe { Self::from_f128_unchecked(val) })
+        }
+    }
+    /// Return the fixed-point number of type `Self` which has a logical value of `val`.
+    ///
+    /// # Safety
+    ///
+    /// No bounds checking is performed; the caller must ensure that `val` is
+    /// finite and lies between `Self::MIN` and `Self::MAX`.
+    #[cfg(feature = "f128")]
+    unsafe fn from_f128_unchecked(val: f128) -> Self;
+    /// Return the logical value of `Self` as `f128`. Return value is guaranteed to be exact.
+    #[cfg(feature = "f128")]
+    fn into_f128(self) -> f128;
     /// Return the fixed-point number of type `Self` which has the same logical value as `val`.
     /// `F` and `Self` must have the same shift and signedness. `Self` must have at least as
     /// many bits as `F`.
@@ -143,12 +298,66 @@ pub trait Num: Clone + Copy + Debug + Eq + Ord + PartialEq + PartialOrd + Sized
     /// Return the fixed-point number of type `F` which has the same logical value as `self`.
     /// `F` and `Self` must have the same shift and signedness. `F` must have at least as
     /// many bits as `Self`.
-    fn into_fp<T: Num, F: Num<Raw = T>>(self) -> F
+    fn into_fp<T, F: Num<Raw = T>>(self) -> F
     where
-        T: TryFrom<Self::Raw>,
+        T: Num + TryFrom<Self::Raw>,
     {
         F::from_fp(self)
     }
+    /// Convert this value to the type `T`, which has the same raw type but may have more bits
+    /// and a larger shift, e.g. from `I32<12, 4>` to `I32<20, 8>`.  This combines `add_bits`
+    /// and `raw_shl` in one step, and it is a compile error unless every value of `Self` is
+    /// exactly representable in `T`.
+    fn resize<T: Num<Raw = Self::Raw>>(self) -> T {
+        requantize::resize(self)
+    }
+    /// Convert this value to the type `W`, which has the same bits, shift and signedness but
+    /// a wider raw type, e.g. from `I32<24, 8>` to `I64<24, 8>` before a large multiply.
+    /// This cannot fail: `W` must implement `From<Self>`, which is the case for each wider
+    /// raw type with the same signedness.
+    fn into_wider<W: Num + From<Self>>(self) -> W {
+        W::from(self)
+    }
+    /// Convert this value to the type `N`, which has the same shift but may have a narrower
+    /// raw type and fewer bits, e.g. from `I64<20, 8>` to `I32<20, 8>` to store a result in
+    /// a compact buffer.  Return a `RangeError` if the value does not fit in `N`.  It is a
+    /// compile error if the shifts differ.
+    fn try_narrow<N: Num>(self) -> Result<N, RangeError>
+    where
+        N::Raw: TryFrom<Self::Raw>,
+    {
+        let () = checked::SameShift::<Self, N>::CHECK;
+        let err = if self.is_negative() {
+            RangeError::TooSmall
+        } else {
+            RangeError::TooLarge
+        };
+        N::new(self.raw().try_into().map_err(|_| err)?)
+    }
+    /// Convert this value to the type `N` like `try_narrow`, but saturate to the range of
+    /// `N` if the value does not fit.
+    fn saturating_narrow<N: Num>(self) -> N
+    where
+        N::Raw: TryFrom<Self::Raw>,
+    {
+        match self.try_narrow() {
+            Ok(val) => val,
+            Err(RangeError::TooSmall) => N::MIN,
+            Err(RangeError::TooLarge) => N::MAX,
+        }
+    }
+    /// Convert this value to the type `N` like `try_narrow`, without checking the range.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that the value lies between `N::MIN` and `N::MAX`.
+    unsafe fn narrow_unchecked<N: Num>(self) -> N
+    where
+        N::Raw: TryFrom<Self::Raw>,
+    {
+        let () = checked::SameShift::<Self, N>::CHECK;
+        unsafe { N::new_unchecked(self.raw().try_into().ok().unwrap_unchecked()) }
+    }
     /// Increase the number of bits used to represent this value. Both the raw and logical
     /// values are unchanged.  This is a type system operation only.
     /// Compilation will fail if the new number of bits is too large for the raw type.
@@ -164,8 +373,11 @@ pub trait Num: Clone + Copy + Debug + Eq + Ord + PartialEq + PartialOrd + Sized
     fn set_bits<const N: u32>(self) -> Result<Self::Output<N, { Self::SHIFT }>, RangeError> {
         Self::Output::new(self.raw())
     }
-    /// Set the number of bits used to represent this value.  Unsafe: no bounds checking
-    /// is performed; the caller must ensure that the value fits within
+    /// Set the number of bits used to represent this value.
+    ///
+    /// # Safety
+    ///
+    /// No bounds checking is performed; the caller must ensure that the value fits within
     /// the new number of bits.  It is almost always better to call `.set_bits().unwrap()`
     /// instead, so that an out-of-bounds
     /// value panics with a reasonable message instead of propagating undefined
@@ -182,6 +394,261 @@ pub trait Num: Clone + Copy + Debug + Eq + Ord + PartialEq + PartialOrd + Sized
             Ok(val) => val,
         }
     }
+    /// Set the number of bits used to represent this value, wrapping around in case of
+    /// overflow, i.e. keeping only the `N` least-significant bits of the raw value (with
+    /// sign extension, for a signed type).  This never panics, e.g. for a phase
+    /// accumulator where wraparound is intended.
+    fn truncate_bits<const N: u32>(self) -> Self::Output<N, { Self::SHIFT }> {
+        let unused = <Self::Raw as Num>::BITS - N;
+        let raw = if unused >= <Self::Raw as Num>::BITS {
+            Self::ZERO.raw()
+        } else {
+            (self.raw() << unused) >> unused
+        };
+        unsafe { Self::Output::new_unchecked(raw) }
+    }
+    /// Return the sum of the array `a`, which has `log2(N)` (rounded up) more bits than
+    /// `Self`, so that it can never overflow, e.g. for the taps of a FIR filter or a block
+    /// average.  The shift is unchanged.
+    fn sum_array<const N: usize>(
+        a: [Self; N],
+    ) -> Self::Output<{ Self::BITS + add_sub::ceil_log2(N) }, { Self::SHIFT }>
+    where
+        [(); (Self::BITS + add_sub::ceil_log2(N)) as usize]:,
+    {
+        let sum = a.iter().fold(Self::ZERO.raw(), |sum, val| sum + val.raw());
+        unsafe { Self::Output::new_unchecked(sum) }
+    }
+    /// Convert this value to the type `Out`, rounding according to `rounding` if `Out` has
+    /// a smaller shift, and saturating to the range of `Out`, e.g. from a 40-bit filter
+    /// accumulator to a Q15 sample.  This combines a rounding shift, saturation and
+    /// narrowing of the raw type in one step, which is computed in `i64` if both types fit
+    /// (and `i128` otherwise).
+    fn requantize<Out: Num>(self, rounding: Rounding) -> Out
+    where
+        Self::Raw: Into<i128>,
+        Out::Raw: Into<i128> + TryFrom<i128>,
+    {
+        requantize::requantize(self, rounding)
+    }
+    /// Convert this value to the type `Out`, which has the same raw type but any bits and
+    /// shift, e.g. from `I32<24, 16>` to `I32<12, 8>`.  The value is rounded to nearest
+    /// (ties toward positive infinity, like `Rounding::Nearest`) if `Out` has a smaller
+    /// shift.  Panics if the result does not fit in `Out`.
+    fn rescale<Out: Num<Raw = Self::Raw>>(self) -> Out
+    where
+        Self::Raw: Into<i128> + TryFrom<i128>,
+    {
+        self.try_rescale().expect("rescaled value out of range")
+    }
+    /// Convert this value to the type `Out` like `rescale`, but saturate to the range of
+    /// `Out` if the result does not fit.
+    fn rescale_saturating<Out: Num<Raw = Self::Raw>>(self) -> Out
+    where
+        Self::Raw: Into<i128> + TryFrom<i128>,
+    {
+        self.requantize(Rounding::Nearest)
+    }
+    /// Convert this value to the type `Out` like `rescale`, or return a `RangeError` if the
+    /// result does not fit.
+    fn try_rescale<Out: Num<Raw = Self::Raw>>(self) -> Result<Out, RangeError>
+    where
+        Self::Raw: Into<i128> + TryFrom<i128>,
+    {
+        requantize::try_rescale(self)
+    }
+    /// Return `self / other` with the shift of the type `Out`, rounded toward zero like
+    /// `/`, e.g. to keep the fractional bits of the quotient of two values with the same
+    /// shift.  The numerator is shifted left in a wider raw type before dividing.  It is a
+    /// compile error if the shifted operands do not fit in `i128`, or if some quotient may
+    /// not fit in `Out`.  Panics if `other` is zero.
+    fn div_to<Out: Num, D: Num>(self, other: D) -> Out
+    where
+        Self::Raw: Into<i128>,
+        D::Raw: Into<i128>,
+        Out::Raw: TryFrom<i128>,
+    {
+        mul_div::div_to(self, other)
+    }
+    /// Return `1 / self` in the type `Out`, rounded toward zero, e.g. to replace many
+    /// divisions by the same value with multiplications.  The reciprocal is computed in a
+    /// wider raw type.  It is a compile error if some reciprocal may not fit in `Out`.
+    /// Panics if `self` is zero.
+    fn recip<Out: Num>(self) -> Out
+    where
+        Self::Raw: Into<i128>,
+        Out::Raw: TryFrom<i128>,
+    {
+        mul_div::recip(self).expect("attempt to take the reciprocal of zero")
+    }
+    /// Return `1 / self` in the type `Out` like `recip`, or `None` if `self` is zero.
+    fn checked_recip<Out: Num>(self) -> Option<Out>
+    where
+        Self::Raw: Into<i128>,
+        Out::Raw: TryFrom<i128>,
+    {
+        mul_div::recip(self)
+    }
+    /// Return the square root of `self` in the type `Out`, rounded down, e.g. with more
+    /// fractional bits than `isqrt` gives.  The radicand is shifted in a wider raw type
+    /// before taking the root.  It is a compile error if the shifted radicand does not fit
+    /// in `u128`, or if some root may not fit in `Out`.  Panics if `self` is negative.
+    fn sqrt_to<Out: Num>(self) -> Out
+    where
+        Self::Raw: Into<i128>,
+        Out::Raw: TryFrom<i128>,
+    {
+        mul_div::sqrt_to(self)
+    }
+    /// Return `sqrt(self^2 + other^2)`, rounded down.  The squares are summed exactly in a
+    /// wider raw type, so this cannot overflow, and the result has one more bit than the
+    /// inputs.  It is a compile error if the sum of squares does not fit in `u128`.
+    fn hypot(self, other: Self) -> Self::Output<{ Self::BITS + 1 }, { Self::SHIFT }>
+    where
+        [(); (Self::BITS + 1) as usize]:,
+        Self::Raw: Into<i128> + TryFrom<i128>,
+    {
+        mul_div::hypot(self, other)
+    }
+    /// Return `self + other`, or `None` if the sum does not fit in `Self`.
+    fn checked_add(self, other: Self) -> Option<Self>
+    where
+        Self::Raw: Into<i128> + TryFrom<i128>,
+    {
+        let sum = self.raw().into().checked_add(other.raw().into())?;
+        Self::new(sum.try_into().ok()?).ok()
+    }
+    /// Return `self - other`, or `None` if the difference does not fit in `Self`.
+    fn checked_sub(self, other: Self) -> Option<Self>
+    where
+        Self::Raw: Into<i128> + TryFrom<i128>,
+    {
+        let difference = self.raw().into().checked_sub(other.raw().into())?;
+        Self::new(difference.try_into().ok()?).ok()
+    }
+    /// Return `self * other` with the same number of bits and shift, or `None` if the
+    /// product does not fit in `Self`.  The low bits of the product are truncated (rounding
+    /// toward negative infinity, like `raw_shr`).
+    fn checked_mul(self, other: Self) -> Option<Self>
+    where
+        Self::Raw: Into<i128> + TryFrom<i128>,
+    {
+        let product = checked::mul(self.raw().into(), other.raw().into(), Self::SHIFT)?;
+        Self::new(product.try_into().ok()?).ok()
+    }
+    /// Return `self / other` with the same number of bits and shift, or `None` if `other`
+    /// is zero or the quotient does not fit in `Self`.  The quotient is rounded toward
+    /// zero, like `/`.
+    fn checked_div(self, other: Self) -> Option<Self>
+    where
+        Self::Raw: Into<i128> + TryFrom<i128>,
+    {
+        let quotient = checked::div(self.raw().into(), other.raw().into(), Self::SHIFT)?;
+        Self::new(quotient.try_into().ok()?).ok()
+    }
+    /// Return `self + other` wrapped to the range of `Self`, i.e. modulo `2^BITS` LSBs, like
+    /// `wrapping_add` on the primitive integers, e.g. for a phase accumulator.
+    fn wrapping_add(self, other: Self) -> Self
+    where
+        Self::Raw: Into<i128> + TryFrom<i128>,
+    {
+        let sum = self.raw().into().wrapping_add(other.raw().into());
+        let wrapped = checked::wrap(sum as u128, Self::BITS, Self::SIGNED);
+        unsafe { Self::new_unchecked(wrapped.try_into().ok().unwrap()) }
+    }
+    /// Return `self - other` wrapped to the range of `Self`, like `wrapping_sub` on the
+    /// primitive integers.
+    fn wrapping_sub(self, other: Self) -> Self
+    where
+        Self::Raw: Into<i128> + TryFrom<i128>,
+    {
+        let difference = self.raw().into().wrapping_sub(other.raw().into());
+        let wrapped = checked::wrap(difference as u128, Self::BITS, Self::SIGNED);
+        unsafe { Self::new_unchecked(wrapped.try_into().ok().unwrap()) }
+    }
+    /// Return `-self` wrapped to the range of `Self`, like `wrapping_neg` on the primitive
+    /// integers: the most negative value of a signed type is unchanged, and a nonzero
+    /// unsigned value wraps to `2^BITS` LSBs minus itself.
+    fn wrapping_neg(self) -> Self
+    where
+        Self::Raw: Into<i128> + TryFrom<i128>,
+    {
+        let negated = 0i128.wrapping_sub(self.raw().into());
+        let wrapped = checked::wrap(negated as u128, Self::BITS, Self::SIGNED);
+        unsafe { Self::new_unchecked(wrapped.try_into().ok().unwrap()) }
+    }
+    /// Return `self + other` wrapped to the range of `Self`, and whether the sum
+    /// overflowed, like `overflowing_add` on the primitive integers.
+    fn overflowing_add(self, other: Self) -> (Self, bool)
+    where
+        Self::Raw: Into<i128> + TryFrom<i128>,
+    {
+        (self.wrapping_add(other), self.checked_add(other).is_none())
+    }
+    /// Return `self - other` wrapped to the range of `Self`, and whether the difference
+    /// overflowed, like `overflowing_sub` on the primitive integers.
+    fn overflowing_sub(self, other: Self) -> (Self, bool)
+    where
+        Self::Raw: Into<i128> + TryFrom<i128>,
+    {
+        (self.wrapping_sub(other), self.checked_sub(other).is_none())
+    }
+    /// Return `self * other` with the same number of bits and shift, wrapped to the range
+    /// of `Self`, and whether the product overflowed.  The product is truncated like
+    /// `checked_mul`.
+    fn overflowing_mul(self, other: Self) -> (Self, bool)
+    where
+        Self::Raw: Into<i128> + TryFrom<i128>,
+    {
+        let product = checked::wrapping_mul(self.raw().into(), other.raw().into(), Self::SHIFT);
+        let wrapped = checked::wrap(product, Self::BITS, Self::SIGNED);
+        let val = unsafe { Self::new_unchecked(wrapped.try_into().ok().unwrap()) };
+        (val, self.checked_mul(other).is_none())
+    }
+    /// Return the multiple of `step` nearest to `self`, with ties toward positive infinity,
+    /// e.g. to snap a setpoint to the resolution of an actuator.  `step` may have different
+    /// bits, but must have the same shift (checked at compile time).  Panics if `step` is
+    /// zero or the result does not fit in `Self`.
+    fn round_to_multiple<T: Num>(self, step: T) -> Self
+    where
+        Self::Raw: Into<i128> + TryFrom<i128>,
+        T::Raw: Into<i128>,
+    {
+        assert!(step.raw().into() != 0, "attempt to round to a multiple of zero");
+        self.checked_round_to_multiple(step)
+            .expect("attempt to round to a multiple with overflow")
+    }
+    /// Return the multiple of `step` nearest to `self` like `round_to_multiple`, or `None` if
+    /// `step` is zero or the result does not fit in `Self`.
+    fn checked_round_to_multiple<T: Num>(self, step: T) -> Option<Self>
+    where
+        Self::Raw: Into<i128> + TryFrom<i128>,
+        T::Raw: Into<i128>,
+    {
+        let () = checked::SameShift::<Self, T>::CHECK;
+        let step = step.raw().into();
+        if step == 0 {
+            return None;
+        }
+        let rounded = checked::round_to_multiple(self.raw().into(), step)?;
+        Self::new(rounded.try_into().ok()?).ok()
+    }
+    /// Return the multiple of `step` nearest to `self` like `round_to_multiple`, but
+    /// saturate to the range of `Self` (which may not be a multiple of `step`) if the result
+    /// does not fit.  Panics if `step` is zero.
+    fn saturating_round_to_multiple<T: Num>(self, step: T) -> Self
+    where
+        Self::Raw: Into<i128> + TryFrom<i128>,
+        T::Raw: Into<i128>,
+    {
+        assert!(step.raw().into() != 0, "attempt to round to a multiple of zero");
+        match self.checked_round_to_multiple(step) {
+            Some(rounded) => rounded,
+            // The rounded value has the same sign as `self`.
+            None if self.raw().into() < 0 => Self::MIN,
+            None => Self::MAX,
+        }
+    }
     /// Shift the logical value of this number left by N bits. (N may be negative
     /// for a right shift).  This is a type system operation only; the raw value
     /// is unchanged.  The logical value is multiplied by 2^N.
@@ -218,9 +685,142 @@ pub trait Num: Clone + Copy + Debug + Eq + Ord + PartialEq + PartialOrd + Sized
     {
         unsafe { Self::Output::new_unchecked(self.raw() >> N) }
     }
+    /// Shift the raw value of this number right by N bits, rounding according to
+    /// `rounding` instead of truncating, which avoids the bias of `raw_shr`.  The result has
+    /// one more bit than that of `raw_shr`, since it may round up to the next power of 2.
+    fn raw_shr_round<const N: u32>(
+        self,
+        rounding: Rounding,
+    ) -> Self::Output<{ Self::BITS - N + 1 }, { Self::SHIFT - N as i32 }>
+    where
+        Self::Raw: Into<i128> + TryFrom<i128>,
+        [(); (Self::BITS - N + 1) as usize]:,
+        [(); (Self::SHIFT - N as i32) as usize]:,
+    {
+        // The result always fits, so `requantize` never saturates.
+        self.requantize(rounding)
+    }
+    /// Shift the raw value of this number right by N bits with stochastic rounding: the
+    /// result rounds up with a probability equal to the discarded fraction, drawn from
+    /// `rng`, so that it is unbiased on average.  The result has the same bits as that of
+    /// `raw_shr_round`.
+    fn raw_shr_stochastic<const N: u32, R: Prng>(
+        self,
+        rng: &mut R,
+    ) -> Self::Output<{ Self::BITS - N + 1 }, { Self::SHIFT - N as i32 }>
+    where
+        Self::Raw: Into<i128> + TryFrom<i128>,
+        [(); (Self::BITS - N + 1) as usize]:,
+        [(); (Self::SHIFT - N as i32) as usize]:,
+    {
+        requantize::shr_stochastic(self, N, rng.next_bits(N))
+    }
+    /// Shift the raw value of this number right by N bits with triangular-PDF dither: the
+    /// difference of two uniform random values of N bits from `rng` (i.e. up to ±1 LSB of
+    /// the result) is added before rounding to nearest, so that the requantization noise is
+    /// decorrelated from the signal, e.g. to reduce the bit depth of audio.  The result has
+    /// the same type as that of `raw_shr`, and saturates to its range.  It is a compile error
+    /// if `Self` has more than 126 signed bits.
+    fn dither_shr<const N: u32, R: Prng>(
+        self,
+        rng: &mut R,
+    ) -> Self::Output<{ Self::BITS - N }, { Self::SHIFT - N as i32 }>
+    where
+        Self::Raw: Into<i128> + TryFrom<i128>,
+        [(); (Self::BITS - N) as usize]:,
+        [(); (Self::SHIFT - N as i32) as usize]:,
+    {
+        requantize::dither_shr(self, N, rng.next_bits(N), rng.next_bits(N))
+    }
 }
 
+mod float;
+mod raw;
+pub use raw::signed_bits;
 mod num_impl;
 pub use num_impl::*;
+mod wide;
+pub use wide::*;
 mod add_sub;
 mod mul_div;
+pub use mul_div::{DivRem, WideningMul};
+mod requantize;
+pub use requantize::Rounding;
+mod checked;
+mod overflow;
+pub use overflow::*;
+mod histogram;
+pub use histogram::*;
+mod p_square;
+pub use p_square::*;
+mod regression;
+pub use regression::*;
+mod accumulator;
+pub use accumulator::*;
+mod bounded;
+pub use bounded::*;
+mod units;
+pub use units::*;
+mod angle;
+pub use angle::*;
+mod duration;
+pub use duration::*;
+mod ratio;
+pub use ratio::*;
+mod money;
+pub use money::*;
+mod complex;
+pub use complex::*;
+mod vector;
+pub use vector::*;
+mod matrix;
+pub use matrix::*;
+mod quaternion;
+pub use quaternion::*;
+mod interval;
+pub use interval::*;
+mod tracked;
+pub use tracked::*;
+#[cfg(feature = "uom")]
+mod uom_impl;
+#[cfg(feature = "uom")]
+pub use uom_impl::*;
+mod prob;
+pub use prob::*;
+mod filter;
+pub use filter::*;
+mod encoder;
+pub use encoder::*;
+mod motion;
+pub use motion::*;
+mod coulomb;
+pub use coulomb::*;
+mod prng;
+pub use prng::*;
+mod conv;
+pub use conv::*;
+mod resample;
+pub use resample::*;
+mod pan;
+pub use pan::*;
+mod gain;
+pub use gain::*;
+mod state_space;
+pub use state_space::*;
+mod control;
+pub use control::*;
+pub mod analyze;
+pub mod motor;
+pub mod nav;
+pub mod sensor;
+#[doc(hidden)]
+pub mod auto_bits;
+pub use auto_bits::{Fix, UFix};
+pub mod cordic;
+pub mod matlab;
+#[cfg(feature = "posit")]
+pub mod posit;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(feature = "python")]
+pub mod python;
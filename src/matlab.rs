@@ -0,0 +1,394 @@
+//! Interoperability with MATLAB and Simulink fixed-point (`fi`) objects.
+//!
+//! A [`NumericType`] describes a MATLAB fixed-point format with binary-point scaling.  It can
+//! be parsed from and formatted as a MATLAB `numerictype(1,16,13)` or `fixdt(1,16,13)`
+//! expression, or a Simulink data type name such as `sfix16_En13`.  MATLAB's word length
+//! and fraction length correspond exactly to `BITS` and `SHIFT` of a fixed-point type in
+//! this crate, so `sfix16_En13` describes the type `I16<16, 13>` (or any wider raw type
+//! with the same `BITS` and `SHIFT`), and the stored integer of a `fi` object is the raw
+//! value.
+//!
+//! With the `mat` feature, test vectors can be exchanged with MATLAB through uncompressed
+//! Level 5 MAT-files (see [`write_mat`] and [`read_mat`]).
+
+use core::fmt;
+use core::str::FromStr;
+
+use crate::Num;
+
+/// A MATLAB fixed-point format with binary-point scaling.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct NumericType {
+    /// Whether the format is signed (two's complement).
+    pub signed: bool,
+    /// The total number of bits, including the sign bit.  Equivalent to `BITS`.
+    pub word_length: u32,
+    /// The number of bits after the binary point.  Equivalent to `SHIFT`.
+    pub fraction_length: i32,
+}
+
+/// An error which can be returned when parsing a [`NumericType`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ParseNumericTypeError;
+
+impl NumericType {
+    /// Return the format of the fixed-point type `T`.
+    pub const fn of<T: Num>() -> Self {
+        Self {
+            signed: T::SIGNED,
+            word_length: T::BITS,
+            fraction_length: T::SHIFT,
+        }
+    }
+    /// Return whether the fixed-point type `T` has exactly this format.
+    pub fn matches<T: Num>(self) -> bool {
+        self == Self::of::<T>()
+    }
+    /// Format as a MATLAB `numerictype(s,w,f)` expression.
+    pub fn to_numerictype(self) -> String {
+        format!(
+            "numerictype({},{},{})",
+            self.signed as u8, self.word_length, self.fraction_length
+        )
+    }
+}
+
+/// Formats as a Simulink data type name, e.g. `sfix16_En13`, `ufix8` or `sfix32_E4`.
+impl fmt::Display for NumericType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let sign = if self.signed { 's' } else { 'u' };
+        write!(f, "{sign}fix{}", self.word_length)?;
+        match self.fraction_length {
+            0 => Ok(()),
+            n if n > 0 => write!(f, "_En{n}"),
+            n => write!(f, "_E{}", n.unsigned_abs()),
+        }
+    }
+}
+
+/// Parses a MATLAB `numerictype(s,w,f)` or `fixdt(s,w,f)` expression, a Simulink data
+/// type name such as `sfix16_En13`, or a built-in integer type name such as `int16`.
+impl FromStr for NumericType {
+    type Err = ParseNumericTypeError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let err = ParseNumericTypeError;
+        let args = s
+            .strip_prefix("numerictype(")
+            .or_else(|| s.strip_prefix("fixdt("));
+        let (signed, word_length, fraction_length) = if let Some(args) = args {
+            let args = args.strip_suffix(')').ok_or(err)?;
+            let mut args = args.split(',').map(str::trim);
+            let signed = match args.next() {
+                Some("1" | "true") => true,
+                Some("0" | "false") => false,
+                _ => return Err(err),
+            };
+            let word_length = args.next().ok_or(err)?.parse().map_err(|_| err)?;
+            let fraction_length = args.next().ok_or(err)?.parse().map_err(|_| err)?;
+            if args.next().is_some() {
+                return Err(err);
+            }
+            (signed, word_length, fraction_length)
+        } else if let Some(bits) = s.strip_prefix("uint") {
+            (false, bits.parse().map_err(|_| err)?, 0)
+        } else if let Some(bits) = s.strip_prefix("int") {
+            (true, bits.parse().map_err(|_| err)?, 0)
+        } else {
+            let signed = match s.get(..4) {
+                Some("sfix") => true,
+                Some("ufix") => false,
+                _ => return Err(err),
+            };
+            let (bits, scaling) = match s[4..].split_once('_') {
+                Some((bits, scaling)) => (bits, Some(scaling)),
+                None => (&s[4..], None),
+            };
+            // The scaling is `En` or `E` followed by digits only, without a sign.
+            let digits = |n: &str| -> Result<i32, ParseNumericTypeError> {
+                if !n.bytes().all(|b| b.is_ascii_digit()) {
+                    return Err(err);
+                }
+                let n: u32 = n.parse().map_err(|_| err)?;
+                i32::try_from(n).map_err(|_| err)
+            };
+            let fraction_length = match scaling {
+                None => 0,
+                Some(n) if n.starts_with("En") => digits(&n[2..])?,
+                Some(n) if n.starts_with('E') => digits(&n[1..])?.checked_neg().ok_or(err)?,
+                Some(_) => return Err(err),
+            };
+            (signed, bits.parse().map_err(|_| err)?, fraction_length)
+        };
+        if word_length == 0 || (word_length == 1 && signed) {
+            return Err(err);
+        }
+        Ok(Self {
+            signed,
+            word_length,
+            fraction_length,
+        })
+    }
+}
+
+#[cfg(feature = "mat")]
+pub use mat::*;
+
+#[cfg(feature = "mat")]
+mod mat {
+    use std::io::{self, Read, Write};
+
+    use super::NumericType;
+    use crate::num_impl::runtime_limits;
+    use crate::Num;
+
+    // Data types and array classes of the Level 5 MAT-file format.
+    const MI_INT8: u32 = 1;
+    const MI_UINT8: u32 = 2;
+    const MI_INT16: u32 = 3;
+    const MI_UINT16: u32 = 4;
+    const MI_INT32: u32 = 5;
+    const MI_UINT32: u32 = 6;
+    const MI_INT64: u32 = 12;
+    const MI_UINT64: u32 = 13;
+    const MI_MATRIX: u32 = 14;
+    const MI_COMPRESSED: u32 = 15;
+    const MI_UTF8: u32 = 16;
+    const MX_CHAR_CLASS: u8 = 4;
+    const MX_INT8_CLASS: u8 = 8;
+
+    /// A vector of fixed-point values, stored as raw values together with their format.
+    ///
+    /// In a MAT-file, a test vector named `x` is stored as a column vector `x` of the stored
+    /// integers (of MATLAB class `int8`, `uint16`, etc.), and a character array `x_type`
+    /// with the Simulink name of its format.  In MATLAB, such a test vector is created from
+    /// a `fi` column vector `v` of type `sfix16_En13` with `x = storedInteger(v)` and
+    /// `x_type = 'sfix16_En13'`, and converted back to a `fi` object with
+    /// `v = fi(zeros(size(x)), fixdt(x_type)); v.int = x`.
+    #[derive(Clone, Debug, Eq, PartialEq)]
+    pub struct TestVector {
+        /// The name of the MATLAB variable holding the stored integers.
+        pub name: String,
+        /// The fixed-point format of the values.
+        pub numeric_type: NumericType,
+        /// The raw values (stored integers).
+        pub raw: Vec<i128>,
+    }
+
+    impl TestVector {
+        /// Return a test vector holding `values`.
+        pub fn from_fp<T: Num>(name: &str, values: &[T]) -> Self
+        where
+            T::Raw: Into<i128>,
+        {
+            Self {
+                name: name.into(),
+                numeric_type: NumericType::of::<T>(),
+                raw: values.iter().map(|val| val.raw().into()).collect(),
+            }
+        }
+        /// Return the values of this test vector as type `T`, or `None` if the format of
+        /// `T` does not match or a value is out of range.
+        pub fn to_fp<T: Num>(&self) -> Option<Vec<T>>
+        where
+            T::Raw: TryFrom<i128>,
+        {
+            if !self.numeric_type.matches::<T>() {
+                return None;
+            }
+            self.raw
+                .iter()
+                .map(|&raw| T::new(raw.try_into().ok()?).ok())
+                .collect()
+        }
+    }
+
+    fn invalid_input(msg: &str) -> io::Error {
+        io::Error::new(io::ErrorKind::InvalidInput, msg)
+    }
+
+    fn invalid_data(msg: &str) -> io::Error {
+        io::Error::new(io::ErrorKind::InvalidData, msg)
+    }
+
+    /// Append a data element with the given data type to `out`, padded to 8 bytes.
+    fn element(out: &mut Vec<u8>, data_type: u32, data: &[u8]) {
+        out.extend(data_type.to_le_bytes());
+        out.extend((data.len() as u32).to_le_bytes());
+        out.extend(data);
+        out.resize(out.len().next_multiple_of(8), 0);
+    }
+
+    /// Append a column vector (of class `class`) to `out`.
+    fn matrix(out: &mut Vec<u8>, name: &str, class: u8, len: usize, data_type: u32, data: &[u8]) {
+        let mut content = vec![];
+        element(&mut content, MI_UINT32, &[class, 0, 0, 0, 0, 0, 0, 0]);
+        let dims = [(len as i32).to_le_bytes(), 1i32.to_le_bytes()].concat();
+        element(&mut content, MI_INT32, &dims);
+        element(&mut content, MI_INT8, name.as_bytes());
+        element(&mut content, data_type, data);
+        element(out, MI_MATRIX, &content);
+    }
+
+    /// Write `vectors` to `writer` as an uncompressed Level 5 MAT-file, which can be loaded
+    /// with MATLAB's `load`.  Returns an error if a word length exceeds 64 bits.
+    pub fn write_mat<W: Write>(mut writer: W, vectors: &[TestVector]) -> io::Result<()> {
+        let mut out =
+            format!("{:<116}", "MATLAB 5.0 MAT-file, written by the fp crate").into_bytes();
+        out.extend([0; 8]);
+        out.extend(0x0100u16.to_le_bytes());
+        out.extend(b"IM");
+        for vector in vectors {
+            let nt = vector.numeric_type;
+            if nt.word_length > 64 {
+                return Err(invalid_input("word length exceeds 64 bits"));
+            }
+            let (min, max) = runtime_limits(nt.word_length, nt.signed);
+            if vector.raw.iter().any(|&raw| raw < min || raw > max) {
+                return Err(invalid_input("raw value does not fit in the word length"));
+            }
+            // The smallest MATLAB integer class which holds the stored integers.
+            let bytes = nt.word_length.next_power_of_two().max(8) as usize / 8;
+            let index = bytes.trailing_zeros() as u8 * 2 + !nt.signed as u8;
+            let data_type = [
+                MI_INT8, MI_UINT8, MI_INT16, MI_UINT16, MI_INT32, MI_UINT32, MI_INT64, MI_UINT64,
+            ][index as usize];
+            let data: Vec<u8> = vector
+                .raw
+                .iter()
+                .flat_map(|raw| raw.to_le_bytes()[..bytes].to_vec())
+                .collect();
+            let class = MX_INT8_CLASS + index;
+            matrix(
+                &mut out,
+                &vector.name,
+                class,
+                vector.raw.len(),
+                data_type,
+                &data,
+            );
+            let name = format!("{}_type", vector.name);
+            let chars: Vec<u8> = nt.to_string().bytes().flat_map(|c| [c, 0]).collect();
+            matrix(
+                &mut out,
+                &name,
+                MX_CHAR_CLASS,
+                chars.len() / 2,
+                MI_UINT16,
+                &chars,
+            );
+        }
+        writer.write_all(&out)
+    }
+
+    /// Split the data element at the start of `data` into its data type and contents, and
+    /// return the remaining data.
+    fn split_element(data: &[u8]) -> io::Result<(u32, &[u8], &[u8])> {
+        let word = |i: usize| u32::from_le_bytes(data[i..i + 4].try_into().unwrap());
+        if data.len() < 8 {
+            return Err(invalid_data("truncated data element"));
+        }
+        if word(0) >> 16 != 0 {
+            // Small data element format: the type, size and data are packed into 8 bytes.
+            let len = (word(0) >> 16) as usize;
+            if len > 4 {
+                return Err(invalid_data("invalid small data element"));
+            }
+            return Ok((word(0) & 0xffff, &data[4..4 + len], &data[8..]));
+        }
+        let len = word(4) as usize;
+        let padded = 8 + len.next_multiple_of(8);
+        if data.len() < 8 + len {
+            return Err(invalid_data("truncated data element"));
+        }
+        Ok((word(0), &data[8..8 + len], &data[padded.min(data.len())..]))
+    }
+
+    /// Decode the contents of a numeric data element.
+    fn decode(data_type: u32, data: &[u8]) -> io::Result<Vec<i128>> {
+        let (size, signed) = match data_type {
+            MI_INT8 => (1, true),
+            MI_UINT8 | MI_UTF8 => (1, false),
+            MI_INT16 => (2, true),
+            MI_UINT16 => (2, false),
+            MI_INT32 => (4, true),
+            MI_UINT32 => (4, false),
+            MI_INT64 => (8, true),
+            MI_UINT64 => (8, false),
+            _ => return Err(invalid_data("unsupported data type")),
+        };
+        Ok(data
+            .chunks_exact(size)
+            .map(|chunk| {
+                let mut bytes = [0; 16];
+                bytes[..size].copy_from_slice(chunk);
+                let raw = i128::from_le_bytes(bytes);
+                // Sign-extend from `size` bytes.
+                let unused = 128 - 8 * size as u32;
+                if signed {
+                    (raw << unused) >> unused
+                } else {
+                    raw
+                }
+            })
+            .collect())
+    }
+
+    /// Read the test vectors from an uncompressed, little-endian Level 5 MAT-file, such as
+    /// one written by [`write_mat`] or by MATLAB's `save -v6`.  Variables which are not
+    /// part of a test vector are ignored.
+    pub fn read_mat<R: Read>(mut reader: R) -> io::Result<Vec<TestVector>> {
+        let mut data = vec![];
+        reader.read_to_end(&mut data)?;
+        if data.len() < 128 || &data[126..128] != b"IM" {
+            return Err(invalid_data("not a little-endian Level 5 MAT-file"));
+        }
+        let mut rest = &data[128..];
+        let mut integers = vec![];
+        let mut types = vec![];
+        while !rest.is_empty() {
+            let (data_type, content, next) = split_element(rest)?;
+            rest = next;
+            match data_type {
+                MI_MATRIX => (),
+                MI_COMPRESSED => {
+                    return Err(invalid_data("compressed MAT-files are not supported"))
+                }
+                _ => continue,
+            }
+            let (_, flags, content) = split_element(content)?;
+            let (_, _, content) = split_element(content)?;
+            let (_, name, content) = split_element(content)?;
+            let name = String::from_utf8_lossy(name).into_owned();
+            let class = *flags.first().ok_or(invalid_data("missing array flags"))?;
+            let complex = flags.get(1).is_some_and(|f| f & 0x08 != 0);
+            let integer = (MX_INT8_CLASS..MX_INT8_CLASS + 8).contains(&class);
+            if content.is_empty() || complex || !(integer || class == MX_CHAR_CLASS) {
+                continue;
+            }
+            let (data_type, values, _) = split_element(content)?;
+            let values = decode(data_type, values)?;
+            if integer {
+                integers.push((name, values));
+            } else {
+                let s = values.iter().filter_map(|&c| char::from_u32(c as u32));
+                types.push((name, s.collect::<String>()));
+            }
+        }
+        let mut vectors = vec![];
+        for (name, raw) in integers {
+            let type_name = format!("{name}_type");
+            if let Some((_, s)) = types.iter().find(|(n, _)| *n == type_name) {
+                let numeric_type = s
+                    .parse()
+                    .map_err(|_| invalid_data("invalid numeric type"))?;
+                vectors.push(TestVector {
+                    name,
+                    numeric_type,
+                    raw,
+                });
+            }
+        }
+        Ok(vectors)
+    }
+}
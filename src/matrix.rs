@@ -0,0 +1,129 @@
+use core::ops::{Add, Mul, Sub};
+
+use crate::add_sub::ceil_log2;
+use crate::Num;
+
+/// A matrix with `R` rows and `C` columns of elements of type `N` (usually a fixed-point
+/// number), e.g. the state transition or covariance matrix of a Kalman filter, or a
+/// rotation between coordinate frames.
+///
+/// As for [`Vec2`](crate::Vec2), the operators act on the elements with the operators of
+/// `N`, so the output types account for bit growth: `+` and `-` add 1 bit, and each element
+/// of a matrix product (or of a matrix-vector product) is the sum of `K` products, so it
+/// has `log2(K)` (rounded up) more bits than a product of the elements.  An overflow is a
+/// compile error rather than a wrapped value.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Matrix<N, const R: usize, const C: usize>([[N; C]; R]);
+
+impl<N, const R: usize, const C: usize> Matrix<N, R, C> {
+    /// Create the matrix with the given rows.
+    pub const fn new(rows: [[N; C]; R]) -> Self {
+        Self(rows)
+    }
+    /// Return the rows.
+    pub fn rows(&self) -> &[[N; C]; R] {
+        &self.0
+    }
+    /// Return the element in row `r` and column `c`.  Panics if either is out of range.
+    pub fn get(&self, r: usize, c: usize) -> &N {
+        &self.0[r][c]
+    }
+}
+
+impl<N: Copy, const R: usize, const C: usize> Matrix<N, R, C> {
+    /// Return the transpose, which has the same elements.
+    pub fn transpose(&self) -> Matrix<N, C, R> {
+        Matrix(core::array::from_fn(|c| {
+            core::array::from_fn(|r| self.0[r][c])
+        }))
+    }
+}
+
+impl<N: Num, const R: usize, const C: usize> Default for Matrix<N, R, C> {
+    /// The zero matrix.
+    fn default() -> Self {
+        Self([[N::ZERO; C]; R])
+    }
+}
+
+/// Return the sum of the products `a[i] * b[i]` in the type `Out`, which has the raw type
+/// and shift of the products and `log2(K)` (rounded up) more bits.
+fn dot<T0, T1, P, Out, const K: usize>(a: [T0; K], b: [T1; K]) -> Out
+where
+    T0: Mul<T1, Output = P>,
+    P: Num,
+    Out: Num<Raw = P::Raw>,
+{
+    let sum = a
+        .into_iter()
+        .zip(b)
+        .fold(P::ZERO.raw(), |sum, (a, b)| sum + (a * b).raw());
+    // The sum of `K` products fits in `Out`, whose bits are checked at compile time.
+    unsafe { Out::new_unchecked(sum) }
+}
+
+/// Matrices with the same shape may be added, if their elements may be added.
+impl<T0, T1, const R: usize, const C: usize> Add<Matrix<T1, R, C>> for Matrix<T0, R, C>
+where
+    T0: Add<T1> + Copy,
+    T1: Copy,
+{
+    type Output = Matrix<T0::Output, R, C>;
+    fn add(self, other: Matrix<T1, R, C>) -> Self::Output {
+        Matrix(core::array::from_fn(|r| {
+            core::array::from_fn(|c| self.0[r][c] + other.0[r][c])
+        }))
+    }
+}
+
+/// Matrices with the same shape may be subtracted, if their elements may be subtracted.
+impl<T0, T1, const R: usize, const C: usize> Sub<Matrix<T1, R, C>> for Matrix<T0, R, C>
+where
+    T0: Sub<T1> + Copy,
+    T1: Copy,
+{
+    type Output = Matrix<T0::Output, R, C>;
+    fn sub(self, other: Matrix<T1, R, C>) -> Self::Output {
+        Matrix(core::array::from_fn(|r| {
+            core::array::from_fn(|c| self.0[r][c] - other.0[r][c])
+        }))
+    }
+}
+
+/// An `R x K` matrix may multiply a `K x C` matrix, if their elements may be multiplied.
+/// Each element of the product is a sum of `K` products, so it has `log2(K)` (rounded up)
+/// more bits than a product of the elements.
+impl<T0, T1, P, const R: usize, const K: usize, const C: usize> Mul<Matrix<T1, K, C>>
+    for Matrix<T0, R, K>
+where
+    T0: Mul<T1, Output = P> + Copy,
+    T1: Copy,
+    P: Num,
+    [(); (P::BITS + ceil_log2(K)) as usize]:,
+    [(); P::SHIFT as usize]:,
+{
+    type Output = Matrix<P::Output<{ P::BITS + ceil_log2(K) }, { P::SHIFT }>, R, C>;
+    fn mul(self, other: Matrix<T1, K, C>) -> Self::Output {
+        let other = other.transpose();
+        Matrix(core::array::from_fn(|r| {
+            core::array::from_fn(|c| dot(self.0[r], other.0[c]))
+        }))
+    }
+}
+
+/// An `R x C` matrix may multiply a vector of `C` elements, if their elements may be
+/// multiplied.  Each element of the product has `log2(C)` (rounded up) more bits than a
+/// product of the elements, as for a matrix product.
+impl<T0, T1, P, const R: usize, const C: usize> Mul<[T1; C]> for Matrix<T0, R, C>
+where
+    T0: Mul<T1, Output = P> + Copy,
+    T1: Copy,
+    P: Num,
+    [(); (P::BITS + ceil_log2(C)) as usize]:,
+    [(); P::SHIFT as usize]:,
+{
+    type Output = [P::Output<{ P::BITS + ceil_log2(C) }, { P::SHIFT }>; R];
+    fn mul(self, other: [T1; C]) -> Self::Output {
+        core::array::from_fn(|r| dot(self.0[r], other))
+    }
+}
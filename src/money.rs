@@ -0,0 +1,247 @@
+use core::fmt;
+use core::marker::PhantomData;
+use core::ops::{Add, Neg, Sub};
+
+use crate::Num;
+
+/// A currency, used only as a type parameter of [`Money`] so that amounts in different
+/// currencies cannot be mixed.
+pub trait Currency {
+    /// ISO 4217 code, e.g. `"USD"`.
+    const CODE: &'static str;
+}
+
+/// Return `num / den`, rounded to nearest with ties to even.  `den` must be positive.
+fn div_half_even(num: i128, den: i128) -> i128 {
+    let (quotient, remainder) = (num.div_euclid(den), num.rem_euclid(den));
+    match (2 * remainder).cmp(&den) {
+        core::cmp::Ordering::Less => quotient,
+        core::cmp::Ordering::Equal => quotient + (quotient & 1),
+        core::cmp::Ordering::Greater => quotient + 1,
+    }
+}
+
+/// An amount of money in the currency `C`, stored as an `i64` number of units of
+/// `10^-SCALE`, e.g. `Money<Usd, 2>` counts cents.
+///
+/// Unlike the binary fixed-point types, the scale is decimal, so amounts such as 0.10 are
+/// exact.  Addition and subtraction are exact (and panic on overflow, like the primitive
+/// integers, with `checked_` variants which return `None`).  Multiplication by a ratio and
+/// conversion to a smaller scale round to nearest with ties to even ("banker's rounding"),
+/// which has no bias.  `allocate` and `split` divide an amount into parts which add up to
+/// exactly the original amount, so no units are ever lost.
+pub struct Money<C: Currency, const SCALE: u32> {
+    amount: i64,
+    _currency: PhantomData<C>,
+}
+
+impl<C: Currency, const SCALE: u32> Money<C, SCALE> {
+    /// Zero.
+    pub const ZERO: Self = Self::new(0);
+    /// Create an amount of `amount * 10^-SCALE`, e.g. a number of cents.
+    pub const fn new(amount: i64) -> Self {
+        Self {
+            amount,
+            _currency: PhantomData,
+        }
+    }
+    /// Return the amount in units of `10^-SCALE`.
+    pub const fn amount(self) -> i64 {
+        self.amount
+    }
+    /// Return `self + other`, or `None` if it overflows.
+    pub const fn checked_add(self, other: Self) -> Option<Self> {
+        match self.amount.checked_add(other.amount) {
+            Some(amount) => Some(Self::new(amount)),
+            None => None,
+        }
+    }
+    /// Return `self - other`, or `None` if it overflows.
+    pub const fn checked_sub(self, other: Self) -> Option<Self> {
+        match self.amount.checked_sub(other.amount) {
+            Some(amount) => Some(Self::new(amount)),
+            None => None,
+        }
+    }
+    /// Return `self * factor` for a fixed-point factor, rounded to the nearest unit with
+    /// ties to even, or `None` if it overflows.
+    pub fn checked_mul_fp<T: Num>(self, factor: T) -> Option<Self>
+    where
+        T::Raw: Into<i128>,
+    {
+        let product = i128::from(self.amount).checked_mul(factor.raw().into())?;
+        let amount = if T::SHIFT >= 0 {
+            let den = 1i128.checked_shl(T::SHIFT as u32).filter(|&den| den > 0);
+            match den {
+                Some(den) => div_half_even(product, den),
+                // The magnitude is less than one half.
+                None => 0,
+            }
+        } else {
+            let scale = 1i128
+                .checked_shl(-T::SHIFT as u32)
+                .filter(|&scale| scale > 0)?;
+            product.checked_mul(scale)?
+        };
+        amount.try_into().ok().map(Self::new)
+    }
+    /// Return `self * num / den`, e.g. `price.checked_mul_ratio(7, 100)` for 7% tax,
+    /// rounded to the nearest unit with ties to even, or `None` if it overflows.  Panics if
+    /// `den` is zero.
+    pub fn checked_mul_ratio(self, num: i64, den: i64) -> Option<Self> {
+        assert!(den != 0, "attempt to divide by zero");
+        let product = i128::from(self.amount) * i128::from(num) * i128::from(den.signum());
+        let amount = div_half_even(product, i128::from(den).abs());
+        amount.try_into().ok().map(Self::new)
+    }
+    /// Convert to another scale, rounding to the nearest unit with ties to even if the
+    /// scale is smaller, or return `None` if it overflows.
+    pub fn checked_rescale<const SCALE1: u32>(self) -> Option<Money<C, SCALE1>> {
+        let amount = i128::from(self.amount);
+        let amount = if SCALE1 >= SCALE {
+            amount.checked_mul(10i128.checked_pow(SCALE1 - SCALE)?)?
+        } else {
+            match 10i128.checked_pow(SCALE - SCALE1) {
+                Some(den) => div_half_even(amount, den),
+                // The magnitude is less than one half.
+                None => 0,
+            }
+        };
+        amount.try_into().ok().map(Money::new)
+    }
+    /// Divide into `N` parts in proportion to `weights`, which add up to exactly `self`.
+    /// Each part is rounded toward zero, and then the remaining units are distributed one
+    /// at a time to the parts with the largest remainders (the earliest part, if there is
+    /// a tie).  Panics if the weights are all zero.
+    pub fn allocate<const N: usize>(self, weights: [u32; N]) -> [Self; N] {
+        let total: u128 = weights.iter().map(|&w| u128::from(w)).sum();
+        assert!(total > 0, "weights must not all be zero");
+        let magnitude = u128::from(self.amount.unsigned_abs());
+        let mut parts = [0u128; N];
+        let mut remainders = [0u128; N];
+        for i in 0..N {
+            let product = magnitude * u128::from(weights[i]);
+            parts[i] = product / total;
+            remainders[i] = product % total;
+        }
+        let mut leftover = magnitude - parts.iter().sum::<u128>();
+        while leftover > 0 {
+            // Fewer than `N` units are left over, so each part receives at most one.
+            let mut best = 0;
+            for i in 1..N {
+                if remainders[i] > remainders[best] {
+                    best = i;
+                }
+            }
+            parts[best] += 1;
+            remainders[best] = 0;
+            leftover -= 1;
+        }
+        parts.map(|part| {
+            // Each part fits in `i64`, but its magnitude may not (for `i64::MIN`).
+            let part = part as u64;
+            Self::new(if self.amount < 0 {
+                part.wrapping_neg()
+            } else {
+                part
+            } as i64)
+        })
+    }
+    /// Divide into `N` equal parts, which add up to exactly `self`.  The parts differ by at
+    /// most one unit, and the larger parts come first.  Panics if `N` is zero.
+    pub fn split<const N: usize>(self) -> [Self; N] {
+        self.allocate([1; N])
+    }
+}
+
+/// Amounts with the same currency and scale may be added.  Panics on overflow.
+impl<C: Currency, const SCALE: u32> Add for Money<C, SCALE> {
+    type Output = Self;
+    fn add(self, other: Self) -> Self {
+        self.checked_add(other)
+            .expect("attempt to add with overflow")
+    }
+}
+
+/// Amounts with the same currency and scale may be subtracted.  Panics on overflow.
+impl<C: Currency, const SCALE: u32> Sub for Money<C, SCALE> {
+    type Output = Self;
+    fn sub(self, other: Self) -> Self {
+        self.checked_sub(other)
+            .expect("attempt to subtract with overflow")
+    }
+}
+
+impl<C: Currency, const SCALE: u32> Neg for Money<C, SCALE> {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Self::new(-self.amount)
+    }
+}
+
+/// The amount with exactly `SCALE` decimal places, followed by the currency code, e.g.
+/// `-12.34 USD`.
+impl<C: Currency, const SCALE: u32> fmt::Display for Money<C, SCALE> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let sign = if self.amount < 0 { "-" } else { "" };
+        let magnitude = u128::from(self.amount.unsigned_abs());
+        match 10u128.checked_pow(SCALE) {
+            Some(unit) if SCALE > 0 => write!(
+                f,
+                "{sign}{}.{:0width$} {}",
+                magnitude / unit,
+                magnitude % unit,
+                C::CODE,
+                width = SCALE as usize
+            ),
+            Some(_) => write!(f, "{sign}{magnitude} {}", C::CODE),
+            None => write!(
+                f,
+                "{sign}0.{:0>width$} {}",
+                magnitude,
+                C::CODE,
+                width = SCALE as usize
+            ),
+        }
+    }
+}
+
+// These are implemented manually, because deriving them would require `C` to implement
+// the same traits.
+impl<C: Currency, const SCALE: u32> Clone for Money<C, SCALE> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+impl<C: Currency, const SCALE: u32> Copy for Money<C, SCALE> {}
+impl<C: Currency, const SCALE: u32> fmt::Debug for Money<C, SCALE> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Money({self})")
+    }
+}
+impl<C: Currency, const SCALE: u32> Default for Money<C, SCALE> {
+    fn default() -> Self {
+        Self::ZERO
+    }
+}
+impl<C: Currency, const SCALE: u32> PartialEq for Money<C, SCALE> {
+    fn eq(&self, other: &Self) -> bool {
+        self.amount == other.amount
+    }
+}
+impl<C: Currency, const SCALE: u32> Eq for Money<C, SCALE> {}
+impl<C: Currency, const SCALE: u32> PartialOrd for Money<C, SCALE> {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl<C: Currency, const SCALE: u32> Ord for Money<C, SCALE> {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.amount.cmp(&other.amount)
+    }
+}
+impl<C: Currency, const SCALE: u32> core::hash::Hash for Money<C, SCALE> {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.amount.hash(state);
+    }
+}
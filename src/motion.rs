@@ -0,0 +1,155 @@
+use core::marker::PhantomData;
+
+use crate::raw::{from_raw, shift_round, signed_bits};
+use crate::Num;
+
+/// Motion profile generator for steppers and servos, producing one position and velocity
+/// setpoint per tick.
+///
+/// Positions have type `P`, velocities (per tick) have type `V`, and accelerations (per
+/// tick squared) have type `A`.  The shifts must satisfy `P::SHIFT <= V::SHIFT <=
+/// A::SHIFT`, and all internal calculations are carried out exactly with the resolution of
+/// `A`.  The moving position always lies between the start and the target, so it cannot
+/// overflow `P`; the internal accumulators are checked at compile time to be wide enough
+/// for the worst-case travel between any two values of `P`.
+///
+/// With `SMOOTH = 1`, the profile is trapezoidal: the velocity ramps up with the maximum
+/// acceleration, cruises at the maximum velocity, and ramps down to arrive exactly at the
+/// target.  With `SMOOTH > 1`, the profile is jerk-limited (S-curve): the trapezoidal
+/// position is smoothed by a moving average over `SMOOTH` ticks, which limits the jerk to
+/// the maximum acceleration divided by `SMOOTH`, while keeping the velocity and
+/// acceleration limits and arriving exactly at the target (`SMOOTH - 1` ticks later).
+#[derive(Clone, Debug)]
+pub struct MotionProfile<P: Num, V: Num, A: Num, const SMOOTH: usize = 1> {
+    _types: PhantomData<(P, V, A)>,
+    max_velocity: i128,
+    accel: i128,
+    // State of the trapezoidal profile, with the shift of `A`.
+    target: i128,
+    position: i128,
+    velocity: i128,
+    // Trapezoidal positions of the last `SMOOTH` ticks, and their sum.
+    history: [i128; SMOOTH],
+    next: usize,
+    sum: i128,
+    // The previous output position (the average of `history`), with the shift of `A`.
+    output: i128,
+}
+
+impl<P: Num, V: Num, A: Num, const SMOOTH: usize> MotionProfile<P, V, A, SMOOTH>
+where
+    P::Raw: Into<i128> + TryFrom<i128>,
+    V::Raw: Into<i128> + TryFrom<i128>,
+    A::Raw: Into<i128>,
+{
+    /// Compile-time check of the shifts and the width of the accumulators.
+    const CHECK: () = {
+        assert!(
+            P::SHIFT <= V::SHIFT && V::SHIFT <= A::SHIFT,
+            "shifts must satisfy P::SHIFT <= V::SHIFT <= A::SHIFT"
+        );
+        assert!(
+            SMOOTH > 0,
+            "smoothing window must contain at least one tick"
+        );
+        // The distance between any two positions with the shift of `A`, and the sum of
+        // `SMOOTH` positions (which needs at most `ilog2(SMOOTH) + 1` more bits).
+        assert!(
+            signed_bits::<P>() + (A::SHIFT - P::SHIFT) as u32 + 1 + SMOOTH.ilog2() < i128::BITS,
+            "too many bits for MotionProfile"
+        );
+    };
+    /// Create a profile which is at rest at `start`.  `max_velocity` and `accel` must be
+    /// positive.
+    pub fn new(start: P, max_velocity: V, accel: A) -> Self {
+        let () = Self::CHECK;
+        let max_velocity = max_velocity.raw().into() << (A::SHIFT - V::SHIFT);
+        let accel = accel.raw().into();
+        assert!(
+            max_velocity > 0 && accel > 0,
+            "velocity and acceleration limits must be positive"
+        );
+        let start = start.raw().into() << (A::SHIFT - P::SHIFT);
+        Self {
+            _types: PhantomData,
+            max_velocity,
+            accel,
+            target: start,
+            position: start,
+            velocity: 0,
+            history: [start; SMOOTH],
+            next: 0,
+            sum: start * SMOOTH as i128,
+            output: start,
+        }
+    }
+    /// Move to `target`.  This may be called at any time, including during a move: the
+    /// profile decelerates first if it is moving away from the new target.
+    pub fn set_target(&mut self, target: P) {
+        self.target = target.raw().into() << (A::SHIFT - P::SHIFT);
+    }
+    /// Return whether the profile is at rest at the target.
+    pub fn is_done(&self) -> bool {
+        self.sum == self.target * SMOOTH as i128 && self.velocity == 0
+    }
+    /// Return the distance covered while decelerating from velocity `v >= 0` to rest, with
+    /// the velocity reduced by `accel` at each tick.
+    fn stopping_distance(&self, v: i128) -> i128 {
+        // (v - a) + (v - 2a) + ... + (v - na), where n = floor(v / a).
+        let n = v / self.accel;
+        (n * v)
+            .checked_sub(self.accel * (n * (n + 1) / 2))
+            .unwrap_or(i128::MAX)
+    }
+    /// Advance the trapezoidal profile by one tick.
+    fn step(&mut self) {
+        let distance = self.target - self.position;
+        if distance == 0 && self.velocity == 0 {
+            return;
+        }
+        // Work in the frame where the target lies ahead.
+        let dir = if distance > 0 || (distance == 0 && self.velocity < 0) {
+            1
+        } else {
+            -1
+        };
+        let (distance, v) = (distance * dir, self.velocity * dir);
+        let v = if v < 0 {
+            // Moving away from the target: brake.
+            (v + self.accel).min(distance)
+        } else {
+            let faster = (v + self.accel).min(self.max_velocity);
+            let slower = (v - self.accel).max(0);
+            let next = if faster + self.stopping_distance(faster) <= distance {
+                faster
+            } else if v + self.stopping_distance(v) <= distance {
+                v.min(self.max_velocity)
+            } else {
+                slower
+            };
+            // Creep at the last step if rounding would stop short, and never overshoot.
+            next.max(self.accel.min(distance)).min(distance)
+        };
+        self.velocity = v * dir;
+        self.position += self.velocity;
+    }
+    /// Advance the profile by one tick, and return the new position and velocity
+    /// setpoints, rounded to nearest.
+    pub fn tick(&mut self) -> (P, V) {
+        self.step();
+        self.sum += self.position - self.history[self.next];
+        self.history[self.next] = self.position;
+        self.next = (self.next + 1) % SMOOTH;
+        // Average, rounded to nearest (ties toward positive infinity).
+        let n = SMOOTH as i128;
+        let output = (self.sum + n / 2).div_euclid(n);
+        let velocity = output - self.output;
+        self.output = output;
+        let position = shift_round(output, P::SHIFT - A::SHIFT).unwrap();
+        let velocity = shift_round(velocity, V::SHIFT - A::SHIFT).unwrap();
+        (
+            from_raw(Ok(position)).unwrap(),
+            from_raw(Ok(velocity)).unwrap(),
+        )
+    }
+}
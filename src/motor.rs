@@ -0,0 +1,231 @@
+//! Reference frame transforms and space-vector modulation for field-oriented control of
+//! three-phase motors.
+//!
+//! Quantities in each reference frame have their own type: `Abc` for the three phase
+//! quantities, `AlphaBeta` for the stationary two-axis frame, and `Dq` for the frame
+//! rotating with the rotor.  Every transform is generic over its input and output
+//! fixed-point formats, so a controller can carry typed Q-formats from the ADC readings to
+//! the PWM duty cycles, and every result is rounded to the nearest value of its output
+//! type (or a `RangeError` is returned if it does not fit).
+//!
+//! The Clarke transform is amplitude-invariant, so a balanced set of phase currents with
+//! amplitude `I` has a space vector of magnitude `I`.  Rotor angles are given in turns
+//! (electrical revolutions), and their sine and cosine are computed with
+//! [`cordic::cos_sin_turns`](crate::cordic::cos_sin_turns).
+//!
+//! Inputs must fit in 64 signed bits (checked at compile time), so that all intermediate
+//! products are exact in `i128`.
+
+use core::marker::PhantomData;
+
+use crate::cordic::{cos_sin_turns_internal, FRAC};
+use crate::raw::{from_raw, shift_round, signed_bits};
+use crate::{Num, RangeError};
+
+/// 1 / sqrt(3), with `FRAC` fractional bits.
+const INV_SQRT_3: i128 = ((1u128 << (2 * FRAC)) / 3).isqrt() as i128;
+/// sqrt(3) / 2, with `FRAC` fractional bits.
+const SQRT_3_2: i128 = ((3u128 << (2 * FRAC)) / 4).isqrt() as i128;
+/// 1 / 3, with `FRAC` fractional bits.
+const ONE_THIRD: i128 = ((1 << FRAC) + 1) / 3;
+
+/// Three phase quantities (e.g. phase currents or voltages).
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Abc<T> {
+    pub a: T,
+    pub b: T,
+    pub c: T,
+}
+
+/// A space vector in the stationary two-axis frame.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct AlphaBeta<T> {
+    pub alpha: T,
+    pub beta: T,
+}
+
+/// A space vector in the frame rotating with the rotor: `d` is aligned with the rotor flux
+/// and `q` leads it by a quarter turn.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Dq<T> {
+    pub d: T,
+    pub q: T,
+}
+
+struct Input<T>(PhantomData<T>);
+
+impl<T: Num> Input<T> {
+    /// Compile-time check that products with `FRAC`-bit constants cannot overflow.
+    const CHECK: () = assert!(
+        signed_bits::<T>() <= 64,
+        "motor control inputs must fit in 64 signed bits"
+    );
+}
+
+struct Duty<T>(PhantomData<T>);
+
+impl<T: Num> Duty<T> {
+    /// Compile-time check that the type `T` can represent every duty cycle in [0, 1].
+    const CHECK: () = assert!(
+        T::SHIFT >= 1 && T::SHIFT <= 62 && T::BITS as i32 - T::SIGNED as i32 > T::SHIFT,
+        "duty cycle type must represent [0, 1] with a shift between 1 and 62"
+    );
+}
+
+fn raw<T: Num>(x: T) -> i128
+where
+    T::Raw: Into<i128>,
+{
+    let () = Input::<T>::CHECK;
+    x.raw().into()
+}
+
+/// Convert a value with the given shift to the output type, rounding to nearest.
+fn round<Out: Num>(val: i128, shift: i32) -> Result<Out, RangeError>
+where
+    Out::Raw: TryFrom<i128>,
+{
+    let err = if val < 0 {
+        RangeError::TooSmall
+    } else {
+        RangeError::TooLarge
+    };
+    from_raw(shift_round(val, Out::SHIFT - shift).ok_or(err))
+}
+
+/// Return the cosine and sine of the angle `theta` in turns, with `FRAC` fractional bits.
+fn cos_sin<Ang: Num>(theta: Ang) -> (i128, i128)
+where
+    Ang::Raw: Into<i128>,
+{
+    cos_sin_turns_internal(theta.raw().into(), Ang::SHIFT)
+}
+
+impl<T: Num> Abc<T>
+where
+    T::Raw: Into<i128>,
+{
+    /// Clarke transform: `alpha = (2a - b - c) / 3`, `beta = (b - c) / sqrt(3)`.  Any
+    /// common-mode (zero-sequence) component is removed.
+    pub fn clarke<Out: Num>(self) -> Result<AlphaBeta<Out>, RangeError>
+    where
+        Out::Raw: TryFrom<i128>,
+    {
+        let (a, b, c) = (raw(self.a), raw(self.b), raw(self.c));
+        let shift = T::SHIFT + FRAC as i32;
+        Ok(AlphaBeta {
+            alpha: round((2 * a - b - c) * ONE_THIRD, shift)?,
+            beta: round((b - c) * INV_SQRT_3, shift)?,
+        })
+    }
+}
+
+impl<T: Num> AlphaBeta<T>
+where
+    T::Raw: Into<i128>,
+{
+    /// Clarke transform from two phase quantities, assuming that `a + b + c = 0` (e.g. with
+    /// two current sensors): `alpha = a`, `beta = (a + 2b) / sqrt(3)`.
+    pub fn from_two_phases<U: Num>(a: U, b: U) -> Result<Self, RangeError>
+    where
+        U::Raw: Into<i128>,
+        T::Raw: TryFrom<i128>,
+    {
+        let (a, b) = (raw(a), raw(b));
+        Ok(Self {
+            alpha: round(a, U::SHIFT)?,
+            beta: round((a + 2 * b) * INV_SQRT_3, U::SHIFT + FRAC as i32)?,
+        })
+    }
+    /// Return the phase quantities with no common-mode component, with `FRAC` extra
+    /// fractional bits.
+    fn phases(self) -> [i128; 3] {
+        let (alpha, beta) = (raw(self.alpha), raw(self.beta));
+        let half_alpha = alpha << (FRAC - 1);
+        [
+            alpha << FRAC,
+            beta * SQRT_3_2 - half_alpha,
+            -beta * SQRT_3_2 - half_alpha,
+        ]
+    }
+    /// Inverse Clarke transform: `a = alpha`, `b = -alpha / 2 + sqrt(3) / 2 beta`,
+    /// `c = -alpha / 2 - sqrt(3) / 2 beta`.
+    pub fn inverse_clarke<Out: Num>(self) -> Result<Abc<Out>, RangeError>
+    where
+        Out::Raw: TryFrom<i128>,
+    {
+        let [a, b, c] = self.phases();
+        let shift = T::SHIFT + FRAC as i32;
+        Ok(Abc {
+            a: round(a, shift)?,
+            b: round(b, shift)?,
+            c: round(c, shift)?,
+        })
+    }
+    /// Park transform into the frame rotated by `theta` (in turns):
+    /// `d = alpha cos + beta sin`, `q = -alpha sin + beta cos`.
+    pub fn park<Out: Num, Ang: Num>(self, theta: Ang) -> Result<Dq<Out>, RangeError>
+    where
+        Out::Raw: TryFrom<i128>,
+        Ang::Raw: Into<i128>,
+    {
+        let (alpha, beta) = (raw(self.alpha), raw(self.beta));
+        let (cos, sin) = cos_sin(theta);
+        let (d, q) = (alpha * cos + beta * sin, beta * cos - alpha * sin);
+        let shift = T::SHIFT + FRAC as i32;
+        Ok(Dq {
+            d: round(d, shift)?,
+            q: round(q, shift)?,
+        })
+    }
+    /// Space-vector modulation: return the duty cycles in [0, 1] which produce the voltage
+    /// vector `self`, expressed as a fraction of the DC bus voltage.
+    ///
+    /// This is computed by min-max (midpoint clamp) injection, which centers the phase
+    /// voltages in the available range and is equivalent to conventional symmetric SVM.
+    /// The linear range is a magnitude of up to `1 / sqrt(3)`; beyond it (overmodulation),
+    /// each duty cycle is clamped to [0, 1].
+    pub fn svm<Out: Num>(self) -> Abc<Out>
+    where
+        Out::Raw: TryFrom<i128>,
+    {
+        let () = Duty::<Out>::CHECK;
+        let phases = self.phases();
+        let max = phases[0].max(phases[1]).max(phases[2]);
+        let min = phases[0].min(phases[1]).min(phases[2]);
+        let offset = (max + min) / 2;
+        let one = 1 << Out::SHIFT;
+        let [a, b, c] = phases.map(|v| {
+            // Phase voltage relative to the midpoint of the bus, saturated if it is far
+            // outside the bus.
+            let v = v - offset;
+            let v = shift_round(v, Out::SHIFT - T::SHIFT - FRAC as i32)
+                .unwrap_or(if v < 0 { -one } else { one })
+                .clamp(-one, one);
+            from_raw(Ok((one / 2 + v).clamp(0, one))).unwrap()
+        });
+        Abc { a, b, c }
+    }
+}
+
+impl<T: Num> Dq<T>
+where
+    T::Raw: Into<i128>,
+{
+    /// Inverse Park transform from the frame rotated by `theta` (in turns):
+    /// `alpha = d cos - q sin`, `beta = d sin + q cos`.
+    pub fn inverse_park<Out: Num, Ang: Num>(self, theta: Ang) -> Result<AlphaBeta<Out>, RangeError>
+    where
+        Out::Raw: TryFrom<i128>,
+        Ang::Raw: Into<i128>,
+    {
+        let (d, q) = (raw(self.d), raw(self.q));
+        let (cos, sin) = cos_sin(theta);
+        let (alpha, beta) = (d * cos - q * sin, d * sin + q * cos);
+        let shift = T::SHIFT + FRAC as i32;
+        Ok(AlphaBeta {
+            alpha: round(alpha, shift)?,
+            beta: round(beta, shift)?,
+        })
+    }
+}
@@ -1,7 +1,266 @@
-use core::ops::{Div, Mul};
+use core::marker::PhantomData;
+use core::ops::{Div, Mul, Rem};
 
+use crate::add_sub::{ceil_log2, max, min};
+use crate::raw::signed_bits;
 use crate::Num;
 
+/// Division which returns both the quotient and the remainder of a single division.
+///
+/// The quotient has the same type as the output of `Div`.  The remainder has the same
+/// shift as the dividend, and its magnitude is bounded by both the dividend and the
+/// divisor, so it needs no more bits than the narrower of the two.
+pub trait DivRem<Rhs> {
+    /// Type of the quotient, which is the same as the output of `Div`.
+    type Quotient: Num;
+    /// Type of the remainder of truncated division.
+    type Remainder: Num;
+    /// Type of the remainder of Euclidean division.  The Euclidean remainder is never
+    /// negative, but it is bounded only by the divisor and not by the dividend.
+    type EuclidRemainder: Num;
+    /// Return the quotient rounded toward zero, and the remainder (which has the same sign
+    /// as the dividend), like `/` and `%` on the primitive integers.
+    fn div_rem(self, rhs: Rhs) -> (Self::Quotient, Self::Remainder);
+    /// Return the Euclidean quotient and remainder, like `div_euclid` and `rem_euclid`
+    /// on the primitive integers: the remainder is never negative.
+    fn div_rem_euclid(self, rhs: Rhs) -> (Self::Quotient, Self::EuclidRemainder);
+    /// Return the Euclidean quotient, like `div_euclid` on the primitive integers.
+    fn div_euclid(self, rhs: Rhs) -> Self::Quotient
+    where
+        Self: Sized,
+    {
+        self.div_rem_euclid(rhs).0
+    }
+    /// Return the Euclidean remainder, like `rem_euclid` on the primitive integers, e.g. to
+    /// wrap an angle or a table index into the range `[0, rhs)`.
+    fn rem_euclid(self, rhs: Rhs) -> Self::EuclidRemainder
+    where
+        Self: Sized,
+    {
+        self.div_rem_euclid(rhs).1
+    }
+    /// Return the quotient rounded to nearest, with ties away from zero.
+    fn div_round(self, rhs: Rhs) -> Self::Quotient
+    where
+        Self: Sized,
+        Rhs: Num,
+        Rhs::Raw: Into<i128>,
+        <Self::Quotient as Num>::Raw: Into<i128> + TryFrom<i128>,
+        <Self::Remainder as Num>::Raw: Into<i128>,
+    {
+        round_quotient(self.div_rem(rhs), rhs, false)
+    }
+    /// Return the quotient rounded to nearest, with ties to even, which has no bias.
+    fn div_round_half_even(self, rhs: Rhs) -> Self::Quotient
+    where
+        Self: Sized,
+        Rhs: Num,
+        Rhs::Raw: Into<i128>,
+        <Self::Quotient as Num>::Raw: Into<i128> + TryFrom<i128>,
+        <Self::Remainder as Num>::Raw: Into<i128>,
+    {
+        round_quotient(self.div_rem(rhs), rhs, true)
+    }
+}
+
+/// Round the truncated quotient `(quotient, remainder)` of a division by `divisor` to
+/// nearest, with ties to even if `even` (and away from zero otherwise).
+fn round_quotient<Q: Num, R: Num, D: Num>(
+    (quotient, remainder): (Q, R),
+    divisor: D,
+    even: bool,
+) -> Q
+where
+    Q::Raw: Into<i128> + TryFrom<i128>,
+    R::Raw: Into<i128>,
+    D::Raw: Into<i128>,
+{
+    let (q, r, d) = (quotient.raw().into(), remainder.raw().into(), divisor.raw().into());
+    let (twice, d) = (2 * r.unsigned_abs(), d.unsigned_abs());
+    if twice > d || (twice == d && (!even || q & 1 != 0)) {
+        // Away from zero, which has the sign of the exact quotient.  The magnitude is at most
+        // that of the dividend, so it fits in the quotient type.
+        let q = if (r < 0) != (divisor.raw().into() < 0) {
+            q - 1
+        } else {
+            q + 1
+        };
+        unsafe { Q::new_unchecked(q.try_into().ok().unwrap()) }
+    } else {
+        quotient
+    }
+}
+
+/// Multiplication which promotes the product to the next wider raw type, e.g. from `I32`
+/// to `I64`, so that the product of two full-width values is representable.
+///
+/// The product has the same bits and shift as the output of `Mul` on the narrower type.
+pub trait WideningMul<Rhs> {
+    /// Type of the product, in the wider raw type.
+    type Output: Num;
+    /// Return the exact product in the wider raw type.
+    fn widening_mul(self, rhs: Rhs) -> Self::Output;
+}
+
+struct DivTo<T, D, Out>(PhantomData<(T, D, Out)>);
+
+impl<T: Num, D: Num, Out: Num> DivTo<T, D, Out> {
+    /// The left shift of the numerator (or of the denominator, if negative) which gives the
+    /// quotient the shift of `Out`.
+    const SHIFT: i32 = Out::SHIFT - T::SHIFT + D::SHIFT;
+    /// Compile-time check that the shifted operands fit in `i128` (excluding `MIN`, so that
+    /// the division cannot overflow), and that every quotient fits in `Out`.
+    const CHECK: () = {
+        let (numerator, denominator) = if Self::SHIFT >= 0 {
+            (signed_bits::<T>() as i32 + Self::SHIFT, signed_bits::<D>() as i32)
+        } else {
+            (signed_bits::<T>() as i32, signed_bits::<D>() as i32 - Self::SHIFT)
+        };
+        assert!(
+            numerator < i128::BITS as i32 && denominator < i128::BITS as i32,
+            "too many bits for division"
+        );
+        // The largest magnitude of the quotient, for a divisor of 1 raw unit, is at most
+        // `2^magnitude`; it equals `2^magnitude` only for a negative dividend, and the
+        // quotient is then positive if the divisor is also negative (e.g. `MIN / -1`).
+        let magnitude = T::BITS as i32 - T::SIGNED as i32 + Self::SHIFT;
+        assert!(
+            Out::SIGNED || !(T::SIGNED || D::SIGNED),
+            "quotient of signed values needs a signed output"
+        );
+        let positive_min = (T::SIGNED && D::SIGNED) as i32;
+        assert!(
+            magnitude + (Out::SIGNED as i32) + positive_min <= Out::BITS as i32,
+            "quotient may not fit in output type"
+        );
+    };
+}
+
+/// Implementation of `Num::div_to`.
+#[inline]
+pub(crate) fn div_to<T: Num, D: Num, Out: Num>(val: T, other: D) -> Out
+where
+    T::Raw: Into<i128>,
+    D::Raw: Into<i128>,
+    Out::Raw: TryFrom<i128>,
+{
+    let () = DivTo::<T, D, Out>::CHECK;
+    let shift = DivTo::<T, D, Out>::SHIFT;
+    let (mut num, mut den) = (val.raw().into(), other.raw().into());
+    if shift >= 0 {
+        num <<= shift;
+    } else {
+        den <<= -shift;
+    }
+    let narrow = signed_bits::<T>() as i32 + shift.max(0) < i64::BITS as i32
+        && signed_bits::<D>() as i32 - shift.min(0) < i64::BITS as i32;
+    let quotient = if narrow {
+        (num as i64 / den as i64) as i128
+    } else {
+        num / den
+    };
+    // The quotient lies within the range of `Out`.
+    unsafe { Out::new_unchecked(quotient.try_into().ok().unwrap()) }
+}
+
+/// Return the bit growth of a multiplication by `k`, which is `ceil(log2(|k|))`.  A signed
+/// value needs one more bit if `-k` is a power of 2, since `MIN * k` is then the positive
+/// power of 2 just beyond the range.
+pub const fn mul_const_bits(k: i64, signed: bool) -> u32 {
+    let magnitude = k.unsigned_abs();
+    let growth = if magnitude > 1 { (magnitude - 1).ilog2() + 1 } else { 0 };
+    growth + (signed && k < 0 && magnitude.is_power_of_two()) as u32
+}
+
+/// Return the number of bits of the product of the largest magnitude of a type with `bits`
+/// bits and `signed`ness and `num`, which is the size of the intermediate of `mul_ratio`.
+/// Panics (at compile time, in a type) if the product does not fit in `i128` or `u128`.
+pub const fn ratio_product_bits(bits: u32, signed: bool, num: i64) -> u32 {
+    let max = if signed { 1 << (bits - 1) } else { u128::MAX >> (128 - bits) };
+    let Some(product) = max.checked_mul(num.unsigned_abs() as u128) else {
+        panic!("too many bits for mul_ratio");
+    };
+    assert!(!signed || product <= i128::MAX as u128, "too many bits for mul_ratio");
+    u128::BITS - product.leading_zeros()
+}
+
+/// Return the number of bits of the result of `mul_ratio::<num, den>` for a type with `bits`
+/// bits and `signed`ness: the width of the largest magnitude of the product divided by
+/// `den`, plus a sign bit if signed.  Panics (at compile time, in a type) if `den` is zero,
+/// or if the ratio is negative for an unsigned type.
+pub const fn ratio_bits(bits: u32, signed: bool, num: i64, den: i64) -> u32 {
+    assert!(den != 0, "zero denominator for mul_ratio");
+    assert!(signed || (num >= 0 && den > 0), "negative ratio for unsigned type");
+    let max = if signed { 1 << (bits - 1) } else { u128::MAX >> (128 - bits) };
+    // The product fits, as checked by `ratio_product_bits`.
+    let quotient = max * num.unsigned_abs() as u128 / den.unsigned_abs() as u128;
+    u128::BITS - quotient.leading_zeros() + signed as u32
+}
+
+struct ConstFactor<T, const K: i64>(PhantomData<T>);
+
+impl<T: Num, const K: i64> ConstFactor<T, K> {
+    /// Compile-time check that an unsigned value is not multiplied by a negative constant.
+    const CHECK: () = assert!(T::SIGNED || K >= 0, "negative factor for unsigned type");
+}
+
+struct Exponent<const N: u32>;
+
+impl<const N: u32> Exponent<N> {
+    /// Compile-time check that the exponent of `pow` is positive, since 1 has no
+    /// representation with 0 bits.
+    const CHECK: () = assert!(N > 0, "exponent must be positive");
+}
+
+struct RoundShift<T, const N: u32>(PhantomData<T>);
+
+impl<T: Num, const N: u32> RoundShift<T, N> {
+    /// Compile-time check that a value of the raw type `T` can be shifted right by `N` bits.
+    const CHECK: () = assert!(N < T::BITS, "too many bits to discard from the raw type");
+}
+
+struct Recip<T, Out>(PhantomData<(T, Out)>);
+
+impl<T: Num, Out: Num> Recip<T, Out> {
+    /// The exponent of the numerator `2^SHIFT` of the raw reciprocal.
+    const SHIFT: i32 = Out::SHIFT + T::SHIFT;
+    /// Compile-time check that the numerator fits in `i128`, and that every reciprocal fits
+    /// in `Out`: the largest is `2^SHIFT`, for 1 raw unit.
+    const CHECK: () = {
+        assert!(Self::SHIFT < i128::BITS as i32 - 1, "too many bits for reciprocal");
+        assert!(
+            Out::SIGNED || !T::SIGNED,
+            "reciprocal of a signed value needs a signed output"
+        );
+        assert!(
+            Self::SHIFT < Out::BITS as i32 - Out::SIGNED as i32,
+            "reciprocal may not fit in output type"
+        );
+    };
+}
+
+/// Implementation of `Num::checked_recip`.
+#[inline]
+pub(crate) fn recip<T: Num, Out: Num>(val: T) -> Option<Out>
+where
+    T::Raw: Into<i128>,
+    Out::Raw: TryFrom<i128>,
+{
+    let () = Recip::<T, Out>::CHECK;
+    let (shift, den) = (Recip::<T, Out>::SHIFT, val.raw().into());
+    let quotient = if den == 0 {
+        return None;
+    } else if shift < 0 {
+        0
+    } else if shift < i64::BITS as i32 - 1 && signed_bits::<T>() <= i64::BITS {
+        ((1 << shift) / den as i64) as i128
+    } else {
+        (1 << shift) / den
+    };
+    // The reciprocal lies within the range of `Out`.
+    Some(unsafe { Out::new_unchecked(quotient.try_into().ok().unwrap()) })
+}
+
 macro_rules! fp_impl {
     ($Name:ident, $T:ty) => {
         use crate::$Name;
@@ -18,6 +277,92 @@ macro_rules! fp_impl {
                 }
             }
         }
+        impl<const B0: u32, const S0: i32> $Name<B0, S0> {
+            /// Raise to the constant power `N`, which must be at least 1.  Like repeated `*`,
+            /// the result has `N` times the bits and the shift of `self`, and it is a compile
+            /// error if this does not fit in the raw type.
+            pub fn pow<const N: u32>(self) -> $Name<{ N * B0 }, { N as i32 * S0 }>
+            where
+                [(); (N * B0) as usize]:,
+                [(); (N as i32 * S0) as usize]:,
+            {
+                let () = Exponent::<N>::CHECK;
+                unsafe { $Name::new_unchecked(self.raw().pow(N)) }
+            }
+            /// Multiply by the constant `K`.  The result has `ceil(log2(|K|))` more bits, e.g.
+            /// 1 for 2, 2 for 3 and 4 for 10, rather than the full width of a general
+            /// multiplication.  A signed value needs one more bit if `-K` is a power of 2, e.g.
+            /// for -1, since `MIN * K` is then just beyond `MAX`.  It is a compile error if `K`
+            /// is negative and `self` is unsigned.
+            pub fn mul_const<const K: i64>(
+                self,
+            ) -> $Name<{ B0 + mul_const_bits(K, <$T>::MIN != 0) }, S0>
+            where
+                [(); (B0 + mul_const_bits(K, <$T>::MIN != 0)) as usize]:,
+            {
+                let () = ConstFactor::<Self, K>::CHECK;
+                unsafe { $Name::new_unchecked(self.raw().unchecked_mul(K as $T)) }
+            }
+            /// Multiply-accumulate: return `self + a * b`, where the product has the same shift
+            /// as `self`.  The result has 1 more bit than the wider of `self` and the product,
+            /// like `+`, so a chain of dot-product steps needs only one call per step.
+            pub fn mla<const B1: u32, const B2: u32, const S1: i32>(
+                self,
+                a: $Name<B1, S1>,
+                b: $Name<B2, { S0 - S1 }>,
+            ) -> $Name<{ max(B0, B1 + B2) + 1 }, S0>
+            where
+                [(); (S0 - S1) as usize]:,
+                [(); (max(B0, B1 + B2) + 1) as usize]:,
+            {
+                let product = unsafe { a.raw().unchecked_mul(b.raw()) };
+                unsafe { $Name::new_unchecked(self.raw().unchecked_add(product)) }
+            }
+            /// Return the dot product of `a` and `b`, which has `log2(N)` (rounded up) more
+            /// bits than `a[i] * b[i]`, so that it can never overflow, e.g. for the taps of a
+            /// FIR filter or a row of a matrix product.
+            pub fn dot<const B1: u32, const S1: i32, const N: usize>(
+                a: [Self; N],
+                b: [$Name<B1, S1>; N],
+            ) -> $Name<{ B0 + B1 + ceil_log2(N) }, { S0 + S1 }>
+            where
+                [(); (B0 + B1 + ceil_log2(N)) as usize]:,
+                [(); (S0 + S1) as usize]:,
+            {
+                let sum = a.iter().zip(&b).fold(0, |sum: $T, (a, b)| unsafe {
+                    sum.unchecked_add(a.raw().unchecked_mul(b.raw()))
+                });
+                unsafe { $Name::new_unchecked(sum) }
+            }
+            /// Multiply by `other`, then discard the `N` least-significant bits of the product,
+            /// rounding to nearest with ties to even.  Unlike `*` followed by `raw_shr`, which
+            /// truncates toward negative infinity, this has no bias.  The product must fit in
+            /// the raw type, like the output of `*`, and `N` must be less than the bits of the
+            /// raw type.  Like `raw_shr_round`, the result has one more bit than the product
+            /// shifted right, since it may round up to the next power of 2.
+            pub fn mul_round<const N: u32, T: Num<Raw = $T>>(
+                self,
+                other: T,
+            ) -> $Name<{ B0 + T::BITS - N + 1 }, { S0 + T::SHIFT - N as i32 }>
+            where
+                [(); (B0 + T::BITS) as usize]:,
+                [(); (B0 + T::BITS - N + 1) as usize]:,
+                [(); (S0 + T::SHIFT - N as i32) as usize]:,
+            {
+                let () = RoundShift::<$T, N>::CHECK;
+                // force the compile-time check that the raw type is wide enough for the product
+                let _ = <$Name<{ B0 + T::BITS }, S0> as Num>::BITS;
+                let product = unsafe { self.raw().unchecked_mul(other.raw()) };
+                if N == 0 {
+                    return unsafe { $Name::new_unchecked(product) };
+                }
+                let floor = product >> N;
+                let rem = product & !(!0 << N);
+                let half = 1 << (N - 1);
+                let up = rem > half || (rem == half && floor & 1 != 0);
+                unsafe { $Name::new_unchecked(floor + up as $T) }
+            }
+        }
         impl<const B0: u32, const B1: u32, const S0: i32, const S1: i32> Div<$Name<B1, S1>>
             for $Name<B0, S0>
         where
@@ -32,6 +377,65 @@ macro_rules! fp_impl {
                 unsafe { Self::Output::new_unchecked(self.raw() / other.raw()) }
             }
         }
+        /// The remainder of `/`, i.e. `x - (x / y) * y`, which has the same sign as the
+        /// dividend, like `%` on the primitive integers.  It has the same shift as the
+        /// dividend, and its magnitude is bounded by both the dividend and the divisor.
+        impl<const B0: u32, const B1: u32, const S0: i32, const S1: i32> Rem<$Name<B1, S1>>
+            for $Name<B0, S0>
+        where
+            [(); min(B0, B1) as usize]:,
+        {
+            type Output = $Name<{ min(B0, B1) }, S0>;
+            fn rem(self: $Name<B0, S0>, other: $Name<B1, S1>) -> Self::Output {
+                // `MIN % -1` overflows the primitive, but its remainder is 0.
+                unsafe { Self::Output::new_unchecked(self.raw().wrapping_rem(other.raw())) }
+            }
+        }
+        impl<const B0: u32, const B1: u32, const S0: i32, const S1: i32> DivRem<$Name<B1, S1>>
+            for $Name<B0, S0>
+        where
+            [(); (B0 + Self::SIGNED as u32) as usize]:,
+            [(); (S0 - S1) as usize]:,
+            [(); min(B0, B1) as usize]:,
+        {
+            type Quotient = $Name<{ B0 + Self::SIGNED as u32 }, { S0 - S1 }>;
+            type Remainder = $Name<{ min(B0, B1) }, S0>;
+            type EuclidRemainder = $Name<B1, S0>;
+            fn div_rem(self, other: $Name<B1, S1>) -> (Self::Quotient, Self::Remainder) {
+                let quotient = self.raw() / other.raw();
+                let remainder = self.raw() - quotient * other.raw();
+                unsafe {
+                    (
+                        Self::Quotient::new_unchecked(quotient),
+                        Self::Remainder::new_unchecked(remainder),
+                    )
+                }
+            }
+            #[allow(unused_comparisons)]
+            fn div_rem_euclid(
+                self,
+                other: $Name<B1, S1>,
+            ) -> (Self::Quotient, Self::EuclidRemainder) {
+                let mut quotient = self.raw() / other.raw();
+                let mut remainder = self.raw() - quotient * other.raw();
+                // Only possible for signed types.
+                if remainder < 0 {
+                    if other.raw() > 0 {
+                        quotient -= 1;
+                        remainder += other.raw();
+                    } else {
+                        quotient += 1;
+                        remainder -= other.raw();
+                    }
+                }
+                unsafe {
+                    (
+                        Self::Quotient::new_unchecked(quotient),
+                        Self::EuclidRemainder::new_unchecked(remainder),
+                    )
+                }
+            }
+        }
     };
 }
 
@@ -48,8 +452,272 @@ fp_impl!(U128, u128);
 fp_impl!(Isize, isize);
 fp_impl!(Usize, usize);
 
+struct SqrtTo<T, Out>(PhantomData<(T, Out)>);
+
+impl<T: Num, Out: Num> SqrtTo<T, Out> {
+    /// The left shift of the radicand (or the right shift, if negative) which gives the root
+    /// the shift of `Out`.
+    const SHIFT: i32 = 2 * Out::SHIFT - T::SHIFT;
+    /// The number of bits of the largest shifted radicand.
+    const BITS: i32 = T::BITS as i32 - T::SIGNED as i32 + Self::SHIFT;
+    /// Compile-time check that the shifted radicand fits in `u128`, and that every root fits
+    /// in `Out`.
+    const CHECK: () = {
+        assert!(Self::BITS <= u128::BITS as i32, "too many bits for square root");
+        let root = if Self::BITS > 0 { (Self::BITS + 1) / 2 } else { 0 };
+        assert!(
+            root + (Out::SIGNED as i32) <= Out::BITS as i32,
+            "square root may not fit in output type"
+        );
+    };
+}
+
+// Integer square root of an unsigned value, rounded down, by Newton-Raphson iteration from an
+// initial estimate which is at least the root, so that the iterates decrease monotonically.
+macro_rules! sqrt_newton_impl {
+    ($name:ident, $T:ty) => {
+        pub(crate) fn $name(n: $T) -> $T {
+            if n < 2 {
+                return n;
+            }
+            let mut x: $T = 1 << (<$T>::BITS - n.leading_zeros()).div_ceil(2);
+            loop {
+                let y = (x + n / x) >> 1;
+                if y >= x {
+                    return x;
+                }
+                x = y;
+            }
+        }
+    };
+}
+
+sqrt_newton_impl!(sqrt_newton_u64, u64);
+sqrt_newton_impl!(sqrt_newton_u128, u128);
+
+/// Implementation of `Num::sqrt_to`.
+#[inline]
+pub(crate) fn sqrt_to<T: Num, Out: Num>(val: T) -> Out
+where
+    T::Raw: Into<i128>,
+    Out::Raw: TryFrom<i128>,
+{
+    let () = SqrtTo::<T, Out>::CHECK;
+    let raw = val.raw().into();
+    assert!(raw >= 0, "argument of sqrt_to is negative");
+    let shift = SqrtTo::<T, Out>::SHIFT;
+    // Truncating the radicand does not change the root, which is rounded down.
+    let n = match shift {
+        0..128 => (raw as u128) << shift,
+        -127..0 => (raw as u128) >> -shift,
+        _ => 0,
+    };
+    let root = if SqrtTo::<T, Out>::BITS <= u64::BITS as i32 {
+        sqrt_newton_u64(n as u64) as i128
+    } else {
+        sqrt_newton_u128(n) as i128
+    };
+    // The root lies within the range of `Out`.
+    unsafe { Out::new_unchecked(root.try_into().ok().unwrap()) }
+}
+
+struct Hypot<T>(PhantomData<T>);
+
+impl<T: Num> Hypot<T> {
+    /// The number of bits of the largest sum of squares.
+    const BITS: u32 = 2 * (T::BITS - T::SIGNED as u32) + 1;
+    /// Compile-time check that the sum of squares fits in `u128`.
+    const CHECK: () = assert!(Self::BITS <= u128::BITS, "too many bits for hypot");
+}
+
+/// Implementation of `Num::hypot`.
+#[inline]
+pub(crate) fn hypot<T: Num, Out: Num<Raw = T::Raw>>(a: T, b: T) -> Out
+where
+    T::Raw: Into<i128> + TryFrom<i128>,
+{
+    let () = Hypot::<T>::CHECK;
+    let (a, b) = (a.raw().into().unsigned_abs(), b.raw().into().unsigned_abs());
+    let sum = a * a + b * b;
+    let root = if Hypot::<T>::BITS <= u64::BITS {
+        sqrt_newton_u64(sum as u64) as i128
+    } else {
+        sqrt_newton_u128(sum) as i128
+    };
+    // The root is less than `sqrt(2)` times the largest magnitude of `T`, which fits in `Out`.
+    unsafe { Out::new_unchecked(root.try_into().ok().unwrap()) }
+}
+
+struct Norm<T, const N: usize>(PhantomData<T>);
+
+impl<T: Num, const N: usize> Norm<T, N> {
+    /// The number of bits of the largest sum of squares.
+    const BITS: u32 = 2 * (T::BITS - T::SIGNED as u32) + ceil_log2(N);
+    /// Compile-time check that the sum of squares fits in `u128`.
+    const CHECK: () = assert!(Self::BITS <= u128::BITS, "too many bits for norm");
+}
+
+/// Euclidean norm `sqrt(sum(x^2))` of `N` values, rounded down, like `hypot`.  `Out` must
+/// hold `sqrt(N)` times the largest magnitude of `T`.
+#[inline]
+pub(crate) fn norm<T: Num, Out: Num, const N: usize>(parts: [T; N]) -> Out
+where
+    T::Raw: Into<i128>,
+    Out::Raw: TryFrom<i128>,
+{
+    let () = Norm::<T, N>::CHECK;
+    let sum = parts.iter().map(|x| x.raw().into().unsigned_abs().pow(2)).sum();
+    let root = if Norm::<T, N>::BITS <= u64::BITS {
+        sqrt_newton_u64(sum as u64) as i128
+    } else {
+        sqrt_newton_u128(sum) as i128
+    };
+    unsafe { Out::new_unchecked(root.try_into().ok().unwrap()) }
+}
+
+struct RootShift<const S: i32, const N: i32>;
+
+impl<const S: i32, const N: i32> RootShift<S, N> {
+    /// Compile-time check that the shift of `isqrt` (N = 2) or `cbrt` (N = 3) is divisible by
+    /// `N`, so that the root has an integer shift.
+    const CHECK: () = assert!(S % N == 0, "root needs a shift divisible by its degree");
+}
+
+// Integer square root of an unsigned raw value, rounded down, by the binary digit-by-digit
+// method, which needs only shifts, additions and comparisons.
+macro_rules! isqrt_impl {
+    ($name:ident, $T:ty) => {
+        const fn $name(n: $T) -> $T {
+            if n == 0 {
+                return 0;
+            }
+            // The largest power of 4 which is at most `n`.
+            let mut bit: $T = 1 << ((<$T>::BITS - 1 - n.leading_zeros()) & !1);
+            let (mut rem, mut root) = (n, 0);
+            while bit != 0 {
+                if rem >= root + bit {
+                    rem -= root + bit;
+                    root = (root >> 1) + bit;
+                } else {
+                    root >>= 1;
+                }
+                bit >>= 2;
+            }
+            root
+        }
+    };
+}
+
+isqrt_impl!(isqrt_u8, u8);
+isqrt_impl!(isqrt_u16, u16);
+isqrt_impl!(isqrt_u32, u32);
+isqrt_impl!(isqrt_u64, u64);
+isqrt_impl!(isqrt_u128, u128);
+isqrt_impl!(isqrt_usize, usize);
+
+// Integer cube root of an unsigned raw value, rounded down, by the binary digit-by-digit
+// method: each step decides one bit of the root `y`, by whether `(2y + 1)^3 - (2y)^3 =
+// 3 * 2y * (2y + 1) + 1`, at the weight of that bit, fits in the remainder.
+macro_rules! cbrt_impl {
+    ($name:ident, $T:ty) => {
+        const fn $name(n: $T) -> $T {
+            let (mut rem, mut root): ($T, $T) = (n, 0);
+            let mut shift = (<$T>::BITS - 1) / 3 * 3;
+            loop {
+                root <<= 1;
+                let step = 3 * root * (root + 1) + 1;
+                // Compare without shifting `step`, which could overflow.
+                if rem >> shift >= step {
+                    rem -= step << shift;
+                    root += 1;
+                }
+                if shift == 0 {
+                    return root;
+                }
+                shift -= 3;
+            }
+        }
+    };
+}
+
+cbrt_impl!(cbrt_u8, u8);
+cbrt_impl!(cbrt_u16, u16);
+cbrt_impl!(cbrt_u32, u32);
+cbrt_impl!(cbrt_u64, u64);
+cbrt_impl!(cbrt_u128, u128);
+cbrt_impl!(cbrt_usize, usize);
+
 macro_rules! fp_signed_unsigned_impl {
-    ($Uname:ident, $Iname:ident) => {
+    ($Uname:ident, $Iname:ident, $isqrt:ident, $cbrt:ident) => {
+        impl<const B: u32, const S: i32> $Uname<B, S> {
+            /// Return the square root, rounded down.  The shift must be even, and the result
+            /// has half the shift and `B / 2 + 1` bits.
+            pub fn isqrt(self) -> $Uname<{ B / 2 + 1 }, { S / 2 }>
+            where
+                [(); (B / 2 + 1) as usize]:,
+                [(); (S / 2) as usize]:,
+            {
+                let () = RootShift::<S, 2>::CHECK;
+                unsafe { $Uname::new_unchecked($isqrt(self.raw())) }
+            }
+            /// Return the cube root, rounded down.  The shift must be divisible by 3, and the
+            /// result has a third of the shift and `B / 3` bits, rounded up.
+            pub fn cbrt(self) -> $Uname<{ B.div_ceil(3) }, { S / 3 }>
+            where
+                [(); B.div_ceil(3) as usize]:,
+                [(); (S / 3) as usize]:,
+            {
+                let () = RootShift::<S, 3>::CHECK;
+                unsafe { $Uname::new_unchecked($cbrt(self.raw())) }
+            }
+        }
+        impl<const B: u32, const S: i32> $Iname<B, S> {
+            /// Return the square root, rounded down, like `isqrt` on unsigned types.  Panics
+            /// if `self` is negative.
+            pub fn isqrt(self) -> $Uname<{ B / 2 + 1 }, { S / 2 }>
+            where
+                [(); (B / 2 + 1) as usize]:,
+                [(); (S / 2) as usize]:,
+            {
+                let () = RootShift::<S, 2>::CHECK;
+                assert!(self.raw() >= 0, "argument of isqrt is negative");
+                unsafe { $Uname::new_unchecked($isqrt(self.raw().unsigned_abs())) }
+            }
+            /// Return the cube root, rounded toward zero.  The shift must be divisible by 3,
+            /// and the result has a third of the shift and `(B - 1) / 3 + 2` bits.
+            pub fn cbrt(self) -> $Iname<{ (B - 1) / 3 + 2 }, { S / 3 }>
+            where
+                [(); ((B - 1) / 3 + 2) as usize]:,
+                [(); (S / 3) as usize]:,
+            {
+                let () = RootShift::<S, 3>::CHECK;
+                let root = $cbrt(self.raw().unsigned_abs()) as <Self as Num>::Raw;
+                let root = if self.raw() < 0 { -root } else { root };
+                unsafe { $Iname::new_unchecked(root) }
+            }
+        }
+        impl<const B: u32, const S: i32> $Iname<B, S> {
+            /// Return the square, which is never negative, as unsigned.  It has `2 * B - 1`
+            /// bits (one fewer than `self * self`), since only `MIN * MIN` needs the top bit.
+            pub fn square(self) -> $Uname<{ 2 * B - 1 }, { 2 * S }>
+            where
+                [(); (2 * B - 1) as usize]:,
+                [(); (2 * S) as usize]:,
+            {
+                let abs = self.raw().unsigned_abs();
+                unsafe { $Uname::new_unchecked(abs.unchecked_mul(abs)) }
+            }
+        }
+        impl<const B: u32, const S: i32> $Uname<B, S> {
+            /// Return the square, with the bits and shift of `self * self`.
+            pub fn square(self) -> $Uname<{ 2 * B }, { 2 * S }>
+            where
+                [(); (2 * B) as usize]:,
+                [(); (2 * S) as usize]:,
+            {
+                unsafe { $Uname::new_unchecked(self.raw().unchecked_mul(self.raw())) }
+            }
+        }
         impl<const B0: u32, const B1: u32, const S0: i32, const S1: i32> Mul<$Uname<B1, S1>>
             for $Iname<B0, S0>
         where
@@ -83,9 +751,12 @@ macro_rules! fp_signed_unsigned_impl {
         {
             type Output = $Iname<B0, { S0 - S1 }>;
             fn div(self: $Iname<B0, S0>, other: $Uname<B1, S1>) -> Self::Output {
-                unsafe {
-                    Self::Output::new_unchecked(self.raw() / other.raw() as <Self::Output as Num>::Raw)
-                }
+                // Divide the magnitude, since the divisor may not fit in the signed raw type,
+                // as in `div_rem`.
+                let magnitude = self.raw().unsigned_abs();
+                let quotient = (magnitude / other.raw()) as <Self::Output as Num>::Raw;
+                let quotient = if self.raw() < 0 { quotient.wrapping_neg() } else { quotient };
+                unsafe { Self::Output::new_unchecked(quotient) }
             }
         }
         impl<const B0: u32, const B1: u32, const S0: i32, const S1: i32> Div<$Iname<B1, S1>>
@@ -101,12 +772,306 @@ macro_rules! fp_signed_unsigned_impl {
                 }
             }
         }
+        /// The remainder of a signed dividend and an unsigned divisor, which has the sign of
+        /// the dividend and needs an extra sign bit beyond the divisor.
+        impl<const B0: u32, const B1: u32, const S0: i32, const S1: i32> Rem<$Uname<B1, S1>>
+            for $Iname<B0, S0>
+        where
+            [(); min(B0, B1 + 1) as usize]:,
+        {
+            type Output = $Iname<{ min(B0, B1 + 1) }, S0>;
+            fn rem(self: $Iname<B0, S0>, other: $Uname<B1, S1>) -> Self::Output {
+                // The magnitude is computed unsigned, since the divisor may not fit in the
+                // signed raw type.  It is at most `-MIN`, which wraps to `MIN` when negated.
+                let magnitude = (self.raw().unsigned_abs() % other.raw()) as <Self::Output as Num>::Raw;
+                let remainder = if self.raw() < 0 {
+                    magnitude.wrapping_neg()
+                } else {
+                    magnitude
+                };
+                unsafe { Self::Output::new_unchecked(remainder) }
+            }
+        }
+        /// The remainder of an unsigned dividend and a signed divisor, which is non-negative
+        /// and smaller than the magnitude of the divisor.
+        impl<const B0: u32, const B1: u32, const S0: i32, const S1: i32> Rem<$Iname<B1, S1>>
+            for $Uname<B0, S0>
+        where
+            [(); min(B0, B1 - 1) as usize]:,
+        {
+            type Output = $Uname<{ min(B0, B1 - 1) }, S0>;
+            fn rem(self: $Uname<B0, S0>, other: $Iname<B1, S1>) -> Self::Output {
+                unsafe { Self::Output::new_unchecked(self.raw() % other.raw().unsigned_abs()) }
+            }
+        }
+        impl<const B0: u32, const B1: u32, const S0: i32, const S1: i32> DivRem<$Uname<B1, S1>>
+            for $Iname<B0, S0>
+        where
+            [(); (S0 - S1) as usize]:,
+            [(); min(B0, B1 + 1) as usize]:,
+            [(); (B1 + 1) as usize]:,
+        {
+            type Quotient = $Iname<B0, { S0 - S1 }>;
+            // The divisor is unsigned, so the remainder needs an extra sign bit.
+            type Remainder = $Iname<{ min(B0, B1 + 1) }, S0>;
+            type EuclidRemainder = $Iname<{ B1 + 1 }, S0>;
+            fn div_rem(self, other: $Uname<B1, S1>) -> (Self::Quotient, Self::Remainder) {
+                // As for `Rem`, divide the magnitude, since the divisor may not fit in the
+                // signed raw type.  Each magnitude is at most `-MIN`, which wraps to `MIN`.
+                let magnitude = self.raw().unsigned_abs();
+                let quotient = (magnitude / other.raw()) as <Self::Quotient as Num>::Raw;
+                let remainder = (magnitude % other.raw()) as <Self::Quotient as Num>::Raw;
+                let (quotient, remainder) = if self.raw() < 0 {
+                    (quotient.wrapping_neg(), remainder.wrapping_neg())
+                } else {
+                    (quotient, remainder)
+                };
+                unsafe {
+                    (
+                        Self::Quotient::new_unchecked(quotient),
+                        Self::Remainder::new_unchecked(remainder),
+                    )
+                }
+            }
+            fn div_rem_euclid(
+                self,
+                other: $Uname<B1, S1>,
+            ) -> (Self::Quotient, Self::EuclidRemainder) {
+                let magnitude = self.raw().unsigned_abs();
+                let mut quotient = magnitude / other.raw();
+                let mut remainder = magnitude % other.raw();
+                if self.raw() < 0 && remainder != 0 {
+                    // The quotient is rounded away from zero, so the remainder is positive.
+                    quotient += 1;
+                    remainder = other.raw() - remainder;
+                }
+                let quotient = quotient as <Self::Quotient as Num>::Raw;
+                let quotient = if self.raw() < 0 { quotient.wrapping_neg() } else { quotient };
+                let remainder = remainder as <Self::Quotient as Num>::Raw;
+                unsafe {
+                    (
+                        Self::Quotient::new_unchecked(quotient),
+                        Self::EuclidRemainder::new_unchecked(remainder),
+                    )
+                }
+            }
+        }
+        impl<const B0: u32, const B1: u32, const S0: i32, const S1: i32> DivRem<$Iname<B1, S1>>
+            for $Uname<B0, S0>
+        where
+            [(); (B0 + 1) as usize]:,
+            [(); (S0 - S1) as usize]:,
+            [(); min(B0, B1 - 1) as usize]:,
+        {
+            type Quotient = $Iname<{ B0 + 1 }, { S0 - S1 }>;
+            // The dividend is non-negative, so the remainder is non-negative and smaller
+            // than the magnitude of the signed divisor.
+            type Remainder = $Uname<{ min(B0, B1 - 1) }, S0>;
+            type EuclidRemainder = $Uname<{ min(B0, B1 - 1) }, S0>;
+            fn div_rem(self, other: $Iname<B1, S1>) -> (Self::Quotient, Self::Remainder) {
+                let dividend = self.raw() as <Self::Quotient as Num>::Raw;
+                let quotient = dividend / other.raw();
+                let remainder = dividend - quotient * other.raw();
+                unsafe {
+                    (
+                        Self::Quotient::new_unchecked(quotient),
+                        Self::Remainder::new_unchecked(remainder as <Self::Remainder as Num>::Raw),
+                    )
+                }
+            }
+            fn div_rem_euclid(
+                self,
+                other: $Iname<B1, S1>,
+            ) -> (Self::Quotient, Self::EuclidRemainder) {
+                self.div_rem(other)
+            }
+        }
+    };
+}
+
+fp_signed_unsigned_impl!(U8, I8, isqrt_u8, cbrt_u8);
+fp_signed_unsigned_impl!(U16, I16, isqrt_u16, cbrt_u16);
+fp_signed_unsigned_impl!(U32, I32, isqrt_u32, cbrt_u32);
+fp_signed_unsigned_impl!(U64, I64, isqrt_u64, cbrt_u64);
+fp_signed_unsigned_impl!(U128, I128, isqrt_u128, cbrt_u128);
+fp_signed_unsigned_impl!(Usize, Isize, isqrt_usize, cbrt_usize);
+
+macro_rules! fp_cross_impl {
+    ($Narrow:ident, $Wide:ident) => {
+        /// Fixed-point integers with different raw types and the same signedness may be
+        /// multiplied.  The narrower input is widened, and the result has the wider raw
+        /// type, with the bits and shift of the product as for the same raw type.
+        impl<const B0: u32, const B1: u32, const S0: i32, const S1: i32> Mul<$Wide<B1, S1>>
+            for $Narrow<B0, S0>
+        where
+            [(); (B0 + B1) as usize]:,
+            [(); (S0 + S1) as usize]:,
+        {
+            type Output = $Wide<{ B0 + B1 }, { S0 + S1 }>;
+            fn mul(self: $Narrow<B0, S0>, other: $Wide<B1, S1>) -> Self::Output {
+                let self_raw = self.raw() as <Self::Output as Num>::Raw;
+                unsafe { Self::Output::new_unchecked(self_raw.unchecked_mul(other.raw())) }
+            }
+        }
+        impl<const B0: u32, const B1: u32, const S0: i32, const S1: i32> Mul<$Narrow<B1, S1>>
+            for $Wide<B0, S0>
+        where
+            [(); (B0 + B1) as usize]:,
+            [(); (S0 + S1) as usize]:,
+        {
+            type Output = $Wide<{ B0 + B1 }, { S0 + S1 }>;
+            fn mul(self: $Wide<B0, S0>, other: $Narrow<B1, S1>) -> Self::Output {
+                let other_raw = other.raw() as <Self::Output as Num>::Raw;
+                unsafe { Self::Output::new_unchecked(self.raw().unchecked_mul(other_raw)) }
+            }
+        }
+        /// Fixed-point integers with different raw types and the same signedness may be
+        /// divided, with the result in the wider raw type and the bits and shift of the
+        /// quotient as for the same raw type.
+        impl<const B0: u32, const B1: u32, const S0: i32, const S1: i32> Div<$Wide<B1, S1>>
+            for $Narrow<B0, S0>
+        where
+            [(); (B0 + Self::SIGNED as u32) as usize]:,
+            [(); (S0 - S1) as usize]:,
+        {
+            type Output = $Wide<{ B0 + Self::SIGNED as u32 }, { S0 - S1 }>;
+            fn div(self: $Narrow<B0, S0>, other: $Wide<B1, S1>) -> Self::Output {
+                let self_raw = self.raw() as <Self::Output as Num>::Raw;
+                unsafe { Self::Output::new_unchecked(self_raw / other.raw()) }
+            }
+        }
+        impl<const B0: u32, const B1: u32, const S0: i32, const S1: i32> Div<$Narrow<B1, S1>>
+            for $Wide<B0, S0>
+        where
+            [(); (B0 + Self::SIGNED as u32) as usize]:,
+            [(); (S0 - S1) as usize]:,
+        {
+            type Output = $Wide<{ B0 + Self::SIGNED as u32 }, { S0 - S1 }>;
+            fn div(self: $Wide<B0, S0>, other: $Narrow<B1, S1>) -> Self::Output {
+                let other_raw = other.raw() as <Self::Output as Num>::Raw;
+                unsafe { Self::Output::new_unchecked(self.raw() / other_raw) }
+            }
+        }
+    };
+}
+
+fp_cross_impl!(U8, U16);
+fp_cross_impl!(I8, I16);
+fp_cross_impl!(U8, U32);
+fp_cross_impl!(I8, I32);
+fp_cross_impl!(U8, U64);
+fp_cross_impl!(I8, I64);
+fp_cross_impl!(U8, U128);
+fp_cross_impl!(I8, I128);
+fp_cross_impl!(U16, U32);
+fp_cross_impl!(I16, I32);
+fp_cross_impl!(U16, U64);
+fp_cross_impl!(I16, I64);
+fp_cross_impl!(U16, U128);
+fp_cross_impl!(I16, I128);
+fp_cross_impl!(U32, U64);
+fp_cross_impl!(I32, I64);
+fp_cross_impl!(U32, U128);
+fp_cross_impl!(I32, I128);
+fp_cross_impl!(U64, U128);
+fp_cross_impl!(I64, I128);
+
+macro_rules! fp_widening_impl {
+    ($Name:ident, $Wider:ident) => {
+        impl<const B0: u32, const B1: u32, const S0: i32, const S1: i32>
+            WideningMul<$Name<B1, S1>> for $Name<B0, S0>
+        where
+            [(); (B0 + B1) as usize]:,
+            [(); (S0 + S1) as usize]:,
+        {
+            type Output = $Wider<{ B0 + B1 }, { S0 + S1 }>;
+            fn widening_mul(self, other: $Name<B1, S1>) -> Self::Output {
+                let self_raw = self.raw() as <Self::Output as Num>::Raw;
+                let other_raw = other.raw() as <Self::Output as Num>::Raw;
+                unsafe { Self::Output::new_unchecked(self_raw.unchecked_mul(other_raw)) }
+            }
+        }
+    };
+}
+
+fp_widening_impl!(I8, I16);
+fp_widening_impl!(U8, U16);
+fp_widening_impl!(I16, I32);
+fp_widening_impl!(U16, U32);
+fp_widening_impl!(I32, I64);
+fp_widening_impl!(U32, U64);
+fp_widening_impl!(I64, I128);
+fp_widening_impl!(U64, U128);
+
+macro_rules! fp_mul_shr_impl {
+    ($Name:ident, $T:ty, $D:ty) => {
+        impl<const B0: u32, const S0: i32> $Name<B0, S0> {
+            #[doc = concat!("Multiply by `other` in the double-width raw type `", stringify!($D), "`, then")]
+            /// shift the product right by `N` bits, truncating like `raw_shr`, in one
+            /// operation.  The product may be as wide as both operands together, but the
+            /// shifted result must fit in the raw type of the operands.  This is the usual
+            /// multiply-high of fixed-point DSP code, e.g. `SMULL` followed by a shift on Arm.
+            pub fn mul_shr<const N: u32, T: Num<Raw = $T>>(
+                self,
+                other: T,
+            ) -> $Name<{ B0 + T::BITS - N }, { S0 + T::SHIFT - N as i32 }>
+            where
+                [(); (B0 + T::BITS - N) as usize]:,
+                [(); (S0 + T::SHIFT - N as i32) as usize]:,
+            {
+                let product = self.raw() as $D * other.raw() as $D;
+                unsafe { $Name::new_unchecked((product >> N) as $T) }
+            }
+        }
+    };
+}
+
+fp_mul_shr_impl!(I8, i8, i16);
+fp_mul_shr_impl!(U8, u8, u16);
+fp_mul_shr_impl!(I16, i16, i32);
+fp_mul_shr_impl!(U16, u16, u32);
+fp_mul_shr_impl!(I32, i32, i64);
+fp_mul_shr_impl!(U32, u32, u64);
+fp_mul_shr_impl!(I64, i64, i128);
+fp_mul_shr_impl!(U64, u64, u128);
+
+macro_rules! fp_ratio_impl {
+    ($Name:ident, $T:ty, $W64:ty, $W128:ty) => {
+        impl<const B0: u32, const S0: i32> $Name<B0, S0> {
+            /// Multiply by the constant ratio `NUM / DEN`, rounded toward zero, e.g. 5/9 for a
+            /// conversion from °F to °C.  The product with `NUM` is computed exactly in a wider
+            /// raw type (`i64` or `u64` if it fits, otherwise `i128` or `u128`), and the result
+            /// has just enough bits for the largest quotient.  It is a compile error if the
+            /// product does not fit, if `DEN` is zero, or if the ratio is negative for an
+            /// unsigned type.
+            pub fn mul_ratio<const NUM: i64, const DEN: i64>(
+                self,
+            ) -> $Name<{ ratio_bits(B0, Self::SIGNED, NUM, DEN) }, S0>
+            where
+                [(); ratio_product_bits(B0, Self::SIGNED, NUM) as usize]:,
+                [(); ratio_bits(B0, Self::SIGNED, NUM, DEN) as usize]:,
+            {
+                let product_bits = ratio_product_bits(B0, Self::SIGNED, NUM);
+                let quotient = if product_bits < i64::BITS {
+                    (self.raw() as $W64 * NUM as $W64 / DEN as $W64) as $T
+                } else {
+                    (self.raw() as $W128 * NUM as $W128 / DEN as $W128) as $T
+                };
+                unsafe { $Name::new_unchecked(quotient) }
+            }
+        }
     };
 }
 
-fp_signed_unsigned_impl!(U8, I8);
-fp_signed_unsigned_impl!(U16, I16);
-fp_signed_unsigned_impl!(U32, I32);
-fp_signed_unsigned_impl!(U64, I64);
-fp_signed_unsigned_impl!(U128, I128);
-fp_signed_unsigned_impl!(Usize, Isize);
+fp_ratio_impl!(I8, i8, i64, i128);
+fp_ratio_impl!(U8, u8, u64, u128);
+fp_ratio_impl!(I16, i16, i64, i128);
+fp_ratio_impl!(U16, u16, u64, u128);
+fp_ratio_impl!(I32, i32, i64, i128);
+fp_ratio_impl!(U32, u32, u64, u128);
+fp_ratio_impl!(I64, i64, i64, i128);
+fp_ratio_impl!(U64, u64, u64, u128);
+fp_ratio_impl!(I128, i128, i64, i128);
+fp_ratio_impl!(U128, u128, u64, u128);
+fp_ratio_impl!(Isize, isize, i64, i128);
+fp_ratio_impl!(Usize, usize, u64, u128);
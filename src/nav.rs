@@ -0,0 +1,116 @@
+//! Great-circle navigation between latitude/longitude positions, computed without floating
+//! point.
+//!
+//! Positions are given in degrees, in any signed fixed-point format of at most 64 bits
+//! (checked at compile time), e.g. `I32<32, 23>` or GNSS-style integers scaled by 10^7
+//! converted to a binary format.  Distances are computed with the haversine formula on a
+//! sphere with the IUGG mean Earth radius of 6,371,008.8 m, and returned in meters.
+//!
+//! All internal calculations use the CORDIC trigonometric kernels with 60 fractional bits.
+//! The numerical error of the distance is below 1 µm for points up to a quarter of the
+//! globe apart, and grows towards antipodal points (where the haversine formula is
+//! ill-conditioned) to a worst case of about 2 cm; the result is then rounded to the
+//! nearest value of the output type.  Note that the spherical model itself differs from the
+//! WGS-84 ellipsoid by up to about 0.5%, which dominates the numerical error by far.
+
+use core::marker::PhantomData;
+
+use crate::cordic::{atan2_turns_internal, cos_sin_turns_internal, FRAC, PI};
+use crate::raw::{from_raw, signed_bits};
+use crate::regression::div_scaled;
+use crate::{Num, RangeError};
+
+/// Mean Earth radius in decimeters.
+const EARTH_RADIUS_DM: i128 = 63_710_088;
+
+/// A position on the Earth, as a latitude and longitude in degrees.  Latitudes are
+/// positive to the north, and longitudes are positive to the east.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct LatLon<T> {
+    pub lat: T,
+    pub lon: T,
+}
+
+struct Degrees<T>(PhantomData<T>);
+
+impl<T: Num> Degrees<T> {
+    /// Compile-time check that angles in degrees can be converted to turns with `FRAC`
+    /// fractional bits without overflow.
+    const CHECK: () = assert!(
+        signed_bits::<T>() <= 64 && signed_bits::<T>() as i32 - T::SHIFT <= 64,
+        "latitudes and longitudes must fit in 64 signed bits, with magnitudes below 2^63"
+    );
+}
+
+/// Convert an angle in degrees to turns, with `FRAC` fractional bits.
+fn turns<T: Num>(degrees: T) -> i128
+where
+    T::Raw: Into<i128>,
+{
+    let () = Degrees::<T>::CHECK;
+    div_scaled(degrees.raw().into(), 360, FRAC as i32 - T::SHIFT).unwrap()
+}
+
+/// Multiply two values with `FRAC` fractional bits.
+fn mul(a: i128, b: i128) -> i128 {
+    (a * b) >> FRAC
+}
+
+impl<T: Num> LatLon<T>
+where
+    T::Raw: Into<i128>,
+{
+    /// Return the great-circle distance to `other` in meters, rounded to the nearest value
+    /// of the type `Out`, or a `RangeError` if it does not fit.
+    pub fn distance_to<Out: Num>(self, other: Self) -> Result<Out, RangeError>
+    where
+        Out::Raw: TryFrom<i128>,
+    {
+        let (lat1, lat2) = (turns(self.lat), turns(other.lat));
+        let d_lon = turns(other.lon) - turns(self.lon);
+        let (cos1, _) = cos_sin_turns_internal(lat1, FRAC as i32);
+        let (cos2, _) = cos_sin_turns_internal(lat2, FRAC as i32);
+        // Sines of the half-angles.
+        let (_, half_lat) = cos_sin_turns_internal(lat2 - lat1, FRAC as i32 + 1);
+        let (_, half_lon) = cos_sin_turns_internal(d_lon, FRAC as i32 + 1);
+        // a = sin²(Δφ/2) + cos φ1 cos φ2 sin²(Δλ/2), which lies in [0, 1].
+        let one = 1 << FRAC;
+        let a = mul(half_lat, half_lat) + mul(mul(cos1, cos2), mul(half_lon, half_lon));
+        let a = a.clamp(0, one);
+        // The central angle is 2 atan2(sqrt(a), sqrt(1 - a)).
+        let sqrt = |v: i128| ((v as u128) << FRAC).isqrt() as i128;
+        let central = 2 * atan2_turns_internal(sqrt(a), sqrt(one - a));
+        let radians = mul(central, 2 * PI);
+        from_raw(
+            div_scaled(radians * EARTH_RADIUS_DM, 10, Out::SHIFT - FRAC as i32)
+                .ok_or(RangeError::TooLarge),
+        )
+    }
+    /// Return the initial bearing of the great circle to `other`, in degrees clockwise from
+    /// north in the range [0, 360), rounded to the nearest value of the type `Out` (a
+    /// bearing which rounds up to 360 is returned as 0).  The bearing to the same or the
+    /// antipodal position is undefined, and returned as 0.
+    pub fn bearing_to<Out: Num>(self, other: Self) -> Result<Out, RangeError>
+    where
+        Out::Raw: TryFrom<i128>,
+    {
+        let (cos1, sin1) = cos_sin_turns_internal(turns(self.lat), FRAC as i32);
+        let (cos2, sin2) = cos_sin_turns_internal(turns(other.lat), FRAC as i32);
+        let d_lon = turns(other.lon) - turns(self.lon);
+        let (cos_lon, sin_lon) = cos_sin_turns_internal(d_lon, FRAC as i32);
+        let y = mul(sin_lon, cos2);
+        let x = mul(cos1, sin2) - mul(mul(sin1, cos2), cos_lon);
+        // Tiny components are rounding noise, where the bearing is undefined.
+        let noise = 1 << 8;
+        let (x, y) = if x.abs() < noise && y.abs() < noise {
+            (0, 0)
+        } else {
+            (x, y)
+        };
+        let turns = atan2_turns_internal(y, x).rem_euclid(1 << FRAC);
+        let full = div_scaled(360, 1, Out::SHIFT).ok_or(RangeError::TooLarge)?;
+        let raw =
+            div_scaled(turns * 360, 1, Out::SHIFT - FRAC as i32).ok_or(RangeError::TooLarge)?;
+        from_raw(Ok(if raw == full { 0 } else { raw }))
+    }
+}
@@ -1,32 +1,58 @@
+use core::cmp::Ordering;
+
+#[cfg(feature = "f128")]
+use crate::float::{f128_from_raw, f128_into_parts};
+use crate::float::{f32_from_raw, f32_into_parts, f64_from_raw, f64_into_parts, from_parts};
 use crate::*;
 
 // Because Rust does not provide suitable traits over the integer types,
 // we have to use a macro for the impls instead of writing one generic impl.
 macro_rules! num_impl {
-    ($Name:ident, $T:ty) => {
+    ($Name:ident, $T:ty, $Iname:ident, $IT:ty, $Uname:ident, $UT:ty) => {
         /// Every integer is also a fixed-point number, considered to have
         /// the maximum number of bits and zero shift.
         impl Num for $T {
             type Raw = $T;
             type Output<const B: u32, const S: i32> = $Name<B, S>;
+            type SignedFp = $IT;
+            type UnsignedFp = $UT;
             const BITS: u32 = <$T>::BITS;
             const SHIFT: i32 = 0;
             const MIN: $T = <$T>::MIN;
             const MAX: $T = <$T>::MAX;
+            const ZERO: $T = 0;
+            const ONE: $T = 1;
             #[allow(unused_comparisons)]
             const SIGNED: bool = <$T>::MIN < 0;
             unsafe fn new_unchecked(val: $T) -> Self {
                 val
             }
+            fn from_f32(val: f32) -> Result<Self, RangeError> {
+                let (negative, mantissa, exp) = f32_into_parts(val);
+                from_parts(negative, mantissa, exp)
+            }
             unsafe fn from_f32_unchecked(val: f32) -> Self {
-                val.to_int_unchecked()
+                unsafe { Self::from_f32(val).unwrap_unchecked() }
+            }
+            fn from_f64(val: f64) -> Result<Self, RangeError> {
+                let (negative, mantissa, exp) = f64_into_parts(val);
+                from_parts(negative, mantissa, exp)
             }
             unsafe fn from_f64_unchecked(val: f64) -> Self {
-                val.to_int_unchecked()
+                unsafe { Self::from_f64(val).unwrap_unchecked() }
             }
             fn raw(self) -> $T {
                 self
             }
+            fn leading_zeros(self) -> u32 {
+                <$T>::leading_zeros(self)
+            }
+            fn trailing_zeros(self) -> u32 {
+                <$T>::trailing_zeros(self)
+            }
+            fn count_ones(self) -> u32 {
+                <$T>::count_ones(self)
+            }
             /// Conversion to f32 is guaranteed to be exact.  Therefore, this function only
             /// works for integer types which are no more than 24 bits wide.
             fn into_f32(self) -> f32 {
@@ -34,7 +60,7 @@ macro_rules! num_impl {
                     Self::BITS <= f32::MANTISSA_DIGITS,
                     "number could be truncated in f32"
                 );
-                self as f32
+                f32_from_raw(self as i128, 0)
             }
             /// Conversion to f64 is guaranteed to be exact.  Therefore, this function only
             /// works for integer types which are no more than 53 bits wide.
@@ -43,23 +69,112 @@ macro_rules! num_impl {
                     Self::BITS <= f32::MANTISSA_DIGITS,
                     "number could be truncated in f64"
                 );
-                self as f64
+                f64_from_raw(self as i128, 0)
+            }
+            #[cfg(feature = "f128")]
+            fn from_f128(val: f128) -> Result<Self, RangeError> {
+                let (negative, mantissa, exp) = f128_into_parts(val);
+                from_parts(negative, mantissa, exp)
+            }
+            #[cfg(feature = "f128")]
+            unsafe fn from_f128_unchecked(val: f128) -> Self {
+                unsafe { Self::from_f128(val).unwrap_unchecked() }
+            }
+            /// Conversion to f128 is guaranteed to be exact.  Therefore, this function only
+            /// works for integer types which are no more than 113 bits wide.
+            #[cfg(feature = "f128")]
+            fn into_f128(self) -> f128 {
+                assert!(
+                    Self::BITS <= f128::MANTISSA_DIGITS,
+                    "number could be truncated in f128"
+                );
+                f128_from_raw(self as i128, 0)
             }
         }
 
         #[repr(transparent)]
-        #[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+        #[derive(Clone, Copy, Debug, Default, Eq, Hash)]
         /// [`#[repr(transparent)]`](https://doc.rust-lang.org/reference/type-layout.html#the-transparent-representation)
         /// struct containing
         #[doc = concat!("[`", stringify!($T), "`]")]
         /// interpreted as a fixed-point number.
         ///
+        /// The transparent layout is a stable guarantee, so this type may be passed across
+        /// an FFI boundary as its raw integer type (see the `ffi` feature).
+        ///
         /// Implements the trait [`fp::Num`](Num) for fixed-point manipulation.
         pub struct $Name<const BITS: u32, const SHIFT: i32>($T);
 
+        /// Fixed-point numbers with the same raw type and the same shift may be compared,
+        /// even if they have different bits, since their raw values have the same scale.
+        impl<const B0: u32, const B1: u32, const S: i32> PartialEq<$Name<B1, S>> for $Name<B0, S> {
+            fn eq(&self, other: &$Name<B1, S>) -> bool {
+                self.0 == other.0
+            }
+        }
+        impl<const B0: u32, const B1: u32, const S: i32> PartialOrd<$Name<B1, S>>
+            for $Name<B0, S>
+        {
+            fn partial_cmp(&self, other: &$Name<B1, S>) -> Option<Ordering> {
+                Some(self.0.cmp(&other.0))
+            }
+        }
+        impl<const BITS: u32, const SHIFT: i32> Ord for $Name<BITS, SHIFT> {
+            fn cmp(&self, other: &Self) -> Ordering {
+                self.0.cmp(&other.0)
+            }
+        }
+
+        impl<const BITS: u32, const SHIFT: i32> $Name<BITS, SHIFT> {
+            /// Return the fixed-point number with the raw value `val`, like `Num::new`, but
+            /// usable to initialize constants.  Panics if `val` is out of range, which is a
+            /// compile-time error in a const context.
+            pub const fn new_const(val: $T) -> Self {
+                assert!(
+                    val >= <Self as Num>::MIN.0 && val <= <Self as Num>::MAX.0,
+                    "value out of range"
+                );
+                Self(val)
+            }
+            /// Return the raw value, like `Num::raw`, but usable in constant expressions.
+            pub const fn raw_const(self) -> $T {
+                self.0
+            }
+            /// Return whether this value is zero, like `Num::is_zero`, but usable in constant
+            /// expressions.
+            pub const fn is_zero(self) -> bool {
+                self.0 == 0
+            }
+            /// Return whether this value is greater than zero, like `Num::is_positive`, but
+            /// usable in constant expressions.
+            pub const fn is_positive(self) -> bool {
+                self.0 > 0
+            }
+            /// Return whether this value is less than zero, like `Num::is_negative`, but
+            /// usable in constant expressions.
+            #[allow(unused_comparisons)]
+            pub const fn is_negative(self) -> bool {
+                self.0 < 0
+            }
+            /// Return the base-2 logarithm of the logical value, rounded down, i.e. the
+            /// exponent of its most significant bit, which is negative for values below 1.
+            /// Panics if the value is not positive, like `ilog2` on the primitive integers.
+            pub fn ilog2(self) -> i32 {
+                self.checked_ilog2()
+                    .expect("argument of integer logarithm must be positive")
+            }
+            /// Return the base-2 logarithm like `ilog2`, or `None` if the value is not
+            /// positive.
+            pub fn checked_ilog2(self) -> Option<i32> {
+                Some(self.0.checked_ilog2()? as i32 - SHIFT)
+            }
+        }
+
         impl<const BITS: u32, const SHIFT: i32> Num for $Name<BITS, SHIFT> {
             type Raw = $T;
             type Output<const B: u32, const S: i32> = $Name<B, S>;
+            type SignedFp = $Iname<BITS, SHIFT>;
+            type UnsignedFp = $Uname<BITS, SHIFT>;
             const BITS: u32 = {
                 assert!(BITS <= <$T>::BITS, concat!("too many bits for ", stringify!($T)));
                 BITS
@@ -81,20 +196,36 @@ macro_rules! num_impl {
                     <$T>::MAX >> (<$T>::BITS - Self::BITS)
                 }
             });
+            const ZERO: Self = Self(0);
+            const ONE: Self = {
+                assert!(
+                    SHIFT >= 0 && (SHIFT as u32) + (Self::SIGNED as u32) < BITS,
+                    "1 is not representable"
+                );
+                Self(1 << SHIFT)
+            };
             const SIGNED: bool = <$T>::SIGNED;
             unsafe fn new_unchecked(val: $T) -> Self {
                 let _ = Self::BITS;  // force the compile-time check that T is wide enough for BITS
                 Self(val)
             }
-            /// The caller must ensure that `val` is within the range of this fixed-point type,
-            /// and that the quantity `val / 2_f32.powi(-SHIFT)` is finite.
+            fn from_f32(val: f32) -> Result<Self, RangeError> {
+                let (negative, mantissa, exp) = f32_into_parts(val);
+                from_parts(negative, mantissa, exp)
+            }
+            /// The caller must ensure that `val` is finite and within the range of this
+            /// fixed-point type.
             unsafe fn from_f32_unchecked(val: f32) -> Self {
-                unsafe { Self::new_unchecked((val / f32_lsb::<SHIFT>()).to_int_unchecked()) }
+                unsafe { Self::from_f32(val).unwrap_unchecked() }
+            }
+            fn from_f64(val: f64) -> Result<Self, RangeError> {
+                let (negative, mantissa, exp) = f64_into_parts(val);
+                from_parts(negative, mantissa, exp)
             }
-            /// The caller must ensure that `val` is within the range of this fixed-point type,
-            /// and that the quantity `val / 2_f64.powi(-SHIFT)` is finite.
+            /// The caller must ensure that `val` is finite and within the range of this
+            /// fixed-point type.
             unsafe fn from_f64_unchecked(val: f64) -> Self {
-                unsafe { Self::new_unchecked((val / f64_lsb::<SHIFT>()).to_int_unchecked()) }
+                unsafe { Self::from_f64(val).unwrap_unchecked() }
             }
             fn raw(self) -> $T {
                 self.0
@@ -118,9 +249,7 @@ macro_rules! num_impl {
                     BITS as i32 - SHIFT <= f32::MAX_EXP as i32,
                     "number could overflow f32"
                 );
-                // `BITS == 0` requires special handling because, in this case only,
-                // `f32_lsb::<SHIFT>()` can overflow f32 (resulting in 0 * infinity).
-                if BITS == 0 { 0. } else { self.0 as f32 * f32_lsb::<SHIFT>() }
+                f32_from_raw(self.0 as i128, SHIFT)
             }
             /// Conversion to f64 is guaranteed to be exact.  Therefore, this function requires
             /// `BITS <= 53` (to prevent truncation), `SHIFT <= 1074` (to prevent underflow),
@@ -141,9 +270,40 @@ macro_rules! num_impl {
                     BITS as i32 - SHIFT <= f64::MAX_EXP as i32,
                     "number could overflow f64"
                 );
-                // `BITS == 0` requires special handling because, in this case only,
-                // `f64_lsb::<SHIFT>()` can overflow f64 (resulting in 0 * infinity).
-                if BITS == 0 { 0. } else { self.0 as f64 * f64_lsb::<SHIFT>() }
+                f64_from_raw(self.0 as i128, SHIFT)
+            }
+            #[cfg(feature = "f128")]
+            fn from_f128(val: f128) -> Result<Self, RangeError> {
+                let (negative, mantissa, exp) = f128_into_parts(val);
+                from_parts(negative, mantissa, exp)
+            }
+            /// The caller must ensure that `val` is finite and within the range of this
+            /// fixed-point type.
+            #[cfg(feature = "f128")]
+            unsafe fn from_f128_unchecked(val: f128) -> Self {
+                unsafe { Self::from_f128(val).unwrap_unchecked() }
+            }
+            /// Conversion to f128 is guaranteed to be exact.  Therefore, this function requires
+            /// `BITS <= 113` (to prevent truncation), `SHIFT <= 16494` (to prevent underflow),
+            /// and `BITS - SHIFT <= 16384` (to prevent overflow).
+            ///
+            /// This makes it possible to export very wide fixed-point numbers (e.g. 96-bit
+            /// accumulators) exactly, which is not possible with `f64`.
+            #[cfg(feature = "f128")]
+            fn into_f128(self) -> f128 {
+                assert!(
+                    BITS <= f128::MANTISSA_DIGITS,
+                    "number could be truncated in f128"
+                );
+                assert!(
+                    SHIFT <= f128::MANTISSA_DIGITS as i32 - f128::MIN_EXP,
+                    "number could underflow f128"
+                );
+                assert!(
+                    BITS as i32 - SHIFT <= f128::MAX_EXP as i32,
+                    "number could overflow f128"
+                );
+                f128_from_raw(self.0 as i128, SHIFT)
             }
         }
 
@@ -159,6 +319,13 @@ macro_rules! num_impl {
             }
         }
 
+        #[cfg(feature = "f128")]
+        impl<const BITS: u32, const SHIFT: i32> From<$Name<BITS, SHIFT>> for f128 {
+            fn from(val: $Name<BITS, SHIFT>) -> f128 {
+                val.into_f128()
+            }
+        }
+
         impl<const BITS: u32, const SHIFT: i32> TryFrom<f32> for $Name<BITS, SHIFT> {
             type Error = RangeError;
             fn try_from(val: f32) -> Result<Self, Self::Error> {
@@ -173,6 +340,14 @@ macro_rules! num_impl {
             }
         }
 
+        #[cfg(feature = "f128")]
+        impl<const BITS: u32, const SHIFT: i32> TryFrom<f128> for $Name<BITS, SHIFT> {
+            type Error = RangeError;
+            fn try_from(val: f128) -> Result<Self, Self::Error> {
+                Self::from_f128(val)
+            }
+        }
+
         #[doc = concat!("`", stringify!($T), "` is the same as `", stringify!($Name), "<", stringify!($T) ,"::BITS, 0>`.")]
         impl From<$T> for $Name<{ <$T>::BITS }, 0> {
             fn from(val: $T) -> Self {
@@ -189,18 +364,18 @@ macro_rules! num_impl {
     };
 }
 
-num_impl!(I8, i8);
-num_impl!(U8, u8);
-num_impl!(I16, i16);
-num_impl!(U16, u16);
-num_impl!(I32, i32);
-num_impl!(U32, u32);
-num_impl!(I64, i64);
-num_impl!(U64, u64);
-num_impl!(I128, i128);
-num_impl!(U128, u128);
-num_impl!(Isize, isize);
-num_impl!(Usize, usize);
+num_impl!(I8, i8, I8, i8, U8, u8);
+num_impl!(U8, u8, I8, i8, U8, u8);
+num_impl!(I16, i16, I16, i16, U16, u16);
+num_impl!(U16, u16, I16, i16, U16, u16);
+num_impl!(I32, i32, I32, i32, U32, u32);
+num_impl!(U32, u32, I32, i32, U32, u32);
+num_impl!(I64, i64, I64, i64, U64, u64);
+num_impl!(U64, u64, I64, i64, U64, u64);
+num_impl!(I128, i128, I128, i128, U128, u128);
+num_impl!(U128, u128, I128, i128, U128, u128);
+num_impl!(Isize, isize, Isize, isize, Usize, usize);
+num_impl!(Usize, usize, Isize, isize, Usize, usize);
 
 macro_rules! num_signed_unsigned_impl {
     ($Uname:ident, $Iname:ident) => {
@@ -211,8 +386,22 @@ macro_rules! num_signed_unsigned_impl {
             {
                 unsafe { $Iname::new_unchecked(self.raw() as <$Iname<{ B + 1 }, S> as Num>::Raw) }
             }
+            /// Return the smallest power of 2 which is at least the logical value (or the
+            /// least significant bit, if the value is 0).  The result has 1 more bit, since it
+            /// may exceed `MAX`.
+            pub fn next_power_of_two(self) -> $Uname<{ B + 1 }, S>
+            where
+                [(); (B + 1) as usize]:,
+            {
+                unsafe { $Uname::new_unchecked(self.raw().next_power_of_two()) }
+            }
         }
         impl<const B: u32, const S: i32> $Iname<B, S> {
+            /// Reinterpret this value as unsigned, dropping the sign bit.
+            ///
+            /// # Safety
+            ///
+            /// The caller must ensure that the value is not negative.
             pub unsafe fn into_unsigned_unchecked(self) -> $Uname<{ B - 1 }, S>
             where
                 [(); (B - 1) as usize]:,
@@ -229,6 +418,11 @@ macro_rules! num_signed_unsigned_impl {
                     None
                 }
             }
+            /// Return the absolute value as unsigned, with the same number of bits, like
+            /// `unsigned_abs` on the primitive integers.  This cannot overflow, even for `MIN`.
+            pub fn unsigned_abs(self) -> $Uname<B, S> {
+                unsafe { $Uname::new_unchecked(self.raw().unsigned_abs()) }
+            }
         }
     };
 }
@@ -240,32 +434,135 @@ num_signed_unsigned_impl!(U64, I64);
 num_signed_unsigned_impl!(U128, I128);
 num_signed_unsigned_impl!(Usize, Isize);
 
-fn f32_lsb<const SHIFT: i32>() -> f32 {
-    // This function returns the exact value of `2_f32.powi(-SHIFT)`.
-    // (On some architectures, powi() rounds subnormal numbers to zero,
-    // so we must construct the raw float manually.) Bounds checking is
-    // not performed; return value is undefined if SHIFT is out of range.
-    let exp = 2 - f32::MIN_EXP - SHIFT;
-    f32::from_bits(if exp > 0 {
-        // normal float
-        (exp as u32) << (f32::MANTISSA_DIGITS - 1)
-    } else {
-        // subnormal float
-        1u32 << (f32::MANTISSA_DIGITS as i32 + exp - 2)
-    })
+// Widening of the raw type, e.g. from `I32<24, 8>` to `I64<24, 8>`, which cannot fail.
+macro_rules! fp_wider_impl {
+    ($Name:ident, $Wide:ident) => {
+        #[doc = concat!("[`", stringify!($Name), "`] widens to [`", stringify!($Wide), "`] with the same bits and shift.")]
+        impl<const B: u32, const S: i32> From<$Name<B, S>> for $Wide<B, S> {
+            fn from(val: $Name<B, S>) -> Self {
+                unsafe { Self::new_unchecked(val.raw().into()) }
+            }
+        }
+    };
+}
+
+fp_wider_impl!(I8, I16);
+fp_wider_impl!(I8, I32);
+fp_wider_impl!(I8, I64);
+fp_wider_impl!(I8, I128);
+fp_wider_impl!(I16, I32);
+fp_wider_impl!(I16, I64);
+fp_wider_impl!(I16, I128);
+fp_wider_impl!(I32, I64);
+fp_wider_impl!(I32, I128);
+fp_wider_impl!(I64, I128);
+fp_wider_impl!(U8, U16);
+fp_wider_impl!(U8, U32);
+fp_wider_impl!(U8, U64);
+fp_wider_impl!(U8, U128);
+fp_wider_impl!(U16, U32);
+fp_wider_impl!(U16, U64);
+fp_wider_impl!(U16, U128);
+fp_wider_impl!(U32, U64);
+fp_wider_impl!(U32, U128);
+fp_wider_impl!(U64, U128);
+
+/// Return the raw limits `(MIN, MAX)` of a format whose bits and signedness are only known
+/// at runtime.  Requires `bits <= 127` for unsigned formats and `bits <= 128` for signed.
+pub(crate) const fn runtime_limits(bits: u32, signed: bool) -> (i128, i128) {
+    match (bits, signed) {
+        (0, _) => (0, 0),
+        (_, true) => (i128::MIN >> (128 - bits), i128::MAX >> (128 - bits)),
+        (_, false) => (0, i128::MAX >> (127 - bits)),
+    }
+}
+
+/// Convert a primitive integer to `i256`, which holds every value of every primitive type.
+macro_rules! to_i256 {
+    ($val:expr) => {{
+        let val = $val;
+        #[allow(unused_comparisons)]
+        if val < 0 {
+            i256::from(val as i128)
+        } else {
+            i256::from_limbs(0, val as u128)
+        }
+    }};
+}
+
+/// Compare `a * 2^shift` with `b`, exactly, where `|a|` and `|b|` are less than `2^128`.
+fn cmp_scaled(a: i256, b: i256, shift: i32) -> Ordering {
+    match shift {
+        ..0 => cmp_scaled(b, a, shift.saturating_neg()).reverse(),
+        0..=127 => (a << shift as u32).cmp(&b),
+        // Any nonzero `a * 2^shift` has a greater magnitude than `b`.
+        _ => a.cmp(&i256::ZERO).then(i256::ZERO.cmp(&b)),
+    }
 }
 
-fn f64_lsb<const SHIFT: i32>() -> f64 {
-    // This function returns the exact value of `2_f64.powi(-SHIFT)`.
-    // (On some architectures, powi() rounds subnormal numbers to zero,
-    // so we must construct the raw float manually.) Bounds checking is
-    // not performed; return value is undefined if SHIFT is out of range.
-    let exp = 2 - f64::MIN_EXP - SHIFT;
-    f64::from_bits(if exp > 0 {
-        // normal float
-        (exp as u64) << (f64::MANTISSA_DIGITS - 1)
-    } else {
-        // subnormal float
-        1u64 << (f64::MANTISSA_DIGITS as i32 + exp - 2)
-    })
+// Comparison of fixed-point numbers with primitive integers, by their logical values.
+macro_rules! int_cmp_impl {
+    ($Name:ident, $($P:ty),*) => {
+        $(
+            impl<const B: u32, const S: i32> PartialEq<$P> for $Name<B, S> {
+                fn eq(&self, other: &$P) -> bool {
+                    self.partial_cmp(other) == Some(Ordering::Equal)
+                }
+            }
+            impl<const B: u32, const S: i32> PartialOrd<$P> for $Name<B, S> {
+                fn partial_cmp(&self, other: &$P) -> Option<Ordering> {
+                    Some(cmp_scaled(to_i256!(self.0), to_i256!(*other), S.saturating_neg()))
+                }
+            }
+        )*
+    };
 }
+
+// Comparison of fixed-point numbers with floats, which is exact.  NaN is unordered, and
+// not equal to any fixed-point number.
+macro_rules! float_cmp_impl {
+    ($Name:ident, $F:ty, $into_parts:ident) => {
+        impl<const B: u32, const S: i32> PartialEq<$F> for $Name<B, S> {
+            fn eq(&self, other: &$F) -> bool {
+                self.partial_cmp(other) == Some(Ordering::Equal)
+            }
+        }
+        impl<const B: u32, const S: i32> PartialOrd<$F> for $Name<B, S> {
+            fn partial_cmp(&self, other: &$F) -> Option<Ordering> {
+                if other.is_nan() {
+                    None
+                } else if other.is_infinite() {
+                    Some(if *other > 0. {
+                        Ordering::Less
+                    } else {
+                        Ordering::Greater
+                    })
+                } else {
+                    let (negative, mantissa, exp) = $into_parts(*other);
+                    let mantissa = i256::from_limbs(0, mantissa);
+                    let other = if negative {
+                        i256::ZERO - mantissa
+                    } else {
+                        mantissa
+                    };
+                    let shift = S.saturating_add(exp).saturating_neg();
+                    Some(cmp_scaled(to_i256!(self.0), other, shift))
+                }
+            }
+        }
+    };
+}
+
+macro_rules! prim_cmp_impl {
+    ($($Name:ident),*) => {
+        $(
+            int_cmp_impl!(
+                $Name, i8, u8, i16, u16, i32, u32, i64, u64, i128, u128, isize, usize
+            );
+            float_cmp_impl!($Name, f32, f32_into_parts);
+            float_cmp_impl!($Name, f64, f64_into_parts);
+        )*
+    };
+}
+
+prim_cmp_impl!(I8, U8, I16, U16, I32, U32, I64, U64, I128, U128, Isize, Usize);
@@ -0,0 +1,198 @@
+use core::iter::{Product, Sum};
+use core::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use crate::*;
+
+/// A fixed-point number whose arithmetic keeps its type and panics on overflow, for
+/// accumulators such as `total += sample`.  Multiplication truncates like `checked_mul`.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Strict<T>(pub T);
+
+/// A fixed-point number whose arithmetic keeps its type and saturates to its range on
+/// overflow.  Multiplication truncates like `checked_mul`.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Saturating<T>(pub T);
+
+/// A fixed-point number whose arithmetic keeps its type and wraps around on overflow, like
+/// `core::num::Wrapping`, i.e. modulo `2^BITS` LSBs, e.g. for phase accumulators and
+/// counters.  Multiplication truncates like `checked_mul`.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Wrapping<T>(pub T);
+
+/// Return 1 in the type `T`, which is the empty product.  Panics if 1 is not representable.
+fn one<T: Num>() -> T {
+    T::try_one().expect("1 is not representable for the empty product")
+}
+
+/// Return whether `val` is negative.
+fn negative<T: Num>(val: T) -> bool
+where
+    T::Raw: Into<i128>,
+{
+    val.raw().into() < 0
+}
+
+// Implement an operator and its assignment form for the wrapper types, with the result
+// of each policy given by a closure of the two operands.
+macro_rules! overflow_impl {
+    ($Op:ident, $op:ident, $OpAssign:ident, $op_assign:ident, $Policy:ident, $result:expr) => {
+        impl<T: Num> $Op for $Policy<T>
+        where
+            T::Raw: Into<i128> + TryFrom<i128>,
+        {
+            type Output = Self;
+            fn $op(self, other: Self) -> Self {
+                let result: fn(T, T) -> T = $result;
+                $Policy(result(self.0, other.0))
+            }
+        }
+        impl<T: Num> $OpAssign for $Policy<T>
+        where
+            T::Raw: Into<i128> + TryFrom<i128>,
+        {
+            fn $op_assign(&mut self, other: Self) {
+                *self = self.$op(other);
+            }
+        }
+    };
+}
+
+overflow_impl!(Add, add, AddAssign, add_assign, Strict, |a, b| {
+    a.checked_add(b).expect("attempt to add with overflow")
+});
+overflow_impl!(Sub, sub, SubAssign, sub_assign, Strict, |a, b| {
+    a.checked_sub(b).expect("attempt to subtract with overflow")
+});
+overflow_impl!(Mul, mul, MulAssign, mul_assign, Strict, |a, b| {
+    a.checked_mul(b).expect("attempt to multiply with overflow")
+});
+
+overflow_impl!(Add, add, AddAssign, add_assign, Saturating, |a, b| {
+    match a.checked_add(b) {
+        Some(sum) => sum,
+        None if negative(b) => T::MIN,
+        None => T::MAX,
+    }
+});
+overflow_impl!(Sub, sub, SubAssign, sub_assign, Saturating, |a, b| {
+    match a.checked_sub(b) {
+        Some(difference) => difference,
+        None if negative(b) => T::MAX,
+        None => T::MIN,
+    }
+});
+overflow_impl!(Mul, mul, MulAssign, mul_assign, Saturating, |a, b| {
+    match a.checked_mul(b) {
+        Some(product) => product,
+        None if negative(a) != negative(b) => T::MIN,
+        None => T::MAX,
+    }
+});
+
+overflow_impl!(Add, add, AddAssign, add_assign, Wrapping, |a, b| {
+    a.wrapping_add(b)
+});
+overflow_impl!(Sub, sub, SubAssign, sub_assign, Wrapping, |a, b| {
+    a.wrapping_sub(b)
+});
+overflow_impl!(Mul, mul, MulAssign, mul_assign, Wrapping, |a, b| {
+    a.overflowing_mul(b).0
+});
+
+/// Negation wraps like `wrapping_neg`, so the most negative value (and any nonzero unsigned
+/// value) wraps around.
+impl<T: Num> Neg for Wrapping<T>
+where
+    T::Raw: Into<i128> + TryFrom<i128>,
+{
+    type Output = Self;
+    fn neg(self) -> Self {
+        Wrapping(self.0.wrapping_neg())
+    }
+}
+
+// Implement `Sum` and `Product` for a wrapper type, with its overflow policy.  An empty sum
+// is 0, and an empty product is 1, which panics if 1 is not representable.
+macro_rules! iter_impl {
+    ($Policy:ident) => {
+        impl<T: Num> Sum for $Policy<T>
+        where
+            T::Raw: Into<i128> + TryFrom<i128>,
+        {
+            fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+                iter.fold($Policy(T::ZERO), |a, b| a + b)
+            }
+        }
+        impl<'a, T: Num> Sum<&'a $Policy<T>> for $Policy<T>
+        where
+            T::Raw: Into<i128> + TryFrom<i128>,
+        {
+            fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+                iter.copied().sum()
+            }
+        }
+        impl<T: Num> Product for $Policy<T>
+        where
+            T::Raw: Into<i128> + TryFrom<i128>,
+        {
+            fn product<I: Iterator<Item = Self>>(mut iter: I) -> Self {
+                match iter.next() {
+                    Some(first) => iter.fold(first, |a, b| a * b),
+                    None => $Policy(one()),
+                }
+            }
+        }
+        impl<'a, T: Num> Product<&'a $Policy<T>> for $Policy<T>
+        where
+            T::Raw: Into<i128> + TryFrom<i128>,
+        {
+            fn product<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+                iter.copied().product()
+            }
+        }
+    };
+}
+
+iter_impl!(Strict);
+iter_impl!(Saturating);
+iter_impl!(Wrapping);
+
+// The fixed-point types themselves sum and multiply like `Strict`, keeping their type and
+// panicking on overflow.  Use `Saturating` or `Wrapping` for another policy.  Like `Strict`
+// and the checked operations of `Num`, this needs a raw type which converts losslessly to
+// and from `i128`, so `U128`, `Isize`, `Usize`, `I256` and `U256` do not implement `Sum`
+// and `Product`.
+macro_rules! fp_iter_impl {
+    ($Name:ident) => {
+        impl<const B: u32, const S: i32> Sum for $Name<B, S> {
+            fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+                iter.map(Strict).sum::<Strict<Self>>().0
+            }
+        }
+        impl<'a, const B: u32, const S: i32> Sum<&'a $Name<B, S>> for $Name<B, S> {
+            fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+                iter.copied().sum()
+            }
+        }
+        impl<const B: u32, const S: i32> Product for $Name<B, S> {
+            fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+                iter.map(Strict).product::<Strict<Self>>().0
+            }
+        }
+        impl<'a, const B: u32, const S: i32> Product<&'a $Name<B, S>> for $Name<B, S> {
+            fn product<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+                iter.copied().product()
+            }
+        }
+    };
+}
+
+fp_iter_impl!(I8);
+fp_iter_impl!(U8);
+fp_iter_impl!(I16);
+fp_iter_impl!(U16);
+fp_iter_impl!(I32);
+fp_iter_impl!(U32);
+fp_iter_impl!(I64);
+fp_iter_impl!(U64);
+fp_iter_impl!(I128);
@@ -0,0 +1,150 @@
+use crate::Num;
+
+/// Streaming estimator for a single quantile of a sequence of fixed-point samples,
+/// using the P² algorithm of Jain and Chlamtac (1985).  Memory use is constant:
+/// the estimator keeps five marker heights of type `T` and their positions, regardless
+/// of how many samples have been recorded.
+///
+/// Marker heights are adjusted with integer arithmetic on the raw values of `T`,
+/// so estimates are rounded to the resolution of `T`.
+#[derive(Clone, Debug)]
+pub struct P2Quantile<T: Num> {
+    // Requested quantile, with 32 fractional bits.
+    p: u64,
+    heights: [T; 5],
+    // Actual marker positions (1-based sample ranks).
+    positions: [u64; 5],
+    // Desired marker positions, with 32 fractional bits.  These are widened to u128 so
+    // that the fractional part does not limit the number of samples which can be recorded.
+    desired: [u128; 5],
+    count: u64,
+}
+
+impl<T: Num> P2Quantile<T>
+where
+    T::Raw: Into<i64> + TryFrom<i64>,
+{
+    /// Create an estimator for the `q` quantile, where `q` is a fixed-point number between
+    /// 0 and 1 (e.g. `q = 0.5` for the median).
+    pub fn new<Q: Num<Raw = u32>>(q: Q) -> Self {
+        assert!(
+            (0..=32).contains(&Q::SHIFT),
+            "quantile must have between 0 and 32 fractional bits"
+        );
+        assert!(q.raw() as u64 <= 1 << Q::SHIFT, "quantile must not exceed 1");
+        let p = (q.raw() as u64) << (32 - Q::SHIFT);
+        let one = 1u128 << 32;
+        Self {
+            p,
+            heights: [T::ZERO; 5],
+            positions: [1, 2, 3, 4, 5],
+            desired: [
+                one,
+                one + 2 * p as u128,
+                one + 4 * p as u128,
+                3 * one + 2 * p as u128,
+                5 * one,
+            ],
+            count: 0,
+        }
+    }
+    /// Return the number of samples recorded so far.
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+    /// Add one sample to the estimator.
+    pub fn record(&mut self, val: T) {
+        if self.count < 5 {
+            self.heights[self.count as usize] = val;
+            self.count += 1;
+            if self.count == 5 {
+                self.heights.sort_unstable();
+            }
+            return;
+        }
+        self.count += 1;
+        // Find the cell containing the new sample, extending the extreme markers if needed.
+        let cell = if val < self.heights[0] {
+            self.heights[0] = val;
+            0
+        } else if val >= self.heights[4] {
+            self.heights[4] = val;
+            3
+        } else {
+            self.heights[1..4].partition_point(|h| *h <= val)
+        };
+        for position in &mut self.positions[cell + 1..] {
+            *position += 1;
+        }
+        let p = self.p as u128;
+        let increments = [0, p / 2, p, ((1 << 32) + p) / 2, 1 << 32];
+        for (desired, increment) in self.desired.iter_mut().zip(increments) {
+            *desired += increment;
+        }
+        // Move each of the middle markers by at most one position towards its desired position.
+        for i in 1..4 {
+            let offset = self.desired[i] as i128 - ((self.positions[i] as i128) << 32);
+            let d = if offset >= 1 << 32 && self.positions[i + 1] - self.positions[i] > 1 {
+                1
+            } else if offset <= -(1 << 32) && self.positions[i] - self.positions[i - 1] > 1 {
+                -1
+            } else {
+                continue;
+            };
+            let height = match self.parabolic(i, d) {
+                Some(h) if self.height(i - 1) < h && h < self.height(i + 1) => h,
+                _ => self.linear(i, d),
+            };
+            self.heights[i] = T::new(height.try_into().ok().unwrap()).unwrap();
+            self.positions[i] = (self.positions[i] as i64 + d) as u64;
+        }
+    }
+    /// Return the current estimate of the quantile, or `None` if no samples have been
+    /// recorded.  Until five samples have been recorded, the estimate is the nearest-rank
+    /// quantile of the recorded samples.
+    pub fn estimate(&self) -> Option<T> {
+        match self.count {
+            0 => None,
+            1..=4 => {
+                let mut sorted = self.heights;
+                let n = self.count as usize;
+                sorted[..n].sort_unstable();
+                let rank = (self.p * (n as u64 - 1) + (1 << 31)) >> 32;
+                Some(sorted[rank as usize])
+            }
+            // The extreme markers track the minimum and maximum exactly.
+            _ if self.p == 0 => Some(self.heights[0]),
+            _ if self.p == 1 << 32 => Some(self.heights[4]),
+            _ => Some(self.heights[2]),
+        }
+    }
+    fn height(&self, i: usize) -> i64 {
+        self.heights[i].raw().into()
+    }
+    // Piecewise-parabolic prediction of the height of marker `i` after moving it by `d`,
+    // or `None` if the intermediate products overflow.
+    fn parabolic(&self, i: usize, d: i64) -> Option<i64> {
+        let (q_lo, q, q_hi) = (
+            self.height(i - 1) as i128,
+            self.height(i) as i128,
+            self.height(i + 1) as i128,
+        );
+        let (n_lo, n, n_hi) = (
+            self.positions[i - 1] as i128,
+            self.positions[i] as i128,
+            self.positions[i + 1] as i128,
+        );
+        let d = d as i128;
+        let upper = (n - n_lo + d).checked_mul(q_hi - q)? / (n_hi - n);
+        let lower = (n_hi - n - d).checked_mul(q - q_lo)? / (n - n_lo);
+        i64::try_from(q + d * (upper + lower) / (n_hi - n_lo)).ok()
+    }
+    // Linear prediction of the height of marker `i` after moving it by `d`.
+    fn linear(&self, i: usize, d: i64) -> i64 {
+        let j = (i as i64 + d) as usize;
+        let q = self.height(i) as i128;
+        let dq = self.height(j) as i128 - q;
+        let dn = self.positions[j] as i128 - self.positions[i] as i128;
+        (q + d as i128 * dq / dn) as i64
+    }
+}
@@ -0,0 +1,155 @@
+use core::marker::PhantomData;
+
+use crate::cordic::{cos_sin_turns_internal, FRAC};
+use crate::raw::{from_raw, shift_round, signed_bits};
+use crate::*;
+
+/// The curve of a pan law or crossfade, as a function of the position `p` in [0, 1].  Both
+/// laws are constant-power: the squares of the two gains always sum to 1.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PanLaw {
+    /// Gains `cos(p π/2)` and `sin(p π/2)`: the usual -3 dB equal-power pan law.
+    Sine,
+    /// Gains `sqrt(1 - p)` and `sqrt(p)`, which are also -3 dB at the center, but change
+    /// faster near the ends.
+    Sqrt,
+}
+
+/// Return the rising gain `g(i / (n - 1))` of the pan law with the given shift, rounded to
+/// nearest.  Requires `0 < shift <= 31` and `i < n`.
+const fn gain_raw(law: PanLaw, i: usize, n: usize, shift: i32) -> i128 {
+    if n == 1 {
+        // A single position is the center.
+        return gain_raw(law, 1, 3, shift);
+    }
+    let one = 1 << shift;
+    let raw = match law {
+        PanLaw::Sine => {
+            // p / 4 turns.
+            let turns = ((i as i128) << FRAC) / (4 * (n as i128 - 1));
+            let (_, sin) = cos_sin_turns_internal(turns, FRAC as i32);
+            shift_round(sin, shift - FRAC as i32).unwrap()
+        }
+        PanLaw::Sqrt => {
+            // sqrt(4 p 2^(2 shift)) = 2 sqrt(p) 2^shift, rounded down, then halved and
+            // rounded to nearest.
+            let twice = (((i as u128) << (2 * shift + 2)) / (n as u128 - 1)).isqrt();
+            ((twice + 1) >> 1) as i128
+        }
+    };
+    if raw > one {
+        one
+    } else {
+        raw
+    }
+}
+
+/// A table of `N` gains of a constant-power pan law, for positions from hard left (0) to
+/// hard right (`N - 1`), with the center at `(N - 1) / 2` for odd `N`.
+///
+/// Tables are computed at compile time for unsigned gain types with a shift of at most 31
+/// which can represent 1, e.g. `U16<16, 15>` (Q15) or `U32<32, 31>` (Q31), as in
+/// `const PAN: PanTable<Q15, 129> = PanTable::<Q15, 129>::new(PanLaw::Sine);`.
+///
+/// The same table serves as a crossfade curve, with position 0 at the start of the
+/// crossfade and `N - 1` at the end.  Since the gains never exceed 1, panning a sample
+/// returns the same type, rounded to nearest.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct PanTable<T: Num, const N: usize> {
+    // The rising gains g(i / (N - 1)); the falling gains are the same table reversed.
+    gains: [T; N],
+}
+
+macro_rules! pan_impl {
+    ($Name:ident, $T:ty) => {
+        impl<const BITS: u32, const SHIFT: i32, const N: usize> PanTable<$Name<BITS, SHIFT>, N> {
+            /// Compute the table of the pan law `law`.
+            pub const fn new(law: PanLaw) -> Self {
+                assert!(N > 0, "pan table must have at least one position");
+                assert!(
+                    SHIFT > 0 && SHIFT <= 31 && BITS as i32 > SHIFT,
+                    "gain type must represent [0, 1] with a shift of at most 31"
+                );
+                let mut gains = [$Name::<BITS, SHIFT>::new_const(0); N];
+                let mut i = 0;
+                while i < N {
+                    gains[i] = $Name::new_const(gain_raw(law, i, N, SHIFT) as $T);
+                    i += 1;
+                }
+                Self { gains }
+            }
+        }
+    };
+}
+
+pan_impl!(U8, u8);
+pan_impl!(U16, u16);
+pan_impl!(U32, u32);
+
+struct Product<T, X>(PhantomData<(T, X)>);
+
+impl<T: Num, X: Num> Product<T, X> {
+    /// Compile-time check that products and sums of samples with gains fit in `i128`.
+    const CHECK: () = assert!(
+        signed_bits::<T>() + signed_bits::<X>() < i128::BITS,
+        "too many bits for pan"
+    );
+}
+
+impl<T: Num, const N: usize> PanTable<T, N>
+where
+    T::Raw: Into<i128>,
+{
+    /// Return the gains `(left, right)` at `position`, which must be less than `N`.  For a
+    /// crossfade, these are the gains of the outgoing and the incoming signal.
+    pub fn gains(&self, position: usize) -> (T, T) {
+        assert!(position < N, "pan position out of range");
+        (self.gains[N - 1 - position], self.gains[position])
+    }
+    /// Return the products of `sample` with both gains at `position`, with the shift of
+    /// `T * X`.
+    fn products<X: Num>(&self, sample: (X, X), position: usize) -> (i128, i128)
+    where
+        X::Raw: Into<i128>,
+    {
+        let () = Product::<T, X>::CHECK;
+        let (left, right) = self.gains(position);
+        (
+            sample.0.raw().into() * left.raw().into(),
+            sample.1.raw().into() * right.raw().into(),
+        )
+    }
+    /// Pan a mono sample to the stereo pair `(left, right)` at `position`, rounded to
+    /// nearest.
+    pub fn pan<X: Num>(&self, sample: X, position: usize) -> (X, X)
+    where
+        X::Raw: Into<i128> + TryFrom<i128>,
+    {
+        let (left, right) = self.products((sample, sample), position);
+        let round = |val| from_raw(Ok(shift_round(val, -T::SHIFT).unwrap())).unwrap();
+        (round(left), round(right))
+    }
+    /// Crossfade from `from` to `to`: return `from * left + to * right` at `position`,
+    /// rounded to the nearest value of the type `Out`, or a `RangeError` if it does not
+    /// fit.  The sum of two correlated signals can reach `sqrt(2)` times their amplitude at
+    /// the center, so `Out` should have one more integer bit than `X`.
+    pub fn crossfade<X: Num, Out: Num>(
+        &self,
+        from: X,
+        to: X,
+        position: usize,
+    ) -> Result<Out, RangeError>
+    where
+        X::Raw: Into<i128>,
+        Out::Raw: TryFrom<i128>,
+    {
+        let (from, to) = self.products((from, to), position);
+        let sum = from + to;
+        let err = if sum < 0 {
+            RangeError::TooSmall
+        } else {
+            RangeError::TooLarge
+        };
+        from_raw(shift_round(sum, Out::SHIFT - X::SHIFT - T::SHIFT).ok_or(err))
+    }
+}
@@ -0,0 +1,157 @@
+//! Conversions between fixed-point numbers and 16- and 32-bit posits.
+//!
+//! Posits are encoded with the same parameters as the SoftPosit library: `Posit16` has one
+//! exponent bit and `Posit32` has two exponent bits.  The codec is built in and uses only
+//! integer arithmetic.
+//!
+//! Conversion to a posit rounds to the nearest posit (ties to even) and, following the posit
+//! rules, never rounds a nonzero value to zero or a finite value to NaR: values beyond the
+//! range of the posit saturate to `MINPOS` or `MAXPOS`.  Conversion from a posit rounds to
+//! the nearest value of the output type (ties away from zero), and returns a `RangeError`
+//! if the result does not fit.
+
+use crate::{Num, RangeError};
+
+/// Encode `(-1)^negative * magnitude * 2^exp` as an `n`-bit posit with `es` exponent bits.
+/// `magnitude` must be nonzero.
+fn encode(negative: bool, magnitude: u128, exp: i32, n: u32, es: u32) -> u32 {
+    let msb = 127 - magnitude.leading_zeros();
+    // The value lies in [2^scale, 2^(scale + 1)).
+    let scale = exp + msb as i32;
+    let k = scale >> es;
+    let regime_len = if k >= 0 { k + 2 } else { 1 - k } as u32;
+    let maxpos = (1u32 << (n - 1)) - 1;
+    let bits = if regime_len > n - 1 {
+        // Posits saturate rather than overflowing to NaR or underflowing to zero.
+        if k >= 0 {
+            maxpos
+        } else {
+            1
+        }
+    } else {
+        // The 64 most significant fraction bits (after the hidden bit), and a sticky bit for
+        // any fraction bits below those.
+        let aligned = magnitude << (127 - msb) << 1;
+        let (frac, mut sticky) = ((aligned >> 64) as u64, aligned as u64 != 0);
+        let regime: u128 = if k >= 0 { ((1 << (k + 1)) - 1) << 1 } else { 1 };
+        let body = (((regime << es) | (scale - (k << es)) as u128) << 64) | frac as u128;
+        let len = regime_len + es + 64;
+        // Round the body to the `n - 1` bits which follow the sign bit.
+        let shift = len - (n - 1);
+        let mut bits = (body >> shift) as u32;
+        let round = (body >> (shift - 1)) & 1 != 0;
+        sticky |= body & ((1 << (shift - 1)) - 1) != 0;
+        if round && (sticky || bits & 1 != 0) {
+            bits += 1;
+        }
+        bits.min(maxpos)
+    };
+    if negative {
+        bits.wrapping_neg() & (u32::MAX >> (32 - n))
+    } else {
+        bits
+    }
+}
+
+/// Decode an `n`-bit posit with `es` exponent bits as `(negative, mantissa, exp)`, where the
+/// value is `(-1)^negative * mantissa * 2^exp`.  Return `None` for NaR.
+fn decode(bits: u32, n: u32, es: u32) -> Option<(bool, u64, i32)> {
+    let mask = u32::MAX >> (32 - n);
+    let bits = bits & mask;
+    if bits == 0 {
+        return Some((false, 0, 0));
+    } else if bits == 1 << (n - 1) {
+        return None;
+    }
+    let negative = bits >> (n - 1) != 0;
+    let magnitude = if negative {
+        bits.wrapping_neg() & mask
+    } else {
+        bits
+    };
+    // The `n - 1` bits after the sign bit, aligned to the top of a u64.
+    let body = (magnitude as u64) << (65 - n);
+    let regime_bit = body >> 63 != 0;
+    let run = if regime_bit {
+        body.leading_ones()
+    } else {
+        body.leading_zeros()
+    }
+    .min(n - 1);
+    let k = if regime_bit {
+        run as i32 - 1
+    } else {
+        -(run as i32)
+    };
+    // Skip the regime and its terminating bit; missing exponent bits are zero.
+    let rest = body.checked_shl(run + 1).unwrap_or(0);
+    let exp = (rest >> (64 - es)) as i32;
+    let frac = rest << es;
+    let scale = (k << es) + exp;
+    Some((negative, (1 << 63) | (frac >> 1), scale - 63))
+}
+
+macro_rules! posit_impl {
+    ($Name:ident, $T:ty, $ES:expr) => {
+        #[doc = concat!("A ", stringify!($T), "-sized posit with ", stringify!($ES), " exponent bit(s).")]
+        #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+        pub struct $Name($T);
+
+        impl $Name {
+            /// Zero.
+            pub const ZERO: Self = Self(0);
+            /// Not a Real, the single exceptional value of a posit.
+            pub const NAR: Self = Self(1 << (<$T>::BITS - 1));
+            /// Smallest positive posit.
+            pub const MINPOS: Self = Self(1);
+            /// Largest positive posit.
+            pub const MAXPOS: Self = Self(<$T>::MAX >> 1);
+            /// Interpret the provided bit pattern as a posit.
+            pub const fn from_bits(bits: $T) -> Self {
+                Self(bits)
+            }
+            /// Return the bit pattern of this posit.
+            pub const fn to_bits(self) -> $T {
+                self.0
+            }
+            /// Return the posit nearest to the logical value of `val`.
+            pub fn from_fp<T: Num>(val: T) -> Self
+            where
+                T::Raw: Into<i128>,
+            {
+                let raw: i128 = val.raw().into();
+                if raw == 0 {
+                    Self::ZERO
+                } else {
+                    let bits = encode(raw < 0, raw.unsigned_abs(), -T::SHIFT, <$T>::BITS, $ES);
+                    Self(bits as $T)
+                }
+            }
+            /// Return the value of type `Out` nearest to this posit, or a `RangeError` if it
+            /// does not fit in `Out`.  Panics if this posit is NaR.
+            pub fn into_fp<Out: Num>(self) -> Result<Out, RangeError>
+            where
+                Out::Raw: TryFrom<i128>,
+            {
+                let (negative, mantissa, exp) =
+                    decode(self.0 as u32, <$T>::BITS, $ES).expect("posit is NaR");
+                let err = if negative { RangeError::TooSmall } else { RangeError::TooLarge };
+                let shift = exp + Out::SHIFT;
+                let magnitude = if mantissa == 0 || shift < -64 {
+                    0
+                } else if shift < 0 {
+                    ((mantissa >> (-shift - 1)) as i128 + 1) >> 1
+                } else if shift < 64 {
+                    (mantissa as i128) << shift
+                } else {
+                    return Err(err);
+                };
+                let raw = if negative { -magnitude } else { magnitude };
+                Out::new(raw.try_into().map_err(|_| err)?)
+            }
+        }
+    };
+}
+
+posit_impl!(Posit16, u16, 1);
+posit_impl!(Posit32, u32, 2);
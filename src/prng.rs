@@ -0,0 +1,153 @@
+use core::marker::PhantomData;
+
+use crate::raw::from_raw;
+use crate::Num;
+
+/// A pseudorandom number generator producing fixed-point values, using only integer
+/// operations.  These generators are small and fast, and intended for dithering, jitter
+/// injection and test-signal generation; they are not suitable for cryptography.
+///
+/// Implementors only provide `next_u32`; values of any fixed-point type are built from it.
+pub trait Prng {
+    /// Return 32 uniformly distributed random bits.
+    fn next_u32(&mut self) -> u32;
+    /// Return 64 uniformly distributed random bits.
+    fn next_u64(&mut self) -> u64 {
+        (self.next_u32() as u64) << 32 | self.next_u32() as u64
+    }
+    /// Return 128 uniformly distributed random bits.
+    fn next_u128(&mut self) -> u128 {
+        (self.next_u64() as u128) << 64 | self.next_u64() as u128
+    }
+    /// Return `bits` uniformly distributed random bits (at most 128), drawing only as many
+    /// 32-bit words as needed.
+    fn next_bits(&mut self, bits: u32) -> u128 {
+        match bits {
+            0 => 0,
+            1..=32 => (self.next_u32() >> (32 - bits)) as u128,
+            33..=64 => (self.next_u64() >> (64 - bits)) as u128,
+            _ => self.next_u128() >> (128 - bits),
+        }
+    }
+    /// Return a value uniformly distributed over every value of the type `T`, from
+    /// `T::MIN` to `T::MAX` inclusive.
+    fn next<T: Num>(&mut self) -> T
+    where
+        T::Raw: Into<i128> + TryFrom<i128>,
+    {
+        let min: i128 = T::MIN.raw().into();
+        let max: i128 = T::MAX.raw().into();
+        let span = max.abs_diff(min);
+        // Rejection sampling from the smallest power-of-two range which contains the span,
+        // which is unbiased and needs fewer than two draws on average.
+        let bits = u128::BITS - span.leading_zeros();
+        let offset = loop {
+            let candidate = self.next_bits(bits);
+            if candidate <= span {
+                break candidate;
+            }
+        };
+        from_raw(Ok(min.wrapping_add(offset as i128))).unwrap()
+    }
+    /// Return a value uniformly distributed over the unit interval [0, 1), with the
+    /// resolution of the type `T`.  `T` must be able to represent [0, 1) (checked at compile
+    /// time).
+    fn next_unit<T: Num>(&mut self) -> T
+    where
+        T::Raw: TryFrom<i128>,
+    {
+        let () = UnitInterval::<T>::CHECK;
+        from_raw(Ok(self.next_bits(T::SHIFT as u32) as i128)).unwrap()
+    }
+}
+
+struct UnitInterval<T>(PhantomData<T>);
+
+impl<T: Num> UnitInterval<T> {
+    /// Compile-time check that the type `T` can represent every value in [0, 1).
+    const CHECK: () = assert!(
+        T::SHIFT >= 0 && T::SHIFT <= 127 && T::BITS as i32 - T::SIGNED as i32 >= T::SHIFT,
+        "type must represent [0, 1) with a shift of at most 127"
+    );
+}
+
+/// Return the next output of the SplitMix64 generator, which is used to expand seeds.
+fn splitmix64(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+/// The xoshiro128++ generator: 128 bits of state, a period of `2^128 - 1`, and excellent
+/// statistical quality, using only 32-bit additions, shifts, rotations and XORs.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Xoshiro128 {
+    state: [u32; 4],
+}
+
+impl Xoshiro128 {
+    /// Create a generator from a 64-bit seed.  Every seed (including zero) gives a valid,
+    /// well-mixed initial state.
+    pub fn new(seed: u64) -> Self {
+        let mut mix = seed;
+        let (a, b) = (splitmix64(&mut mix), splitmix64(&mut mix));
+        Self {
+            state: [a as u32, (a >> 32) as u32, b as u32, (b >> 32) as u32],
+        }
+    }
+}
+
+impl Prng for Xoshiro128 {
+    fn next_u32(&mut self) -> u32 {
+        let s = &mut self.state;
+        let result = s[0].wrapping_add(s[3]).rotate_left(7).wrapping_add(s[0]);
+        let t = s[1] << 9;
+        s[2] ^= s[0];
+        s[3] ^= s[1];
+        s[1] ^= s[2];
+        s[0] ^= s[3];
+        s[2] ^= t;
+        s[3] = s[3].rotate_left(11);
+        result
+    }
+}
+
+/// A 32-bit maximal-length Galois linear-feedback shift register, with the feedback
+/// polynomial `x^32 + x^22 + x^2 + x + 1` and a period of `2^32 - 1`.
+///
+/// This is the smallest possible generator (one word of state), and each output bit costs
+/// only a shift and a conditional XOR, but its outputs are correlated and fail statistical
+/// tests.  It is adequate for dithering, and matches the LFSRs commonly built in hardware.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Lfsr32 {
+    state: u32,
+}
+
+impl Lfsr32 {
+    /// Feedback taps of the polynomial `x^32 + x^22 + x^2 + x + 1`.
+    const TAPS: u32 = 0x8020_0003;
+    /// Create a generator from a seed.  The all-zero state is a fixed point of the LFSR, so
+    /// a zero seed is replaced by all ones.
+    pub fn new(seed: u32) -> Self {
+        Self {
+            state: if seed == 0 { u32::MAX } else { seed },
+        }
+    }
+    /// Advance the register by one step, and return the output bit.
+    pub fn next_bit(&mut self) -> bool {
+        let bit = self.state & 1 != 0;
+        self.state >>= 1;
+        if bit {
+            self.state ^= Self::TAPS;
+        }
+        bit
+    }
+}
+
+impl Prng for Lfsr32 {
+    fn next_u32(&mut self) -> u32 {
+        (0..32).fold(0, |word, _| word << 1 | self.next_bit() as u32)
+    }
+}
@@ -0,0 +1,191 @@
+use core::marker::PhantomData;
+
+use crate::cordic::{exp2_raw, log2_raw};
+use crate::raw::{from_raw, shift_round, signed_bits};
+use crate::{Num, RangeError};
+
+/// A probability in [0, 1], stored as a fixed-point number of type `T`.
+///
+/// `T` must be able to represent 1, and must have a `SHIFT` between 0 and 62 (checked at
+/// compile time).  Operations which combine probabilities are generic over the output type,
+/// so the result can be requantized to any other probability type; all results are rounded
+/// to the nearest value of the output type.
+///
+/// Log-odds are expressed in base 2 (i.e. in bits), which makes them cheap to compute with
+/// the base-2 logarithm and exponential functions.
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub struct Prob<T: Num>(T);
+
+struct ProbRange<T>(PhantomData<T>);
+
+struct LerpRange<T, U>(PhantomData<(T, U)>);
+
+impl<T: Num, U: Num> LerpRange<T, U> {
+    /// Compile-time check that interpolation between values of type `U` with a probability
+    /// of type `T` cannot overflow.
+    const CHECK: () = assert!(
+        signed_bits::<U>() as i32 + T::SHIFT < 127,
+        "too many bits for lerp"
+    );
+}
+
+/// Return `(a << frac) + t (b - a)`, i.e. the linear interpolation from `a` to `b` at
+/// `t / 2^frac`, with `frac` more fractional bits than `a` and `b` (and without rounding).
+pub(crate) fn lerp_raw(a: i128, b: i128, t: i128, frac: i32) -> i128 {
+    (a << frac) + t * (b - a)
+}
+
+impl<T: Num> ProbRange<T> {
+    /// Compile-time check that the type `T` can represent every probability.
+    const CHECK: () = assert!(
+        T::SHIFT >= 0 && T::SHIFT <= 62 && T::BITS as i32 - T::SIGNED as i32 > T::SHIFT,
+        "probability type must represent [0, 1] with a shift of at most 62"
+    );
+}
+
+impl<T: Num> Prob<T>
+where
+    T::Raw: Into<i128> + TryFrom<i128>,
+{
+    fn raw(self) -> i128 {
+        self.0.raw().into()
+    }
+    /// Raw value of 1.
+    fn one_raw() -> i128 {
+        let () = ProbRange::<T>::CHECK;
+        1 << T::SHIFT
+    }
+    /// Return the probability with the raw value `raw` and shift `shift`, rounded to
+    /// nearest.  The value must lie in [0, 1].
+    pub(crate) fn from_raw_shift(raw: i128, shift: i32) -> Self {
+        let () = ProbRange::<T>::CHECK;
+        let raw = shift_round(raw, T::SHIFT - shift).unwrap();
+        debug_assert!(raw >= 0 && raw <= Self::one_raw());
+        Self(from_raw(Ok(raw)).unwrap())
+    }
+    /// Return the probability `val`, or a `RangeError` if it lies outside [0, 1].
+    pub fn new(val: T) -> Result<Self, RangeError> {
+        let raw: i128 = val.raw().into();
+        if raw < 0 {
+            Err(RangeError::TooSmall)
+        } else if raw > Self::one_raw() {
+            Err(RangeError::TooLarge)
+        } else {
+            Ok(Self(val))
+        }
+    }
+    /// The impossible event.
+    pub fn zero() -> Self {
+        Self::from_raw_shift(0, 0)
+    }
+    /// The certain event.
+    pub fn one() -> Self {
+        Self::from_raw_shift(1, 0)
+    }
+    /// Return the probability as a fixed-point number.
+    pub fn get(self) -> T {
+        self.0
+    }
+    /// Return this probability, rounded to the nearest value of the type `Out`.
+    pub fn requantize<Out: Num>(self) -> Prob<Out>
+    where
+        Out::Raw: Into<i128> + TryFrom<i128>,
+    {
+        Prob::from_raw_shift(self.raw(), T::SHIFT)
+    }
+    /// Return the probability of the complementary event, `1 - p`.  This is exact.
+    pub fn complement(self) -> Self {
+        Self::from_raw_shift(Self::one_raw() - self.raw(), T::SHIFT)
+    }
+    /// Return the probability that both of two independent events occur, `p q`.
+    pub fn and<U: Num, Out: Num>(self, other: Prob<U>) -> Prob<Out>
+    where
+        U::Raw: Into<i128> + TryFrom<i128>,
+        Out::Raw: Into<i128> + TryFrom<i128>,
+    {
+        let product = self.raw() * other.raw();
+        Prob::from_raw_shift(product, T::SHIFT + U::SHIFT)
+    }
+    /// Return the probability that either of two independent events occurs,
+    /// `1 - (1 - p) (1 - q)`.
+    pub fn or<U: Num, Out: Num>(self, other: Prob<U>) -> Prob<Out>
+    where
+        U::Raw: Into<i128> + TryFrom<i128>,
+        Out::Raw: Into<i128> + TryFrom<i128>,
+    {
+        let neither = self.complement().raw() * other.complement().raw();
+        let one = Self::one_raw() << U::SHIFT;
+        Prob::from_raw_shift(one - neither, T::SHIFT + U::SHIFT)
+    }
+    /// Linear interpolation `a + p (b - a)`, from `a` (for `p = 0`) to `b` (for `p = 1`),
+    /// rounded to nearest.  The result lies between `a` and `b`, so it cannot overflow.
+    pub fn lerp<U: Num>(self, a: U, b: U) -> U
+    where
+        U::Raw: Into<i128> + TryFrom<i128>,
+    {
+        let () = LerpRange::<T, U>::CHECK;
+        let raw = lerp_raw(a.raw().into(), b.raw().into(), self.raw(), T::SHIFT);
+        from_raw(Ok(shift_round(raw, -T::SHIFT).unwrap())).unwrap()
+    }
+    /// Bayesian update of a binary state: return the posterior probability of the state,
+    /// where `self` is the prior probability and `likelihood` is the probability of the
+    /// observation if the state is true, relative to the probability of the observation
+    /// either way.  That is, `p l / (p l + (1 - p) (1 - l))`.
+    ///
+    /// Return `None` if the prior and the observation are certain and contradictory.
+    pub fn update<U: Num, Out: Num>(self, likelihood: Prob<U>) -> Option<Prob<Out>>
+    where
+        U::Raw: Into<i128> + TryFrom<i128>,
+        Out::Raw: Into<i128> + TryFrom<i128>,
+    {
+        let mut num = self.raw() * likelihood.raw();
+        let against = self.complement().raw() * likelihood.complement().raw();
+        let mut den = num + against;
+        if den == 0 {
+            return None;
+        }
+        // Keep 64 significant bits of the denominator, so that the division cannot overflow.
+        let excess = (64 - den.leading_zeros() as i32).max(0);
+        num >>= excess;
+        den >>= excess;
+        let raw = ((num << Out::SHIFT) + den / 2) / den;
+        Some(Prob::from_raw_shift(raw, Out::SHIFT))
+    }
+    /// Return the base-2 log-odds `log2(p / (1 - p))`, rounded to the nearest value of the
+    /// type `Out`.  Return `RangeError::TooSmall` for 0 and `RangeError::TooLarge` for 1,
+    /// whose log-odds are infinite, or if the result does not fit in `Out`.
+    pub fn log_odds<Out: Num>(self) -> Result<Out, RangeError>
+    where
+        Out::Raw: TryFrom<i128>,
+    {
+        const FRAC: i32 = 60;
+        let raw = self.raw();
+        if raw == 0 {
+            return Err(RangeError::TooSmall);
+        } else if raw == Self::one_raw() {
+            return Err(RangeError::TooLarge);
+        }
+        let log_odds =
+            log2_raw(raw, T::SHIFT, FRAC)? - log2_raw(Self::one_raw() - raw, T::SHIFT, FRAC)?;
+        let err = if log_odds < 0 {
+            RangeError::TooSmall
+        } else {
+            RangeError::TooLarge
+        };
+        from_raw(shift_round(log_odds, Out::SHIFT - FRAC).ok_or(err))
+    }
+    /// Return the probability with the base-2 log-odds `val`, i.e. `1 / (1 + 2^-val)`.
+    pub fn from_log_odds<U: Num>(val: U) -> Self
+    where
+        U::Raw: Into<i128>,
+    {
+        const FRAC: i32 = 62;
+        let val: i128 = val.raw().into();
+        // 2^-|val|, which lies in (0, 1].
+        let small = exp2_raw(-val.abs(), U::SHIFT, FRAC).unwrap();
+        let num = if val >= 0 { 1 << FRAC } else { small };
+        let den = (1 << FRAC) + small;
+        let raw = ((num << T::SHIFT) + den / 2) / den;
+        Self::from_raw_shift(raw, T::SHIFT)
+    }
+}
@@ -0,0 +1,327 @@
+//! Python bindings, enabled by the `python` feature, for prototyping against the exact
+//! bit-true implementation used in firmware.
+//!
+//! Python has no const generics, so the bindings provide a single class `fp.Fixed`, which
+//! carries its raw value along with its `bits`, `shift` and signedness at runtime.  Raw
+//! values are stored as `i128`, so signed values may have up to 128 bits and unsigned
+//! values up to 127 bits.  Arithmetic follows the same bit-growth rules as the Rust types,
+//! and the elementary functions use the same CORDIC kernels as [`crate::cordic`].
+//!
+//! Only `Fixed` and the scalar elementary functions are exposed.  The DSP blocks, such as
+//! the filters, [`crate::Histogram`], [`crate::P2Quantile`] and
+//! [`crate::LinearRegression`], are generic over their const formats, so they have no
+//! binding.
+//!
+//! The library itself is built as an rlib only, so that dependents don't pay for a
+//! `cdylib`.  Build the extension module by asking for one explicitly, e.g.
+//! `cargo rustc --release --lib --features python --crate-type cdylib`.
+
+use pyo3::exceptions::{PyOverflowError, PyTypeError, PyValueError};
+use pyo3::prelude::*;
+
+use crate::float::{f64_from_raw, f64_into_parts, from_parts_in};
+use crate::num_impl::runtime_limits;
+use crate::{cordic, RangeError};
+
+/// A fixed-point number whose format is known at runtime.
+#[pyclass(name = "Fixed", module = "fp", frozen, eq, hash)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct PyFixed {
+    raw: i128,
+    bits: u32,
+    shift: i32,
+    signed: bool,
+}
+
+fn range_error(err: RangeError) -> PyErr {
+    match err {
+        RangeError::TooSmall => PyOverflowError::new_err("value too small for format"),
+        RangeError::TooLarge => PyOverflowError::new_err("value too large for format"),
+    }
+}
+
+fn too_many_bits() -> PyErr {
+    PyValueError::new_err("too many bits")
+}
+
+impl PyFixed {
+    /// Return the value with the given format, or an error if the format is unsupported or
+    /// `raw` is out of range.
+    fn with_format(raw: i128, bits: u32, shift: i32, signed: bool) -> PyResult<Self> {
+        if bits > 127 + signed as u32 {
+            return Err(too_many_bits());
+        }
+        let (min, max) = runtime_limits(bits, signed);
+        if raw < min {
+            Err(range_error(RangeError::TooSmall))
+        } else if raw > max {
+            Err(range_error(RangeError::TooLarge))
+        } else {
+            Ok(Self {
+                raw,
+                bits,
+                shift,
+                signed,
+            })
+        }
+    }
+    fn from_result(
+        raw: Result<i128, RangeError>,
+        bits: u32,
+        shift: i32,
+        signed: bool,
+    ) -> PyResult<Self> {
+        Self::with_format(raw.map_err(range_error)?, bits, shift, signed)
+    }
+    /// Check that `self` can only represent values in [-1, 1).
+    fn check_unit_range(&self) -> PyResult<()> {
+        if self.bits as i64 - self.signed as i64 - self.shift as i64 <= 0 {
+            Ok(())
+        } else {
+            Err(PyValueError::new_err("input must lie in [-1, 1)"))
+        }
+    }
+    /// Check that `self` and `other` have the same shift and signedness, like the operands
+    /// of the Rust arithmetic operators.
+    fn check_compatible(&self, other: &Self) -> PyResult<()> {
+        if self.shift != other.shift {
+            Err(PyTypeError::new_err("operands must have the same shift"))
+        } else if self.signed != other.signed {
+            Err(PyTypeError::new_err(
+                "operands must have the same signedness",
+            ))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[pymethods]
+impl PyFixed {
+    /// Interpret the raw value `raw` as a fixed-point number with the given format.
+    #[new]
+    #[pyo3(signature = (raw, bits, shift, signed = true))]
+    fn new(raw: i128, bits: u32, shift: i32, signed: bool) -> PyResult<Self> {
+        Self::with_format(raw, bits, shift, signed)
+    }
+    /// Return the fixed-point number with the logical value of `value`, rounded toward
+    /// zero.  Raise `OverflowError` if it is out of range.
+    #[staticmethod]
+    #[pyo3(signature = (value, bits, shift, signed = true))]
+    fn from_float(value: f64, bits: u32, shift: i32, signed: bool) -> PyResult<Self> {
+        if !value.is_finite() {
+            return Err(PyValueError::new_err("float is not finite"));
+        }
+        if bits > 127 + signed as u32 {
+            return Err(too_many_bits());
+        }
+        let (negative, mantissa, exp) = f64_into_parts(value);
+        let (min, max) = runtime_limits(bits, signed);
+        let exp = exp.saturating_add(shift);
+        let raw = from_parts_in(negative, mantissa, exp, min, max).map_err(range_error)?;
+        Self::with_format(raw, bits, shift, signed)
+    }
+    #[getter]
+    fn raw(&self) -> i128 {
+        self.raw
+    }
+    #[getter]
+    fn bits(&self) -> u32 {
+        self.bits
+    }
+    #[getter]
+    fn shift(&self) -> i32 {
+        self.shift
+    }
+    #[getter]
+    fn signed(&self) -> bool {
+        self.signed
+    }
+    /// Return the logical value as a float.  Like `Num::into_f64`, this raises `ValueError`
+    /// unless the conversion is guaranteed to be exact for every value of the format.
+    fn __float__(&self) -> PyResult<f64> {
+        if self.bits > f64::MANTISSA_DIGITS {
+            Err(PyValueError::new_err("number could be truncated in f64"))
+        } else if self.shift > f64::MANTISSA_DIGITS as i32 - f64::MIN_EXP {
+            Err(PyValueError::new_err("number could underflow f64"))
+        } else if self.bits as i64 - self.shift as i64 > f64::MAX_EXP as i64 {
+            Err(PyValueError::new_err("number could overflow f64"))
+        } else {
+            Ok(f64_from_raw(self.raw, self.shift))
+        }
+    }
+    /// Return the exact logical value as a decimal string.
+    #[pyo3(name = "to_decimal")]
+    fn decimal_string(&self) -> String {
+        decimal(self.raw, self.shift)
+    }
+    fn __str__(&self) -> String {
+        self.decimal_string()
+    }
+    fn __repr__(&self) -> String {
+        let signed = if self.signed { "True" } else { "False" };
+        format!(
+            "Fixed(raw={}, bits={}, shift={}, signed={})",
+            self.raw, self.bits, self.shift, signed
+        )
+    }
+    /// The sum has 1 more bit than the wider operand.
+    fn __add__(&self, other: &Self) -> PyResult<Self> {
+        self.check_compatible(other)?;
+        let bits = self.bits.max(other.bits) + 1;
+        let raw = self.raw.checked_add(other.raw).ok_or(too_many_bits())?;
+        Self::with_format(raw, bits, self.shift, self.signed)
+    }
+    /// The difference is always signed, and has 1 more bit than the wider operand.
+    fn __sub__(&self, other: &Self) -> PyResult<Self> {
+        self.check_compatible(other)?;
+        let bits = self.bits.max(other.bits) + 1;
+        let raw = self.raw.checked_sub(other.raw).ok_or(too_many_bits())?;
+        Self::with_format(raw, bits, self.shift, true)
+    }
+    /// The negation is always signed, and has 1 more bit than the operand.
+    fn __neg__(&self) -> PyResult<Self> {
+        let raw = self.raw.checked_neg().ok_or(too_many_bits())?;
+        Self::with_format(raw, self.bits + 1, self.shift, true)
+    }
+    /// The product has the sum of the bits and the sum of the shifts of the operands.
+    fn __mul__(&self, other: &Self) -> PyResult<Self> {
+        if self.signed != other.signed {
+            return Err(PyTypeError::new_err(
+                "operands must have the same signedness",
+            ));
+        }
+        let raw = self.raw.checked_mul(other.raw).ok_or(too_many_bits())?;
+        let shift = self.shift.checked_add(other.shift);
+        let shift = shift.ok_or(PyOverflowError::new_err("shift out of range"))?;
+        Self::with_format(raw, self.bits + other.bits, shift, self.signed)
+    }
+}
+
+/// Return the exact decimal representation of `raw * 2^-shift`.
+fn decimal(raw: i128, shift: i32) -> String {
+    let sign = if raw < 0 { "-" } else { "" };
+    let magnitude = raw.unsigned_abs();
+    if shift <= 0 {
+        let mut digits = Decimal::from(magnitude);
+        for _ in 0..shift.unsigned_abs() {
+            digits.mul_small(2);
+        }
+        return format!("{sign}{digits}");
+    }
+    let (int, frac) = if shift < 128 {
+        (magnitude >> shift, magnitude & ((1 << shift) - 1))
+    } else {
+        (0, magnitude)
+    };
+    if frac == 0 {
+        return format!("{sign}{int}");
+    }
+    // frac / 2^shift = frac * 5^shift / 10^shift
+    let mut digits = Decimal::from(frac);
+    for _ in 0..shift {
+        digits.mul_small(5);
+    }
+    let digits = format!("{digits:0>width$}", width = shift as usize);
+    format!("{sign}{int}.{}", digits.trim_end_matches('0'))
+}
+
+/// An arbitrary-precision unsigned integer, stored as little-endian base 10^9 limbs.
+struct Decimal(Vec<u32>);
+
+impl Decimal {
+    const BASE: u64 = 1_000_000_000;
+    fn mul_small(&mut self, factor: u32) {
+        let mut carry = 0;
+        for limb in self.0.iter_mut() {
+            let val = *limb as u64 * factor as u64 + carry;
+            *limb = (val % Self::BASE) as u32;
+            carry = val / Self::BASE;
+        }
+        if carry > 0 {
+            self.0.push(carry as u32);
+        }
+    }
+}
+
+impl From<u128> for Decimal {
+    fn from(mut val: u128) -> Self {
+        let mut limbs = vec![];
+        while val > 0 {
+            limbs.push((val % Self::BASE as u128) as u32);
+            val /= Self::BASE as u128;
+        }
+        Self(limbs)
+    }
+}
+
+impl core::fmt::Display for Decimal {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        let mut s = match self.0.last() {
+            Some(limb) => limb.to_string(),
+            None => "0".into(),
+        };
+        for limb in self.0.iter().rev().skip(1) {
+            s += &format!("{limb:09}");
+        }
+        f.pad(&s)
+    }
+}
+
+/// Hyperbolic sine of an input in [-1, 1), with the result in the given format.
+#[pyfunction(name = "sinh")]
+#[pyo3(signature = (x, bits, shift, signed = true))]
+fn py_sinh(x: PyFixed, bits: u32, shift: i32, signed: bool) -> PyResult<PyFixed> {
+    x.check_unit_range()?;
+    PyFixed::from_result(cordic::sinh_raw(x.raw, x.shift, shift), bits, shift, signed)
+}
+
+/// Hyperbolic cosine of an input in [-1, 1), with the result in the given format.
+#[pyfunction(name = "cosh")]
+#[pyo3(signature = (x, bits, shift, signed = true))]
+fn py_cosh(x: PyFixed, bits: u32, shift: i32, signed: bool) -> PyResult<PyFixed> {
+    x.check_unit_range()?;
+    PyFixed::from_result(cordic::cosh_raw(x.raw, x.shift, shift), bits, shift, signed)
+}
+
+/// Inverse hyperbolic tangent of an input in [-1, 1), with the result in the given format.
+#[pyfunction(name = "atanh")]
+#[pyo3(signature = (x, bits, shift, signed = true))]
+fn py_atanh(x: PyFixed, bits: u32, shift: i32, signed: bool) -> PyResult<PyFixed> {
+    x.check_unit_range()?;
+    PyFixed::from_result(
+        cordic::atanh_raw(x.raw, x.shift, shift),
+        bits,
+        shift,
+        signed,
+    )
+}
+
+/// Exponential function, with the result in the given format.
+#[pyfunction(name = "exp")]
+#[pyo3(signature = (x, bits, shift, signed = true))]
+fn py_exp(x: PyFixed, bits: u32, shift: i32, signed: bool) -> PyResult<PyFixed> {
+    PyFixed::from_result(cordic::exp_raw(x.raw, x.shift, shift), bits, shift, signed)
+}
+
+/// Natural logarithm of a positive input, with the result in the given format.
+#[pyfunction(name = "ln")]
+#[pyo3(signature = (x, bits, shift, signed = true))]
+fn py_ln(x: PyFixed, bits: u32, shift: i32, signed: bool) -> PyResult<PyFixed> {
+    if x.raw <= 0 {
+        return Err(PyValueError::new_err("logarithm of a non-positive number"));
+    }
+    PyFixed::from_result(cordic::ln_raw(x.raw, x.shift, shift), bits, shift, signed)
+}
+
+/// The `fp` Python module.
+#[pymodule]
+pub fn fp(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<PyFixed>()?;
+    m.add_function(wrap_pyfunction!(py_sinh, m)?)?;
+    m.add_function(wrap_pyfunction!(py_cosh, m)?)?;
+    m.add_function(wrap_pyfunction!(py_atanh, m)?)?;
+    m.add_function(wrap_pyfunction!(py_exp, m)?)?;
+    m.add_function(wrap_pyfunction!(py_ln, m)?)?;
+    Ok(())
+}
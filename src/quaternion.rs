@@ -0,0 +1,284 @@
+use core::marker::PhantomData;
+use core::ops::{Add, Mul, Neg, Sub};
+
+use crate::cordic::{atan2_turns_internal, cos_sin_turns_internal, FRAC};
+use crate::mul_div::sqrt_newton_u128;
+use crate::raw::{from_raw, shift_round};
+use crate::{Num, Ratio, Vec3, I16, I32, I64, I8};
+
+/// Number of fractional bits of a `Ratio<u32>`.
+const RATIO_FRAC: u32 = u32::BITS - 1;
+
+struct Unit<Out>(PhantomData<Out>);
+
+impl<Out: Num> Unit<Out> {
+    /// Compile-time check that `Out` holds every value in [-1, 1], with few enough fractional
+    /// bits for `normalize_internal`.
+    const CHECK: () = assert!(
+        Out::SIGNED && Out::SHIFT >= 0 && Out::SHIFT <= 62 && Out::SHIFT + 2 <= Out::BITS as i32,
+        "unit quaternion needs a signed output type which holds 1, with at most 62 fractional bits"
+    );
+}
+
+/// Return the components `c` divided by their norm, with `frac` fractional bits (at most 62)
+/// and rounded to nearest.  The scale of `c` does not matter.  Panics if `c` is zero.
+fn normalize_internal(c: [i128; 4], frac: u32) -> [i128; 4] {
+    let max = c.iter().map(|x| x.unsigned_abs()).max().unwrap();
+    assert!(max > 0, "cannot normalize the zero quaternion");
+    // Scale the largest component to 59 bits, so that the sum of squares (and 64 times it)
+    // fits in `u128`.
+    let bits = 128 - max.leading_zeros() as i32;
+    let c = c.map(|x| shift_round(x, 59 - bits).unwrap());
+    let sum: u128 = c.iter().map(|x| x.unsigned_abs().pow(2)).sum();
+    // The norm times 8, which is at least 2^62.
+    let root = sqrt_newton_u128(sum << 6) as i128;
+    c.map(|x| ((x << (frac + 4)) + root).div_euclid(2 * root))
+}
+
+/// Return the spherical linear interpolation from `a` (when `t` is zero) to `b` (when `t` is
+/// one), with `frac` fractional bits, where `t` has `RATIO_FRAC` fractional bits.
+fn slerp_internal(a: [i128; 4], b: [i128; 4], t: i128, frac: u32) -> [i128; 4] {
+    let one = 1 << FRAC;
+    let a = normalize_internal(a, FRAC);
+    let b = normalize_internal(b, FRAC);
+    let mut dot: i128 = a.iter().zip(b).map(|(a, b)| (a * b) >> FRAC).sum();
+    // `b` and `-b` are the same rotation: take the shorter path.
+    let b = if dot < 0 {
+        dot = -dot;
+        b.map(|x| -x)
+    } else {
+        b
+    };
+    // The angle between `a` and `b` in turns, from the components of `b` along and
+    // perpendicular to `a`.
+    let perp: u128 = (0..4)
+        .map(|i| (b[i] - ((dot * a[i]) >> FRAC)).pow(2) as u128)
+        .sum();
+    let angle = atan2_turns_internal(sqrt_newton_u128(perp) as i128, dot);
+    let t = t << (FRAC - RATIO_FRAC);
+    let (wa, wb) = if angle < one >> 24 {
+        // The angle is too small for its sines to be accurate, but linear interpolation is.
+        (one - t, t)
+    } else {
+        let angle_b = (angle * t) >> FRAC;
+        let (_, wa) = cos_sin_turns_internal(angle - angle_b, FRAC as i32);
+        let (_, wb) = cos_sin_turns_internal(angle_b, FRAC as i32);
+        (wa, wb)
+    };
+    // The weights should be divided by the sine of the angle, but normalization does that.
+    normalize_internal(
+        core::array::from_fn(|i| (wa * a[i] + wb * b[i]) >> FRAC),
+        frac,
+    )
+}
+
+/// A quaternion `w + x i + y j + z k` with components of type `N` (usually a signed
+/// fixed-point number), e.g. the orientation of an IMU.
+///
+/// As for [`Complex`](crate::Complex), the operators act on the components with the
+/// operators of `N`, so the output types account for bit growth: `+` and `-` add 1 bit, and
+/// each component of the Hamilton product is a sum of four products, so it has 2 more bits
+/// than a product of the components.  For raw types of up to 32 bits, unit quaternions
+/// (i.e. rotations) may also be normalized, applied to a [`Vec3`], and interpolated with
+/// `slerp`, which use integer square roots and CORDIC rather than floating point.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Quaternion<N> {
+    w: N,
+    x: N,
+    y: N,
+    z: N,
+}
+
+impl<N> Quaternion<N> {
+    /// Create the quaternion `w + x i + y j + z k`.
+    pub const fn new(w: N, x: N, y: N, z: N) -> Self {
+        Self { w, x, y, z }
+    }
+}
+
+impl<N: Copy> Quaternion<N> {
+    /// Return the real (scalar) component.
+    pub fn w(self) -> N {
+        self.w
+    }
+    /// Return the `i` component.
+    pub fn x(self) -> N {
+        self.x
+    }
+    /// Return the `j` component.
+    pub fn y(self) -> N {
+        self.y
+    }
+    /// Return the `k` component.
+    pub fn z(self) -> N {
+        self.z
+    }
+}
+
+/// Quaternions may be added, if their components may be added.
+impl<T0: Add<T1>, T1> Add<Quaternion<T1>> for Quaternion<T0> {
+    type Output = Quaternion<T0::Output>;
+    fn add(self, other: Quaternion<T1>) -> Self::Output {
+        Quaternion::new(
+            self.w + other.w,
+            self.x + other.x,
+            self.y + other.y,
+            self.z + other.z,
+        )
+    }
+}
+
+/// Quaternions may be subtracted, if their components may be subtracted.
+impl<T0: Sub<T1>, T1> Sub<Quaternion<T1>> for Quaternion<T0> {
+    type Output = Quaternion<T0::Output>;
+    fn sub(self, other: Quaternion<T1>) -> Self::Output {
+        Quaternion::new(
+            self.w - other.w,
+            self.x - other.x,
+            self.y - other.y,
+            self.z - other.z,
+        )
+    }
+}
+
+/// A quaternion may be negated, if its components may be negated.
+impl<T: Neg> Neg for Quaternion<T> {
+    type Output = Quaternion<T::Output>;
+    fn neg(self) -> Self::Output {
+        Quaternion::new(-self.w, -self.x, -self.y, -self.z)
+    }
+}
+
+/// The Hamilton product, if the components may be multiplied (with a signed product).
+/// Each component of the result is a sum of four products, so it has 2 more bits than a
+/// product of the components.  For rotations, `q0 * q1` applies `q1` first.
+impl<T0, T1, P> Mul<Quaternion<T1>> for Quaternion<T0>
+where
+    T0: Mul<T1, Output = P> + Copy,
+    T1: Copy,
+    P: Num,
+    [(); (P::BITS + 2) as usize]:,
+    [(); P::SHIFT as usize]:,
+{
+    type Output = Quaternion<P::Output<{ P::BITS + 2 }, { P::SHIFT }>>;
+    fn mul(self, other: Quaternion<T1>) -> Self::Output {
+        let p = |a: T0, b: T1| (a * b).raw();
+        let (a, b) = (self, other);
+        let w = p(a.w, b.w) - p(a.x, b.x) - p(a.y, b.y) - p(a.z, b.z);
+        let x = p(a.w, b.x) + p(a.x, b.w) + p(a.y, b.z) - p(a.z, b.y);
+        let y = p(a.w, b.y) - p(a.x, b.z) + p(a.y, b.w) + p(a.z, b.x);
+        let z = p(a.w, b.z) + p(a.x, b.y) - p(a.y, b.x) + p(a.z, b.w);
+        // Each sum of four products fits in the output, whose bits are checked at compile
+        // time.
+        unsafe {
+            Quaternion::new(
+                Num::new_unchecked(w),
+                Num::new_unchecked(x),
+                Num::new_unchecked(y),
+                Num::new_unchecked(z),
+            )
+        }
+    }
+}
+
+macro_rules! quaternion_impl {
+    ($Iname:ident) => {
+        impl<const B: u32, const S: i32> Quaternion<$Iname<B, S>> {
+            /// Return the conjugate `w - x i - y j - z k`, which is the inverse rotation of a
+            /// unit quaternion.  The components have 1 more bit, like negation.
+            pub fn conj(self) -> Quaternion<$Iname<{ B + 1 }, S>>
+            where
+                [(); (B + 1) as usize]:,
+            {
+                let w = unsafe { $Iname::new_unchecked(self.w.raw()) };
+                Quaternion::new(w, -self.x, -self.y, -self.z)
+            }
+        }
+    };
+}
+
+quaternion_impl!(I8);
+quaternion_impl!(I16);
+quaternion_impl!(I32);
+quaternion_impl!(I64);
+
+macro_rules! unit_quaternion_impl {
+    ($Iname:ident) => {
+        impl<const B: u32, const S: i32> Quaternion<$Iname<B, S>> {
+            /// Return the components as `i128`.
+            fn raw_components(self) -> [i128; 4] {
+                [self.w, self.x, self.y, self.z].map(|c| c.raw().into())
+            }
+            /// Return the unit quaternion in the same direction, i.e. `self` divided by its
+            /// norm, rounded to nearest.  `Out` must be signed and hold 1, e.g. `I32<32, 30>`,
+            /// and it is a compile error otherwise.  Panics if `self` is zero.
+            pub fn normalize<Out: Num>(self) -> Quaternion<Out>
+            where
+                Out::Raw: TryFrom<i128>,
+            {
+                let () = Unit::<Out>::CHECK;
+                let [w, x, y, z] = normalize_internal(self.raw_components(), Out::SHIFT as u32)
+                    .map(|c| from_raw(Ok(c)).unwrap());
+                Quaternion::new(w, x, y, z)
+            }
+            /// Rotate `v` by this unit quaternion, i.e. return `q v q*`, rounded to nearest.
+            /// The result has the same shift as `v` and 1 more bit, since a rotation may
+            /// increase the magnitude of a component by up to `sqrt(3)`.  If `self` is not
+            /// normalized, the result is also scaled by its squared norm, and it panics if
+            /// this overflows.
+            pub fn rotate<const B1: u32, const S1: i32>(
+                self,
+                v: Vec3<$Iname<B1, S1>>,
+            ) -> Vec3<$Iname<{ B1 + 1 }, S1>>
+            where
+                [(); (B1 + 1) as usize]:,
+            {
+                let [w, x, y, z] = self.raw_components();
+                // The rotation matrix, with `2 * S` fractional bits.
+                let r = [
+                    [
+                        w * w + x * x - y * y - z * z,
+                        2 * (x * y - w * z),
+                        2 * (x * z + w * y),
+                    ],
+                    [
+                        2 * (x * y + w * z),
+                        w * w - x * x + y * y - z * z,
+                        2 * (y * z - w * x),
+                    ],
+                    [
+                        2 * (x * z - w * y),
+                        2 * (y * z + w * x),
+                        w * w - x * x - y * y + z * z,
+                    ],
+                ];
+                let v = [v.x(), v.y(), v.z()].map(|c| i128::from(c.raw()));
+                let [x, y, z] = r.map(|row| {
+                    let sum = row[0] * v[0] + row[1] * v[1] + row[2] * v[2];
+                    from_raw(Ok(shift_round(sum, -2 * S).unwrap()))
+                        .expect("rotated vector out of range: the quaternion is not normalized")
+                });
+                Vec3::new(x, y, z)
+            }
+            /// Return the spherical linear interpolation from `self` (when `t` is zero) to
+            /// `other` (when `t` is one), normalized and rounded to nearest, which rotates at
+            /// a constant rate along the shorter path between the two orientations.  The
+            /// inputs need not be normalized.  `Out` must be signed and hold 1, as for
+            /// `normalize`.  Panics if either input is zero.
+            pub fn slerp<Out: Num>(self, other: Self, t: Ratio<u32>) -> Quaternion<Out>
+            where
+                Out::Raw: TryFrom<i128>,
+            {
+                let () = Unit::<Out>::CHECK;
+                let (a, b) = (self.raw_components(), other.raw_components());
+                let [w, x, y, z] = slerp_internal(a, b, t.raw().into(), Out::SHIFT as u32)
+                    .map(|c| from_raw(Ok(c)).unwrap());
+                Quaternion::new(w, x, y, z)
+            }
+        }
+    };
+}
+
+unit_quaternion_impl!(I8);
+unit_quaternion_impl!(I16);
+unit_quaternion_impl!(I32);
@@ -0,0 +1,110 @@
+use core::ops::Mul;
+
+use crate::raw::{from_raw, shift_round};
+use crate::{i256, Num, RangeError, U16, U32, U64, U8};
+
+/// Return `(a * (one - r) + b * r) / one`, with `one = 2^frac` and `r` in `0..=one`, rounded
+/// to nearest (ties toward positive infinity).  The products may not fit in `i128`, but the
+/// result does, since it lies between `a` and `b`.
+fn lerp(a: i128, b: i128, r: i128, frac: u32) -> i128 {
+    let one = 1 << frac;
+    let sum = i256::from(a) * i256::from(one - r) + i256::from(b) * i256::from(r);
+    let (_, lo) = ((sum + i256::from(one >> 1)) >> frac).limbs();
+    lo as i128
+}
+
+macro_rules! ratio_impl {
+    ($T:ty, $Uname:ident) => {
+        impl Ratio<$T> {
+            /// Number of fractional bits of the raw value.
+            const FRAC: u32 = <$T>::BITS - 1;
+            /// Zero.
+            pub const ZERO: Self = Self(0);
+            /// One half.
+            pub const HALF: Self = Self(1 << (Self::FRAC - 1));
+            /// One.
+            pub const ONE: Self = Self(1 << Self::FRAC);
+            /// Create the ratio `raw * 2^-(BITS - 1)`, where `BITS` is the width of the raw
+            /// type, or return `RangeError::TooLarge` if it is greater than one.
+            pub const fn new(raw: $T) -> Result<Self, RangeError> {
+                if raw > Self::ONE.0 {
+                    Err(RangeError::TooLarge)
+                } else {
+                    Ok(Self(raw))
+                }
+            }
+            /// Return the raw value, which is the ratio times `2^(BITS - 1)`.
+            pub const fn raw(self) -> $T {
+                self.0
+            }
+            /// Return the ratio as a fixed-point number.
+            pub const fn value(self) -> $Uname<{ <$T>::BITS }, { <$T>::BITS as i32 - 1 }> {
+                $Uname::new_const(self.0)
+            }
+            /// Return the ratio nearest to `x`, or return a `RangeError` if it is negative
+            /// or greater than one.
+            pub fn from_fp<T: Num>(x: T) -> Result<Self, RangeError>
+            where
+                T::Raw: Into<i128>,
+            {
+                let val: i128 = x.raw().into();
+                match shift_round(val, Self::FRAC as i32 - T::SHIFT) {
+                    Some(raw) if (0..=Self::ONE.0.into()).contains(&raw) => Ok(Self(raw as $T)),
+                    _ if val < 0 => Err(RangeError::TooSmall),
+                    _ => Err(RangeError::TooLarge),
+                }
+            }
+            /// Return `1 - self`, which is exact.
+            pub const fn complement(self) -> Self {
+                Self(Self::ONE.0 - self.0)
+            }
+            /// Return `max * self`, rounded to nearest, e.g. the compare value of a PWM
+            /// timer with period `max` for a duty cycle of `self`.  This is the same as
+            /// `self * max`, and it never overflows.
+            pub fn scale_to<T: Num>(self, max: T) -> T
+            where
+                T::Raw: Into<i128> + TryFrom<i128>,
+            {
+                self * max
+            }
+            /// Return `a + (b - a) * self`, rounded to nearest, i.e. linear interpolation
+            /// from `a` (when `self` is zero) to `b` (when `self` is one).  The result lies
+            /// between `a` and `b`, so it never overflows.
+            pub fn lerp<T: Num>(self, a: T, b: T) -> T
+            where
+                T::Raw: Into<i128> + TryFrom<i128>,
+            {
+                let raw = lerp(a.raw().into(), b.raw().into(), self.0.into(), Self::FRAC);
+                from_raw(Ok(raw)).unwrap()
+            }
+        }
+        /// A ratio may multiply any fixed-point number whose raw type converts losslessly
+        /// into `i128`.  The product is rounded to nearest, and has the same type as the
+        /// number, since its magnitude is no greater.
+        impl<T: Num> Mul<T> for Ratio<$T>
+        where
+            T::Raw: Into<i128> + TryFrom<i128>,
+        {
+            type Output = T;
+            fn mul(self, other: T) -> T {
+                let raw = lerp(0, other.raw().into(), self.0.into(), Self::FRAC);
+                from_raw(Ok(raw)).unwrap()
+            }
+        }
+    };
+}
+
+/// A ratio in the unit interval [0, 1], such as a duty cycle, a mix level or an
+/// interpolation factor, stored in an unsigned raw type with one integer bit, e.g.
+/// `Ratio<u16>` is a multiple of 2^-15.  Both zero and one are represented exactly.
+///
+/// Multiplying a fixed-point number by a ratio never overflows, so the product has the
+/// same type as the number (rounded to nearest), without the extra bits of an ordinary
+/// product.  For the same reason, `lerp` always returns a value of the input type.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Ratio<T>(T);
+
+ratio_impl!(u8, U8);
+ratio_impl!(u16, U16);
+ratio_impl!(u32, U32);
+ratio_impl!(u64, U64);
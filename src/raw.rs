@@ -0,0 +1,42 @@
+// Helpers for raw values which are shared across the crate: the width of a type as a signed
+// integer, rounding shifts, and conversion of a raw result to an output type.
+
+use crate::{Num, RangeError};
+
+/// Number of bits needed to hold any value of `T` as a signed integer.  This is public
+/// because it appears in the output types of accumulators, e.g. `LinearRegression::sum_x`.
+pub const fn signed_bits<T: Num>() -> u32 {
+    T::BITS + !T::SIGNED as u32
+}
+
+/// Convert a raw output value (or error) to the output type.
+pub(crate) fn from_raw<Out: Num>(raw: Result<i128, RangeError>) -> Result<Out, RangeError>
+where
+    Out::Raw: TryFrom<i128>,
+{
+    let raw = raw?;
+    let err = if raw < 0 {
+        RangeError::TooSmall
+    } else {
+        RangeError::TooLarge
+    };
+    Out::new(raw.try_into().map_err(|_| err)?)
+}
+
+/// Return `val * 2^shift`, rounded to the nearest integer (ties toward positive infinity),
+/// or `None` if the result overflows.
+pub(crate) const fn shift_round(val: i128, shift: i32) -> Option<i128> {
+    if shift >= 0 {
+        if val == 0 {
+            Some(0)
+        } else if shift < val.unsigned_abs().leading_zeros() as i32 - 1 {
+            Some(val << shift)
+        } else {
+            None
+        }
+    } else if shift > -(i128::BITS as i32) {
+        Some(((val >> (-shift - 1)) + 1) >> 1)
+    } else {
+        Some(0)
+    }
+}
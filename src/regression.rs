@@ -0,0 +1,163 @@
+use core::marker::PhantomData;
+
+use crate::raw::signed_bits;
+use crate::{Num, RangeError, I128};
+
+/// Streaming least-squares fit of `y = slope * x + intercept`, accumulating up to
+/// `2^CAPACITY` samples.
+///
+/// The sums Σx, Σy, Σxy and Σx² are kept in `i128` accumulators.  Their sizes are checked
+/// at compile time: it is a compile error to instantiate a `LinearRegression` whose
+/// capacity and input formats could overflow the accumulators, or the intermediate
+/// products used to compute the slope and intercept.
+#[derive(Clone, Debug)]
+pub struct LinearRegression<X: Num, Y: Num, const CAPACITY: u32> {
+    _types: PhantomData<(X, Y)>,
+    count: u64,
+    sum_x: i128,
+    sum_y: i128,
+    sum_xy: i128,
+    sum_xx: i128,
+}
+
+impl<X: Num, Y: Num, const CAPACITY: u32> LinearRegression<X, Y, CAPACITY>
+where
+    X::Raw: Into<i64>,
+    Y::Raw: Into<i64>,
+{
+    /// Maximum number of samples.  Evaluating this constant also checks at compile time
+    /// that the accumulators and intermediates fit in `i128`.
+    const MAX_COUNT: u64 = {
+        // The widest intermediate is the intercept numerator `Σx²·Σy - Σx·Σxy`, which has
+        // (at most) `2 * CAPACITY + 2 * bits(x) + bits(y) + 2` bits including the sign.
+        assert!(
+            CAPACITY < 64
+                && 2 * CAPACITY + 2 * signed_bits::<X>() + signed_bits::<Y>() + 2 <= i128::BITS,
+            "too many samples or bits for LinearRegression"
+        );
+        1 << CAPACITY
+    };
+    /// Create an empty accumulator.
+    pub fn new() -> Self {
+        let _ = Self::MAX_COUNT; // force the compile-time check of the accumulator widths
+        Self {
+            _types: PhantomData,
+            count: 0,
+            sum_x: 0,
+            sum_y: 0,
+            sum_xy: 0,
+            sum_xx: 0,
+        }
+    }
+    /// Return the number of samples recorded so far.
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+    /// Add one sample to the fit.  Return `RangeError::TooLarge`, without recording the
+    /// sample, if `2^CAPACITY` samples have already been recorded.
+    pub fn record(&mut self, x: X, y: Y) -> Result<(), RangeError> {
+        if self.count >= Self::MAX_COUNT {
+            return Err(RangeError::TooLarge);
+        }
+        let x = x.raw().into() as i128;
+        let y = y.raw().into() as i128;
+        self.count += 1;
+        self.sum_x += x;
+        self.sum_y += y;
+        self.sum_xy += x * y;
+        self.sum_xx += x * x;
+        Ok(())
+    }
+    /// Return Σx, which has the same shift as `X`.
+    pub fn sum_x(&self) -> I128<{ signed_bits::<X>() + CAPACITY }, { X::SHIFT }> {
+        unsafe { I128::new_unchecked(self.sum_x) }
+    }
+    /// Return Σy, which has the same shift as `Y`.
+    pub fn sum_y(&self) -> I128<{ signed_bits::<Y>() + CAPACITY }, { Y::SHIFT }> {
+        unsafe { I128::new_unchecked(self.sum_y) }
+    }
+    /// Return Σxy, which has the shift of `X * Y`.
+    pub fn sum_xy(
+        &self,
+    ) -> I128<{ signed_bits::<X>() + signed_bits::<Y>() + CAPACITY }, { X::SHIFT + Y::SHIFT }>
+    {
+        unsafe { I128::new_unchecked(self.sum_xy) }
+    }
+    /// Return Σx², which has the shift of `X * X`.
+    pub fn sum_xx(&self) -> I128<{ 2 * signed_bits::<X>() + CAPACITY }, { 2 * X::SHIFT }> {
+        unsafe { I128::new_unchecked(self.sum_xx) }
+    }
+    /// Return the least-squares slope in the caller's chosen format, rounded to the nearest
+    /// representable value.  Return `None` if fewer than two distinct `x` values have been
+    /// recorded, or if the slope does not fit in `Out`.
+    pub fn slope<Out: Num>(&self) -> Option<Out>
+    where
+        Out::Raw: TryFrom<i128>,
+    {
+        let n = self.count as i128;
+        let num = n * self.sum_xy - self.sum_x * self.sum_y;
+        // The slope has shift `Y::SHIFT - X::SHIFT`.
+        let raw = div_scaled(num, self.denominator()?, Out::SHIFT - Y::SHIFT + X::SHIFT)?;
+        Out::new(raw.try_into().ok()?).ok()
+    }
+    /// Return the least-squares intercept in the caller's chosen format, rounded to the
+    /// nearest representable value.  Return `None` if fewer than two distinct `x` values
+    /// have been recorded, or if the intercept does not fit in `Out`.
+    pub fn intercept<Out: Num>(&self) -> Option<Out>
+    where
+        Out::Raw: TryFrom<i128>,
+    {
+        let num = self.sum_xx * self.sum_y - self.sum_x * self.sum_xy;
+        // The intercept has the same shift as `Y`.
+        let raw = div_scaled(num, self.denominator()?, Out::SHIFT - Y::SHIFT)?;
+        Out::new(raw.try_into().ok()?).ok()
+    }
+    // `n·Σx² - (Σx)²`, which is zero if and only if all `x` values are identical.
+    fn denominator(&self) -> Option<i128> {
+        let den = self.count as i128 * self.sum_xx - self.sum_x * self.sum_x;
+        (den > 0).then_some(den)
+    }
+}
+
+impl<X: Num, Y: Num, const CAPACITY: u32> Default for LinearRegression<X, Y, CAPACITY>
+where
+    X::Raw: Into<i64>,
+    Y::Raw: Into<i64>,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Return `num * 2^shift / den`, rounded to the nearest integer (ties away from zero), or
+/// `None` if the result does not fit in `i128`.  `den` must be positive.
+pub(crate) fn div_scaled(num: i128, den: i128, shift: i32) -> Option<i128> {
+    let negative = num < 0;
+    let (mut num, mut den, mut shift) = (num.unsigned_abs(), den as u128, shift);
+    // A negative shift is applied to the denominator while it has headroom, and to the
+    // numerator after that (at which point the discarded bits are insignificant).
+    while shift < 0 && den.leading_zeros() > 1 {
+        den <<= 1;
+        shift += 1;
+    }
+    if shift < 0 {
+        num >>= -shift;
+        shift = 0;
+    }
+    let mut quotient = num / den;
+    let mut remainder = num % den;
+    // Long division for the bits below the binary point of `num / den`.
+    for _ in 0..shift {
+        quotient = quotient.checked_mul(2)?;
+        remainder <<= 1;
+        if remainder >= den {
+            quotient += 1;
+            remainder -= den;
+        }
+    }
+    if remainder >= den - remainder {
+        quotient = quotient.checked_add(1)?;
+    }
+    let result = i128::try_from(quotient).ok()?;
+    Some(if negative { -result } else { result })
+}
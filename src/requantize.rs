@@ -0,0 +1,292 @@
+use core::cmp::Ordering;
+use core::marker::PhantomData;
+
+use crate::raw::{from_raw, signed_bits};
+use crate::*;
+
+/// Rounding mode for `Num::requantize`.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Rounding {
+    /// Round toward negative infinity, i.e. truncate the raw value (like `raw_shr`).
+    Floor,
+    /// Round toward positive infinity.
+    Ceil,
+    /// Round toward zero.
+    TowardZero,
+    /// Round to nearest, with ties toward positive infinity.
+    #[default]
+    Nearest,
+    /// Round to nearest, with ties to even ("banker's rounding"), which has no bias.
+    NearestEven,
+}
+
+// Requantization of a raw value in a native integer type.  The same code is generated for
+// `i64` (the fast path, for types of at most 64 signed bits) and `i128`.
+macro_rules! requantize_impl {
+    ($name:ident, $T:ty) => {
+        /// Return `val * 2^shift`, rounded according to `rounding` and saturated to
+        /// `[min, max]`.
+        #[inline]
+        fn $name(val: $T, shift: i32, rounding: Rounding, min: $T, max: $T) -> $T {
+            let saturated = if val < 0 { min } else { max };
+            let val = if shift >= 0 {
+                if shift as u32 >= <$T>::BITS || (val << shift) >> shift != val {
+                    saturated
+                } else {
+                    val << shift
+                }
+            } else if -shift as u32 >= <$T>::BITS {
+                // The magnitude is at most one half.
+                match rounding {
+                    Rounding::Floor if val < 0 => -1,
+                    Rounding::Ceil if val > 0 => 1,
+                    _ => 0,
+                }
+            } else {
+                let shift = -shift as u32;
+                let floor = val >> shift;
+                let rem = val & ((1 << shift) - 1);
+                let half = 1 << (shift - 1);
+                let up = match rounding {
+                    Rounding::Floor => false,
+                    Rounding::Ceil => rem != 0,
+                    Rounding::TowardZero => val < 0 && rem != 0,
+                    Rounding::Nearest => rem >= half,
+                    Rounding::NearestEven => rem > half || (rem == half && floor & 1 != 0),
+                };
+                floor + up as $T
+            };
+            // A clamp of a native integer, which compiles to saturating instructions (e.g.
+            // `ssat` and `usat` on Arm) where the target has them.
+            val.clamp(min, max)
+        }
+    };
+}
+
+requantize_impl!(requantize_i64, i64);
+requantize_impl!(requantize_i128, i128);
+
+/// Implementation of `Num::requantize`.
+#[inline]
+pub(crate) fn requantize<T: Num, Out: Num>(val: T, rounding: Rounding) -> Out
+where
+    T::Raw: Into<i128>,
+    Out::Raw: Into<i128> + TryFrom<i128>,
+{
+    let shift = Out::SHIFT - T::SHIFT;
+    let (min, max) = (Out::MIN.raw().into(), Out::MAX.raw().into());
+    let val = val.raw().into();
+    let raw = if signed_bits::<T>() <= i64::BITS && signed_bits::<Out>() <= i64::BITS {
+        requantize_i64(val as i64, shift, rounding, min as i64, max as i64) as i128
+    } else {
+        requantize_i128(val, shift, rounding, min, max)
+    };
+    // The result lies within the range of `Out`.
+    unsafe { Out::new_unchecked(raw.try_into().ok().unwrap()) }
+}
+
+/// Implementation of `Num::try_rescale`, which rounds to nearest like `requantize`.
+pub(crate) fn try_rescale<T: Num, Out: Num>(val: T) -> Result<Out, RangeError>
+where
+    T::Raw: Into<i128>,
+    Out::Raw: TryFrom<i128>,
+{
+    try_requantize(val, Rounding::Nearest)
+}
+
+/// Convert `val` to the type `Out` like `requantize`, but return a `RangeError` instead of
+/// saturating if the result does not fit.
+pub(crate) fn try_requantize<T: Num, Out: Num>(
+    val: T,
+    rounding: Rounding,
+) -> Result<Out, RangeError>
+where
+    T::Raw: Into<i128>,
+    Out::Raw: TryFrom<i128>,
+{
+    let shift = Out::SHIFT - T::SHIFT;
+    // A result which overflows `i128` saturates, so it is out of range for `Out` as well.
+    let raw = requantize_i128(val.raw().into(), shift, rounding, i128::MIN, i128::MAX);
+    from_raw(Ok(raw))
+}
+
+/// Implementation of `Num::raw_shr_stochastic`, which rounds up if `random` (with `shift`
+/// uniformly distributed bits) is less than the discarded fraction.
+pub(crate) fn shr_stochastic<T: Num, Out: Num>(val: T, shift: u32, random: u128) -> Out
+where
+    T::Raw: Into<i128>,
+    Out::Raw: TryFrom<i128>,
+{
+    let val = val.raw().into();
+    // A shift of all 128 bits leaves only the sign, and the whole value is the fraction.
+    let floor = val >> shift.min(i128::BITS - 1);
+    let fraction = val.wrapping_sub(floor.checked_shl(shift).unwrap_or(0)) as u128;
+    from_raw(Ok(floor + (random < fraction) as i128)).unwrap()
+}
+
+struct Resize<T, Out>(PhantomData<(T, Out)>);
+
+impl<T: Num, Out: Num> Resize<T, Out> {
+    /// Compile-time check that every value of `T` is exactly representable in `Out`.
+    const CHECK: () = {
+        let shift = Out::SHIFT as i64 - T::SHIFT as i64;
+        assert!(
+            shift >= 0 && Out::BITS as i64 >= T::BITS as i64 + shift,
+            "resize would lose precision or range"
+        );
+    };
+}
+
+/// Implementation of `Num::resize`.
+pub(crate) fn resize<T: Num, Out: Num<Raw = T::Raw>>(val: T) -> Out {
+    let () = Resize::<T, Out>::CHECK;
+    unsafe { Out::new_unchecked(val.raw() << (Out::SHIFT - T::SHIFT) as u32) }
+}
+
+struct Align<A, B>(PhantomData<(A, B)>);
+
+impl<A: Num, B: Num> Align<A, B> {
+    /// Compile-time check that both values, shifted to the finer of the two shifts, fit in
+    /// `i128`.
+    const CHECK: () = {
+        let shift = if A::SHIFT > B::SHIFT {
+            A::SHIFT
+        } else {
+            B::SHIFT
+        };
+        assert!(
+            signed_bits::<A>() as i64 + (shift - A::SHIFT) as i64 <= i128::BITS as i64
+                && signed_bits::<B>() as i64 + (shift - B::SHIFT) as i64 <= i128::BITS as i64,
+            "too many bits to align the shifts"
+        );
+    };
+}
+
+/// Implementation of `Num::cmp_logical`.
+pub(crate) fn cmp_logical<A: Num, B: Num>(a: A, b: B) -> Ordering
+where
+    A::Raw: Into<i128>,
+    B::Raw: Into<i128>,
+{
+    let () = Align::<A, B>::CHECK;
+    let shift = A::SHIFT.max(B::SHIFT);
+    let a = a.raw().into() << (shift - A::SHIFT);
+    let b = b.raw().into() << (shift - B::SHIFT);
+    a.cmp(&b)
+}
+
+struct Dither<T>(PhantomData<T>);
+
+impl<T: Num> Dither<T> {
+    /// Compile-time check that a value of `T` plus the dither fits in `i128`.
+    const CHECK: () = assert!(
+        signed_bits::<T>() < i128::BITS - 1,
+        "too many bits for dither"
+    );
+}
+
+/// Implementation of `Num::dither_shr`, with the two uniform random values `a` and `b` of
+/// `shift` bits.
+pub(crate) fn dither_shr<T: Num, Out: Num>(val: T, shift: u32, a: u128, b: u128) -> Out
+where
+    T::Raw: Into<i128>,
+    Out::Raw: Into<i128> + TryFrom<i128>,
+{
+    let () = Dither::<T>::CHECK;
+    let (min, max) = (Out::MIN.raw().into(), Out::MAX.raw().into());
+    let val = val.raw().into() + (a as i128 - b as i128);
+    let raw = requantize_i128(val, -(shift as i32), Rounding::Nearest, min, max);
+    // The result lies within the range of `Out`.
+    unsafe { Out::new_unchecked(raw.try_into().ok().unwrap()) }
+}
+
+struct IntegerPart<const B: u32, const S: i32>;
+
+impl<const B: u32, const S: i32> IntegerPart<B, S> {
+    /// Compile-time check that the value has both fractional and integer bits.
+    const CHECK: () = assert!(
+        S > 0 && (S as u32) < B,
+        "rounding to an integer needs a positive shift less than the bits"
+    );
+}
+
+// Rounding to the integer-valued type with shift 0, and the fractional part.  `floor` and
+// `trunc` keep the integer bits of `self`, while `ceil` and `round` may round up to the
+// next power of 2, so they need one more bit.
+macro_rules! fp_round_impl {
+    ($Name:ident, $T:ty, $Uname:ident, $U:ty) => {
+        impl<const B: u32, const S: i32> $Name<B, S> {
+            /// Return the fractional bits of the raw value, and whether they are at least
+            /// one half.
+            fn fraction(self) -> ($U, bool) {
+                let () = IntegerPart::<B, S>::CHECK;
+                let fraction = self.raw() as $U & !(!0 << S);
+                (fraction, fraction >= 1 << (S - 1))
+            }
+            /// Round toward negative infinity to an integer.
+            pub fn floor(self) -> $Name<{ B - S as u32 }, 0>
+            where
+                [(); (B - S as u32) as usize]:,
+            {
+                let () = IntegerPart::<B, S>::CHECK;
+                unsafe { $Name::new_unchecked(self.raw() >> S) }
+            }
+            /// Round toward positive infinity to an integer.
+            pub fn ceil(self) -> $Name<{ B - S as u32 + 1 }, 0>
+            where
+                [(); (B - S as u32 + 1) as usize]:,
+            {
+                let up = self.fraction().0 != 0;
+                unsafe { $Name::new_unchecked((self.raw() >> S) + up as $T) }
+            }
+            /// Round to the nearest integer, with ties toward positive infinity (like
+            /// `Rounding::Nearest`).
+            pub fn round(self) -> $Name<{ B - S as u32 + 1 }, 0>
+            where
+                [(); (B - S as u32 + 1) as usize]:,
+            {
+                let up = self.fraction().1;
+                unsafe { $Name::new_unchecked((self.raw() >> S) + up as $T) }
+            }
+            /// Round toward zero to an integer, i.e. return the integer part.
+            pub fn trunc(self) -> $Name<{ B - S as u32 }, 0>
+            where
+                [(); (B - S as u32) as usize]:,
+            {
+                #[allow(unused_comparisons)]
+                let up = self.raw() < 0 && self.fraction().0 != 0;
+                unsafe { $Name::new_unchecked((self.raw() >> S) + up as $T) }
+            }
+            /// Return the fractional part `self - self.floor()`, which is never negative,
+            /// e.g. the interpolation weight between two entries of a table.
+            pub fn fract(self) -> $Uname<{ S as u32 }, S>
+            where
+                [(); S as u32 as usize]:,
+            {
+                unsafe { $Uname::new_unchecked(self.fraction().0) }
+            }
+            /// Return both the integer part `self.floor()` and the fractional part
+            /// `self.fract()`, whose sum is `self`.
+            pub fn split(self) -> ($Name<{ B - S as u32 }, 0>, $Uname<{ S as u32 }, S>)
+            where
+                [(); (B - S as u32) as usize]:,
+                [(); S as u32 as usize]:,
+            {
+                (self.floor(), self.fract())
+            }
+        }
+    };
+}
+
+fp_round_impl!(I8, i8, U8, u8);
+fp_round_impl!(U8, u8, U8, u8);
+fp_round_impl!(I16, i16, U16, u16);
+fp_round_impl!(U16, u16, U16, u16);
+fp_round_impl!(I32, i32, U32, u32);
+fp_round_impl!(U32, u32, U32, u32);
+fp_round_impl!(I64, i64, U64, u64);
+fp_round_impl!(U64, u64, U64, u64);
+fp_round_impl!(I128, i128, U128, u128);
+fp_round_impl!(U128, u128, U128, u128);
+fp_round_impl!(Isize, isize, Usize, usize);
+fp_round_impl!(Usize, usize, Usize, usize);
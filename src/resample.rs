@@ -0,0 +1,80 @@
+use core::marker::PhantomData;
+
+use crate::prob::lerp_raw;
+use crate::raw::{from_raw, shift_round, signed_bits};
+use crate::{Num, Prob, U32};
+
+/// Fractional sample coordinates, in the unit interval with 16 fractional bits.
+pub type SampleFraction = Prob<U32<17, 16>>;
+
+struct PixelRange<P>(PhantomData<P>);
+
+impl<P: Num> PixelRange<P> {
+    /// Compile-time check that two nested interpolations cannot overflow.
+    const CHECK: () = assert!(
+        signed_bits::<P>() <= 64,
+        "pixels must fit in 64 signed bits"
+    );
+}
+
+/// Return the position in a row (or column) of `from` pixels which is sampled for pixel `i`
+/// of a row of `to` pixels, as the index of the left neighbour and the fractional distance
+/// to the right neighbour.
+///
+/// Pixel centers are aligned, so the input position is `(i + 1/2) from / to - 1/2`, clamped
+/// to the first and last pixel.  The fraction is rounded to the nearest multiple of 2^-16.
+pub fn sample_position(i: usize, from: usize, to: usize) -> (usize, SampleFraction) {
+    assert!(from > 0 && to > 0, "rows and columns must not be empty");
+    // The position is num / den.
+    let num = (2 * i as i128 + 1) * from as i128 - to as i128;
+    let den = 2 * to as i128;
+    let (index, rest) = (num.div_euclid(den), num.rem_euclid(den));
+    let frac = if num < 0 || index >= from as i128 - 1 {
+        0
+    } else {
+        ((rest << 16) + den / 2) / den
+    };
+    let index = index.clamp(0, from as i128 - 1) as usize;
+    let frac = SampleFraction::new(U32::new(frac as u32).unwrap()).unwrap();
+    (index, frac)
+}
+
+/// Resize an image stored as row-major pixels, `width` pixels per row, to the size of
+/// `output` with `out_width` pixels per row, by bilinear interpolation.
+///
+/// Each output pixel interpolates the four nearest input pixels with the fractional
+/// coordinates of `sample_position`, interpolating along rows and then between rows with
+/// `lerp`.  The interpolation is exact, and only the final result is rounded to the
+/// nearest pixel value, so the output never leaves the range of its input neighbourhood.
+///
+/// Panics unless `input.len()` and `output.len()` are nonzero multiples of `width` and
+/// `out_width` respectively.
+pub fn resize_bilinear<P: Num>(input: &[P], width: usize, output: &mut [P], out_width: usize)
+where
+    P::Raw: Into<i128> + TryFrom<i128>,
+{
+    let () = PixelRange::<P>::CHECK;
+    let rows = |len: usize, width: usize| {
+        assert!(
+            width > 0 && len > 0 && len.is_multiple_of(width),
+            "image must consist of whole rows"
+        );
+        len / width
+    };
+    let height = rows(input.len(), width);
+    let out_height = rows(output.len(), out_width);
+    let pixel = |x: usize, y: usize| -> i128 { input[y * width + x.min(width - 1)].raw().into() };
+    for y in 0..out_height {
+        let (y0, ty) = sample_position(y, height, out_height);
+        let y1 = (y0 + 1).min(height - 1);
+        let ty: i128 = ty.get().raw().into();
+        for x in 0..out_width {
+            let (x0, tx) = sample_position(x, width, out_width);
+            let tx: i128 = tx.get().raw().into();
+            let top = lerp_raw(pixel(x0, y0), pixel(x0 + 1, y0), tx, 16);
+            let bottom = lerp_raw(pixel(x0, y1), pixel(x0 + 1, y1), tx, 16);
+            let raw = shift_round(lerp_raw(top, bottom, ty, 16), -32).unwrap();
+            output[y * out_width + x] = from_raw(Ok(raw)).unwrap();
+        }
+    }
+}
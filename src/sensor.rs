@@ -0,0 +1,553 @@
+//! Sensor linearization pipelines, e.g. from ADC counts to a temperature.
+//!
+//! A pipeline is a chain of stages, each of which converts a fixed-point input to a
+//! fixed-point output: an `Affine` calibration, a piecewise-linear interpolation (`Pwl`, or
+//! `NtcTable` for a thermistor), and a `Polynomial` correction.  Stages are created with
+//! const fns and combined with `Chain`, so a whole pipeline can be a constant:
+//!
+//! `const SENSOR: Chain<Affine<..>, NtcTable<..>> = Chain::new(Affine::new(..), NtcTable::new(..));`
+//!
+//! Each stage computes its result exactly and rounds it once to its output type.  Each
+//! stage also reports a bound on its error, in units of the least-significant bit of its
+//! output, relative to the exact function which it implements (for `NtcTable`, the Beta
+//! model of the thermistor).  The bound of a chain combines the error of each stage with
+//! the sensitivity of the following stages to that error, so `error_bound` of a pipeline is
+//! a guaranteed end-to-end bound for every input in range.
+
+use core::marker::PhantomData;
+
+use crate::cordic::{ln_internal, FRAC};
+use crate::raw::{from_raw, shift_round, signed_bits};
+use crate::regression::div_scaled;
+use crate::{Num, RangeError};
+
+/// A stage of a sensor pipeline, which converts a value of type `Input` to type `Output`.
+pub trait Stage {
+    type Input: Num;
+    type Output: Num;
+    /// Convert `x`, or return a `RangeError` if the result does not fit in `Output`.
+    fn apply(&self, x: Self::Input) -> Result<Self::Output, RangeError>;
+    /// Return a bound on the error of `apply` (relative to the exact function of the stage),
+    /// as a raw value of `Output`.
+    fn error_raw(&self) -> i128;
+    /// Return a bound on the change of the exact function of the stage, as a raw value of
+    /// `Output`, when its input changes by at most `delta`, as a raw value of `Input`.
+    fn sensitivity_raw(&self, delta: i128) -> i128;
+    /// Return a bound on the error of `apply` (saturated to `Output::MAX`).
+    fn error_bound(&self) -> Self::Output
+    where
+        <Self::Output as Num>::Raw: TryFrom<i128>,
+    {
+        from_raw(Ok(self.error_raw())).unwrap_or(Self::Output::MAX)
+    }
+}
+
+/// Return `val * 2^shift` for `val >= 0`, rounded up and saturated to `i128::MAX`.
+fn ceil_shift(val: i128, shift: i32) -> i128 {
+    if shift >= 0 {
+        shift_round(val, shift).unwrap_or(i128::MAX)
+    } else if shift > -(i128::BITS as i32) {
+        let floor = val >> -shift;
+        floor + (floor << -shift != val) as i128
+    } else {
+        (val > 0) as i128
+    }
+}
+
+/// Return `num / den` for `num >= 0` and `den > 0`, rounded up.
+fn ceil_div(num: i128, den: i128) -> i128 {
+    num / den + (num % den != 0) as i128
+}
+
+struct Width<L, R, M>(PhantomData<(L, R, M)>);
+
+impl<L: Num, R: Num, M: Num> Width<L, R, M> {
+    /// Compile-time check that the sum of a product of `L` with `R` and a value of `M`, at
+    /// the finer of their shifts, fits in `i128`.
+    const CHECK: () = {
+        let product = L::SHIFT + R::SHIFT;
+        let shift = if product > M::SHIFT {
+            product
+        } else {
+            M::SHIFT
+        };
+        let first = (signed_bits::<L>() + signed_bits::<R>()) as i32 + shift - product;
+        let second = signed_bits::<M>() as i32 + shift - M::SHIFT;
+        let widest = if first > second { first } else { second };
+        assert!(widest < i128::BITS as i32, "too many bits for sensor stage");
+    };
+}
+
+/// An affine calibration `y = gain * x + offset`, e.g. to correct the gain and offset
+/// errors of an ADC, which is exact up to the final rounding.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Affine<X, G, O, Y> {
+    gain: G,
+    offset: O,
+    _types: PhantomData<(X, Y)>,
+}
+
+impl<X, G, O, Y> Affine<X, G, O, Y> {
+    /// Create a calibration with the given gain and offset.
+    pub const fn new(gain: G, offset: O) -> Self {
+        Self {
+            gain,
+            offset,
+            _types: PhantomData,
+        }
+    }
+}
+
+impl<X: Num, G: Num, O: Num, Y: Num> Affine<X, G, O, Y> {
+    /// Shift of the exact result.
+    const SHIFT: i32 = if G::SHIFT + X::SHIFT > O::SHIFT {
+        G::SHIFT + X::SHIFT
+    } else {
+        O::SHIFT
+    };
+}
+
+impl<X: Num, G: Num, O: Num, Y: Num> Stage for Affine<X, G, O, Y>
+where
+    X::Raw: Into<i128>,
+    G::Raw: Into<i128>,
+    O::Raw: Into<i128>,
+    Y::Raw: TryFrom<i128>,
+{
+    type Input = X;
+    type Output = Y;
+    fn apply(&self, x: X) -> Result<Y, RangeError> {
+        let () = Width::<G, X, O>::CHECK;
+        let product = self.gain.raw().into() * x.raw().into();
+        let sum = (product << (Self::SHIFT - G::SHIFT - X::SHIFT))
+            + (self.offset.raw().into() << (Self::SHIFT - O::SHIFT));
+        let err = if sum < 0 {
+            RangeError::TooSmall
+        } else {
+            RangeError::TooLarge
+        };
+        from_raw(shift_round(sum, Y::SHIFT - Self::SHIFT).ok_or(err))
+    }
+    fn error_raw(&self) -> i128 {
+        (Y::SHIFT < Self::SHIFT) as i128
+    }
+    fn sensitivity_raw(&self, delta: i128) -> i128 {
+        match self.gain.raw().into().abs().checked_mul(delta) {
+            Some(change) => ceil_shift(change, Y::SHIFT - G::SHIFT - X::SHIFT),
+            None => i128::MAX,
+        }
+    }
+}
+
+struct PwlWidth<X, Y>(PhantomData<(X, Y)>);
+
+impl<X: Num, Y: Num> PwlWidth<X, Y> {
+    /// Compile-time check that the products of differences of `X` and `Y` fit in `i128`.
+    const CHECK: () = assert!(
+        signed_bits::<X>() + signed_bits::<Y>() + 2 < i128::BITS,
+        "too many bits for sensor stage"
+    );
+}
+
+/// Piecewise-linear interpolation between `N` breakpoints `(x[i], y[i])`, with `x` in
+/// increasing order.  Inputs outside `[x[0], x[N - 1]]` are clamped to that range.  The
+/// interpolation is exact up to the final rounding.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Pwl<X, Y, const N: usize> {
+    x: [X; N],
+    y: [Y; N],
+}
+
+impl<X, Y, const N: usize> Pwl<X, Y, N> {
+    /// Create an interpolation between the given breakpoints.  Panics if `N` is zero.
+    pub const fn new(x: [X; N], y: [Y; N]) -> Self {
+        assert!(N > 0, "interpolation needs at least one breakpoint");
+        Self { x, y }
+    }
+}
+
+impl<X: Num, Y: Num, const N: usize> Pwl<X, Y, N>
+where
+    X::Raw: Into<i128>,
+    Y::Raw: Into<i128>,
+{
+    /// Return the segments as `(x0, y0, dx, dy)`.
+    fn segments(&self) -> impl Iterator<Item = (i128, i128, i128, i128)> + '_ {
+        self.x.windows(2).zip(self.y.windows(2)).map(|(x, y)| {
+            let (x0, y0) = (x[0].raw().into(), y[0].raw().into());
+            (x0, y0, x[1].raw().into() - x0, y[1].raw().into() - y0)
+        })
+    }
+}
+
+impl<X: Num, Y: Num, const N: usize> Stage for Pwl<X, Y, N>
+where
+    X::Raw: Into<i128>,
+    Y::Raw: Into<i128> + TryFrom<i128>,
+{
+    type Input = X;
+    type Output = Y;
+    fn apply(&self, x: X) -> Result<Y, RangeError> {
+        let () = PwlWidth::<X, Y>::CHECK;
+        let x = x.raw().into();
+        let mut result = self.y[0];
+        for (x0, y0, dx, dy) in self.segments() {
+            if x > x0 && dx > 0 {
+                let offset = (x - x0).min(dx);
+                result = from_raw(Ok(div_scaled(y0 * dx + dy * offset, dx, 0).unwrap()))?;
+            }
+        }
+        Ok(result)
+    }
+    fn error_raw(&self) -> i128 {
+        1
+    }
+    fn sensitivity_raw(&self, delta: i128) -> i128 {
+        let mut max = 0;
+        for (_, _, dx, dy) in self.segments() {
+            let change = match dy.abs().checked_mul(delta) {
+                Some(change) if dx > 0 => ceil_div(change, dx),
+                Some(_) => 0,
+                None => i128::MAX,
+            };
+            max = change.max(max);
+        }
+        max
+    }
+}
+
+/// A polynomial `Σ coefficients[k] x^k`, evaluated exactly (with Horner's method) up to the
+/// final rounding.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Polynomial<X, C, Y, const N: usize> {
+    coefficients: [C; N],
+    _types: PhantomData<(X, Y)>,
+}
+
+impl<X, C, Y, const N: usize> Polynomial<X, C, Y, N> {
+    /// Create a polynomial with the given coefficients, from the constant term up.
+    pub const fn new(coefficients: [C; N]) -> Self {
+        Self {
+            coefficients,
+            _types: PhantomData,
+        }
+    }
+}
+
+impl<X: Num, C: Num, Y: Num, const N: usize> Stage for Polynomial<X, C, Y, N>
+where
+    X::Raw: Into<i128>,
+    C::Raw: Into<i128>,
+    Y::Raw: TryFrom<i128>,
+{
+    type Input = X;
+    type Output = Y;
+    fn apply(&self, x: X) -> Result<Y, RangeError> {
+        let x = x.raw().into();
+        let (mut sum, mut shift) = (0, C::SHIFT);
+        for (k, c) in self.coefficients.iter().enumerate().rev() {
+            let c = c.raw().into();
+            if k == N - 1 {
+                sum = c;
+                continue;
+            }
+            // sum * x + c, at the finer of the two shifts.
+            let next = shift + X::SHIFT;
+            let common = next.max(C::SHIFT);
+            let product = sum
+                .checked_mul(x)
+                .and_then(|p| shift_round(p, common - next));
+            let term = shift_round(c, common - C::SHIFT);
+            match (product, term) {
+                (Some(p), Some(c)) if p.checked_add(c).is_some() => sum = p + c,
+                // The sign of the result is that of the overflowing product.
+                _ if sum.signum() * x.signum() < 0 => return Err(RangeError::TooSmall),
+                _ => return Err(RangeError::TooLarge),
+            }
+            shift = common;
+        }
+        let err = if sum < 0 {
+            RangeError::TooSmall
+        } else {
+            RangeError::TooLarge
+        };
+        from_raw(shift_round(sum, Y::SHIFT - shift).ok_or(err))
+    }
+    fn error_raw(&self) -> i128 {
+        1
+    }
+    fn sensitivity_raw(&self, delta: i128) -> i128 {
+        // |p(x + d) - p(x)| <= Σ |c_k| ((r + d)^k - r^k) for |x| <= r.
+        let min: i128 = X::MIN.raw().into();
+        let r = (-min).max(X::MAX.raw().into());
+        let mut total: i128 = 0;
+        for (k, c) in self.coefficients.iter().enumerate().skip(1) {
+            let c: i128 = c.raw().into();
+            let change = r
+                .checked_add(delta)
+                .and_then(|far| far.checked_pow(k as u32))
+                .and_then(|far| far.checked_sub(r.pow(k as u32)))
+                .and_then(|change| change.checked_mul(c.abs()));
+            let change = match change {
+                Some(change) => ceil_shift(change, Y::SHIFT - C::SHIFT - k as i32 * X::SHIFT),
+                None => i128::MAX,
+            };
+            total = total.saturating_add(change);
+        }
+        total
+    }
+}
+
+/// The voltage divider of an NTC thermistor and a fixed resistor, between the ADC reference
+/// and ground.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Divider {
+    /// The thermistor is connected to ground, so the ADC counts decrease with temperature.
+    NtcToGround,
+    /// The thermistor is connected to the reference, so the ADC counts increase with
+    /// temperature.
+    NtcToReference,
+}
+
+/// Number of points per segment at which the interpolation error of an `NtcTable` is
+/// measured.
+const NTC_SAMPLES: i128 = 8;
+
+/// 0 °C in kelvins, with `FRAC` fractional bits.
+const ZERO_CELSIUS: i128 = (27315 << FRAC) / 100;
+
+/// Lookup table of the temperature in °C of an NTC thermistor in a voltage divider, as a
+/// function of the ADC counts, computed at compile time from the Beta model
+/// `1/T = 1/T0 + ln(R/R0) / β`, where `R0` is the resistance at `T0 = 25 °C`.
+///
+/// The table has `N` equally spaced breakpoints from `lo` to `hi` counts, and is
+/// interpolated linearly; inputs outside this range are clamped.  The breakpoints are kept
+/// with 60 fractional bits, and the interpolation is exact up to the final rounding.  The
+/// input must be an unsigned type of at most 32 bits, whose full range is the full scale of
+/// the ADC, e.g. `U16<12, 0>` for a 12-bit ADC.
+///
+/// The error of the linear interpolation relative to the Beta model is measured at compile
+/// time at 8 points in each segment (where the error is greatest near the middle), with a
+/// margin of 1/16, so the size of the table can be chosen for a given error bound.  The
+/// sensitivity to errors of the input is bounded with the derivative of the Beta model.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct NtcTable<X, Y, const N: usize> {
+    lo: i128,
+    hi: i128,
+    table: [i128; N],
+    // The interpolation error, and a bound on the slope of the Beta model per raw value of
+    // `X`, with `FRAC` fractional bits.
+    interpolation_error: i128,
+    max_slope: i128,
+    _types: PhantomData<(X, Y)>,
+}
+
+impl<X: Num, Y, const N: usize> NtcTable<X, Y, N> {
+    /// Create a table for a thermistor with the parameter `beta` (in kelvins) and the
+    /// resistance `r0` at 25 °C, in the given divider with the fixed resistance `fixed`
+    /// (in the same unit as `r0`), for the ADC counts from `lo` to `hi` (as raw values of
+    /// `X`).
+    ///
+    /// Panics unless `N >= 2` and `0 < lo < hi < 2^BITS` (for the input type `X`), which is
+    /// a compile-time error in a const context.
+    pub const fn new(beta: u32, r0: u32, divider: Divider, fixed: u32, lo: u32, hi: u32) -> Self {
+        assert!(
+            !X::SIGNED && X::BITS <= 32,
+            "ADC counts must be unsigned with at most 32 bits"
+        );
+        assert!(N >= 2, "NTC table needs at least two breakpoints");
+        let full = 1i128 << X::BITS;
+        let (lo, hi) = (lo as i128, hi as i128);
+        assert!(0 < lo && lo < hi && hi < full, "ADC range out of order");
+        assert!(
+            beta > 0 && r0 > 0 && fixed > 0,
+            "NTC parameters must be positive"
+        );
+        // ln(fixed / r0).
+        let ln_ratio = ln_internal(fixed as i128) - ln_internal(r0 as i128);
+        let mut table = [0; N];
+        let segments = N as i128 - 1;
+        let mut max_slope = 0;
+        let mut i = 0;
+        while i < N {
+            table[i] = ntc_celsius(
+                beta,
+                ln_ratio,
+                divider,
+                full,
+                lo * segments + i as i128 * (hi - lo),
+                segments,
+            );
+            if i > 0 {
+                let slope = ntc_slope(beta, full, lo, hi, segments, &table, i);
+                if slope > max_slope {
+                    max_slope = slope;
+                }
+            }
+            i += 1;
+        }
+        let mut interpolation_error = 0;
+        let mut i = 0;
+        while i + 1 < N {
+            let mut k = 1;
+            while k < NTC_SAMPLES {
+                let exact = ntc_celsius(
+                    beta,
+                    ln_ratio,
+                    divider,
+                    full,
+                    (lo * segments + i as i128 * (hi - lo)) * NTC_SAMPLES + k * (hi - lo),
+                    segments * NTC_SAMPLES,
+                );
+                let interpolated = table[i] + (table[i + 1] - table[i]) * k / NTC_SAMPLES;
+                let error = (exact - interpolated).abs();
+                if error > interpolation_error {
+                    interpolation_error = error;
+                }
+                k += 1;
+            }
+            i += 1;
+        }
+        Self {
+            lo,
+            hi,
+            table,
+            // Allow for the rounding of the model and the breakpoints.
+            interpolation_error: interpolation_error + interpolation_error / 16 + (1 << 8),
+            max_slope,
+            _types: PhantomData,
+        }
+    }
+}
+
+/// Return an upper bound on the slope of the Beta model (in °C per count, with `FRAC`
+/// fractional bits) between the breakpoints `i - 1` and `i` of `table`.
+///
+/// The slope is `|dT/dc| = T² full / (beta c (full - c))` (in kelvins), which is bounded on
+/// the segment by the larger temperature and the smaller product `c (full - c)` of its two
+/// ends, since the temperature is monotonic and the product is concave.
+const fn ntc_slope(
+    beta: u32,
+    full: i128,
+    lo: i128,
+    hi: i128,
+    segments: i128,
+    table: &[i128],
+    i: usize,
+) -> i128 {
+    // Temperatures in kelvins with `FRAC / 2` fractional bits.
+    let a = (table[i - 1] + ZERO_CELSIUS) >> (FRAC / 2);
+    let b = (table[i] + ZERO_CELSIUS) >> (FRAC / 2);
+    // T² with `FRAC` fractional bits, rounded up.
+    let t = if a > b { a } else { b } + 1;
+    let t2 = t * t;
+    // The counts at the ends are num / segments, and scaled = full * segments.
+    let scaled = full * segments;
+    let (first, second) = (
+        lo * segments + (i as i128 - 1) * (hi - lo),
+        lo * segments + i as i128 * (hi - lo),
+    );
+    let num = if first * (scaled - first) < second * (scaled - second) {
+        first
+    } else {
+        second
+    };
+    let slope = ((t2 * segments) as u128).div_ceil(num as u128) as i128;
+    let Some(slope) = slope.checked_mul(scaled) else {
+        panic!("too many bits for NTC table");
+    };
+    (slope as u128).div_ceil(beta as u128 * (scaled - num) as u128) as i128
+}
+
+/// Return the temperature in °C (with `FRAC` fractional bits) of the Beta model, for the
+/// ADC counts `num / scale` with the full scale `full`, where `ln_ratio` is
+/// `ln(fixed / r0)` (with `FRAC` fractional bits).
+const fn ntc_celsius(
+    beta: u32,
+    ln_ratio: i128,
+    divider: Divider,
+    full: i128,
+    num: i128,
+    scale: i128,
+) -> i128 {
+    // The fraction of the reference voltage is counts / full = num / (full * scale).
+    let rest = full * scale - num;
+    let (upper, lower) = match divider {
+        // R / (R + fixed) = num / (full * scale)
+        Divider::NtcToGround => (num, rest),
+        // fixed / (R + fixed) = num / (full * scale)
+        Divider::NtcToReference => (rest, num),
+    };
+    // ln(R / r0), where R = fixed * upper / lower.  The offsets of `ln_internal` for integer
+    // arguments cancel.
+    let ln = ln_ratio + ln_internal(upper) - ln_internal(lower);
+    // 1/T = 1/T0 + ln(R / r0) / beta, with T0 = 298.15 K.
+    let inverse = (100 << FRAC) / 29815 + ln / beta as i128;
+    assert!(inverse > 0, "NTC model out of range");
+    (1 << (2 * FRAC)) / inverse - ZERO_CELSIUS
+}
+
+impl<X: Num, Y: Num, const N: usize> Stage for NtcTable<X, Y, N>
+where
+    X::Raw: Into<i128>,
+    Y::Raw: TryFrom<i128>,
+{
+    type Input = X;
+    type Output = Y;
+    fn apply(&self, x: X) -> Result<Y, RangeError> {
+        let width = self.hi - self.lo;
+        let position = (x.raw().into().clamp(self.lo, self.hi) - self.lo) * (N as i128 - 1);
+        let (i, offset) = ((position / width) as usize, position % width);
+        let raw = if i == N - 1 {
+            self.table[i] * width
+        } else {
+            self.table[i] * width + (self.table[i + 1] - self.table[i]) * offset
+        };
+        let err = if raw < 0 {
+            RangeError::TooSmall
+        } else {
+            RangeError::TooLarge
+        };
+        from_raw(div_scaled(raw, width, Y::SHIFT - FRAC as i32).ok_or(err))
+    }
+    fn error_raw(&self) -> i128 {
+        // The interpolation error and the final rounding.
+        ceil_shift(self.interpolation_error, Y::SHIFT - FRAC as i32).saturating_add(1)
+    }
+    fn sensitivity_raw(&self, delta: i128) -> i128 {
+        let change = self.max_slope.checked_mul(delta).unwrap_or(i128::MAX);
+        ceil_shift(change, Y::SHIFT - FRAC as i32)
+    }
+}
+
+/// A pipeline of two stages, which applies `first` and then `second`.  Chains can be nested
+/// to build longer pipelines.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Chain<A, B> {
+    first: A,
+    second: B,
+}
+
+impl<A, B> Chain<A, B> {
+    /// Create a pipeline which applies `first` and then `second`.
+    pub const fn new(first: A, second: B) -> Self {
+        Self { first, second }
+    }
+}
+
+impl<A: Stage, B: Stage<Input = A::Output>> Stage for Chain<A, B> {
+    type Input = A::Input;
+    type Output = B::Output;
+    fn apply(&self, x: A::Input) -> Result<B::Output, RangeError> {
+        self.second.apply(self.first.apply(x)?)
+    }
+    fn error_raw(&self) -> i128 {
+        // The error of the first stage changes the input of the second.
+        let propagated = self.second.sensitivity_raw(self.first.error_raw());
+        self.second.error_raw().saturating_add(propagated)
+    }
+    fn sensitivity_raw(&self, delta: i128) -> i128 {
+        self.second
+            .sensitivity_raw(self.first.sensitivity_raw(delta))
+    }
+}
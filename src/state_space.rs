@@ -0,0 +1,210 @@
+use core::marker::PhantomData;
+
+use crate::raw::{from_raw, shift_round, signed_bits};
+use crate::{Num, RangeError};
+
+/// Return the shift of the exact sum of products of `L` with `R` and of `M` with `S`, which
+/// is the larger of the two product shifts.
+const fn sum_shift<L: Num, R: Num, M: Num, S: Num>() -> i32 {
+    let (first, second) = (L::SHIFT + R::SHIFT, M::SHIFT + S::SHIFT);
+    if first > second {
+        first
+    } else {
+        second
+    }
+}
+
+struct Accumulator<L, R, M, S, const J: usize, const K: usize>(PhantomData<(L, R, M, S)>);
+
+impl<L: Num, R: Num, M: Num, S: Num, const J: usize, const K: usize> Accumulator<L, R, M, S, J, K> {
+    /// Compile-time check that a sum of `J` products of `L` with `R` and `K` products of `M`
+    /// with `S`, aligned to the shift of `sum_shift`, fits in `i128`.
+    const CHECK: () = {
+        let shift = sum_shift::<L, R, M, S>();
+        let first =
+            signed_bits::<L>() as i32 + signed_bits::<R>() as i32 + shift - (L::SHIFT + R::SHIFT);
+        let second =
+            signed_bits::<M>() as i32 + signed_bits::<S>() as i32 + shift - (M::SHIFT + S::SHIFT);
+        let widest = if first > second { first } else { second };
+        let terms = if J + K > 1 {
+            (J + K - 1).ilog2() + 1
+        } else {
+            0
+        };
+        assert!(
+            widest + (terms as i32) < i128::BITS as i32,
+            "too many bits for state-space accumulator"
+        );
+    };
+}
+
+/// Return `Σ left[i] * right[i]` with the shift of the product, left-shifted by `shift`.
+fn dot<L: Num, R: Num>(left: &[L], right: &[R], shift: i32) -> i128
+where
+    L::Raw: Into<i128>,
+    R::Raw: Into<i128>,
+{
+    let sum: i128 = left
+        .iter()
+        .zip(right)
+        .map(|(l, r)| l.raw().into() * r.raw().into())
+        .sum();
+    sum << shift
+}
+
+/// Return the largest magnitude of a value of the type `T`, as a raw value.
+fn max_abs<T: Num>() -> i128
+where
+    T::Raw: Into<i128>,
+{
+    let min: i128 = T::MIN.raw().into();
+    (-min).max(T::MAX.raw().into())
+}
+
+/// Return the least value of `Σ coef[i] * v[i]` for values `v[i]` of the type `T`, with the
+/// shift of the products.
+fn min_sum<T: Num>(coef: &[i128]) -> i128
+where
+    T::Raw: Into<i128>,
+{
+    let (min, max): (i128, i128) = (T::MIN.raw().into(), T::MAX.raw().into());
+    coef.iter().map(|c| (c * min).min(c * max)).sum()
+}
+
+/// A discrete-time linear state-space model with `N` states, `M` inputs and `P` outputs:
+///
+/// `x[k+1] = A x[k] + B u[k]`, `y[k] = C x[k] + D u[k]`
+///
+/// The matrices have the element types `A`, `B`, `C` and `D`, the state has the type `X`,
+/// and the inputs have the type `U`.  Every product is computed exactly, at the combined
+/// width and shift of its operands, and every sum of products is computed exactly at the
+/// larger shift of its two terms.  Results are only rounded at the requantization points,
+/// which are chosen by the types: the new state is rounded to nearest in `X`, and the
+/// output is rounded to nearest in the type requested from `output`.  It is a compile error
+/// if the exact sums could overflow `i128`.
+///
+/// The state has static headroom: `new` verifies that each row of `A` and `B` satisfies
+/// `Σ|A_ij| max|X| + Σ|B_ij| max|U| <= max X` (after rounding), i.e. that the state cannot
+/// leave the range of `X` for any state and input.  If `X` is unsigned, `new` also verifies
+/// that no state and input make the new state negative (e.g. it rejects a negative entry of
+/// `A`).  Thus `step` can never overflow.  This
+/// bound is sufficient but conservative; e.g. it rejects a pure integrator, which has no
+/// bounded state.
+#[derive(Clone, Debug)]
+pub struct StateSpace<A, B, C, D, X, U, const N: usize, const M: usize, const P: usize> {
+    a: [[A; N]; N],
+    b: [[B; M]; N],
+    c: [[C; N]; P],
+    d: [[D; M]; P],
+    x: [X; N],
+    _input: PhantomData<U>,
+}
+
+impl<A, B, C, D, X, U, const N: usize, const M: usize, const P: usize>
+    StateSpace<A, B, C, D, X, U, N, M, P>
+where
+    A: Num,
+    B: Num,
+    C: Num,
+    D: Num,
+    X: Num,
+    U: Num,
+    A::Raw: Into<i128>,
+    B::Raw: Into<i128>,
+    C::Raw: Into<i128>,
+    D::Raw: Into<i128>,
+    X::Raw: Into<i128> + TryFrom<i128>,
+    U::Raw: Into<i128>,
+{
+    /// Create a model with the matrices `A`, `B`, `C`, `D` given as rows, and the initial
+    /// state `x`.  Returns `RangeError::TooLarge` (or `TooSmall`, for a negative state of
+    /// an unsigned `X`) if the state headroom cannot be verified (see above).
+    pub fn new(
+        a: [[A; N]; N],
+        b: [[B; M]; N],
+        c: [[C; N]; P],
+        d: [[D; M]; P],
+        x: [X; N],
+    ) -> Result<Self, RangeError> {
+        let () = Accumulator::<A, X, B, U, N, M>::CHECK;
+        let shift = sum_shift::<A, X, B, U>();
+        for (a, b) in a.iter().zip(&b) {
+            let abs = |row: &[_]| -> i128 { row.iter().map(|v: &i128| v.abs()).sum() };
+            let a: [i128; N] = a.map(|v| v.raw().into());
+            let b: [i128; M] = b.map(|v| v.raw().into());
+            let bound = ((abs(&a) * max_abs::<X>()) << (shift - A::SHIFT - X::SHIFT))
+                + ((abs(&b) * max_abs::<U>()) << (shift - B::SHIFT - U::SHIFT));
+            match shift_round(bound, X::SHIFT - shift) {
+                Some(bound) if bound <= X::MAX.raw().into() => (),
+                _ => return Err(RangeError::TooLarge),
+            }
+            if !X::SIGNED {
+                let least = (min_sum::<X>(&a) << (shift - A::SHIFT - X::SHIFT))
+                    + (min_sum::<U>(&b) << (shift - B::SHIFT - U::SHIFT));
+                // Rounding is monotonic, so a least sum which rounds to zero is in range.
+                match shift_round(least, X::SHIFT - shift) {
+                    Some(least) if least >= 0 => (),
+                    _ => return Err(RangeError::TooSmall),
+                }
+            }
+        }
+        Ok(Self {
+            a,
+            b,
+            c,
+            d,
+            x,
+            _input: PhantomData,
+        })
+    }
+    /// Return the current state.
+    pub fn state(&self) -> &[X; N] {
+        &self.x
+    }
+    /// Replace the current state.
+    pub fn set_state(&mut self, x: [X; N]) {
+        self.x = x;
+    }
+    /// Return the output `C x + D u` for the current state and the input `u`, rounded to
+    /// the nearest value of the type `Y`, or a `RangeError` if it does not fit.
+    pub fn output<Y: Num>(&self, u: &[U; M]) -> Result<[Y; P], RangeError>
+    where
+        Y::Raw: TryFrom<i128>,
+    {
+        let () = Accumulator::<C, X, D, U, N, M>::CHECK;
+        let shift = sum_shift::<C, X, D, U>();
+        let mut y = [Y::MIN; P];
+        for (y, (c, d)) in y.iter_mut().zip(self.c.iter().zip(&self.d)) {
+            let sum = dot(c, &self.x, shift - C::SHIFT - X::SHIFT)
+                + dot(d, u, shift - D::SHIFT - U::SHIFT);
+            let err = if sum < 0 {
+                RangeError::TooSmall
+            } else {
+                RangeError::TooLarge
+            };
+            *y = from_raw(shift_round(sum, Y::SHIFT - shift).ok_or(err))?;
+        }
+        Ok(y)
+    }
+    /// Advance the state to `A x + B u` for the input `u`, rounded to nearest.
+    pub fn step(&mut self, u: &[U; M]) {
+        let shift = sum_shift::<A, X, B, U>();
+        let x = self.x;
+        for (next, (a, b)) in self.x.iter_mut().zip(self.a.iter().zip(&self.b)) {
+            let sum =
+                dot(a, &x, shift - A::SHIFT - X::SHIFT) + dot(b, u, shift - B::SHIFT - U::SHIFT);
+            // Cannot fail, since the headroom was verified by `new`.
+            *next = from_raw(Ok(shift_round(sum, X::SHIFT - shift).unwrap())).unwrap();
+        }
+    }
+    /// Return the output for the input `u` like `output`, then advance the state like
+    /// `step`.  The state is not advanced if the output does not fit.
+    pub fn update<Y: Num>(&mut self, u: &[U; M]) -> Result<[Y; P], RangeError>
+    where
+        Y::Raw: TryFrom<i128>,
+    {
+        let y = self.output(u)?;
+        self.step(u);
+        Ok(y)
+    }
+}
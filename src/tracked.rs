@@ -0,0 +1,225 @@
+use core::ops::{Add, Mul, Neg, Sub};
+
+use crate::requantize::try_requantize;
+use crate::{Num, RangeError, Rounding};
+
+/// Return `2^-shift`, i.e. the value of one LSB of a type with the given shift.
+fn lsb(shift: i32) -> f64 {
+    2f64.powi(-shift)
+}
+
+/// Return the largest error of rounding a value with shift `shift` to shift `out_shift`.
+fn rounding_error(rounding: Rounding, shift: i32, out_shift: i32) -> f64 {
+    if out_shift >= shift {
+        return 0.0;
+    }
+    match rounding {
+        Rounding::Nearest | Rounding::NearestEven => lsb(out_shift) / 2.0,
+        Rounding::Floor | Rounding::Ceil | Rounding::TowardZero => lsb(out_shift) - lsb(shift),
+    }
+}
+
+/// Return the logical value of `val`, rounded to `f64`.  Unlike `Num::into_f64`, this
+/// accepts any number of bits, which is enough for computing an error bound.
+fn approx_f64<N: Num>(val: N) -> f64
+where
+    N::Raw: Into<i128>,
+{
+    val.raw().into() as f64 * lsb(N::SHIFT)
+}
+
+/// Return the error bound of the product of `a` and `b`, with error bounds `ea` and `eb`.
+fn product_error(a: f64, ea: f64, b: f64, eb: f64) -> f64 {
+    // a' b' - a b = a' (b' - b) + b (a' - a), where |b| <= |b'| + eb.
+    a.abs() * eb + (b.abs() + eb) * ea
+}
+
+/// A value of type `N` (usually a fixed-point number) with a bound on its accumulated
+/// quantization error, i.e. the largest difference between the value and the result of the
+/// same computation in exact arithmetic, e.g. to verify that a filter meets its precision
+/// specification.
+///
+/// Addition, subtraction and multiplication of fixed-point numbers are exact, so they only
+/// propagate the errors of their inputs, while division and right shifts also add the error
+/// of their rounding.  An input may start with an error, e.g. half an LSB for an ADC
+/// reading.  The bound is computed in `f64`, and only with the `tracked` feature: without
+/// it, `Tracked<N>` has the same representation as `N`, and its operations compile to
+/// those of `N`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(not(feature = "tracked"), repr(transparent))]
+pub struct Tracked<N> {
+    val: N,
+    /// Bound on the absolute error of the logical value.
+    #[cfg(feature = "tracked")]
+    err: f64,
+}
+
+impl<N> Tracked<N> {
+    /// Create a tracked value with the error bound computed by `err`, which is only called
+    /// with the `tracked` feature.
+    #[cfg(feature = "tracked")]
+    #[inline]
+    fn with(val: N, err: impl FnOnce() -> f64) -> Self {
+        Self { val, err: err() }
+    }
+    #[cfg(not(feature = "tracked"))]
+    #[inline]
+    fn with(val: N, _err: impl FnOnce() -> f64) -> Self {
+        Self { val }
+    }
+    /// Return the bound on the absolute error (zero without the `tracked` feature).
+    #[inline]
+    fn err(&self) -> f64 {
+        #[cfg(feature = "tracked")]
+        return self.err;
+        #[cfg(not(feature = "tracked"))]
+        return 0.0;
+    }
+}
+
+impl<N: Num> Tracked<N> {
+    /// Create an exact value.
+    pub fn new(val: N) -> Self {
+        Self::with(val, || 0.0)
+    }
+    /// Create a value whose error is at most `lsb` LSBs of `N`.
+    pub fn with_error(val: N, lsb: f64) -> Self {
+        Self::with(val, || lsb * self::lsb(N::SHIFT))
+    }
+    /// Return the value.
+    pub fn get(self) -> N {
+        self.val
+    }
+    /// Return the bound on the accumulated error, in LSBs of `N`.
+    #[cfg(feature = "tracked")]
+    pub fn error_bound(self) -> f64 {
+        self.err / lsb(N::SHIFT)
+    }
+    /// Return `self / other` with the shift of `Out`, like `Num::div_to`.  The error bound
+    /// includes that of the rounding toward zero, which is less than one LSB of `Out`.  It
+    /// is infinite if the bound of `other` includes zero.
+    pub fn div_to<Out: Num, D: Num>(self, other: Tracked<D>) -> Tracked<Out>
+    where
+        N::Raw: Into<i128>,
+        D::Raw: Into<i128>,
+        Out::Raw: TryFrom<i128>,
+    {
+        Tracked::with(self.val.div_to(other.val), || {
+            let (a, b) = (approx_f64(self.val).abs(), approx_f64(other.val).abs());
+            let (ea, eb) = (self.err(), other.err());
+            // a/b - a'/b' = ((a - a') b' + a' (b' - b)) / (b b'), where |b| >= |b'| - eb.
+            let err = if b > eb {
+                (ea * b + a * eb) / ((b - eb) * b)
+            } else {
+                f64::INFINITY
+            };
+            err + lsb(Out::SHIFT)
+        })
+    }
+    /// Convert to the type `Out` like `Num::requantize`, but return a `RangeError` instead
+    /// of saturating.  The error bound includes that of the rounding.
+    pub fn try_requantize<Out: Num>(self, rounding: Rounding) -> Result<Tracked<Out>, RangeError>
+    where
+        N::Raw: Into<i128>,
+        Out::Raw: TryFrom<i128>,
+    {
+        let val = try_requantize(self.val, rounding)?;
+        Ok(Tracked::with(val, || {
+            self.err() + rounding_error(rounding, N::SHIFT, Out::SHIFT)
+        }))
+    }
+    /// Shift the raw value left by `K` bits, like `Num::raw_shl`, which is exact.
+    pub fn raw_shl<const K: u32>(
+        self,
+    ) -> Tracked<N::Output<{ N::BITS + K }, { N::SHIFT + K as i32 }>>
+    where
+        [(); (N::BITS + K) as usize]:,
+        [(); (N::SHIFT + K as i32) as usize]:,
+    {
+        Tracked::with(self.val.raw_shl::<K>(), || self.err())
+    }
+    /// Shift the raw value right by `K` bits, like `Num::raw_shr`.  The error bound includes
+    /// that of the truncation, which is less than one LSB of the result.
+    pub fn raw_shr<const K: u32>(
+        self,
+    ) -> Tracked<N::Output<{ N::BITS - K }, { N::SHIFT - K as i32 }>>
+    where
+        [(); (N::BITS - K) as usize]:,
+        [(); (N::SHIFT - K as i32) as usize]:,
+    {
+        Tracked::with(self.val.raw_shr::<K>(), || {
+            self.err() + rounding_error(Rounding::Floor, N::SHIFT, N::SHIFT - K as i32)
+        })
+    }
+    /// Shift the raw value right by `K` bits with the given rounding, like
+    /// `Num::raw_shr_round`.  The error bound includes that of the rounding, which is half
+    /// an LSB of the result when rounding to nearest.
+    pub fn raw_shr_round<const K: u32>(
+        self,
+        rounding: Rounding,
+    ) -> Tracked<N::Output<{ N::BITS - K + 1 }, { N::SHIFT - K as i32 }>>
+    where
+        N::Raw: Into<i128> + TryFrom<i128>,
+        [(); (N::BITS - K + 1) as usize]:,
+        [(); (N::SHIFT - K as i32) as usize]:,
+    {
+        Tracked::with(self.val.raw_shr_round::<K>(rounding), || {
+            self.err() + rounding_error(rounding, N::SHIFT, N::SHIFT - K as i32)
+        })
+    }
+}
+
+/// Tracked values may be added, if their values may be added.  The sum is exact, so the
+/// error bounds add.
+impl<T0: Add<T1>, T1> Add<Tracked<T1>> for Tracked<T0> {
+    type Output = Tracked<T0::Output>;
+    #[inline]
+    fn add(self, other: Tracked<T1>) -> Self::Output {
+        let err = self.err() + other.err();
+        Tracked::with(self.val + other.val, || err)
+    }
+}
+
+/// Tracked values may be subtracted, if their values may be subtracted.  The difference is
+/// exact, so the error bounds add.
+impl<T0: Sub<T1>, T1> Sub<Tracked<T1>> for Tracked<T0> {
+    type Output = Tracked<T0::Output>;
+    #[inline]
+    fn sub(self, other: Tracked<T1>) -> Self::Output {
+        let err = self.err() + other.err();
+        Tracked::with(self.val - other.val, || err)
+    }
+}
+
+/// A tracked value may be negated, if its value may be negated, with the same error bound.
+impl<T: Neg> Neg for Tracked<T> {
+    type Output = Tracked<T::Output>;
+    #[inline]
+    fn neg(self) -> Self::Output {
+        let err = self.err();
+        Tracked::with(-self.val, || err)
+    }
+}
+
+/// Tracked fixed-point numbers may be multiplied, if their values may be multiplied.  The
+/// product is exact, so it only propagates the errors of the inputs, scaled by the
+/// magnitudes of the values.
+impl<T0: Num + Mul<T1>, T1: Num> Mul<Tracked<T1>> for Tracked<T0>
+where
+    T0::Raw: Into<i128>,
+    T1::Raw: Into<i128>,
+{
+    type Output = Tracked<<T0 as Mul<T1>>::Output>;
+    #[inline]
+    fn mul(self, other: Tracked<T1>) -> Self::Output {
+        let err = || {
+            product_error(
+                approx_f64(self.val),
+                self.err(),
+                approx_f64(other.val),
+                other.err(),
+            )
+        };
+        Tracked::with(self.val * other.val, err)
+    }
+}
@@ -0,0 +1,137 @@
+use core::marker::PhantomData;
+use core::ops::{Add, Div, Mul, Neg, Sub};
+
+/// Physical dimension, as the exponents of the SI base units second, metre, kilogram and
+/// ampere.  This is used only as a type parameter of [`Quantity`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Dim<const S: i32, const M: i32, const KG: i32, const A: i32>;
+
+/// No dimension, e.g. a ratio or a gain.
+pub type Dimensionless = Dim<0, 0, 0, 0>;
+/// Time.
+pub type Seconds = Dim<1, 0, 0, 0>;
+/// Frequency, in s⁻¹.
+pub type Hertz = Dim<-1, 0, 0, 0>;
+/// Length.
+pub type Metres = Dim<0, 1, 0, 0>;
+/// Velocity, in m·s⁻¹.
+pub type MetresPerSecond = Dim<-1, 1, 0, 0>;
+/// Mass.
+pub type Kilograms = Dim<0, 0, 1, 0>;
+/// Force, in kg·m·s⁻².
+pub type Newtons = Dim<-2, 1, 1, 0>;
+/// Energy, in kg·m²·s⁻².
+pub type Joules = Dim<-2, 2, 1, 0>;
+/// Power, in kg·m²·s⁻³.
+pub type Watts = Dim<-3, 2, 1, 0>;
+/// Electric current.
+pub type Amperes = Dim<0, 0, 0, 1>;
+/// Electric charge, in s·A.
+pub type Coulombs = Dim<1, 0, 0, 1>;
+/// Electric potential, in kg·m²·s⁻³·A⁻¹.
+pub type Volts = Dim<-3, 2, 1, -1>;
+/// Electrical resistance, in kg·m²·s⁻³·A⁻².
+pub type Ohms = Dim<-3, 2, 1, -2>;
+
+/// A value of type `T` (usually a fixed-point number) tagged with the physical dimension
+/// `U`, e.g. `Quantity<I32<16, 8>, Volts>`.
+///
+/// Multiplying or dividing quantities composes their dimensions, and adding, subtracting or
+/// comparing quantities requires them to have the same dimension, so dimensional errors are
+/// caught at compile time alongside the bit and shift checking of the underlying types.  The
+/// dimension has no runtime cost.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Quantity<T, U> {
+    val: T,
+    _unit: PhantomData<U>,
+}
+
+impl<T, U> Quantity<T, U> {
+    /// Tag `val` with the dimension `U`.
+    pub const fn new(val: T) -> Self {
+        Self {
+            val,
+            _unit: PhantomData,
+        }
+    }
+    /// Return the untagged value.
+    pub fn get(self) -> T {
+        self.val
+    }
+}
+
+/// Quantities with the same dimension may be added, if their values may be added.
+impl<T0: Add<T1>, T1, U> Add<Quantity<T1, U>> for Quantity<T0, U> {
+    type Output = Quantity<T0::Output, U>;
+    fn add(self, other: Quantity<T1, U>) -> Self::Output {
+        Quantity::new(self.val + other.val)
+    }
+}
+
+/// Quantities with the same dimension may be subtracted, if their values may be subtracted.
+impl<T0: Sub<T1>, T1, U> Sub<Quantity<T1, U>> for Quantity<T0, U> {
+    type Output = Quantity<T0::Output, U>;
+    fn sub(self, other: Quantity<T1, U>) -> Self::Output {
+        Quantity::new(self.val - other.val)
+    }
+}
+
+/// A quantity may be negated, if its value may be negated.
+impl<T: Neg, U> Neg for Quantity<T, U> {
+    type Output = Quantity<T::Output, U>;
+    fn neg(self) -> Self::Output {
+        Quantity::new(-self.val)
+    }
+}
+
+/// Quantities may be multiplied, if their values may be multiplied.  The exponents of the
+/// dimension of the result are the sums of the exponents of the inputs.
+impl<
+        T0: Mul<T1>,
+        T1,
+        const S0: i32,
+        const M0: i32,
+        const KG0: i32,
+        const A0: i32,
+        const S1: i32,
+        const M1: i32,
+        const KG1: i32,
+        const A1: i32,
+    > Mul<Quantity<T1, Dim<S1, M1, KG1, A1>>> for Quantity<T0, Dim<S0, M0, KG0, A0>>
+where
+    [(); (S0 + S1) as usize]:,
+    [(); (M0 + M1) as usize]:,
+    [(); (KG0 + KG1) as usize]:,
+    [(); (A0 + A1) as usize]:,
+{
+    type Output = Quantity<T0::Output, Dim<{ S0 + S1 }, { M0 + M1 }, { KG0 + KG1 }, { A0 + A1 }>>;
+    fn mul(self, other: Quantity<T1, Dim<S1, M1, KG1, A1>>) -> Self::Output {
+        Quantity::new(self.val * other.val)
+    }
+}
+
+/// Quantities may be divided, if their values may be divided.  The exponents of the
+/// dimension of the result are the differences of the exponents of the inputs.
+impl<
+        T0: Div<T1>,
+        T1,
+        const S0: i32,
+        const M0: i32,
+        const KG0: i32,
+        const A0: i32,
+        const S1: i32,
+        const M1: i32,
+        const KG1: i32,
+        const A1: i32,
+    > Div<Quantity<T1, Dim<S1, M1, KG1, A1>>> for Quantity<T0, Dim<S0, M0, KG0, A0>>
+where
+    [(); (S0 - S1) as usize]:,
+    [(); (M0 - M1) as usize]:,
+    [(); (KG0 - KG1) as usize]:,
+    [(); (A0 - A1) as usize]:,
+{
+    type Output = Quantity<T0::Output, Dim<{ S0 - S1 }, { M0 - M1 }, { KG0 - KG1 }, { A0 - A1 }>>;
+    fn div(self, other: Quantity<T1, Dim<S1, M1, KG1, A1>>) -> Self::Output {
+        Quantity::new(self.val / other.val)
+    }
+}
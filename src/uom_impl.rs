@@ -0,0 +1,53 @@
+use core::marker::PhantomData;
+
+use crate::{Amperes, Coulombs, Dimensionless, Hertz, Joules, Kilograms, Metres, MetresPerSecond};
+use crate::{Newtons, Num, Ohms, Quantity, RangeError, Seconds, Volts, Watts};
+
+/// The `uom` dimension corresponding to a dimension of [`Quantity`].
+pub trait UomDimension {
+    type Dimension: ::uom::si::Dimension + ?Sized;
+}
+
+macro_rules! uom_dimension_impl {
+    ($Dim:ty, $module:ident) => {
+        impl UomDimension for $Dim {
+            type Dimension = ::uom::si::$module::Dimension;
+        }
+    };
+}
+
+uom_dimension_impl!(Dimensionless, ratio);
+uom_dimension_impl!(Seconds, time);
+uom_dimension_impl!(Hertz, frequency);
+uom_dimension_impl!(Metres, length);
+uom_dimension_impl!(MetresPerSecond, velocity);
+uom_dimension_impl!(Kilograms, mass);
+uom_dimension_impl!(Newtons, force);
+uom_dimension_impl!(Joules, energy);
+uom_dimension_impl!(Watts, power);
+uom_dimension_impl!(Amperes, electric_current);
+uom_dimension_impl!(Coulombs, electric_charge);
+uom_dimension_impl!(Volts, electric_potential);
+uom_dimension_impl!(Ohms, electrical_resistance);
+
+/// The `uom` quantity (with `f64` storage) corresponding to a dimension of [`Quantity`].
+pub type Uom<U> = ::uom::si::Quantity<<U as UomDimension>::Dimension, ::uom::si::SI<f64>, f64>;
+
+impl<T: Num, U: UomDimension> Quantity<T, U> {
+    /// Convert to a `uom` quantity.  Both store the value in SI base units, and the
+    /// conversion of the fixed-point value to `f64` is exact (which requires
+    /// `BITS <= 53`, like `into_f64`).
+    pub fn into_uom(self) -> Uom<U> {
+        ::uom::si::Quantity {
+            dimension: PhantomData,
+            units: PhantomData,
+            value: self.get().into_f64(),
+        }
+    }
+    /// Convert from a `uom` quantity, which is exact if its value in SI base units is
+    /// representable by `T`, and otherwise rounds toward zero like `from_f64`.  Return a
+    /// `RangeError` if the value is out of range.
+    pub fn try_from_uom(val: Uom<U>) -> Result<Self, RangeError> {
+        T::from_f64(val.value).map(Self::new)
+    }
+}
@@ -0,0 +1,229 @@
+use core::ops::{Add, Mul, Neg, Sub};
+
+use crate::mul_div;
+use crate::{Num, I128, I16, I32, I64, I8, U128, U16, U32, U64, U8};
+
+/// A 2D vector with components of type `N` (usually a fixed-point number), e.g. a position
+/// or velocity in the plane.
+///
+/// As for [`Complex`](crate::Complex), the operators act on the components with the
+/// operators of `N`, so the output types account for the bit growth of each step: `+` and
+/// `-` add 1 bit, a scalar product has the bits of the inputs, and `dot` and `cross` add 1
+/// bit to a product for their sum of two products.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Vec2<N> {
+    x: N,
+    y: N,
+}
+
+/// A 3D vector with components of type `N` (usually a fixed-point number), e.g. a position,
+/// an acceleration or an angular rate.
+///
+/// The output types account for bit growth as for [`Vec2`], except that `dot` adds 2 bits
+/// to a product for its sum of three products.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Vec3<N> {
+    x: N,
+    y: N,
+    z: N,
+}
+
+impl<N> Vec2<N> {
+    /// Create the vector `(x, y)`.
+    pub const fn new(x: N, y: N) -> Self {
+        Self { x, y }
+    }
+}
+
+impl<N: Copy> Vec2<N> {
+    /// Return the x component.
+    pub fn x(self) -> N {
+        self.x
+    }
+    /// Return the y component.
+    pub fn y(self) -> N {
+        self.y
+    }
+    /// Return the dot product `x0 * x1 + y0 * y1`, which has 1 more bit than a product of
+    /// the components.
+    pub fn dot<T1: Copy, P>(self, other: Vec2<T1>) -> P::Output
+    where
+        N: Mul<T1, Output = P>,
+        P: Add<P>,
+    {
+        self.x * other.x + self.y * other.y
+    }
+    /// Return the cross product `x0 * y1 - y0 * x1`, i.e. the z component of the cross
+    /// product of the vectors in the plane.  It has 1 more bit than a product of the
+    /// components, and it is signed, like any difference.
+    pub fn cross<T1: Copy, P>(self, other: Vec2<T1>) -> P::Output
+    where
+        N: Mul<T1, Output = P>,
+        P: Sub<P>,
+    {
+        self.x * other.y - self.y * other.x
+    }
+}
+
+impl<N> Vec3<N> {
+    /// Create the vector `(x, y, z)`.
+    pub const fn new(x: N, y: N, z: N) -> Self {
+        Self { x, y, z }
+    }
+}
+
+impl<N: Copy> Vec3<N> {
+    /// Return the x component.
+    pub fn x(self) -> N {
+        self.x
+    }
+    /// Return the y component.
+    pub fn y(self) -> N {
+        self.y
+    }
+    /// Return the z component.
+    pub fn z(self) -> N {
+        self.z
+    }
+    /// Return the dot product `x0 * x1 + y0 * y1 + z0 * z1`, which has 2 more bits than a
+    /// product of the components.
+    pub fn dot<T1: Copy, P, Q>(self, other: Vec3<T1>) -> Q::Output
+    where
+        N: Mul<T1, Output = P>,
+        P: Add<P, Output = Q>,
+        Q: Add<P>,
+    {
+        self.x * other.x + self.y * other.y + self.z * other.z
+    }
+    /// Return the cross product, whose components have 1 more bit than a product of the
+    /// components, and are signed, like any difference.
+    pub fn cross<T1: Copy, P>(self, other: Vec3<T1>) -> Vec3<P::Output>
+    where
+        N: Mul<T1, Output = P>,
+        P: Sub<P>,
+    {
+        Vec3::new(
+            self.y * other.z - self.z * other.y,
+            self.z * other.x - self.x * other.z,
+            self.x * other.y - self.y * other.x,
+        )
+    }
+}
+
+/// Vectors may be added, if their components may be added.
+impl<T0: Add<T1>, T1> Add<Vec2<T1>> for Vec2<T0> {
+    type Output = Vec2<T0::Output>;
+    fn add(self, other: Vec2<T1>) -> Self::Output {
+        Vec2::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+/// Vectors may be subtracted, if their components may be subtracted.
+impl<T0: Sub<T1>, T1> Sub<Vec2<T1>> for Vec2<T0> {
+    type Output = Vec2<T0::Output>;
+    fn sub(self, other: Vec2<T1>) -> Self::Output {
+        Vec2::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+/// A vector may be negated, if its components may be negated.
+impl<T: Neg> Neg for Vec2<T> {
+    type Output = Vec2<T::Output>;
+    fn neg(self) -> Self::Output {
+        Vec2::new(-self.x, -self.y)
+    }
+}
+
+/// A vector may be multiplied by a scalar, if its components may be multiplied by it.
+impl<T: Mul<K>, K: Num> Mul<K> for Vec2<T> {
+    type Output = Vec2<T::Output>;
+    fn mul(self, k: K) -> Self::Output {
+        Vec2::new(self.x * k, self.y * k)
+    }
+}
+
+/// Vectors may be added, if their components may be added.
+impl<T0: Add<T1>, T1> Add<Vec3<T1>> for Vec3<T0> {
+    type Output = Vec3<T0::Output>;
+    fn add(self, other: Vec3<T1>) -> Self::Output {
+        Vec3::new(self.x + other.x, self.y + other.y, self.z + other.z)
+    }
+}
+
+/// Vectors may be subtracted, if their components may be subtracted.
+impl<T0: Sub<T1>, T1> Sub<Vec3<T1>> for Vec3<T0> {
+    type Output = Vec3<T0::Output>;
+    fn sub(self, other: Vec3<T1>) -> Self::Output {
+        Vec3::new(self.x - other.x, self.y - other.y, self.z - other.z)
+    }
+}
+
+/// A vector may be negated, if its components may be negated.
+impl<T: Neg> Neg for Vec3<T> {
+    type Output = Vec3<T::Output>;
+    fn neg(self) -> Self::Output {
+        Vec3::new(-self.x, -self.y, -self.z)
+    }
+}
+
+/// A vector may be multiplied by a scalar, if its components may be multiplied by it.
+impl<T: Mul<K>, K: Num> Mul<K> for Vec3<T> {
+    type Output = Vec3<T::Output>;
+    fn mul(self, k: K) -> Self::Output {
+        Vec3::new(self.x * k, self.y * k, self.z * k)
+    }
+}
+
+macro_rules! vector_impl {
+    ($Uname:ident, $Iname:ident) => {
+        impl<const B: u32, const S: i32> Vec2<$Iname<B, S>> {
+            /// Return the squared norm `x^2 + y^2`, which is exact.  It has twice the bits
+            /// and shift of the components, and it is a compile error if this does not fit
+            /// in the raw type.
+            pub fn norm_sqr(self) -> $Uname<{ 2 * B }, { 2 * S }>
+            where
+                [(); (2 * B) as usize]:,
+                [(); (2 * S) as usize]:,
+            {
+                let (x, y) = (self.x.raw().unsigned_abs(), self.y.raw().unsigned_abs());
+                unsafe { $Uname::new_unchecked(x * x + y * y) }
+            }
+            /// Return the norm `sqrt(x^2 + y^2)`, rounded down.  It has the same shift as
+            /// the components and 1 more bit.
+            pub fn norm(self) -> $Iname<{ B + 1 }, S>
+            where
+                [(); (B + 1) as usize]:,
+            {
+                mul_div::norm([self.x, self.y])
+            }
+        }
+        impl<const B: u32, const S: i32> Vec3<$Iname<B, S>> {
+            /// Return the squared norm `x^2 + y^2 + z^2`, which is exact.  It has twice the
+            /// bits and shift of the components, since each square is at most `2^(2B - 2)`,
+            /// and it is a compile error if this does not fit in the raw type.
+            pub fn norm_sqr(self) -> $Uname<{ 2 * B }, { 2 * S }>
+            where
+                [(); (2 * B) as usize]:,
+                [(); (2 * S) as usize]:,
+            {
+                let (x, y) = (self.x.raw().unsigned_abs(), self.y.raw().unsigned_abs());
+                let z = self.z.raw().unsigned_abs();
+                unsafe { $Uname::new_unchecked(x * x + y * y + z * z) }
+            }
+            /// Return the norm `sqrt(x^2 + y^2 + z^2)`, rounded down.  It has the same shift
+            /// as the components and 1 more bit.
+            pub fn norm(self) -> $Iname<{ B + 1 }, S>
+            where
+                [(); (B + 1) as usize]:,
+            {
+                mul_div::norm([self.x, self.y, self.z])
+            }
+        }
+    };
+}
+
+vector_impl!(U8, I8);
+vector_impl!(U16, I16);
+vector_impl!(U32, I32);
+vector_impl!(U64, I64);
+vector_impl!(U128, I128);
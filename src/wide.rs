@@ -0,0 +1,552 @@
+use core::cmp::Ordering;
+use core::ops::{Add, Mul, Neg, Shl, Shr, Sub};
+
+use crate::add_sub::max;
+#[cfg(feature = "f128")]
+use crate::float::{f128_from_raw, f128_into_parts};
+use crate::float::{f32_from_raw, f32_into_parts, f64_from_raw, f64_into_parts};
+use crate::*;
+
+/// Full 256-bit product of two `u128` values, as `(high, low)` limbs.
+const fn widening_mul_u128(a: u128, b: u128) -> (u128, u128) {
+    const MASK: u128 = u64::MAX as u128;
+    let (a1, a0) = (a >> 64, a & MASK);
+    let (b1, b0) = (b >> 64, b & MASK);
+    let low = a0 * b0;
+    let (mid, mid_carry) = (a1 * b0).overflowing_add(a0 * b1);
+    let (lo, lo_carry) = low.overflowing_add(mid << 64);
+    let hi = a1 * b1 + (mid >> 64) + ((mid_carry as u128) << 64) + lo_carry as u128;
+    (hi, lo)
+}
+
+// The 256-bit raw types are built from two 128-bit limbs.  The high limb determines the
+// signedness, so the derived ordering (high limb first, then low limb) is correct for
+// both the signed and the unsigned type.
+macro_rules! wide_impl {
+    (
+        $Name:ident, $T:ident, $H:ty, $Name128:ident,
+        $Iname:ident, $IT:ident, $Uname:ident, $UT:ident
+    ) => {
+        #[allow(non_camel_case_types)]
+        #[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+        #[doc = concat!("A 256-bit integer with the same signedness as [`", stringify!($H), "`],")]
+        /// stored as two 128-bit limbs.  This is the raw type of
+        #[doc = concat!("[`", stringify!($Name), "`].")]
+        ///
+        /// Arithmetic operators panic on overflow in debug builds, like those of the
+        /// primitive integer types, except multiplication which always wraps.
+        pub struct $T {
+            hi: $H,
+            lo: u128,
+        }
+
+        impl $T {
+            /// Size of this integer type in bits.
+            pub const BITS: u32 = 256;
+            /// Smallest value of this integer type.
+            pub const MIN: Self = Self { hi: <$H>::MIN, lo: 0 };
+            /// Largest value of this integer type.
+            pub const MAX: Self = Self { hi: <$H>::MAX, lo: u128::MAX };
+            /// Assemble a 256-bit integer from its high and low 128-bit halves.
+            pub const fn from_limbs(hi: $H, lo: u128) -> Self {
+                Self { hi, lo }
+            }
+            /// Return the high and low 128-bit halves of this integer.
+            pub const fn limbs(self) -> ($H, u128) {
+                (self.hi, self.lo)
+            }
+            const fn shl_const(self, n: u32) -> Self {
+                if n == 0 {
+                    self
+                } else if n < 128 {
+                    let hi = ((self.hi as u128) << n | self.lo >> (128 - n)) as $H;
+                    Self { hi, lo: self.lo << n }
+                } else {
+                    Self { hi: (self.lo << (n - 128)) as $H, lo: 0 }
+                }
+            }
+            const fn shr_const(self, n: u32) -> Self {
+                if n == 0 {
+                    self
+                } else if n < 128 {
+                    let lo = self.lo >> n | (self.hi as u128) << (128 - n);
+                    Self { hi: self.hi >> n, lo }
+                } else {
+                    // `(hi >> 64) >> 64` fills with copies of the sign bit if signed,
+                    // and with zeros if unsigned.
+                    Self { hi: (self.hi >> 64) >> 64, lo: (self.hi >> (n - 128)) as u128 }
+                }
+            }
+            /// Return `mantissa * 2^(exp + shift)` (negated if `negative`), rounded toward
+            /// zero, where the parts are those of a float.  The result is unspecified if the
+            /// rounded value is out of range.
+            fn from_float_parts(parts: (bool, u128, i32), shift: i32) -> Self {
+                let (negative, mantissa, exp) = (parts.0, parts.1, parts.2 + shift);
+                let magnitude = if exp >= 0 {
+                    Self::from_limbs(0, mantissa).shl_const(exp as u32)
+                } else if exp > -128 {
+                    Self::from_limbs(0, mantissa >> -exp)
+                } else {
+                    Self::from_limbs(0, 0)
+                };
+                if negative {
+                    Self::from_limbs(0, 0) - magnitude
+                } else {
+                    magnitude
+                }
+            }
+            /// Like `from_float_parts`, but return a `RangeError` unless the rounded value
+            /// lies within `min..=max`.  Values which round to `min` or `max` but lie beyond
+            /// them are out of range, as in `float::from_parts`.
+            fn try_from_float_parts(
+                parts: (bool, u128, i32),
+                shift: i32,
+                min: Self,
+                max: Self,
+            ) -> Result<Self, RangeError> {
+                let (negative, mantissa, exp) = (parts.0, parts.1, parts.2.saturating_add(shift));
+                let err = if negative {
+                    RangeError::TooSmall
+                } else {
+                    RangeError::TooLarge
+                };
+                let zero = Self::from_limbs(0, 0);
+                // The magnitude is less than `2^msb`, and it is exactly `2^(msb - 1)` if the
+                // mantissa is a power of 2.
+                let msb = (u128::BITS - mantissa.leading_zeros()) as i64 + exp as i64;
+                let value_bits = (Self::BITS - <Self as Num>::SIGNED as u32) as i64;
+                let raw = if mantissa == 0 {
+                    return Ok(zero);
+                } else if msb > value_bits + 1 {
+                    return Err(err);
+                } else if msb == value_bits + 1 {
+                    // Only the most negative value of a signed type has this magnitude.
+                    if !(negative && <Self as Num>::SIGNED && mantissa.is_power_of_two()) {
+                        return Err(err);
+                    }
+                    Self::MIN
+                } else {
+                    let magnitude = Self::from_float_parts((false, mantissa, exp), 0);
+                    if !negative || magnitude == zero {
+                        magnitude
+                    } else if <Self as Num>::SIGNED {
+                        zero - magnitude
+                    } else {
+                        return Err(err);
+                    }
+                };
+                let inexact = exp < 0 && mantissa.trailing_zeros() < exp.unsigned_abs();
+                if raw < min {
+                    Err(RangeError::TooSmall)
+                } else if raw > max {
+                    Err(RangeError::TooLarge)
+                } else if raw == if negative { min } else { max } && inexact {
+                    Err(err)
+                } else {
+                    Ok(raw)
+                }
+            }
+        }
+
+        impl From<$H> for $T {
+            fn from(val: $H) -> Self {
+                // Sign-extend into the high limb.
+                Self { hi: (val >> 64) >> 64, lo: val as u128 }
+            }
+        }
+
+        impl Add for $T {
+            type Output = Self;
+            fn add(self, other: Self) -> Self {
+                let (lo, carry) = self.lo.overflowing_add(other.lo);
+                // The sum overflows if exactly one step does: if both do, the carry brings
+                // the high limb back into range.
+                let (hi, overflow) = self.hi.overflowing_add(other.hi);
+                let (hi, carry_overflow) = hi.overflowing_add(carry as $H);
+                debug_assert!(overflow == carry_overflow, "attempt to add with overflow");
+                Self { hi, lo }
+            }
+        }
+
+        impl Sub for $T {
+            type Output = Self;
+            fn sub(self, other: Self) -> Self {
+                let (lo, borrow) = self.lo.overflowing_sub(other.lo);
+                // As for addition, the difference overflows if exactly one step does.
+                let (hi, overflow) = self.hi.overflowing_sub(other.hi);
+                let (hi, borrow_overflow) = hi.overflowing_sub(borrow as $H);
+                debug_assert!(overflow == borrow_overflow, "attempt to subtract with overflow");
+                Self { hi, lo }
+            }
+        }
+
+        impl Mul for $T {
+            type Output = Self;
+            /// Return the low 256 bits of the product.
+            fn mul(self, other: Self) -> Self {
+                let (hi, lo) = widening_mul_u128(self.lo, other.lo);
+                let hi = hi
+                    .wrapping_add((self.hi as u128).wrapping_mul(other.lo))
+                    .wrapping_add(self.lo.wrapping_mul(other.hi as u128));
+                Self { hi: hi as $H, lo }
+            }
+        }
+
+        impl Shl<u32> for $T {
+            type Output = Self;
+            fn shl(self, n: u32) -> Self {
+                assert!(n < 256, "attempt to shift left with overflow");
+                self.shl_const(n)
+            }
+        }
+
+        impl Shr<u32> for $T {
+            type Output = Self;
+            fn shr(self, n: u32) -> Self {
+                assert!(n < 256, "attempt to shift right with overflow");
+                self.shr_const(n)
+            }
+        }
+
+        /// Every integer is also a fixed-point number, considered to have
+        /// the maximum number of bits and zero shift.
+        impl Num for $T {
+            type Raw = $T;
+            type Output<const B: u32, const S: i32> = $Name<B, S>;
+            type SignedFp = $IT;
+            type UnsignedFp = $UT;
+            const BITS: u32 = 256;
+            const SHIFT: i32 = 0;
+            const MIN: $T = <$T>::MIN;
+            const MAX: $T = <$T>::MAX;
+            const ZERO: $T = <$T>::from_limbs(0, 0);
+            const ONE: $T = <$T>::from_limbs(0, 1);
+            #[allow(unused_comparisons)]
+            const SIGNED: bool = <$H>::MIN < 0;
+            unsafe fn new_unchecked(val: $T) -> Self {
+                val
+            }
+            fn from_f32(val: f32) -> Result<Self, RangeError> {
+                Self::try_from_float_parts(f32_into_parts(val), 0, Self::MIN, Self::MAX)
+            }
+            unsafe fn from_f32_unchecked(val: f32) -> Self {
+                Self::from_float_parts(f32_into_parts(val), 0)
+            }
+            fn from_f64(val: f64) -> Result<Self, RangeError> {
+                Self::try_from_float_parts(f64_into_parts(val), 0, Self::MIN, Self::MAX)
+            }
+            unsafe fn from_f64_unchecked(val: f64) -> Self {
+                Self::from_float_parts(f64_into_parts(val), 0)
+            }
+            fn raw(self) -> $T {
+                self
+            }
+            fn leading_zeros(self) -> u32 {
+                let (hi, lo) = self.limbs();
+                match (hi as u128).leading_zeros() {
+                    128 => 128 + lo.leading_zeros(),
+                    zeros => zeros,
+                }
+            }
+            fn trailing_zeros(self) -> u32 {
+                let (hi, lo) = self.limbs();
+                match lo.trailing_zeros() {
+                    128 => 128 + (hi as u128).trailing_zeros(),
+                    zeros => zeros,
+                }
+            }
+            fn count_ones(self) -> u32 {
+                let (hi, lo) = self.limbs();
+                (hi as u128).count_ones() + lo.count_ones()
+            }
+            /// Conversion to f32 is guaranteed to be exact, which is never the case for
+            /// a 256-bit integer.  This function always panics.
+            fn into_f32(self) -> f32 {
+                panic!("number could be truncated in f32")
+            }
+            /// Conversion to f64 is guaranteed to be exact, which is never the case for
+            /// a 256-bit integer.  This function always panics.
+            fn into_f64(self) -> f64 {
+                panic!("number could be truncated in f64")
+            }
+            #[cfg(feature = "f128")]
+            fn from_f128(val: f128) -> Result<Self, RangeError> {
+                Self::try_from_float_parts(f128_into_parts(val), 0, Self::MIN, Self::MAX)
+            }
+            #[cfg(feature = "f128")]
+            unsafe fn from_f128_unchecked(val: f128) -> Self {
+                Self::from_float_parts(f128_into_parts(val), 0)
+            }
+            /// Conversion to f128 is guaranteed to be exact, which is never the case for
+            /// a 256-bit integer.  This function always panics.
+            #[cfg(feature = "f128")]
+            fn into_f128(self) -> f128 {
+                panic!("number could be truncated in f128")
+            }
+        }
+
+        #[repr(transparent)]
+        #[derive(Clone, Copy, Debug, Default, Eq, Hash)]
+        /// [`#[repr(transparent)]`](https://doc.rust-lang.org/reference/type-layout.html#the-transparent-representation)
+        /// struct containing
+        #[doc = concat!("[`", stringify!($T), "`]")]
+        /// interpreted as a fixed-point number.
+        ///
+        /// Implements the trait [`fp::Num`](Num) for fixed-point manipulation.
+        pub struct $Name<const BITS: u32, const SHIFT: i32>($T);
+
+        /// Fixed-point numbers with the same raw type and the same shift may be compared,
+        /// even if they have different bits, since their raw values have the same scale.
+        impl<const B0: u32, const B1: u32, const S: i32> PartialEq<$Name<B1, S>> for $Name<B0, S> {
+            fn eq(&self, other: &$Name<B1, S>) -> bool {
+                self.0 == other.0
+            }
+        }
+        impl<const B0: u32, const B1: u32, const S: i32> PartialOrd<$Name<B1, S>>
+            for $Name<B0, S>
+        {
+            fn partial_cmp(&self, other: &$Name<B1, S>) -> Option<Ordering> {
+                Some(self.0.cmp(&other.0))
+            }
+        }
+        impl<const BITS: u32, const SHIFT: i32> Ord for $Name<BITS, SHIFT> {
+            fn cmp(&self, other: &Self) -> Ordering {
+                self.0.cmp(&other.0)
+            }
+        }
+
+        impl<const BITS: u32, const SHIFT: i32> Num for $Name<BITS, SHIFT> {
+            type Raw = $T;
+            type Output<const B: u32, const S: i32> = $Name<B, S>;
+            type SignedFp = $Iname<BITS, SHIFT>;
+            type UnsignedFp = $Uname<BITS, SHIFT>;
+            const BITS: u32 = {
+                assert!(BITS <= <$T>::BITS, concat!("too many bits for ", stringify!($T)));
+                BITS
+            };
+            const SHIFT: i32 = SHIFT;
+            const MIN: Self = Self({
+                if Self::BITS == 0 {
+                    <$T>::from_limbs(0, 0)
+                } else {
+                    <$T>::MIN.shr_const(<$T>::BITS - Self::BITS)
+                }
+            });
+            const MAX: Self = Self({
+                if Self::BITS == 0 {
+                    <$T>::from_limbs(0, 0)
+                } else {
+                    <$T>::MAX.shr_const(<$T>::BITS - Self::BITS)
+                }
+            });
+            const ZERO: Self = Self(<$T>::from_limbs(0, 0));
+            const ONE: Self = {
+                assert!(
+                    SHIFT >= 0 && (SHIFT as u32) + (Self::SIGNED as u32) < BITS,
+                    "1 is not representable"
+                );
+                Self(<$T>::from_limbs(0, 1).shl_const(SHIFT as u32))
+            };
+            const SIGNED: bool = <$T as Num>::SIGNED;
+            unsafe fn new_unchecked(val: $T) -> Self {
+                let _ = Self::BITS; // force the compile-time check that T is wide enough for BITS
+                Self(val)
+            }
+            fn from_f32(val: f32) -> Result<Self, RangeError> {
+                let (min, max) = (Self::MIN.0, Self::MAX.0);
+                let raw = <$T>::try_from_float_parts(f32_into_parts(val), SHIFT, min, max)?;
+                Ok(unsafe { Self::new_unchecked(raw) })
+            }
+            /// The caller must ensure that `val` is within the range of this fixed-point type,
+            /// and that `val` is finite.
+            unsafe fn from_f32_unchecked(val: f32) -> Self {
+                unsafe { Self::new_unchecked(<$T>::from_float_parts(f32_into_parts(val), SHIFT)) }
+            }
+            fn from_f64(val: f64) -> Result<Self, RangeError> {
+                let (min, max) = (Self::MIN.0, Self::MAX.0);
+                let raw = <$T>::try_from_float_parts(f64_into_parts(val), SHIFT, min, max)?;
+                Ok(unsafe { Self::new_unchecked(raw) })
+            }
+            /// The caller must ensure that `val` is within the range of this fixed-point type,
+            /// and that `val` is finite.
+            unsafe fn from_f64_unchecked(val: f64) -> Self {
+                unsafe { Self::new_unchecked(<$T>::from_float_parts(f64_into_parts(val), SHIFT)) }
+            }
+            fn raw(self) -> $T {
+                self.0
+            }
+            /// Conversion to f32 is guaranteed to be exact.  Therefore, this function requires
+            /// `BITS <= 24` (to prevent truncation), `SHIFT <= 149` (to prevent underflow),
+            /// and `BITS - SHIFT <= 128` (to prevent overflow).
+            fn into_f32(self) -> f32 {
+                assert!(
+                    BITS <= f32::MANTISSA_DIGITS,
+                    "number could be truncated in f32"
+                );
+                assert!(
+                    SHIFT <= f32::MANTISSA_DIGITS as i32 - f32::MIN_EXP,
+                    "number could underflow f32"
+                );
+                assert!(
+                    BITS as i32 - SHIFT <= f32::MAX_EXP as i32,
+                    "number could overflow f32"
+                );
+                // The value fits entirely in the low limb, because `BITS <= 24`.
+                f32_from_raw(self.0.lo as $H as i128, SHIFT)
+            }
+            /// Conversion to f64 is guaranteed to be exact.  Therefore, this function requires
+            /// `BITS <= 53` (to prevent truncation), `SHIFT <= 1074` (to prevent underflow),
+            /// and `BITS - SHIFT <= 1024` (to prevent overflow).
+            fn into_f64(self) -> f64 {
+                assert!(
+                    BITS <= f64::MANTISSA_DIGITS,
+                    "number could be truncated in f64"
+                );
+                assert!(
+                    SHIFT <= f64::MANTISSA_DIGITS as i32 - f64::MIN_EXP,
+                    "number could underflow f64"
+                );
+                assert!(
+                    BITS as i32 - SHIFT <= f64::MAX_EXP as i32,
+                    "number could overflow f64"
+                );
+                // The value fits entirely in the low limb, because `BITS <= 53`.
+                f64_from_raw(self.0.lo as $H as i128, SHIFT)
+            }
+            #[cfg(feature = "f128")]
+            fn from_f128(val: f128) -> Result<Self, RangeError> {
+                let (min, max) = (Self::MIN.0, Self::MAX.0);
+                let raw = <$T>::try_from_float_parts(f128_into_parts(val), SHIFT, min, max)?;
+                Ok(unsafe { Self::new_unchecked(raw) })
+            }
+            /// The caller must ensure that `val` is within the range of this fixed-point type,
+            /// and that `val` is finite.
+            #[cfg(feature = "f128")]
+            unsafe fn from_f128_unchecked(val: f128) -> Self {
+                unsafe { Self::new_unchecked(<$T>::from_float_parts(f128_into_parts(val), SHIFT)) }
+            }
+            /// Conversion to f128 is guaranteed to be exact.  Therefore, this function requires
+            /// `BITS <= 113` (to prevent truncation), `SHIFT <= 16494` (to prevent underflow),
+            /// and `BITS - SHIFT <= 16384` (to prevent overflow).
+            #[cfg(feature = "f128")]
+            fn into_f128(self) -> f128 {
+                assert!(
+                    BITS <= f128::MANTISSA_DIGITS,
+                    "number could be truncated in f128"
+                );
+                assert!(
+                    SHIFT <= f128::MANTISSA_DIGITS as i32 - f128::MIN_EXP,
+                    "number could underflow f128"
+                );
+                assert!(
+                    BITS as i32 - SHIFT <= f128::MAX_EXP as i32,
+                    "number could overflow f128"
+                );
+                // The value fits entirely in the low limb, because `BITS <= 113`.
+                f128_from_raw(self.0.lo as $H as i128, SHIFT)
+            }
+        }
+
+        #[doc = concat!("[`", stringify!($Name128), "`] widens to [`", stringify!($Name), "`] with the same bits and shift.")]
+        impl<const B: u32, const S: i32> From<$Name128<B, S>> for $Name<B, S> {
+            fn from(val: $Name128<B, S>) -> Self {
+                unsafe { Self::new_unchecked(val.raw().into()) }
+            }
+        }
+
+        /// Two fixed-point integers with the same raw type and the same shift may be
+        /// added together.  The result has the same raw type and the same shift.  The result
+        /// has 1 more bit than the number of bits in the wider of the two inputs.
+        impl<const B0: u32, const B1: u32, const S: i32> Add<$Name<B1, S>> for $Name<B0, S>
+        where
+            [(); (max(B0, B1) + 1) as usize]:,
+        {
+            type Output = $Name<{ max(B0, B1) + 1 }, S>;
+            fn add(self, other: $Name<B1, S>) -> Self::Output {
+                unsafe { Self::Output::new_unchecked(self.raw() + other.raw()) }
+            }
+        }
+
+        /// Two fixed-point integers with the same raw type and the same shift may be
+        /// subtracted.  The result is always signed, even if the inputs were unsigned.
+        /// The result has the same shift as the inputs, and 1 more bit than the number
+        /// of bits in the wider of the two inputs.
+        impl<const B0: u32, const B1: u32, const S: i32> Sub<$Name<B1, S>> for $Name<B0, S>
+        where
+            [(); (max(B0, B1) + 1) as usize]:,
+        {
+            type Output = $Iname<{ max(B0, B1) + 1 }, S>;
+            fn sub(self, other: $Name<B1, S>) -> Self::Output {
+                let (a, b) = (self.raw(), other.raw());
+                unsafe {
+                    Self::Output::new_unchecked(
+                        $IT::from_limbs(a.hi as i128, a.lo) - $IT::from_limbs(b.hi as i128, b.lo),
+                    )
+                }
+            }
+        }
+
+        /// Any fixed-point integer may be negated.  The result of negation is always
+        /// signed.  Negation adds a bit: unsigned values gain a sign bit; signed values
+        /// can overflow from `MIN` to `-MIN = MAX + 1`.  The shift is unchanged.
+        impl<const B: u32, const S: i32> Neg for $Name<B, S>
+        where
+            [(); (B + 1) as usize]:,
+        {
+            type Output = $Iname<{ B + 1 }, S>;
+            fn neg(self) -> Self::Output {
+                let a = self.raw();
+                unsafe {
+                    Self::Output::new_unchecked(
+                        <$IT as Num>::ZERO - $IT::from_limbs(a.hi as i128, a.lo),
+                    )
+                }
+            }
+        }
+
+        impl<const B0: u32, const B1: u32, const S0: i32, const S1: i32> Mul<$Name<B1, S1>>
+            for $Name<B0, S0>
+        where
+            [(); (B0 + B1) as usize]:,
+            [(); (S0 + S1) as usize]:,
+        {
+            type Output = $Name<{ B0 + B1 }, { S0 + S1 }>;
+            fn mul(self, other: $Name<B1, S1>) -> Self::Output {
+                unsafe { Self::Output::new_unchecked(self.raw() * other.raw()) }
+            }
+        }
+
+        #[doc = concat!("[`", stringify!($Name128), "`] widens to [`", stringify!($Name), "`] for a full-width product.")]
+        impl<const B0: u32, const B1: u32, const S0: i32, const S1: i32>
+            WideningMul<$Name128<B1, S1>> for $Name128<B0, S0>
+        where
+            [(); (B0 + B1) as usize]:,
+            [(); (S0 + S1) as usize]:,
+        {
+            type Output = $Name<{ B0 + B1 }, { S0 + S1 }>;
+            fn widening_mul(self, other: $Name128<B1, S1>) -> Self::Output {
+                $Name::from(self) * $Name::from(other)
+            }
+        }
+
+        impl<const B0: u32, const S0: i32> $Name128<B0, S0> {
+            #[doc = concat!("Multiply by `other` in the double-width raw type `", stringify!($T), "`, then")]
+            /// shift the product right by `N` bits, truncating like `raw_shr`, in one
+            /// operation.  The shifted result must fit in the raw type of the operands.
+            pub fn mul_shr<const N: u32, T: Num<Raw = $H>>(
+                self,
+                other: T,
+            ) -> $Name128<{ B0 + T::BITS - N }, { S0 + T::SHIFT - N as i32 }>
+            where
+                [(); (B0 + T::BITS - N) as usize]:,
+                [(); (S0 + T::SHIFT - N as i32) as usize]:,
+            {
+                let product = $T::from(self.raw()) * $T::from(other.raw());
+                unsafe { $Name128::new_unchecked((product >> N).lo as $H) }
+            }
+        }
+    };
+}
+
+wide_impl!(I256, i256, i128, I128, I256, i256, U256, u256);
+wide_impl!(U256, u256, u128, U128, I256, i256, U256, u256);
@@ -0,0 +1,27 @@
+use fp::*;
+
+#[test]
+fn capacity() {
+    let mut acc = Accumulator::<I8<8, 4>, 3>::new();
+    for _ in 0..8 {
+        acc.add(I8::MIN).unwrap();
+    }
+    assert_eq!(acc.count(), 8);
+    assert_eq!(acc.remaining(), 0);
+    assert_eq!(acc.add(I8::ZERO), Err(RangeError::TooLarge));
+    let total: I128<11, 4> = acc.total();
+    assert_eq!(total.raw(), -128 * 8);
+    acc.reset();
+    assert_eq!(acc.total().raw(), 0);
+    assert_eq!(acc.remaining(), 8);
+}
+
+#[test]
+fn unsigned_and_wide() {
+    let mut acc = Accumulator::<U64<64, 0>, 63>::default();
+    acc.add(U64::MAX).unwrap();
+    acc.add(U64::MAX).unwrap();
+    let total: I128<128, 0> = acc.total();
+    assert_eq!(total.raw(), 2 * i128::from(u64::MAX));
+    assert_eq!(acc.remaining(), (1 << 63) - 2);
+}
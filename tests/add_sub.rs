@@ -31,6 +31,25 @@ where
     }
 }
 
+fn validate_mixed<I, U, C: Num>()
+where
+    I: Num + Add<U, Output = C> + Sub<U, Output = C>,
+    U: Num + Add<I, Output = C> + Sub<I, Output = C>,
+    I::Raw: Into<i128>,
+    U::Raw: Into<i128>,
+    C::Raw: Into<i128>,
+{
+    for i in [I::MIN, I::ZERO, I::MAX] {
+        for u in [U::MIN, U::MAX] {
+            let (i_raw, u_raw) = (i.raw().into(), u.raw().into());
+            assert!((i + u).raw().into() == i_raw + u_raw);
+            assert!((u + i).raw().into() == i_raw + u_raw);
+            assert!((i - u).raw().into() == i_raw - u_raw);
+            assert!((u - i).raw().into() == u_raw - i_raw);
+        }
+    }
+}
+
 #[test]
 fn add_sub_limits() {
     validate::<I8<7, -3>, I8<8, -3>, I8<8, -3>>();
@@ -38,3 +57,146 @@ fn add_sub_limits() {
     validate::<Usize<12, 0>, Usize<13, 0>, Isize<13, 0>>();
     validate::<U128<127, 41>, U128<128, 41>, I128<128, 41>>();
 }
+
+#[test]
+fn add_sub_mixed_limits() {
+    validate_mixed::<I8<7, 2>, U8<5, 2>, I8<8, 2>>();
+    validate_mixed::<I8<3, 0>, U8<6, 0>, I8<8, 0>>();
+    validate_mixed::<I32<30, -4>, U32<30, -4>, I32<32, -4>>();
+    validate_mixed::<I64<63, 10>, U64<62, 10>, I64<64, 10>>();
+}
+
+#[test]
+fn add_sub_cross_width() {
+    validate_mixed::<I8<8, 2>, I32<20, 2>, I32<21, 2>>();
+    validate_mixed::<I64<40, -3>, I16<16, -3>, I64<41, -3>>();
+    validate_mixed::<I8<8, 0>, I64<63, 0>, I64<64, 0>>();
+    let (a, b) = (U8::<8, 4>::MAX, U32::<12, 4>::MAX);
+    let sum: U32<13, 4> = a + b;
+    assert_eq!(sum.raw(), 255 + 4095);
+    let difference: I32<13, 4> = a - b;
+    assert_eq!(difference.raw(), 255 - 4095);
+    let difference: I32<13, 4> = b - a;
+    assert_eq!(difference.raw(), 4095 - 255);
+    let sum: U128<128, 0> = U128::<127, 0>::MAX + U16::<16, 0>::MAX;
+    assert_eq!(sum.raw(), (u128::MAX >> 1) + 65535);
+}
+
+#[test]
+fn abs_limits() {
+    for raw in -64..64 {
+        let x = I8::<7, 3>::new(raw).unwrap();
+        let abs: I8<8, 3> = x.abs();
+        assert_eq!(abs.raw(), raw.abs());
+        assert_eq!(x.saturating_abs().raw(), raw.abs().min(63));
+    }
+    assert_eq!(I64::<64, 0>::MIN.saturating_abs(), I64::<64, 0>::MAX);
+    assert_eq!(I128::<100, 50>::MIN.abs().raw(), 1 << 99);
+}
+
+#[test]
+fn unsigned_abs_limits() {
+    for raw in -64..64 {
+        let x = I8::<7, 3>::new(raw).unwrap();
+        let abs: U8<7, 3> = x.unsigned_abs();
+        assert_eq!(abs.raw(), raw.unsigned_abs());
+    }
+    assert_eq!(I8::<8, 0>::MIN.unsigned_abs().raw(), 128);
+    assert_eq!(I128::<128, 9>::MIN.unsigned_abs().raw(), 1 << 127);
+}
+
+#[test]
+fn signum_copysign() {
+    for raw in -64..64 {
+        let x = I8::<7, 3>::new(raw).unwrap();
+        assert_eq!(x.signum().raw(), raw.signum());
+        for sign in [-5, 0, 5] {
+            let y = I8::<4, -2>::new(sign).unwrap();
+            let z: I8<8, 3> = x.copysign(y);
+            let expected = if sign < 0 { -raw.abs() } else { raw.abs() };
+            assert_eq!(z.raw(), expected);
+        }
+    }
+    assert_eq!(I32::<32, 4>::new(i32::MIN + 1).unwrap().signum().raw(), -1);
+    let (min, y) = (I16::<15, 2>::MIN, I16::<8, 0>::new(1).unwrap());
+    assert_eq!(min.copysign(y).raw(), -min.raw());
+    assert_eq!(min.copysign(-y).raw(), min.raw());
+}
+
+#[test]
+fn abs_diff_limits() {
+    for a in -16..16 {
+        for b in -64..64 {
+            let x = I8::<5, 1>::new(a).unwrap();
+            let y = I8::<7, 1>::new(b).unwrap();
+            let diff: U8<7, 1> = x.abs_diff(y);
+            assert_eq!(diff.raw(), a.abs_diff(b));
+        }
+    }
+    assert_eq!(I8::<8, 0>::MIN.abs_diff(I8::<8, 0>::MAX).raw(), 255);
+    let diff: U64<64, 3> = U64::<64, 3>::MAX.abs_diff(U64::<1, 3>::MAX);
+    assert_eq!(diff.raw(), u64::MAX - 1);
+}
+
+#[test]
+fn midpoint_rounds_down() {
+    for a in i8::MIN..=i8::MAX {
+        for b in i8::MIN..=i8::MAX {
+            let (x, y) = (I8::<8, 2>::new(a).unwrap(), I8::<8, 2>::new(b).unwrap());
+            let expected = (i16::from(a) + i16::from(b)).div_euclid(2);
+            assert_eq!(i16::from(x.midpoint(y).raw()), expected);
+        }
+    }
+    let max = U32::<32, 0>::MAX;
+    assert_eq!(max.midpoint(max), max);
+    assert_eq!(
+        max.midpoint(U32::<32, 0>::new(0).unwrap()).raw(),
+        u32::MAX / 2
+    );
+}
+
+#[test]
+fn sum_array_widens() {
+    let min: I16<8, 4> = I16::MIN;
+    let sum: I16<8, 4> = Num::sum_array([min]);
+    assert_eq!(sum, min);
+    let sum: I16<10, 4> = Num::sum_array([min; 3]);
+    assert_eq!(sum.raw(), -128 * 3);
+    let sum: I16<10, 4> = Num::sum_array([I16::<8, 4>::MAX; 4]);
+    assert_eq!(sum.raw(), 127 * 4);
+    let sum: U16<16, 0> = Num::sum_array([U16::<8, 0>::MAX; 256]);
+    assert_eq!(sum.raw(), 255 * 256);
+    let empty: [U8<4, 2>; 0] = [];
+    let sum: U8<4, 2> = Num::sum_array(empty);
+    assert_eq!(sum.raw(), 0);
+    let sum: I128<128, 0> = Num::sum_array([I128::<127, 0>::MIN; 2]);
+    assert_eq!(sum.raw(), i128::MIN);
+}
+
+#[test]
+fn min_max_clamp_across_bits() {
+    for a in i8::MIN..=i8::MAX {
+        for b in -8..8 {
+            let (x, y) = (I8::<8, 2>::new(a).unwrap(), I8::<4, 2>::new(b).unwrap());
+            let (min, max): (I8<8, 2>, I8<8, 2>) = (x.min_fp(y), y.max_fp(x));
+            assert_eq!((min.raw(), max.raw()), (a.min(b), a.max(b)));
+            if b >= -2 {
+                let clamped: I8<4, 2> = x.clamp_fp(I8::<2, 2>::new(-2).unwrap(), y);
+                assert_eq!(clamped.raw(), a.clamp(-2, b));
+            }
+        }
+    }
+    let wide = I64::<40, 16>::new(-1 << 30).unwrap();
+    let out: I64<16, 16> = wide.clamp_fp(I64::<16, 16>::MIN, I64::<16, 16>::MAX);
+    assert_eq!(out, I64::<16, 16>::MIN);
+    assert_eq!(U128::<128, 0>::MAX.min_fp(U128::<1, 0>::MAX).raw(), 1);
+    // `Ord::max` and `Ord::clamp` are not shadowed for values of the same type.
+    assert_eq!(wide.max(I64::<40, 16>::ZERO), I64::<40, 16>::ZERO);
+    assert_eq!(wide.clamp(wide, wide), wide);
+}
+
+#[test]
+#[should_panic]
+fn clamp_out_of_order() {
+    U8::<8, 0>::MAX.clamp_fp(U8::<2, 0>::MAX, U8::<1, 0>::MAX);
+}
@@ -0,0 +1,69 @@
+#![allow(incomplete_features)]
+#![feature(generic_const_exprs)]
+
+use fp::analyze::*;
+use fp::*;
+
+const REPORT: Analysis = fp_analyze! {
+    x: I16<12, 8> = -1.5..=2.0,
+    y: I16<6, 4> = 0..=1,
+    z: I16<16, 12> = -4..=4;
+    (x * y) + (-z)
+};
+const _: () = REPORT.assert_headroom_at_most(8);
+
+#[test]
+fn report() {
+    let nodes = REPORT.nodes();
+    let summary: Vec<_> = nodes
+        .iter()
+        .map(|node| (node.expr, node.bits, node.needed_bits(), node.headroom()))
+        .collect();
+    assert_eq!(
+        summary,
+        [
+            ("x", 12, 11, 1),
+            ("y", 6, 6, 0),
+            ("z", 16, 16, 0),
+            ("x * y", 18, 15, 3),
+            ("- z", 17, 16, 1),
+            ("(x * y) + (-z)", 19, 16, 3),
+        ]
+    );
+    assert_eq!(nodes[3].range(), (-1.5, 2.));
+    assert_eq!(nodes[3].shift, 12);
+    assert_eq!(nodes[5].range(), (-5.5, 6.));
+    assert!(REPORT
+        .to_string()
+        .starts_with("x: BITS = 12, needed = 11, headroom = 1, range = [-1.5, 2]\n"));
+}
+
+#[test]
+fn unsigned() {
+    let report = fp_analyze! {
+        a: U8<8, 0> = 0..=3,
+        b: U8<8, 0> = 10..=20;
+        a - b
+    };
+    let node = report.nodes()[2];
+    assert_eq!((node.min, node.max, node.signed), (-20, -7, true));
+    assert_eq!((node.bits, node.needed_bits()), (9, 6));
+    assert_eq!(report.nodes()[0].needed_bits(), 2);
+}
+
+#[test]
+#[should_panic(expected = "slack")]
+fn headroom_budget() {
+    fp_analyze! {
+        tight: U8<5, 0> = 10..=20,
+        slack: U8<8, 0> = 0..=3;
+        tight - slack
+    }
+    .assert_headroom_at_most(5);
+}
+
+#[test]
+#[should_panic(expected = "declared range does not fit the input type")]
+fn input_range() {
+    fp_analyze! { a: I8<4, 2> = -2..=2; -a };
+}
@@ -0,0 +1,125 @@
+use fp::*;
+
+#[test]
+fn wrapping_arithmetic() {
+    let a = Angle::<u16>::new(0xf000);
+    let b = Angle::<u16>::new(0x2000);
+    assert_eq!((a + b).raw(), 0x1000);
+    assert_eq!((b - a).raw(), 0x3000);
+    assert_eq!((-b).raw(), 0xe000);
+    assert_eq!(-Angle::<u8>::HALF_TURN, Angle::<u8>::HALF_TURN);
+    assert_eq!(a.diff(b).raw(), -0x3000);
+    assert_eq!(b.diff(a).raw(), 0x3000);
+    assert_eq!(
+        Angle::<u16>::HALF_TURN.diff(Angle::<u16>::ZERO).raw(),
+        i16::MIN
+    );
+    for x in 0..=u8::MAX {
+        for y in 0..=u8::MAX {
+            let (x, y) = (Angle::<u8>::new(x), Angle::<u8>::new(y));
+            let diff = x.diff(y);
+            assert_eq!(y + Angle::<u8>::from_turns(diff), x);
+        }
+    }
+}
+
+#[test]
+fn from_turns() {
+    // 2.75 turns and -0.25 turns are both three quarters of a turn.
+    assert_eq!(
+        Angle::<u32>::from_turns(I16::<8, 2>::new(11).unwrap()).raw(),
+        0xc000_0000
+    );
+    assert_eq!(
+        Angle::<u32>::from_turns(I8::<8, 2>::new(-1).unwrap()).raw(),
+        0xc000_0000
+    );
+    assert_eq!(
+        Angle::<u8>::from_turns(I32::<32, -4>::new(123).unwrap()),
+        Angle::<u8>::ZERO
+    );
+    // 2^-10 turns rounds to nearest with 8 bits, ties toward positive infinity.
+    assert_eq!(
+        Angle::<u8>::from_turns(U16::<16, 10>::new(3).unwrap()).raw(),
+        1
+    );
+    assert_eq!(
+        Angle::<u8>::from_turns(U16::<16, 10>::new(2).unwrap()).raw(),
+        1
+    );
+    assert_eq!(
+        Angle::<u8>::from_turns(U16::<16, 10>::new(1).unwrap()).raw(),
+        0
+    );
+    assert_eq!(
+        Angle::<u8>::from_turns(U16::<16, 10>::new(1023).unwrap()).raw(),
+        0
+    );
+    assert_eq!(Angle::<u64>::from_turns(U64::<64, 64>::MAX).raw(), u64::MAX);
+    let turns = I16::<16, 12>::new(-0x7ff).unwrap();
+    assert_eq!(Angle::<u16>::from_turns(turns).to_turns(), Ok(turns));
+    assert_eq!(
+        Angle::<u16>::HALF_TURN.to_turns(),
+        Ok(I8::<8, 1>::new(1).unwrap())
+    );
+    assert_eq!(
+        Angle::<u16>::HALF_TURN.to_turns::<I8<8, 8>>(),
+        Err(RangeError::TooLarge)
+    );
+}
+
+#[test]
+fn degrees() {
+    let deg = |x: f64| I32::<32, 8>::from_f64(x).unwrap();
+    assert_eq!(Angle::<u16>::from_degrees(deg(90.)).raw(), 0x4000);
+    assert_eq!(Angle::<u16>::from_degrees(deg(-90.)).raw(), 0xc000);
+    assert_eq!(Angle::<u16>::from_degrees(deg(450.)).raw(), 0x4000);
+    assert_eq!(Angle::<u16>::from_degrees(deg(1.)).raw(), 182);
+    // 2^60 degrees is 2^60 mod 360 = 136 degrees.
+    let angle = Angle::<u32>::from_degrees(I64::<64, -60>::new(1).unwrap());
+    assert_eq!(angle.raw(), ((136u64 << 32) / 360 + 1) as u32);
+    assert_eq!(
+        Angle::<u8>::from_degrees(I32::<32, 120>::MAX),
+        Angle::<u8>::ZERO
+    );
+    assert_eq!(
+        Angle::<u16>::new(0x4000).to_degrees(),
+        Ok(I16::<9, 0>::new(90).unwrap())
+    );
+    assert_eq!(
+        Angle::<u16>::new(0xc000).to_degrees(),
+        Ok(I16::<9, 0>::new(-90).unwrap())
+    );
+    assert_eq!(
+        Angle::<u16>::HALF_TURN.to_degrees(),
+        Ok(I16::<9, 0>::new(180).unwrap())
+    );
+    for raw in 0..=u16::MAX {
+        let angle = Angle::<u16>::new(raw);
+        let degrees: I32<32, 16> = angle.to_degrees().unwrap();
+        assert_eq!(Angle::<u16>::from_degrees(degrees), angle);
+        assert!(degrees.raw() > -180 << 16 && degrees.raw() <= 180 << 16);
+    }
+}
+
+#[test]
+fn radians() {
+    let rad = |x: f64| I64::<64, 40>::from_f64(x).unwrap();
+    let pi = core::f64::consts::PI;
+    assert_eq!(Angle::<u16>::from_radians(rad(pi / 2.)).raw(), 0x4000);
+    assert_eq!(Angle::<u16>::from_radians(rad(-pi)).raw(), 0x8000);
+    assert_eq!(Angle::<u16>::from_radians(rad(5. * pi / 2.)).raw(), 0x4000);
+    assert_eq!(Angle::<u32>::from_radians(rad(1.)).raw(), 683565276);
+    let half: I32<32, 28> = Angle::<u8>::HALF_TURN.to_radians().unwrap();
+    assert_eq!(half.raw(), 843314857);
+    for raw in 0..=u16::MAX {
+        let angle = Angle::<u16>::new(raw);
+        let radians: I32<32, 28> = angle.to_radians().unwrap();
+        assert_eq!(Angle::<u16>::from_radians(radians), angle);
+        assert!(
+            (radians.into_f64() - angle.to_turns::<I32<32, 16>>().unwrap().into_f64() * 2. * pi)
+                .abs()
+                < 2e-9
+        );
+    }
+}
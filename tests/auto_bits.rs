@@ -0,0 +1,54 @@
+#![allow(incomplete_features)]
+#![feature(generic_const_exprs)]
+
+use fp::*;
+
+const X: U16<11, 4> = auto_bits!(100, shift = 4);
+
+#[test]
+fn narrowest() {
+    assert_eq!(X.raw(), 1600);
+    let y: I8<3, 1> = auto_bits!(-1.5, shift = 1);
+    assert_eq!(y.raw(), -3);
+    let z: I8<1, 0> = auto_bits!(-1, shift = 0);
+    assert_eq!(z.raw(), -1);
+    let w: U8<2, -2> = auto_bits!(12, shift = -2);
+    assert_eq!(w.raw(), 3);
+    let v: U8<8, 8> = auto_bits!(0.99609375, shift = 8);
+    assert_eq!(v.raw(), 255);
+    let u: U32<17, 16> = auto_bits!(1, shift = 16);
+    assert_eq!(u.into_f64(), 1.);
+    let t: I64<34, 0> = auto_bits!(-8589934592i64, shift = 0);
+    assert_eq!(t.raw(), -1 << 33);
+    let zero: U8<0, 3> = auto_bits!(0, shift = 3);
+    assert_eq!(zero.raw(), 0);
+}
+
+#[test]
+fn exact_integers() {
+    // Integer literals beyond 2^53 are not rounded through f64.
+    let a: U64<53, -1> = auto_bits!(9007199254740994i64, shift = -1);
+    assert_eq!(a.raw(), 4503599627370497);
+    let b: U64<54, 0> = auto_bits!(9007199254740993i64, shift = 0);
+    assert_eq!(b.raw(), 9007199254740993);
+    let c: I128<128, 0> = auto_bits!(-170141183460469231731687303715884105728i128, shift = 0);
+    assert_eq!(c.raw(), i128::MIN);
+    let d: U128<127, 64> = auto_bits!(0x7fff_ffff_ffff_ffffu64, shift = 64);
+    assert_eq!(d.raw(), (i64::MAX as u128) << 64);
+    let e: I8<4, -3> = auto_bits!(-0o100, shift = -3);
+    assert_eq!(e.raw(), -8);
+    let f: U8<3, 0> = auto_bits!(0b101, shift = 0);
+    assert_eq!(f.raw(), 5);
+}
+
+#[test]
+fn fix_aliases() {
+    let a: I16<12, 4> = Fix::<12, 4>::MAX;
+    let b: U8<8, 0> = UFix::<8, 0>::MAX;
+    let c: Fix<9, -2> = I16::<9, -2>::MIN;
+    let d: UFix<65, 3> = U128::<65, 3>::MAX;
+    let e: Fix<200, 0> = I256::<200, 0>::ZERO;
+    assert_eq!((a.raw(), b.raw(), c.raw()), (2047, 255, -256));
+    assert_eq!((d.raw(), e), (u128::MAX >> 63, I256::<200, 0>::ZERO));
+    assert_eq!(Fix::<32, 16>::new(-1).unwrap(), I32::<32, 16>::new(-1).unwrap());
+}
@@ -0,0 +1,73 @@
+#![allow(incomplete_features)]
+#![feature(generic_const_exprs)]
+
+use fp::*;
+
+#[test]
+fn new_checks_range() {
+    type Percent = Bounded<U8<7, 0>, 0, 100>;
+    assert_eq!(Percent::new(U8::new(100).unwrap()).unwrap().raw(), 100);
+    assert_eq!(
+        Percent::new(U8::new(101).unwrap()),
+        Err(RangeError::TooLarge)
+    );
+    type Centered = Bounded<I16<8, 4>, -5, 20>;
+    assert_eq!(
+        Centered::new(I16::new(-5).unwrap()).unwrap().get().raw(),
+        -5
+    );
+    assert_eq!(
+        Centered::new(I16::new(-6).unwrap()),
+        Err(RangeError::TooSmall)
+    );
+}
+
+#[test]
+fn range_bits() {
+    assert_eq!(fp::range_bits(0, 0), 1);
+    assert_eq!(fp::range_bits(0, 25), 6);
+    assert_eq!(fp::range_bits(-1, 0), 1);
+    assert_eq!(fp::range_bits(-32, 31), 6);
+    assert_eq!(fp::range_bits(-33, 31), 7);
+    assert_eq!(fp::range_bits(i128::MIN, i128::MAX), 128);
+    assert_eq!(fp::product_bound(-5, 5, -5, 5, true), -25);
+    assert_eq!(fp::product_bound(-5, 5, -5, 5, false), 25);
+    assert_eq!(fp::product_bound(-3, -2, 4, 7, false), -8);
+}
+
+#[test]
+fn arithmetic_tracks_ranges() {
+    let x = Bounded::<I8<4, 0>, -5, 5>::new(I8::new(-5).unwrap()).unwrap();
+    let product: Bounded<I128<6, 0>, -25, 25> = x * x;
+    assert_eq!(product.raw(), 25);
+
+    let a = Bounded::<U8<7, 2>, 0, 100>::new(U8::new(100).unwrap()).unwrap();
+    let b = Bounded::<U8<2, 2>, 1, 3>::new(U8::new(2).unwrap()).unwrap();
+    let sum: Bounded<I128<8, 2>, 1, 103> = a + b;
+    assert_eq!(sum.raw(), 102);
+    let difference: Bounded<I128<8, 2>, -3, 99> = a - b;
+    assert_eq!(difference.raw(), 98);
+    let negated: Bounded<I128<8, 2>, -100, 0> = -a;
+    assert_eq!(negated.raw(), -100);
+    let scaled: Bounded<I128<10, 4>, 0, 300> = a * b;
+    assert_eq!(scaled.raw(), 200);
+    let narrow: Bounded<I16<10, 4>, 0, 300> = scaled.cast();
+    assert_eq!(narrow.get(), I16::<10, 4>::new(200).unwrap());
+}
+
+#[test]
+fn exhaustive_products() {
+    for i in -3..=6 {
+        for j in -7..=2 {
+            let x = Bounded::<I8<4, 0>, -3, 6>::new(I8::new(i).unwrap()).unwrap();
+            let y = Bounded::<I8<4, 1>, -7, 2>::new(I8::new(j).unwrap()).unwrap();
+            let product: Bounded<I128<7, 1>, -42, 21> = x * y;
+            assert_eq!(product.raw(), i128::from(i * j));
+            let z = Bounded::<I16<4, 0>, -7, 2>::new(I16::new(j.into()).unwrap()).unwrap();
+            let difference: Bounded<I128<5, 0>, -5, 13> = x - z;
+            assert_eq!(difference.raw(), i128::from(i - j));
+            let widened: Bounded<I16<4, 0>, -8, 7> = z.widen();
+            assert_eq!(widened.get().raw(), i16::from(j));
+        }
+    }
+}
@@ -0,0 +1,157 @@
+#![allow(incomplete_features)]
+#![feature(generic_const_exprs)]
+
+use fp::*;
+
+/// Return `Some(raw)` if `raw` lies in the range of `T`.
+fn fits<T: Num>(raw: i128) -> Option<i128>
+where
+    T::Raw: Into<i128>,
+{
+    (T::MIN.raw().into()..=T::MAX.raw().into())
+        .contains(&raw)
+        .then_some(raw)
+}
+
+/// Return `raw` wrapped to the range of `T`.
+fn wrap<T: Num>(raw: i128) -> i128
+where
+    T::Raw: Into<i128>,
+{
+    let (min, max) = (T::MIN.raw().into(), T::MAX.raw().into());
+    (raw - min).rem_euclid(max - min + 1) + min
+}
+
+/// Check the checked, wrapping and overflowing operations against an exact integer
+/// reference for every pair of values of `T`.
+fn check<T: Num>()
+where
+    T::Raw: Into<i128> + TryFrom<i128>,
+{
+    let (min, max) = (T::MIN.raw().into(), T::MAX.raw().into());
+    let scale = |val: i128, shift: i32| {
+        if shift >= 0 {
+            val << shift
+        } else {
+            val >> -shift
+        }
+    };
+    for a in min..=max {
+        let x = T::new(a.try_into().ok().unwrap()).unwrap();
+        assert_eq!(x.wrapping_neg().raw().into(), wrap::<T>(-a), "-{a}");
+        for b in min..=max {
+            let y = T::new(b.try_into().ok().unwrap()).unwrap();
+            let raw = |val: Option<T>| val.map(|v| v.raw().into());
+            assert_eq!(raw(x.checked_add(y)), fits::<T>(a + b), "{a} + {b}");
+            assert_eq!(raw(x.checked_sub(y)), fits::<T>(a - b), "{a} - {b}");
+            let product = scale(a * b, -T::SHIFT);
+            assert_eq!(raw(x.checked_mul(y)), fits::<T>(product), "{a} * {b}");
+            let wrapped = |val: T| -> i128 { val.raw().into() };
+            assert_eq!(wrapped(x.wrapping_add(y)), wrap::<T>(a + b), "{a} + {b}");
+            assert_eq!(wrapped(x.wrapping_sub(y)), wrap::<T>(a - b), "{a} - {b}");
+            let overflowing = |(val, overflow): (T, bool)| (wrapped(val), overflow);
+            let expected = |raw: i128| (wrap::<T>(raw), fits::<T>(raw).is_none());
+            assert_eq!(overflowing(x.overflowing_add(y)), expected(a + b));
+            assert_eq!(overflowing(x.overflowing_sub(y)), expected(a - b));
+            assert_eq!(overflowing(x.overflowing_mul(y)), expected(product));
+            let quotient = if b == 0 {
+                None
+            } else if T::SHIFT >= 0 {
+                fits::<T>((a << T::SHIFT) / b)
+            } else {
+                fits::<T>(a / (b << -T::SHIFT))
+            };
+            assert_eq!(raw(x.checked_div(y)), quotient, "{a} / {b}");
+        }
+    }
+}
+
+#[test]
+fn exhaustive() {
+    check::<I8<8, 0>>();
+    check::<I8<6, 2>>();
+    check::<I8<8, 7>>();
+    check::<I8<5, -2>>();
+    check::<U8<8, 0>>();
+    check::<U8<7, 4>>();
+    check::<U8<6, -3>>();
+    check::<I16<10, 12>>();
+}
+
+#[test]
+fn wide() {
+    let x = I64::<64, 32>::MAX;
+    let y = I64::<64, 32>::new(-1 << 32).unwrap();
+    assert_eq!(x.checked_mul(y).unwrap().raw(), -x.raw());
+    assert_eq!(I64::<64, 32>::MIN.checked_mul(y), None);
+    assert_eq!(I64::<64, 32>::MIN.checked_div(y), None);
+    assert_eq!(x.checked_div(y).unwrap().raw(), -x.raw());
+    assert_eq!(x.checked_add(y.checked_sub(y).unwrap()), Some(x));
+    let x = I128::<128, 64>::MAX;
+    assert_eq!(x.checked_mul(x), None);
+    assert_eq!(x.checked_div(x).unwrap().raw(), 1 << 64);
+    assert_eq!(x.checked_div(I128::<128, 64>::new(0).unwrap()), None);
+    let z = U64::<64, 0>::new(1 << 63).unwrap();
+    assert_eq!(z.checked_add(z), None);
+    assert_eq!(z.wrapping_add(z).raw(), 0);
+    assert_eq!(I128::<128, 64>::MIN.wrapping_neg(), I128::<128, 64>::MIN);
+    assert_eq!(I8::<0, 0>::new(0).unwrap().wrapping_neg().raw(), 0);
+    let phase = U32::<24, 24>::new(0xff_0000).unwrap();
+    let step = U32::<24, 24>::new(0x02_0000).unwrap();
+    assert_eq!(phase.wrapping_add(step).raw(), 0x01_0000);
+    assert_eq!(step.wrapping_sub(phase).raw(), 0x03_0000);
+    let lsb = I16::<12, 4>::new(1).unwrap();
+    assert_eq!(I16::<12, 4>::MIN.wrapping_sub(lsb), I16::<12, 4>::MAX);
+    assert_eq!(z.overflowing_add(z), (U64::<64, 0>::new(0).unwrap(), true));
+    let x = I128::<128, 64>::MAX;
+    let (product, overflow) = x.overflowing_mul(x);
+    assert!(overflow);
+    // (2^127 - 1)^2 / 2^64, rounded down, is 2^190 - 2^64, which wraps to -2^64.
+    assert_eq!(product.raw(), -(1 << 64));
+    let y = I128::<128, 64>::new(-3 << 64).unwrap();
+    assert_eq!(x.overflowing_sub(y).0.raw(), x.raw().wrapping_sub(y.raw()));
+}
+
+#[test]
+fn round_to_multiple() {
+    for a in i8::MIN..=i8::MAX {
+        for b in (i8::MIN..=i8::MAX).filter(|&b| b != 0) {
+            let (x, step) = (
+                I8::<8, 3>::new(a).unwrap(),
+                I16::<9, 3>::new(b.into()).unwrap(),
+            );
+            let step_abs = f64::from(b).abs();
+            let expected = (f64::from(a) / step_abs + 0.5).floor() * step_abs;
+            let fits = (-128. ..=127.).contains(&expected);
+            let checked = x.checked_round_to_multiple(step);
+            assert_eq!(
+                checked.map(|y| f64::from(y.raw())),
+                fits.then_some(expected)
+            );
+            let saturated = f64::from(expected.clamp(-128., 127.) as i8);
+            assert_eq!(
+                f64::from(x.saturating_round_to_multiple(step).raw()),
+                saturated
+            );
+        }
+        assert_eq!(
+            I8::<8, 3>::new(a)
+                .unwrap()
+                .checked_round_to_multiple(I8::<8, 3>::ZERO),
+            None
+        );
+    }
+    let max = I128::<128, 0>::MAX;
+    assert_eq!(max.checked_round_to_multiple(I128::<128, 0>::MIN), None);
+    assert_eq!(max.round_to_multiple(U8::<8, 0>::new(1).unwrap()), max);
+    assert_eq!(
+        max.saturating_round_to_multiple(U8::<8, 0>::new(2).unwrap()),
+        max
+    );
+}
+
+#[test]
+#[should_panic(expected = "attempt to round to a multiple of zero")]
+fn round_to_multiple_of_zero() {
+    I8::<8, 3>::MAX.saturating_round_to_multiple(U8::<8, 3>::ZERO);
+}
@@ -0,0 +1,55 @@
+#![allow(incomplete_features)]
+#![feature(generic_const_exprs)]
+
+use fp::*;
+
+fn c32(re: i32, im: i32) -> Complex<I32<8, 4>> {
+    Complex::new(I32::new(re).unwrap(), I32::new(im).unwrap())
+}
+
+#[test]
+fn add_sub_neg() {
+    let sum: Complex<I32<9, 4>> = c32(127, -128) + c32(127, -128);
+    assert_eq!((sum.re().raw(), sum.im().raw()), (254, -256));
+    let diff: Complex<I32<9, 4>> = c32(-128, 5) - c32(127, 7);
+    assert_eq!((diff.re().raw(), diff.im().raw()), (-255, -2));
+    let neg: Complex<I32<9, 4>> = -c32(-128, 3);
+    assert_eq!((neg.re().raw(), neg.im().raw()), (128, -3));
+}
+
+#[test]
+fn mul() {
+    // (3 + 4i)(1 - 2i) = 11 - 2i
+    let product: Complex<I32<17, 8>> = c32(3, 4) * c32(1, -2);
+    assert_eq!((product.re().raw(), product.im().raw()), (11, -2));
+    let product: Complex<I32<17, 8>> = c32(-128, 127) * c32(-128, -127);
+    assert_eq!((product.re().raw(), product.im().raw()), (32513, 0));
+    // The extreme case needs every bit: (MIN + MIN i)^2 = 2 MIN^2 i.
+    let product: Complex<I32<17, 8>> = c32(-128, -128) * c32(-128, -128);
+    assert_eq!((product.re().raw(), product.im().raw()), (0, 1 << 15));
+    // A complex number times its conjugate is its squared magnitude.
+    let z = c32(-7, 12);
+    let product: Complex<I32<18, 8>> = z * z.conj();
+    assert_eq!(product.re().raw() as u32, z.norm_sqr().raw());
+    assert_eq!(product.im().raw(), 0);
+}
+
+#[test]
+fn conj_norm_abs() {
+    let conj: Complex<I32<9, 4>> = c32(5, -128).conj();
+    assert_eq!((conj.re().raw(), conj.im().raw()), (5, 128));
+    let norm: U32<16, 8> = c32(-128, -128).norm_sqr();
+    assert_eq!(norm.raw(), 1 << 15);
+    let abs: I32<9, 4> = c32(3, -4).abs();
+    assert_eq!(abs.raw(), 5);
+    // sqrt(2) * 128 = 181.02
+    assert_eq!(c32(-128, -128).abs().raw(), 181);
+    for re in -128..=127 {
+        for im in [-128, -37, 0, 1, 99, 127] {
+            let z = c32(re, im);
+            let expected = f64::from(re).hypot(f64::from(im)).floor();
+            assert_eq!(f64::from(z.abs().raw()), expected);
+            assert_eq!(z.norm_sqr().raw(), (re * re + im * im) as u32);
+        }
+    }
+}
@@ -0,0 +1,72 @@
+#![allow(incomplete_features)]
+#![feature(generic_const_exprs)]
+
+use fp::*;
+
+type Gain = I16<16, 15>;
+type Value = I16<16, 8>;
+
+fn value(val: f64) -> Value {
+    Value::from_f64(val).unwrap()
+}
+
+#[test]
+fn integrator() {
+    let k = Gain::from_f64(0.5).unwrap();
+    let mut integrator = Integrator::<_, Value, _>::new(k, value(-10.), value(10.));
+    assert_eq!(integrator.output(), value(0.));
+    for i in 1..=6 {
+        assert_eq!(integrator.update(value(3.)), value(1.5 * i as f64));
+    }
+    // Clamping: the state stops at the limit, and unwinds immediately.
+    assert_eq!(integrator.update(value(3.)), value(10.));
+    assert_eq!(integrator.update(value(-1.)), value(9.5));
+    // Back-calculation with the excess of a controller output saturated at 5.
+    integrator.back_calculate(Gain::from_f64(0.25).unwrap(), value(-4.));
+    assert_eq!(integrator.output(), value(8.5));
+    integrator.back_calculate(U8::<8, 0>::new(255).unwrap(), value(-127.));
+    assert_eq!(integrator.output(), value(-10.));
+    integrator.set(value(100.));
+    assert_eq!(integrator.output(), value(10.));
+    integrator.set(value(-2.));
+    assert_eq!(integrator.output(), value(-2.));
+
+    // The state is exact: the smallest gain and input add up to one bit of output after
+    // 2^15 updates, which rounds up after 2^14.
+    let tiny = Value::new(1).unwrap();
+    let mut integrator =
+        Integrator::<_, Value, _>::new(Gain::new(1).unwrap(), Value::MIN, Value::MAX);
+    for _ in 1..1 << 14 {
+        assert_eq!(integrator.update(tiny), value(0.));
+    }
+    assert_eq!(integrator.update(tiny), tiny);
+    assert_eq!(integrator.update(Value::new(-1).unwrap()), value(0.));
+}
+
+#[test]
+fn differentiator() {
+    let k = Gain::from_f64(0.5).unwrap();
+    let mut unfiltered = Differentiator::<_, Value, U16<16, 15>>::new(k, Prob::one());
+    assert_eq!(unfiltered.update::<Value>(value(100.)), Ok(value(0.)));
+    assert_eq!(unfiltered.update::<Value>(value(103.)), Ok(value(1.5)));
+    assert_eq!(unfiltered.update::<Value>(value(102.)), Ok(value(-0.5)));
+    assert_eq!(
+        unfiltered.update::<I8<8, 1>>(value(-120.)),
+        Err(RangeError::TooSmall)
+    );
+    unfiltered.reset();
+    assert_eq!(unfiltered.update::<Value>(value(50.)), Ok(value(0.)));
+    assert_eq!(unfiltered.output::<Value>(), Ok(value(0.)));
+
+    // A filtered derivative of a step decays geometrically.
+    let alpha = Prob::new(U16::<16, 15>::from_f64(0.25).unwrap()).unwrap();
+    let mut filtered = Differentiator::<_, Value, _>::new(k, alpha);
+    filtered.update::<Value>(value(0.)).unwrap();
+    let mut expected = 0.25 * 0.5 * 64.;
+    assert_eq!(filtered.update::<Value>(value(64.)), Ok(value(expected)));
+    for _ in 0..10 {
+        expected *= 0.75;
+        let y: I32<32, 23> = filtered.update(value(64.)).unwrap();
+        assert_eq!(y.into_f64(), expected);
+    }
+}
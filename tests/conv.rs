@@ -0,0 +1,84 @@
+#![allow(incomplete_features)]
+#![feature(generic_const_exprs)]
+
+use fp::*;
+
+type Pixel = U8<8, 0>;
+type Weight = I8<3, 0>;
+
+fn image(raw: &[u8]) -> Vec<Pixel> {
+    raw.iter().map(|&p| Pixel::new(p).unwrap()).collect()
+}
+
+fn kernel<const N: usize>(raw: [[i8; N]; N]) -> Kernel<Weight, N> {
+    Kernel::new(raw.map(|row| row.map(|w| Weight::new(w).unwrap())))
+}
+
+#[test]
+fn sobel() {
+    let sobel_x = kernel([[-1, 0, 1], [-2, 0, 2], [-1, 0, 1]]);
+    // A vertical edge between columns 1 and 2.
+    let input = image(&[
+        10, 10, 200, 200, //
+        10, 10, 200, 200, //
+        10, 10, 200, 200, //
+    ]);
+    let mut output = [I64::<16, 0>::new(0).unwrap(); 12];
+    assert_eq!(conv2d_bits::<Pixel, Weight, 3>(), 16);
+    sobel_x.conv2d(&input, 4, Edge::Replicate, &mut output);
+    let raw: Vec<i64> = output.iter().map(|o| o.raw()).collect();
+    assert_eq!(raw, [0, 760, 760, 0].repeat(3));
+
+    // With zero padding, the corners see the edge of the image.
+    sobel_x.conv2d(&input, 4, Edge::Zero, &mut output);
+    let raw: Vec<i64> = output.iter().map(|o| o.raw()).collect();
+    assert_eq!(
+        raw,
+        [
+            30, 570, 570, -600, //
+            40, 760, 760, -800, //
+            30, 570, 570, -600, //
+        ]
+    );
+
+    // A single row reflects onto itself, so each column of the kernel is summed.
+    let input = image(&[0, 0, 255, 255, 0, 0]);
+    let mut output = [I64::<16, 0>::new(0).unwrap(); 6];
+    sobel_x.conv2d(&input, 6, Edge::Reflect, &mut output);
+    let raw: Vec<i64> = output.iter().map(|o| o.raw()).collect();
+    assert_eq!(raw, [0, 1020, 1020, -1020, -1020, 0]);
+}
+
+#[test]
+fn blur() {
+    // A 5x5 box blur with weights of 1/32 (the sum is 25/32).
+    let weight = U8::<1, 5>::new(1).unwrap();
+    let blur = Kernel::new([[weight; 5]; 5]);
+    let input: Vec<I16<12, 4>> = (0..9).map(|i| I16::new(i * 16).unwrap()).collect();
+    let mut output = [I64::<{ 12 + 2 + 5 }, 9>::new(0).unwrap(); 9];
+    blur.conv2d(&input, 3, Edge::Wrap, &mut output);
+    // Wrapping a 3x3 image under a 5x5 kernel tiles it: each pixel is counted 25/9 times
+    // on average, and the center sees rows 2, 0, 1, 2, 0 and likewise for columns.
+    let center: i64 = [2, 0, 1, 2, 0]
+        .iter()
+        .flat_map(|&y| [2, 0, 1, 2, 0].map(|x| (3 * y + x) * 16))
+        .sum();
+    assert_eq!(output[4].raw(), center);
+    assert_eq!(output[4].into_f64(), center as f64 / 512.);
+
+    blur.conv2d(&input, 3, Edge::Reflect, &mut output);
+    // Rows and columns 0, 1, 2 reflect to 2, 1, 0, 1, 2 around the center.
+    let center: i64 = [2, 1, 0, 1, 2]
+        .iter()
+        .flat_map(|&y| [2, 1, 0, 1, 2].map(|x| (3 * y + x) * 16))
+        .sum();
+    assert_eq!(output[0].raw(), center);
+}
+
+#[test]
+#[should_panic(expected = "image must consist of whole rows")]
+fn ragged() {
+    let blur = kernel([[1]]);
+    let mut output = [I64::<12, 0>::new(0).unwrap(); 5];
+    blur.conv2d(&image(&[1, 2, 3, 4, 5]), 2, Edge::Zero, &mut output);
+}
@@ -0,0 +1,115 @@
+use fp::cordic;
+use fp::*;
+
+type Out = I64<40, 32>;
+
+fn close(actual: Result<Out, RangeError>, expected: f64) {
+    let actual = actual.unwrap().into_f64();
+    // Correctly rounded to 2^-32.
+    assert!((actual - expected).abs() <= 0.5f64.powi(32), "{actual} vs {expected}");
+}
+
+#[test]
+fn hyperbolic() {
+    for raw in (-(1 << 15)..(1 << 15)).step_by(97) {
+        let x = I32::<16, 15>::new(raw).unwrap();
+        let xf = x.into_f64();
+        close(cordic::sinh(x), xf.sinh());
+        close(cordic::cosh(x), xf.cosh());
+        if raw > -1 << 15 {
+            close(cordic::atanh(x), xf.atanh());
+        }
+    }
+    let minus_one = I32::<16, 15>::new(-1 << 15).unwrap();
+    assert_eq!(cordic::atanh::<Out, _>(minus_one), Err(RangeError::TooSmall));
+}
+
+#[test]
+fn exp_ln() {
+    for raw in (-(1 << 20)..(1 << 20)).step_by(4099) {
+        let x = I32::<24, 16>::new(raw).unwrap();
+        let xf = x.into_f64();
+        if xf < 4.8 {
+            close(cordic::exp(x), xf.exp());
+        }
+        if raw > 0 {
+            close(cordic::ln(x), xf.ln());
+        }
+    }
+    assert_eq!(cordic::exp::<Out, _>(100i8), Err(RangeError::TooLarge));
+    assert_eq!(cordic::exp::<Out, _>(-100i8).unwrap().raw(), 0);
+    assert_eq!(cordic::exp::<Out, _>(i64::MAX), Err(RangeError::TooLarge));
+    assert_eq!(cordic::exp::<Out, _>(0u8).unwrap().raw(), 1 << 32);
+    assert_eq!(cordic::ln::<Out, _>(1u64).unwrap().raw(), 0);
+    close(cordic::ln(u64::MAX), (u64::MAX as f64).ln());
+    close(cordic::ln(U64::<1, 64>::new(1).unwrap()), -64. * 2f64.ln());
+}
+
+#[test]
+#[should_panic(expected = "logarithm of a non-positive number")]
+fn ln_zero() {
+    let _ = cordic::ln::<Out, _>(0i32);
+}
+
+#[test]
+fn exp2_log2() {
+    for raw in (-(1 << 20)..(1 << 20)).step_by(4099) {
+        let x = I32::<24, 16>::new(raw).unwrap();
+        let xf = x.into_f64();
+        if xf < 6.9 {
+            close(cordic::exp2(x), xf.exp2());
+        }
+        if raw > 0 {
+            close(cordic::log2(x), xf.log2());
+        }
+    }
+    assert_eq!(cordic::exp2::<Out, _>(3u8).unwrap().raw(), 8 << 32);
+    assert_eq!(cordic::exp2::<Out, _>(-32i8).unwrap().raw(), 1);
+    assert_eq!(cordic::exp2::<Out, _>(7u8), Err(RangeError::TooLarge));
+    assert_eq!(cordic::log2::<Out, _>(1u64 << 40).unwrap().raw(), 40 << 32);
+    close(cordic::log2(U64::<1, 64>::new(1).unwrap()), -64.);
+}
+
+#[test]
+fn cos_sin_turns() {
+    for raw in (-(1 << 20)..(1 << 20)).step_by(3001) {
+        let x = I32::<24, 16>::new(raw).unwrap();
+        let angle = x.into_f64() * std::f64::consts::TAU;
+        let (cos, sin) = cordic::cos_sin_turns::<Out, _>(x).unwrap();
+        close(Ok(cos), angle.cos());
+        close(Ok(sin), angle.sin());
+    }
+    let one = 1 << 32;
+    let (cos, sin) = cordic::cos_sin_turns::<Out, _>(7u8).unwrap();
+    assert_eq!((cos.raw(), sin.raw()), (one, 0));
+    let (cos, sin) = cordic::cos_sin_turns::<Out, _>(U8::<2, 2>::new(1).unwrap()).unwrap();
+    assert_eq!((cos.raw(), sin.raw()), (0, one));
+    // Angles from a wrapping counter: 3/4 turn is the same as -1/4 turn.
+    let three_quarters = U16::<16, 16>::new(3 << 14).unwrap();
+    let (cos, sin) = cordic::cos_sin_turns::<Out, _>(three_quarters).unwrap();
+    assert_eq!((cos.raw(), sin.raw()), (0, -one));
+    assert_eq!(
+        cordic::cos_sin_turns::<I8<8, 7>, _>(0u8),
+        Err(RangeError::TooLarge)
+    );
+}
+
+#[test]
+fn atan2_turns() {
+    for raw in (-(1 << 20)..(1 << 20)).step_by(3001) {
+        let x = I32::<24, 16>::new(raw).unwrap();
+        let (cos, sin) = cordic::cos_sin_turns::<I64<48, 40>, _>(x).unwrap();
+        let expected = x.into_f64().rem_euclid(1.);
+        let expected = if expected > 0.5 { expected - 1. } else { expected };
+        let actual: Out = cordic::atan2_turns(sin, cos).unwrap();
+        assert!((actual.into_f64() - expected).abs() <= 0.5f64.powi(32) + 0.5f64.powi(39));
+    }
+    let turns = |y: i32, x: i32| cordic::atan2_turns::<Out, _>(y, x).unwrap().into_f64();
+    assert_eq!(turns(0, 0), 0.);
+    assert_eq!(turns(0, 5), 0.);
+    assert_eq!(turns(3, 0), 0.25);
+    assert_eq!(turns(0, -1), 0.5);
+    assert_eq!(turns(-7, 0), -0.25);
+    assert_eq!(turns(-1, -1), -0.375);
+    assert_eq!(turns(i32::MAX, i32::MAX), 0.125);
+}
@@ -0,0 +1,64 @@
+#![allow(incomplete_features)]
+#![feature(generic_const_exprs)]
+
+use fp::*;
+
+// Current in amps, time steps in seconds, capacity in amp-seconds.
+type Amps = I16<16, 8>;
+type Seconds = U16<10, 10>;
+type Soc = U16<16, 15>;
+
+fn soc(val: f64) -> Prob<Soc> {
+    Prob::new(Soc::from_f64(val).unwrap()).unwrap()
+}
+
+#[test]
+fn integrate() {
+    // 2 Ah = 7200 As, starting half full.
+    let capacity = U16::<13, 0>::new(7200).unwrap();
+    let mut gauge = CoulombCounter::<Amps, Seconds, 20>::new(capacity, soc(0.5));
+    assert_eq!(gauge.state_of_charge::<Soc>(), soc(0.5));
+
+    // Discharge at 1.5 A for 30 minutes, sampled every 0.25 s: 2700 As.
+    let dt = Seconds::from_f64(0.25).unwrap();
+    for _ in 0..7200 {
+        gauge.record(Amps::from_f64(-1.5).unwrap(), dt).unwrap();
+    }
+    assert_eq!(gauge.count(), 7200);
+    assert_eq!(gauge.charge().into_f64(), -2700.);
+    assert_eq!(gauge.state_of_charge::<Soc>(), soc(0.125));
+
+    // Charge at 3 A for an hour, which saturates at full.
+    for _ in 0..14400 {
+        gauge.record(Amps::from_f64(3.).unwrap(), dt).unwrap();
+    }
+    assert_eq!(gauge.charge().into_f64(), 8100.);
+    assert_eq!(gauge.state_of_charge::<Soc>(), Prob::one());
+    gauge.reset(Prob::<Soc>::one());
+    assert_eq!(gauge.count(), 0);
+    assert_eq!(gauge.charge().raw(), 0);
+
+    // A small discharge is resolved, and rounded to the nearest output value.
+    gauge.record(Amps::from_f64(-0.5).unwrap(), dt).unwrap();
+    let expected = 1. - 0.125 / 7200.;
+    let actual = gauge.state_of_charge::<U32<32, 31>>().get().into_f64();
+    assert!((actual - expected).abs() <= 0.5f64.powi(32));
+    // 1.7e-5 below full is more than half a step of 2^-15.
+    assert_eq!(gauge.state_of_charge::<Soc>().get().raw(), (1 << 15) - 1);
+}
+
+#[test]
+fn max_runtime() {
+    let mut gauge = CoulombCounter::<I8<8, 0>, U8<8, 0>, 4>::new(1u8, soc(0.));
+    for _ in 0..16 {
+        gauge
+            .record(I8::new(-128).unwrap(), U8::new(255).unwrap())
+            .unwrap();
+    }
+    assert_eq!(
+        gauge.record(I8::new(0).unwrap(), U8::new(0).unwrap()),
+        Err(RangeError::TooLarge)
+    );
+    assert_eq!(gauge.charge().raw(), -128 * 255 * 16);
+    assert_eq!(gauge.state_of_charge::<Soc>(), Prob::zero());
+}
@@ -0,0 +1,105 @@
+use core::time::Duration;
+use fp::*;
+
+#[test]
+fn arithmetic() {
+    let a = FixedDuration::from_raw(3 << 31);
+    let b = FixedDuration::SECOND;
+    assert_eq!((a + b).raw(), 5 << 31);
+    assert_eq!((a - b).raw(), 1 << 31);
+    assert_eq!(b.checked_sub(a), None);
+    assert_eq!(b.saturating_sub(a), FixedDuration::ZERO);
+    assert_eq!((a * 3).raw(), 9 << 31);
+    assert_eq!((a / 3).raw(), 1 << 31);
+    assert_eq!(
+        FixedDuration::MAX.checked_add(FixedDuration::from_raw(1)),
+        None
+    );
+    assert_eq!(FixedDuration::MAX.checked_mul(2), None);
+    assert_eq!(a.checked_div(0), None);
+    assert_eq!((a * 5).as_secs(), 7);
+    assert_eq!(a.seconds(), U64::<64, 32>::new(3 << 31).unwrap());
+    assert_eq!(FixedDuration::from_seconds(U64::new(3 << 31).unwrap()), a);
+}
+
+#[test]
+#[should_panic(expected = "overflow when subtracting durations")]
+fn negative_difference() {
+    let _ = FixedDuration::ZERO - FixedDuration::from_raw(1);
+}
+
+#[test]
+fn scale() {
+    let a = FixedDuration::from_raw(1000);
+    assert_eq!(
+        a.checked_scale(U8::<8, 4>::new(24).unwrap()).unwrap().raw(),
+        1500
+    );
+    assert_eq!(
+        a.checked_scale(I16::<16, 12>::new(3).unwrap())
+            .unwrap()
+            .raw(),
+        1
+    );
+    assert_eq!(
+        a.checked_scale(I16::<16, 12>::new(2).unwrap())
+            .unwrap()
+            .raw(),
+        0
+    );
+    assert_eq!(a.checked_scale(I8::<8, 0>::new(-1).unwrap()), None);
+    assert_eq!(a.checked_scale(I64::<64, -8>::new(1 << 60).unwrap()), None);
+    assert_eq!(
+        a.checked_scale(I32::<32, -8>::new(1 << 10).unwrap())
+            .unwrap()
+            .raw(),
+        1000 << 18
+    );
+}
+
+#[test]
+fn ticks() {
+    let hz = 32768;
+    assert_eq!(
+        FixedDuration::from_ticks(hz.into(), hz),
+        Some(FixedDuration::SECOND)
+    );
+    assert_eq!(FixedDuration::from_ticks(1, hz).unwrap().raw(), 1 << 17);
+    assert_eq!(FixedDuration::from_ticks(1, 3).unwrap().raw(), 0x5555_5555);
+    assert_eq!(FixedDuration::from_ticks(2, 3).unwrap().raw(), 0xaaaa_aaab);
+    assert_eq!(FixedDuration::from_ticks(u64::MAX, 1), None);
+    assert_eq!(FixedDuration::SECOND.to_ticks(48_000_000), 48_000_000);
+    for ticks in 0..2000 {
+        let duration = FixedDuration::from_ticks(ticks, 1_000_003).unwrap();
+        assert_eq!(duration.to_ticks(1_000_003), ticks);
+    }
+}
+
+#[test]
+fn core_duration() {
+    let d = Duration::from_millis(1500);
+    let fixed = FixedDuration::try_from(d).unwrap();
+    assert_eq!(fixed.raw(), 3 << 31);
+    assert_eq!(Duration::from(fixed), d);
+    assert_eq!(
+        FixedDuration::try_from(Duration::from_secs(1 << 32)),
+        Err(RangeError::TooLarge)
+    );
+    assert_eq!(
+        FixedDuration::try_from(Duration::new(u32::MAX.into(), 999_999_999)).unwrap(),
+        FixedDuration::from_raw(u64::MAX - 3)
+    );
+    assert_eq!(
+        Duration::from(FixedDuration::MAX),
+        Duration::from_secs(1 << 32)
+    );
+    assert_eq!(Duration::from(FixedDuration::from_raw(1)), Duration::ZERO);
+    assert_eq!(
+        Duration::from(FixedDuration::from_raw(3)),
+        Duration::from_nanos(1)
+    );
+    for nanos in (0..1_000_000_000).step_by(999_983) {
+        let d = Duration::new(12, nanos);
+        assert_eq!(Duration::from(FixedDuration::try_from(d).unwrap()), d);
+    }
+}
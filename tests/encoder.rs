@@ -0,0 +1,82 @@
+#![allow(incomplete_features)]
+#![feature(generic_const_exprs)]
+
+use fp::*;
+
+type Revs = I32<32, 16>;
+
+#[test]
+fn position_wraps() {
+    // 1000-line encoder (4000 counts per revolution) on a 16-bit counter.
+    let mut enc = Encoder::<4>::new(4000, 16, 1000);
+    let mut count = 65000u32;
+    enc.update(count);
+    assert_eq!(enc.counts(), 0);
+    for _ in 0..10 {
+        count = (count + 1000) % 65536;
+        enc.update(count);
+    }
+    assert_eq!(enc.counts(), 10_000);
+    assert_eq!(enc.position::<Revs>().unwrap().raw(), 5 << 15);
+    assert_eq!(enc.angle::<U16<16, 16>>().unwrap().raw(), 1 << 15);
+    for _ in 0..30 {
+        count = count.wrapping_sub(1000) & 0xffff;
+        enc.update(count);
+    }
+    assert_eq!(enc.counts(), -20_000);
+    assert_eq!(enc.position::<Revs>().unwrap().raw(), -5 << 16);
+    assert_eq!(enc.angle::<U16<16, 16>>().unwrap().raw(), 0);
+    assert_eq!(enc.position::<I8<8, 6>>(), Err(RangeError::TooSmall));
+    // 3999/4000 of a revolution rounds up to a full revolution, which wraps to zero.
+    enc.update(count + 3999);
+    assert_eq!(enc.angle::<U8<8, 8>>().unwrap().raw(), 0);
+}
+
+#[test]
+fn velocity() {
+    let mut enc = Encoder::<8>::new(4000, 32, 1000);
+    let mut count = 0u32;
+    // 3 counts per sample at 1 kHz = 0.75 rev/s.
+    for _ in 0..20 {
+        enc.update(count);
+        count = count.wrapping_sub(3);
+    }
+    assert_eq!(enc.velocity::<Revs>().unwrap().into_f64(), -0.75);
+    // Alternating 1 and 2 counts per sample averages out over the window.
+    for i in 0..16 {
+        enc.update(count);
+        count += 1 + i % 2;
+    }
+    assert_eq!(enc.velocity::<Revs>().unwrap().into_f64(), 0.375);
+
+    // With filtering, a step in velocity is approached exponentially.
+    let alpha = U16::<16, 16>::one_pole_alpha(1, 10);
+    let mut enc = Encoder::<1>::new(4000, 32, 1000).with_filter(alpha);
+    let mut count = 0u32;
+    let mut previous = 0.;
+    for _ in 0..100 {
+        enc.update(count);
+        count += 40;
+        let v = enc.velocity::<Revs>().unwrap().into_f64();
+        assert!(v >= previous && v <= 10.);
+        previous = v;
+    }
+    assert!(previous > 9.99);
+}
+
+#[test]
+fn index() {
+    let mut enc = Encoder::<2>::new(4000, 16, 1000);
+    enc.update(100);
+    enc.update(1100);
+    // The index is latched 1020 counts after the start, so the position is off by 20.
+    assert_eq!(enc.index(1120), -1020);
+    assert_eq!(enc.counts(), -20);
+    enc.update(5100);
+    // A correct index one revolution later needs no correction.
+    assert_eq!(enc.index(5120), 0);
+    // Three missed counts are corrected at the next index.
+    enc.update(9100);
+    assert_eq!(enc.index(9117), 3);
+    assert_eq!(enc.counts(), 7983);
+}
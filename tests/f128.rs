@@ -0,0 +1,46 @@
+#![allow(incomplete_features)]
+#![feature(generic_const_exprs)]
+#![cfg(feature = "f128")]
+#![feature(f128)]
+
+use fp::*;
+
+#[test]
+fn wide_accumulator() {
+    // A 96-bit energy counter cannot be exported exactly as f64, but it can as f128.
+    let energy = I128::<96, 32>::MAX;
+    let val = energy.into_f128();
+    assert_eq!(val, (((1i128 << 95) - 1) as f128) / (1u64 << 32) as f128);
+    assert_eq!(I128::<96, 32>::from_f128(val), Ok(energy));
+    assert_eq!(
+        I128::<96, 32>::from_f128(-val - 1.),
+        Err(RangeError::TooSmall)
+    );
+    assert_eq!(
+        I128::<96, 32>::from_f128(val + 1.),
+        Err(RangeError::TooLarge)
+    );
+    let x = U128::<113, 100>::new((1 << 113) - 1).unwrap();
+    assert_eq!(U128::<113, 100>::try_from(f128::from(x)), Ok(x));
+    let x = I256::<200, 100>::from_f128(-1.5).unwrap();
+    assert_eq!(x.raw(), i256::from(-3) << 99);
+    assert!(I256::<200, 100>::from_f128(1e40).is_err());
+}
+
+#[test]
+fn extreme_shifts() {
+    let tiny = I8::<1, 16494>::new(-1).unwrap();
+    assert_eq!(tiny.into_f128(), -f128::from_bits(1));
+    let huge = U8::<1, -16383>::new(1).unwrap();
+    assert_eq!(huge.into_f128(), f128::from_bits(0x7ffe << 112));
+    assert_eq!(
+        I256::<100, 50>::from_f128(-0.75).unwrap().raw(),
+        i256::from(-3) << 48
+    );
+}
+
+#[test]
+#[should_panic(expected = "number could be truncated in f128")]
+fn truncation() {
+    I128::<114, 0>::MAX.into_f128();
+}
@@ -0,0 +1,128 @@
+#![allow(incomplete_features)]
+#![feature(generic_const_exprs)]
+#![cfg(feature = "ffi")]
+
+use fp::ffi::*;
+use fp::*;
+
+#[test]
+fn layout() {
+    assert_eq!(
+        core::mem::size_of::<I32<20, 10>>(),
+        core::mem::size_of::<i32>()
+    );
+    assert_eq!(
+        core::mem::align_of::<U16<9, 3>>(),
+        core::mem::align_of::<u16>()
+    );
+    // A raw value written by C can be reinterpreted as the fixed-point type.
+    let mut raw = 0i32;
+    assert_eq!(
+        unsafe { fp_i32_from_f64(2.375, 20, 3, &mut raw) },
+        FpStatus::Ok
+    );
+    let val: I32<20, 3> = unsafe { core::mem::transmute(raw) };
+    assert_eq!(val, I32::<20, 3>::from_f64(2.375).unwrap());
+}
+
+#[test]
+fn conversions() {
+    assert!(fp_i8_in_range(-4, 3));
+    assert!(!fp_i8_in_range(4, 3));
+    assert!(!fp_u64_in_range(0, 65));
+    assert!(fp_u64_in_range(u64::MAX, 64));
+
+    let mut raw = 0i16;
+    assert_eq!(
+        unsafe { fp_i16_from_f64(-1.9, 8, 2, &mut raw) },
+        FpStatus::Ok
+    );
+    assert_eq!(raw, -7);
+    assert_eq!(
+        unsafe { fp_i16_from_f64(32., 8, 2, &mut raw) },
+        FpStatus::TooLarge
+    );
+    assert_eq!(
+        unsafe { fp_u8_from_f64(-1., 8, 0, &mut 0) },
+        FpStatus::TooSmall
+    );
+    assert_eq!(
+        unsafe { fp_i32_from_f64(f64::NAN, 8, 0, &mut 0) },
+        FpStatus::Invalid
+    );
+    assert_eq!(raw, -7);
+
+    let mut val = 0.;
+    assert_eq!(unsafe { fp_i64_to_f64(-7, 2, &mut val) }, FpStatus::Ok);
+    assert_eq!(val, -1.75);
+    assert_eq!(
+        unsafe { fp_u64_to_f64(1 << 60, 1100, &mut val) },
+        FpStatus::Ok
+    );
+    assert_eq!(val, f64::from_bits(1 << 34));
+    assert_eq!(
+        unsafe { fp_u64_to_f64(u64::MAX, 0, &mut val) },
+        FpStatus::Inexact
+    );
+    assert_eq!(
+        unsafe { fp_i32_to_f64(1, 1075, &mut val) },
+        FpStatus::Inexact
+    );
+    assert_eq!(val, f64::from_bits(1 << 34));
+    // Extreme shifts from C are out of range, not a panic.
+    for shift in [i32::MIN, i32::MAX] {
+        assert_eq!(
+            unsafe { fp_i32_to_f64(-2, shift, &mut val) },
+            FpStatus::Inexact
+        );
+    }
+    let mut raw = 1i32;
+    assert_eq!(
+        unsafe { fp_i32_from_f64(1.5, 8, i32::MAX, &mut raw) },
+        FpStatus::TooLarge
+    );
+    assert_eq!(
+        unsafe { fp_i32_from_f64(1.5, 8, i32::MIN, &mut raw) },
+        FpStatus::Ok
+    );
+    assert_eq!(raw, 0);
+
+    let mut raw = 0i32;
+    assert_eq!(
+        unsafe { fp_i32_rescale(-11, 3, 1, 8, &mut raw) },
+        FpStatus::Ok
+    );
+    assert_eq!(raw, -3);
+    assert_eq!(
+        unsafe { fp_i32_rescale(10, 0, 4, 8, &mut raw) },
+        FpStatus::TooLarge
+    );
+    assert_eq!(
+        unsafe { fp_i32_rescale(-5, 0, 40, 32, &mut raw) },
+        FpStatus::TooSmall
+    );
+    assert_eq!(
+        unsafe { fp_u32_rescale(7, 0, -40, 32, &mut 1) },
+        FpStatus::Ok
+    );
+
+    // No shift may panic across the boundary.
+    let mut raw = 1i32;
+    assert_eq!(
+        unsafe { fp_i32_rescale(0, 0, 200, 32, &mut raw) },
+        FpStatus::Ok
+    );
+    assert_eq!(raw, 0);
+    assert_eq!(
+        unsafe { fp_i32_rescale(0, i32::MIN, i32::MAX, 32, &mut raw) },
+        FpStatus::Ok
+    );
+    assert_eq!(
+        unsafe { fp_i64_rescale(1, i32::MIN, i32::MAX, 64, &mut 0) },
+        FpStatus::TooLarge
+    );
+    assert_eq!(
+        unsafe { fp_i64_rescale(-1, i32::MAX, i32::MIN, 64, &mut 1) },
+        FpStatus::Ok
+    );
+}
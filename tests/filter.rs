@@ -0,0 +1,97 @@
+#![allow(incomplete_features)]
+#![feature(generic_const_exprs)]
+
+use fp::*;
+
+// 1 kHz sample rate, 20 ms time constant.
+const ALPHA: U16<16, 16> = U16::one_pole_alpha(1, 20);
+const DECAY: I32<32, 30> = I32::one_pole_decay(1, 20);
+
+fn assert_rounded(raw: i128, shift: i32, expected: f64) {
+    let err = raw as f64 - expected * 2f64.powi(shift);
+    assert!(err.abs() <= 0.5 + 1e-6, "{raw} vs {expected}");
+}
+
+#[test]
+fn one_pole() {
+    assert_rounded(ALPHA.raw() as i128, 16, 1. - (-0.05f64).exp());
+    assert_rounded(DECAY.raw() as i128, 30, (-0.05f64).exp());
+    assert_eq!(
+        U32::<32, 30>::one_pole_alpha(1, 20).raw() + DECAY.raw() as u32,
+        1 << 30
+    );
+    for (period, tau) in [
+        (1, 1),
+        (3, 7),
+        (1, 1_000_000),
+        (100, 1),
+        (1_000_000, 48_000 * 250),
+    ] {
+        let ratio = period as f64 / tau as f64;
+        let alpha = I64::<40, 38>::one_pole_alpha(period, tau);
+        assert_rounded(alpha.raw() as i128, 38, -(-ratio).exp_m1());
+        let decay = U64::<52, 50>::one_pole_decay(period, tau);
+        assert_rounded(decay.raw() as i128, 50, (-ratio).exp());
+    }
+    // Very long time constants round to zero, and very short ones to one.
+    assert_eq!(U8::<8, 7>::one_pole_alpha(1, 1000).raw(), 0);
+    assert_eq!(U8::<8, 7>::one_pole_alpha(1000, 1).raw(), 1 << 7);
+    assert_eq!(U8::<8, 7>::one_pole_decay(u64::MAX, 1).raw(), 0);
+}
+
+#[test]
+#[should_panic(expected = "coefficient does not fit in raw type")]
+fn too_wide() {
+    let _ = U16::<16, 16>::one_pole_alpha(1000, 1);
+}
+
+// Butterworth lowpass biquad at fs / 10, with the feedback coefficients in Q30.
+const BIQUAD: [I32<32, 30>; 2] =
+    I32::assert_iir_stable([I32::new_const(-1_278_229_352), I32::new_const(443_340_104)]);
+
+#[test]
+fn iir_stability() {
+    assert!(iir_is_stable(BIQUAD));
+    assert!(iir_is_stable::<I8<8, 6>, 0>([]));
+    // The stability triangle of second-order filters: |a2| < 1 and |a1| < 1 + a2.
+    for a1 in i8::MIN..=i8::MAX {
+        for a2 in i8::MIN..=i8::MAX {
+            let expected = (a2 as i32).abs() < 64 && (a1 as i32).abs() < 64 + a2 as i32;
+            let a = [I8::<8, 6>::new(a1).unwrap(), I8::new(a2).unwrap()];
+            assert_eq!(iir_is_stable(a), expected, "{a1} {a2}");
+        }
+    }
+    // Third order, from roots: (z - 0.875)^3 is stable, but moving one root onto or
+    // outside the unit circle is not.
+    let cubic = |roots: [f64; 3]| {
+        let [r, s, t] = roots;
+        [-(r + s + t), r * s + r * t + s * t, -r * s * t]
+            .map(|coef| I32::<32, 24>::from_f64(coef).unwrap())
+    };
+    assert!(iir_is_stable(cubic([0.875, 0.875, 0.875])));
+    assert!(iir_is_stable(cubic([-0.96875, 0.5, 0.25])));
+    assert!(!iir_is_stable(cubic([1., 0.5, 0.25])));
+    assert!(!iir_is_stable(cubic([-1.03125, 0.5, 0.25])));
+    // A resonator with poles at radius 0.9999 is made unstable by quantizing its
+    // coefficients too coarsely.
+    let (r, theta) = (0.9999f64, 0.01f64);
+    let a = [-2. * r * theta.cos(), r * r];
+    let q30 = a.map(|coef| I32::<32, 30>::new((coef * 2f64.powi(30)).round() as i32).unwrap());
+    let q12 = a.map(|coef| I16::<16, 12>::new((coef * 2f64.powi(12)).round() as i16).unwrap());
+    assert!(iir_is_stable(q30));
+    assert!(!iir_is_stable(q12));
+}
+
+#[test]
+#[should_panic(expected = "IIR filter is unstable")]
+fn iir_unstable() {
+    let _ = I16::<16, 14>::assert_iir_stable([I16::new_const(-1 << 15), I16::new_const(1 << 14)]);
+}
+
+#[test]
+#[should_panic(expected = "too many bits for IIR stability check")]
+fn iir_too_wide() {
+    let a: [I64<64, 60>; 8] =
+        core::array::from_fn(|i| I64::new((1 << 50) + 2 * i as i64 + 1).unwrap());
+    let _ = iir_is_stable(a);
+}
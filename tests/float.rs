@@ -129,3 +129,42 @@ fn f64_truncation() {
     let num: I64<{ f64::MANTISSA_DIGITS + 1 }, 0> = unsafe { I64::new_unchecked(0) };
     let _ = num.into_f64();
 }
+
+#[test]
+fn integer_only_conversions() {
+    // Compare against float arithmetic for a range of raw values and shifts.
+    for raw in (-(1 << 23)..1 << 23).step_by(4099) {
+        let x = I32::<24, 30>::new(raw).unwrap();
+        assert_eq!(x.into_f32(), raw as f32 / 2f32.powi(30));
+        assert_eq!(I32::<24, 30>::from_f32(x.into_f32()), Ok(x));
+        let y = I64::<53, -900>::new(raw as i64).unwrap();
+        assert_eq!(y.into_f64(), raw as f64 * 2f64.powi(900));
+        assert_eq!(I64::<53, -900>::from_f64(y.into_f64()), Ok(y));
+    }
+    // Conversion from float truncates toward zero.
+    assert_eq!(I8::<8, 1>::from_f32(-1.75).unwrap().raw(), -3);
+    assert_eq!(U8::<8, 1>::from_f64(1.75).unwrap().raw(), 3);
+    // Values which truncate to MIN or MAX, but lie beyond them, are out of range.
+    assert_eq!(I8::<4, 0>::from_f32(7.5), Err(RangeError::TooLarge));
+    assert_eq!(I8::<4, 0>::from_f32(-8.5), Err(RangeError::TooSmall));
+    assert_eq!(U8::<4, 0>::from_f64(-0.5), Err(RangeError::TooSmall));
+    assert_eq!(U8::<4, 0>::from_f64(-0.0), Ok(U8::ZERO));
+    // Types which are too wide for exact conversion to float can still convert from float.
+    assert_eq!(I128::<128, 0>::from_f64(-2f64.powi(127)), Ok(I128::MIN));
+    assert_eq!(u128::from_f32(3e38).unwrap(), 3e38f32 as u128);
+    assert_eq!(i32::from_f64(2f64.powi(31)), Err(RangeError::TooLarge));
+    // Likewise for the 256-bit accumulators, whose range checks never convert to float.
+    assert_eq!(
+        I256::<256, 0>::from_f64(-2f64.powi(255)),
+        Ok(I256::<256, 0>::MIN)
+    );
+    assert_eq!(u256::from_f32(3e38).unwrap(), u256::from(3e38f32 as u128));
+    assert_eq!(U256::<200, 8>::from_f64(-0.0), Ok(U256::<200, 8>::ZERO));
+    assert_eq!(U256::<200, 8>::from_f64(1e60), Err(RangeError::TooLarge));
+}
+
+#[test]
+#[should_panic(expected = "float is not finite")]
+fn infinity() {
+    let _ = I32::<32, 0>::from_f32(f32::INFINITY);
+}
@@ -1,3 +1,6 @@
+#![allow(incomplete_features)]
+#![feature(generic_const_exprs)]
+
 use fp::*;
 
 #[test]
@@ -6,3 +9,57 @@ fn good_conversions() {
     let x: I32<8, 0> = 125i8.into_fp();
     let x: u16 = U32::<16, 0>::new(5).unwrap().into_fp();
 }
+
+/// Return `val` as signed and back, through the associated types of `Num` only.
+fn round_trip<T: Num>(val: T) -> (i128, Option<i128>)
+where
+    T::Raw: Into<i128>,
+    <T::SignedFp as Num>::Raw: TryFrom<i128> + Into<i128>,
+    <T::UnsignedFp as Num>::Raw: TryFrom<i128> + Into<i128>,
+    [(); (T::BITS + !T::SIGNED as u32) as usize]:,
+    [(); (T::BITS - T::SIGNED as u32) as usize]:,
+    [(); T::SHIFT as usize]:,
+{
+    let signed = val.to_signed();
+    let unsigned = val.try_to_unsigned();
+    (signed.raw().into(), unsigned.map(|u| u.raw().into()))
+}
+
+#[test]
+fn generic_sign_conversion() {
+    let signed: I16<13, 4> = U16::<12, 4>::MAX.to_signed();
+    assert_eq!(signed.raw(), 4095);
+    let unsigned: Option<U16<11, 4>> = I16::<12, 4>::MAX.try_to_unsigned();
+    assert_eq!(unsigned.unwrap().raw(), 2047);
+    assert_eq!(I16::<12, 4>::MIN.try_to_unsigned(), None);
+    assert_eq!(round_trip(I8::<8, 0>::new(-5).unwrap()), (-5, None));
+    assert_eq!(round_trip(U32::<31, -3>::MAX), (i32::MAX.into(), Some(i32::MAX.into())));
+    assert_eq!(round_trip(-7i64), (-7, None));
+}
+
+#[test]
+fn into_wider() {
+    let x = I32::<24, 8>::MIN;
+    let wide: I64<24, 8> = x.into_wider();
+    assert_eq!(wide.raw(), i64::from(x.raw()));
+    let wider = I128::from(wide);
+    assert_eq!(wider, I128::<24, 8>::new(i128::from(x.raw())).unwrap());
+    assert_eq!(U8::<8, -3>::MAX.into_wider::<U16<8, -3>>().raw(), 255);
+    let widest: U256<128, 0> = U128::<128, 0>::MAX.into_wider();
+    assert_eq!(widest.raw(), u256::from(u128::MAX));
+}
+
+#[test]
+fn narrow() {
+    let x = I64::<20, 8>::MIN;
+    let narrow: I32<20, 8> = x.try_narrow().unwrap();
+    assert_eq!(i64::from(narrow.raw()), x.raw());
+    assert_eq!(x.try_narrow::<I16<16, 8>>(), Err(RangeError::TooSmall));
+    assert_eq!(x.try_narrow::<U32<20, 8>>(), Err(RangeError::TooSmall));
+    assert_eq!(I64::<40, 8>::MAX.try_narrow::<I32<32, 8>>(), Err(RangeError::TooLarge));
+    assert_eq!(x.saturating_narrow::<I16<12, 8>>(), I16::<12, 8>::MIN);
+    assert_eq!(I64::<40, 8>::MAX.saturating_narrow::<U8<8, 8>>(), U8::<8, 8>::MAX);
+    let small = I128::<100, -4>::new(-7).unwrap();
+    assert_eq!(small.saturating_narrow::<I8<4, -4>>().raw(), -7);
+    assert_eq!(unsafe { small.narrow_unchecked::<I8<4, -4>>() }.raw(), -7);
+}
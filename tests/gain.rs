@@ -0,0 +1,77 @@
+#![allow(incomplete_features)]
+#![feature(generic_const_exprs)]
+
+use fp::*;
+
+type Db = I16<16, 8>;
+type Rate = U16<16, 16>;
+type Gain = U32<32, 24>;
+
+fn db(val: f64) -> Db {
+    Db::from_f64(val).unwrap()
+}
+
+fn linear(db: f64) -> f64 {
+    10f64.powf(db / 20.)
+}
+
+#[test]
+fn ramp() {
+    let rate = Rate::from_f64(0.25).unwrap();
+    let mut ramp = GainRamp::<Gain>::new(db(0.), rate);
+    assert!(ramp.is_settled());
+    assert_eq!(ramp.gain().unwrap().into_f64(), 1.);
+    assert_eq!(ramp.next_gain().unwrap().into_f64(), 1.);
+
+    ramp.set_target(db(-20.));
+    let mut last = 1.;
+    let mut samples = 0;
+    while !ramp.is_settled() {
+        let gain = ramp.next_gain().unwrap().into_f64();
+        samples += 1;
+        // Each step is exactly 0.25 dB, up to the rounding of the gain.
+        assert!(gain < last);
+        assert!((gain / last - linear(-0.25)).abs() < 1e-6);
+        last = gain;
+    }
+    assert_eq!(samples, 80);
+    assert!((last - 0.1).abs() <= 0.5f64.powi(24));
+
+    // Reversing mid-ramp, and a target which is not a whole number of steps away.
+    ramp.set_target(db(6.1));
+    for _ in 0..10 {
+        ramp.next_gain().unwrap();
+    }
+    assert!((ramp.gain().unwrap().into_f64() - linear(-17.5)).abs() <= 0.5f64.powi(24));
+    while !ramp.is_settled() {
+        ramp.next_gain().unwrap();
+    }
+    let target = db(6.1).into_f64();
+    assert!((ramp.gain().unwrap().into_f64() - linear(target)).abs() <= 0.5f64.powi(24));
+}
+
+#[test]
+fn apply() {
+    let rate = Rate::from_f64(3.).unwrap_or(Rate::MAX);
+    let half = I32::<32, 24>::from_f64(-20. * 2f64.log10()).unwrap();
+    let mut ramp = GainRamp::<U16<16, 15>>::new(half, rate);
+    let sample = I16::<16, 15>::new(-20000).unwrap();
+    let out: I16<16, 15> = ramp.apply(sample).unwrap();
+    assert_eq!(out.raw(), -10000);
+
+    // The gain overflows its type above 2 (+6 dB).
+    ramp.set_target(db(12.));
+    let mut result = Ok(out);
+    while !ramp.is_settled() {
+        result = ramp.apply(sample);
+    }
+    assert_eq!(result, Err(RangeError::TooLarge));
+    assert_eq!(ramp.gain(), Err(RangeError::TooLarge));
+
+    // Silence: the gain underflows to zero.
+    let mut ramp = GainRamp::<U16<16, 15>>::new(db(-120.), rate);
+    assert_eq!(
+        ramp.apply::<_, I16<16, 15>>(sample),
+        Ok(I16::new(0).unwrap())
+    );
+}
@@ -0,0 +1,55 @@
+use fp::*;
+
+#[test]
+fn uniform_bins() {
+    let lo = I32::<16, 4>::from_f32(-2.0).unwrap();
+    let width = I32::<16, 4>::from_f32(1.0).unwrap();
+    let mut hist = Histogram::<_, 4>::uniform(lo, width).unwrap();
+    for x in [-3.0, -2.0, -1.5, 0.0, 0.5, 0.9375, 1.0, 1.9375, 2.0, 7.0] {
+        hist.record(I32::from_f32(x).unwrap());
+    }
+    assert_eq!(hist.underflow(), 1);
+    assert_eq!(hist.counts(), &[2, 0, 3, 2]);
+    assert_eq!(hist.overflow(), 2);
+    assert_eq!(hist.total(), 10);
+    assert_eq!(
+        hist.bin_edges(3),
+        (I32::from_f32(1.0).unwrap(), I32::from_f32(2.0).unwrap())
+    );
+    hist.clear();
+    assert_eq!(hist.total(), 0);
+}
+
+#[test]
+fn uniform_overflow() {
+    let lo = U8::<8, 0>::new(200).unwrap();
+    let width = U8::<8, 0>::new(10).unwrap();
+    assert!(Histogram::<_, 5>::uniform(lo, width).is_ok());
+    assert_eq!(
+        Histogram::<_, 6>::uniform(lo, width).unwrap_err(),
+        RangeError::TooLarge
+    );
+}
+
+#[test]
+fn quantiles() {
+    let edges = [0u16, 10, 20, 50].map(|x| U16::<8, 0>::new(x).unwrap());
+    let mut hist = Histogram::new(edges, U16::new(100).unwrap());
+    for x in 0..100 {
+        hist.record(U16::new(x).unwrap());
+    }
+    let q = |x: f32| U32::<17, 16>::from_f32(x).unwrap();
+    assert_eq!(hist.quantile(q(0.0)).unwrap().raw(), 10);
+    assert_eq!(hist.quantile(q(0.1)).unwrap().raw(), 10);
+    assert_eq!(hist.quantile(q(0.11)).unwrap().raw(), 20);
+    assert_eq!(hist.quantile(q(0.5)).unwrap().raw(), 50);
+    assert_eq!(hist.quantile(q(1.0)).unwrap().raw(), 100);
+    hist.record(U16::new(200).unwrap());
+    assert_eq!(hist.quantile(q(1.0)).unwrap_err(), RangeError::TooLarge);
+}
+
+#[test]
+#[should_panic(expected = "histogram edges must be strictly increasing")]
+fn unsorted_edges() {
+    let _ = Histogram::new([0, 2].map(|x| I8::<8, 0>::new(x).unwrap()), I8::new(1).unwrap());
+}
@@ -0,0 +1,67 @@
+#![allow(incomplete_features)]
+#![feature(generic_const_exprs)]
+
+use fp::*;
+
+fn iv(lo: i32, hi: i32) -> Interval<I32<8, 4>> {
+    Interval::new(I32::new(lo).unwrap(), I32::new(hi).unwrap()).unwrap()
+}
+
+fn raw<N: Num>(a: Interval<N>) -> (N::Raw, N::Raw) {
+    (a.lo().raw(), a.hi().raw())
+}
+
+#[test]
+fn construction() {
+    let one = I32::<8, 4>::new(16).unwrap();
+    assert_eq!(Interval::new(one, I32::new(15).unwrap()), None);
+    assert_eq!(raw(Interval::point(one)), (16, 16));
+    let a = iv(-3, 5);
+    assert!(a.contains(I32::new(-3).unwrap()) && a.contains(I32::new(5).unwrap()));
+    assert!(!a.contains(I32::new(6).unwrap()));
+    let width: I32<9, 4> = a.width();
+    assert_eq!(width.raw(), 8);
+    assert_eq!(raw(a.hull(iv(10, 12))), (-3, 12));
+    assert_eq!(a.intersect(iv(2, 12)).map(raw), Some((2, 5)));
+    assert_eq!(a.intersect(iv(6, 12)), None);
+}
+
+#[test]
+fn arithmetic() {
+    let (a, b) = (iv(-3, 5), iv(2, 127));
+    let sum: Interval<I32<9, 4>> = a + b;
+    assert_eq!(raw(sum), (-1, 132));
+    let diff: Interval<I32<9, 4>> = a - b;
+    assert_eq!(raw(diff), (-130, 3));
+    assert_eq!(raw(-iv(-128, 5)), (-5, 128));
+    let product: Interval<I32<16, 8>> = a * b;
+    assert_eq!(raw(product), (-381, 635));
+    assert_eq!(raw(iv(-128, -2) * iv(-128, 3)), (-384, 1 << 14));
+    assert_eq!(raw(iv(2, 3) * iv(4, 5)), (8, 15));
+    // Every product of values in the intervals lies in the product interval.
+    for x in -3..=5 {
+        for y in [2, 50, 127] {
+            let xy = I32::<8, 4>::new(x).unwrap() * I32::<8, 4>::new(y).unwrap();
+            assert!(product.contains(xy));
+        }
+    }
+}
+
+#[test]
+fn directed_rounding() {
+    // [-0.375, 0.375] with 3 fractional bits rounds outward to [-0.5, 0.5] with 1.
+    let a = Interval::new(I16::<8, 3>::new(-3).unwrap(), I16::new(3).unwrap()).unwrap();
+    let b: Interval<I16<8, 1>> = a.try_requantize().unwrap();
+    assert_eq!(raw(b), (-1, 1));
+    // Exact bounds are unchanged, and more fractional bits are exact.
+    let c: Interval<I16<8, 1>> = iv(-16, 8).try_requantize().unwrap();
+    assert_eq!(raw(c), (-2, 1));
+    let d: Interval<I64<20, 12>> = iv(-16, 8).try_requantize().unwrap();
+    assert_eq!(raw(d), (-4096, 2048));
+    // The upper bound rounds up out of range.
+    let e = Interval::new(I16::<8, 3>::new(0).unwrap(), I16::new(127).unwrap()).unwrap();
+    assert_eq!(e.try_requantize::<I16<4, 0>>(), Err(RangeError::TooLarge));
+    assert_eq!((-e).try_requantize::<I16<5, 0>>().map(raw), Ok((-16, 0)));
+    assert_eq!(e.try_requantize::<U8<8, 3>>().map(raw), Ok((0, 127)));
+    assert_eq!((-e).try_requantize::<U8<8, 3>>(), Err(RangeError::TooSmall));
+}
@@ -0,0 +1,87 @@
+#![allow(incomplete_features)]
+#![feature(generic_const_exprs)]
+
+use fp::matlab::*;
+use fp::*;
+
+#[test]
+fn numeric_type() {
+    let nt = NumericType {
+        signed: true,
+        word_length: 16,
+        fraction_length: 13,
+    };
+    assert_eq!("sfix16_En13".parse(), Ok(nt));
+    assert_eq!("numerictype(1,16,13)".parse(), Ok(nt));
+    assert_eq!(" fixdt(true, 16, 13) ".parse(), Ok(nt));
+    assert_eq!(nt.to_string(), "sfix16_En13");
+    assert_eq!(nt.to_numerictype(), "numerictype(1,16,13)");
+    assert!(nt.matches::<I16<16, 13>>());
+    assert!(nt.matches::<I32<16, 13>>());
+    assert!(!nt.matches::<U16<16, 13>>());
+    assert!(!nt.matches::<I16<15, 13>>());
+
+    let nt: NumericType = "ufix8_E2".parse().unwrap();
+    assert_eq!(nt, NumericType::of::<U8<8, -2>>());
+    assert_eq!(nt.to_string(), "ufix8_E2");
+    assert_eq!(nt.to_numerictype(), "numerictype(0,8,-2)");
+    assert_eq!("uint8".parse(), Ok(NumericType::of::<u8>()));
+    assert_eq!("int64".parse(), Ok(NumericType::of::<i64>()));
+    assert_eq!(NumericType::of::<i32>().to_string(), "sfix32");
+    let nt: NumericType = "fixdt(1,16,-2147483648)".parse().unwrap();
+    assert_eq!(nt.to_string(), "sfix16_E2147483648");
+
+    for s in [
+        "",
+        "sfix",
+        "sfix16_",
+        "sfix16_X3",
+        "sfix16_E-3",
+        "sfix16_En+3",
+        "sfix16_E-2147483648",
+        "sfix16_En2147483648",
+        "sfix1",
+        "ufix0",
+        "numerictype(1,16)",
+        "numerictype(2,16,13)",
+        "numerictype(1,16,13,0)",
+        "fixdt(1,16,13",
+        "double",
+    ] {
+        assert_eq!(s.parse::<NumericType>(), Err(ParseNumericTypeError), "{s}");
+    }
+}
+
+#[cfg(feature = "mat")]
+#[test]
+fn mat_file() {
+    let x = [-4096, 0, 8191].map(|raw| I16::<16, 13>::new(raw).unwrap());
+    let y = [0, 1, 1 << 23, (1 << 24) - 1].map(|raw| U32::<24, 0>::new(raw).unwrap());
+    let vectors = [TestVector::from_fp("x", &x), TestVector::from_fp("y", &y)];
+    let mut file = vec![];
+    write_mat(&mut file, &vectors).unwrap();
+    assert_eq!(&file[..10], b"MATLAB 5.0");
+    assert_eq!(&file[124..128], b"\x00\x01IM");
+    assert_eq!(file.len() % 8, 0);
+
+    let read = read_mat(&file[..]).unwrap();
+    assert_eq!(read, vectors);
+    assert_eq!(read[0].to_fp::<I16<16, 13>>(), Some(x.to_vec()));
+    assert_eq!(read[0].to_fp::<I32<16, 13>>().unwrap()[2].raw(), 8191);
+    assert_eq!(read[0].to_fp::<I16<16, 12>>(), None);
+    assert_eq!(read[1].to_fp::<U32<24, 0>>(), Some(y.to_vec()));
+
+    let wide = TestVector::from_fp::<I128<65, 0>>("z", &[]);
+    assert!(write_mat(&mut vec![], &[wide]).is_err());
+    // Raw values must fit in the declared word length, rather than being truncated.
+    let mut bad = TestVector::from_fp("w", &x);
+    bad.raw[1] = 1 << 15;
+    let err = write_mat(&mut vec![], &[bad.clone()]).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+    bad.raw[1] = -(1 << 15) - 1;
+    assert!(write_mat(&mut vec![], &[bad]).is_err());
+    let mut bad = TestVector::from_fp("v", &y);
+    bad.raw[0] = -1;
+    assert!(write_mat(&mut vec![], &[bad]).is_err());
+    assert!(read_mat(&b"not a MAT-file"[..]).is_err());
+}
@@ -0,0 +1,58 @@
+#![allow(incomplete_features)]
+#![feature(generic_const_exprs)]
+
+use fp::*;
+
+fn m<const R: usize, const C: usize>(rows: [[i32; C]; R]) -> Matrix<I32<8, 4>, R, C> {
+    Matrix::new(rows.map(|row| row.map(|x| I32::new(x).unwrap())))
+}
+
+fn raw<N: Num, const R: usize, const C: usize>(a: &Matrix<N, R, C>) -> [[N::Raw; C]; R] {
+    a.rows().map(|row| row.map(|x| x.raw()))
+}
+
+#[test]
+fn add_sub_transpose() {
+    let a = m([[1, -2, 3], [127, -128, 0]]);
+    let b = m([[4, 5, -6], [127, -128, 1]]);
+    let sum: Matrix<I32<9, 4>, 2, 3> = a + b;
+    assert_eq!(raw(&sum), [[5, 3, -3], [254, -256, 1]]);
+    let diff: Matrix<I32<9, 4>, 2, 3> = a - b;
+    assert_eq!(raw(&diff), [[-3, -7, 9], [0, 0, -1]]);
+    let t: Matrix<I32<8, 4>, 3, 2> = a.transpose();
+    assert_eq!(raw(&t), [[1, 127], [-2, -128], [3, 0]]);
+    assert_eq!(t.transpose(), a);
+    assert_eq!(a.get(1, 0).raw(), 127);
+    assert_eq!(raw(&Matrix::<I32<8, 4>, 2, 2>::default()), [[0; 2]; 2]);
+}
+
+#[test]
+fn mul() {
+    let a = m([[1, 2, 3], [4, 5, 6]]);
+    let b = m([[7, 8], [9, 10], [11, 12]]);
+    // Three products per element need 2 more bits.
+    let product: Matrix<I32<18, 8>, 2, 2> = a * b;
+    assert_eq!(raw(&product), [[58, 64], [139, 154]]);
+    let product: Matrix<I32<17, 8>, 3, 3> = b * a;
+    assert_eq!(raw(&product)[2], [59, 82, 105]);
+    // Two products per element need 1 more bit, and the largest needs every bit.
+    let min = m([[-128, -128], [-128, -128]]);
+    let product: Matrix<I32<17, 8>, 2, 2> = min * min;
+    assert_eq!(raw(&product), [[1 << 15; 2]; 2]);
+    // A single product needs no more bits.
+    let outer: Matrix<I32<16, 8>, 2, 2> = m([[3], [-4]]) * m([[5, 6]]);
+    assert_eq!(raw(&outer), [[15, 18], [-20, -24]]);
+}
+
+#[test]
+fn mul_vector() {
+    // Rotation by a quarter turn, with a vector of a different type.
+    let rotation = m([[0, -16], [16, 0]]);
+    let v = [I16::<12, 0>::new(100).unwrap(), I16::new(-7).unwrap()];
+    let rotated: [I32<21, 4>; 2] = rotation * v.map(I32::from);
+    assert_eq!(rotated.map(|x| x.raw()), [112, 1600]);
+    let a = m([[1, 2, 3], [4, 5, 6]]);
+    let x = [1, -1, 2].map(|x| I32::<8, 4>::new(x).unwrap());
+    let y: [I32<18, 8>; 2] = a * x;
+    assert_eq!(y.map(|y| y.raw()), [5, 11]);
+}
@@ -43,3 +43,112 @@ fn max_error_unsigned() {
     let a = U64::<8, 4>::MAX;
     let _ = U64::<8, 4>::new(a.raw() + 1).unwrap();
 }
+
+/// Return the sign predicates of `val` through `Num`, as generic code sees them.
+fn sign<T: Num>(val: T) -> (bool, bool, bool) {
+    (val.is_zero(), val.is_positive(), val.is_negative())
+}
+
+#[test]
+fn sign_predicates() {
+    const { assert!(I16::<12, 4>::new_const(-3).is_negative()) };
+    const { assert!(U8::<8, 0>::new_const(0).is_zero()) };
+    assert_eq!(sign(I16::<12, 4>::MIN), (false, false, true));
+    assert_eq!(sign(I16::<12, 4>::ZERO), (true, false, false));
+    assert_eq!(sign(U128::<128, -8>::MAX), (false, true, false));
+    assert_eq!(sign(-5i64), (false, false, true));
+    assert_eq!(sign(i256::ZERO), (true, false, false));
+}
+
+#[test]
+fn compare_across_bits() {
+    let (a, b) = (
+        I32::<10, 5>::new(-300).unwrap(),
+        I32::<14, 5>::new(-300).unwrap(),
+    );
+    assert!(a == b && b == a);
+    assert!(a < I32::<14, 5>::new(-299).unwrap());
+    assert!(I32::<14, 5>::MIN < a && I32::<14, 5>::MAX > a);
+    assert!(U8::<3, 0>::MAX <= U8::<8, 0>::new(7).unwrap());
+    assert!(U256::<200, 4>::MAX > U256::<100, 4>::MAX);
+    assert_ne!(I128::<1, 0>::MIN, I128::<128, 0>::MIN);
+}
+
+#[test]
+fn compare_with_primitives() {
+    let x = I32::<16, 8>::new(0x180).unwrap();
+    assert!(x > 1 && x < 2u8 && x > -3i128 && x != 1usize);
+    assert!(x == 1.5 && x == 1.5f32 && x < 1.5000001 && x > 1.4999999);
+    assert!(x < f64::INFINITY && x > f32::NEG_INFINITY);
+    let nan = f64::NAN;
+    assert!(!x.eq(&nan) && x.partial_cmp(&nan).is_none());
+    assert!(I8::<8, -4>::new(-3).unwrap() == -48 && U8::<8, -4>::MAX > 4079);
+    assert!(U128::<128, 0>::MAX == u128::MAX && U128::<128, 0>::MAX > i128::MAX);
+    assert!(I128::<128, 127>::MIN == -1 && I128::<128, 127>::MIN < -0.99);
+    assert!(U64::<64, 200>::new(1).unwrap() > 0 && U64::<64, 200>::new(1).unwrap() < 1e-60);
+    assert!(I16::<16, -200>::MIN < i128::MIN && I16::<16, -200>::MIN > -1e300 * 1e10);
+    assert!(I16::<16, -200>::MIN == -(2f64.powi(215)));
+    for raw in i8::MIN..=i8::MAX {
+        let x = I8::<8, 2>::new(raw).unwrap();
+        for k in -40i16..40 {
+            assert_eq!(
+                x.partial_cmp(&k),
+                (f64::from(raw) / 4.).partial_cmp(&f64::from(k))
+            );
+        }
+        let f = f64::from(raw) / 4.;
+        assert!(x == f && x > f - 0.125 && x < f + 0.125);
+    }
+}
+
+#[test]
+fn one() {
+    assert_eq!(I32::<16, 8>::ONE.raw(), 0x100);
+    assert_eq!(U8::<8, 7>::ONE.raw(), 0x80);
+    assert_eq!(I8::<8, 0>::ONE.raw(), 1);
+    assert_eq!(U256::<200, 150>::ONE.raw().limbs(), (1 << 22, 0));
+    assert_eq!(I32::<16, 8>::try_one(), Some(I32::ONE));
+    assert_eq!(U8::<8, 7>::try_one(), Some(U8::ONE));
+    assert_eq!(I8::<8, 7>::try_one(), None);
+    assert_eq!(U8::<8, 8>::try_one(), None);
+    assert_eq!(I16::<16, -1>::try_one(), None);
+    assert_eq!(I64::<0, 0>::try_one(), None);
+    assert_eq!(i16::try_one(), Some(1));
+    assert_eq!(
+        I256::<256, 200>::try_one().unwrap().raw().limbs(),
+        (1 << 72, 0)
+    );
+    assert_eq!(I256::<256, 255>::try_one(), None);
+}
+
+#[test]
+fn default_is_zero() {
+    #[derive(Default)]
+    struct State {
+        x: I32<16, 8>,
+        y: U8<4, -2>,
+        z: I256<200, 100>,
+    }
+    let s = State::default();
+    assert_eq!(s.x.raw(), 0);
+    assert_eq!(s.y.raw(), 0);
+    assert!(s.z.is_zero());
+    let mut x = I16::<10, 3>::MAX;
+    assert_eq!(core::mem::take(&mut x), I16::<10, 3>::MAX);
+    assert_eq!(x.raw(), 0);
+}
+
+#[test]
+fn hash_raw_value() {
+    use std::collections::HashMap;
+    let mut map = HashMap::new();
+    for raw in -8..8 {
+        map.insert(I8::<4, 2>::new(raw).unwrap(), raw);
+    }
+    map.insert(I8::<4, 2>::new(3).unwrap(), 30);
+    assert_eq!(map.len(), 16);
+    assert_eq!(map[&I8::<4, 2>::new(-5).unwrap()], -5);
+    assert_eq!(map[&I8::<4, 2>::new(3).unwrap()], 30);
+    let set: std::collections::HashSet<_> = [I256::<200, 0>::MAX, I256::ZERO, I256::MAX].into();
+    assert_eq!(set.len(), 2);
+}
@@ -0,0 +1,98 @@
+use fp::*;
+
+struct Usd;
+impl Currency for Usd {
+    const CODE: &'static str = "USD";
+}
+
+type Cents = Money<Usd, 2>;
+
+#[test]
+fn add_sub() {
+    let a = Cents::new(1050);
+    let b = Cents::new(-275);
+    assert_eq!((a + b).amount(), 775);
+    assert_eq!((a - b).amount(), 1325);
+    assert_eq!((-a).amount(), -1050);
+    assert_eq!(Cents::new(i64::MAX).checked_add(Cents::new(1)), None);
+    assert_eq!(Cents::new(i64::MIN).checked_sub(Cents::new(1)), None);
+    assert_eq!(Cents::default(), Cents::ZERO);
+    assert!(a > b);
+}
+
+#[test]
+fn banker_rounding() {
+    // Ties go to the even neighbour, in both directions.
+    assert_eq!(Cents::new(5).checked_mul_ratio(1, 2).unwrap().amount(), 2);
+    assert_eq!(Cents::new(7).checked_mul_ratio(1, 2).unwrap().amount(), 4);
+    assert_eq!(Cents::new(-5).checked_mul_ratio(1, 2).unwrap().amount(), -2);
+    assert_eq!(Cents::new(-7).checked_mul_ratio(1, 2).unwrap().amount(), -4);
+    assert_eq!(Cents::new(5).checked_mul_ratio(-1, 2).unwrap().amount(), -2);
+    // 7% of 19.99 is 1.3993.
+    assert_eq!(
+        Cents::new(1999).checked_mul_ratio(7, 100).unwrap().amount(),
+        140
+    );
+    assert_eq!(Cents::new(i64::MAX).checked_mul_ratio(2, 1), None);
+    // 1.5 and 2.5 times a cent.
+    let half = I8::<8, 1>::new(3).unwrap();
+    assert_eq!(Cents::new(1).checked_mul_fp(half).unwrap().amount(), 2);
+    let half = I8::<8, 1>::new(5).unwrap();
+    assert_eq!(Cents::new(1).checked_mul_fp(half).unwrap().amount(), 2);
+    let four = I8::<8, -2>::new(1).unwrap();
+    assert_eq!(Cents::new(-3).checked_mul_fp(four).unwrap().amount(), -12);
+}
+
+#[test]
+fn rescale() {
+    let mills = Money::<Usd, 3>::new(12345);
+    assert_eq!(mills.checked_rescale::<2>().unwrap().amount(), 1234);
+    assert_eq!(
+        Money::<Usd, 3>::new(12355)
+            .checked_rescale::<2>()
+            .unwrap()
+            .amount(),
+        1236
+    );
+    assert_eq!(
+        Cents::new(-1234).checked_rescale::<4>().unwrap().amount(),
+        -123400
+    );
+    assert_eq!(Cents::new(i64::MAX).checked_rescale::<3>(), None);
+    assert_eq!(
+        Cents::new(i64::MAX)
+            .checked_rescale::<0>()
+            .unwrap()
+            .amount(),
+        92233720368547758
+    );
+}
+
+#[test]
+fn allocate_split() {
+    let parts = Cents::new(100).split::<3>();
+    assert_eq!(parts.map(Cents::amount), [34, 33, 33]);
+    let parts = Cents::new(-100).split::<3>();
+    assert_eq!(parts.map(Cents::amount), [-34, -33, -33]);
+    // Both parts have a remainder of one half, so the earlier part gets the extra cent.
+    let parts = Cents::new(5).allocate([3, 7]);
+    assert_eq!(parts.map(Cents::amount), [2, 3]);
+    let parts = Cents::new(1000).allocate([1, 0, 2]);
+    assert_eq!(parts.map(Cents::amount), [333, 0, 667]);
+    let parts = Cents::new(i64::MIN).split::<2>();
+    assert_eq!(parts.map(Cents::amount), [i64::MIN / 2; 2]);
+    let parts = Cents::new(i64::MIN).allocate([1]);
+    assert_eq!(parts.map(Cents::amount), [i64::MIN]);
+    for amount in -50..50 {
+        let parts = Cents::new(amount).allocate([2, 3, 5, 1]);
+        assert_eq!(parts.iter().map(|p| p.amount()).sum::<i64>(), amount);
+    }
+}
+
+#[test]
+fn display() {
+    assert_eq!(Cents::new(-1234).to_string(), "-12.34 USD");
+    assert_eq!(Cents::new(5).to_string(), "0.05 USD");
+    assert_eq!(Money::<Usd, 0>::new(42).to_string(), "42 USD");
+    assert_eq!(format!("{:?}", Cents::new(100)), "Money(1.00 USD)");
+}
@@ -0,0 +1,84 @@
+#![allow(incomplete_features)]
+#![feature(generic_const_exprs)]
+
+use fp::*;
+
+type P = I32<32, 8>;
+type V = I32<24, 12>;
+type A = I32<24, 16>;
+
+/// Run the profile to completion, returning the position and velocity setpoints.
+fn run<const SMOOTH: usize>(profile: &mut MotionProfile<P, V, A, SMOOTH>) -> Vec<(f64, f64)> {
+    let mut out = vec![];
+    while !profile.is_done() {
+        let (p, v) = profile.tick();
+        out.push((p.into_f64(), v.into_f64()));
+        assert!(out.len() < 100_000);
+    }
+    out
+}
+
+#[test]
+fn trapezoid() {
+    let vmax = V::from_f64(2.5).unwrap();
+    let accel = A::from_f64(0.1).unwrap();
+    let mut profile = MotionProfile::<P, V, A>::new(P::from_f64(-10.).unwrap(), vmax, accel);
+    assert!(profile.is_done());
+    profile.set_target(P::from_f64(1000.).unwrap());
+    let out = run(&mut profile);
+    assert_eq!(out.last().unwrap(), &(1000., 0.));
+    let mut last = (-10., 0.);
+    for &(p, v) in &out {
+        assert!(p >= last.0 && p <= 1000.);
+        assert!(v >= 0. && v <= vmax.into_f64());
+        assert!((v - last.1).abs() <= accel.into_f64() + 0.5f64.powi(12));
+        last = (p, v);
+    }
+    // Reaches the maximum velocity and cruises.
+    assert!(out.iter().filter(|(_, v)| *v == 2.5).count() > 300);
+
+    // A short move never reaches the maximum velocity.
+    profile.set_target(P::from_f64(995.).unwrap());
+    let out = run(&mut profile);
+    assert_eq!(out.last().unwrap(), &(995., 0.));
+    assert!(out.iter().all(|&(p, v)| p >= 995. && v <= 0. && v > -1.));
+
+    // Reversing mid-move decelerates first.
+    profile.set_target(P::from_f64(2000.).unwrap());
+    for _ in 0..100 {
+        profile.tick();
+    }
+    profile.set_target(P::from_f64(0.).unwrap());
+    let mut p = profile.tick().0.into_f64();
+    let mut turned = false;
+    for &(q, _) in &run(&mut profile) {
+        turned |= q < p;
+        assert!(turned || q >= p);
+        assert!(!turned || q <= p);
+        p = q;
+    }
+    assert_eq!(p, 0.);
+}
+
+#[test]
+fn s_curve() {
+    let accel = A::from_f64(0.125).unwrap();
+    let mut profile =
+        MotionProfile::<P, V, A, 8>::new(P::from_f64(0.).unwrap(), V::from_f64(4.).unwrap(), accel);
+    profile.set_target(P::from_f64(-500.).unwrap());
+    let out = run(&mut profile);
+    assert_eq!(out.last().unwrap().0, -500.);
+    assert_eq!(
+        profile.tick(),
+        (P::from_f64(-500.).unwrap(), V::new(0).unwrap())
+    );
+    let (mut v0, mut a0) = (0., 0.);
+    for &(_, v) in &out {
+        let a = v - v0;
+        assert!((-4. ..=0.).contains(&v));
+        assert!(a.abs() <= 0.125 + 0.5f64.powi(11));
+        // Jerk is limited to accel / SMOOTH, up to rounding of the velocity.
+        assert!((a - a0).abs() <= 0.125 / 8. + 0.5f64.powi(10));
+        (v0, a0) = (v, a);
+    }
+}
@@ -0,0 +1,129 @@
+#![allow(incomplete_features)]
+#![feature(generic_const_exprs)]
+
+use fp::motor::*;
+use fp::*;
+
+type Amps = I16<16, 8>;
+type Current = I32<32, 16>;
+type Angle = U16<16, 16>;
+
+fn amps(val: f64) -> Amps {
+    Amps::from_f64(val).unwrap()
+}
+
+fn close(actual: Current, expected: f64) {
+    let actual = actual.into_f64();
+    assert!(
+        (actual - expected).abs() <= 0.5f64.powi(16),
+        "{actual} vs {expected}"
+    );
+}
+
+#[test]
+fn transforms() {
+    let sqrt3 = 3f64.sqrt();
+    // A balanced set of phase currents with amplitude 10 A at 30 degrees.
+    let abc = Abc {
+        a: amps(10. * sqrt3 / 2.),
+        b: amps(0.),
+        c: amps(-10. * sqrt3 / 2.),
+    };
+    let ab: AlphaBeta<Current> = abc.clarke().unwrap();
+    close(ab.alpha, abc.a.into_f64());
+    close(ab.beta, (abc.a.into_f64() + 2. * abc.b.into_f64()) / sqrt3);
+    assert_eq!(AlphaBeta::from_two_phases(abc.a, abc.b), Ok(ab));
+    // The common mode is removed.
+    let shifted = Abc {
+        a: amps(1.),
+        b: amps(1.),
+        c: amps(1.),
+    };
+    assert_eq!(shifted.clarke::<Current>().unwrap().alpha.raw(), 0);
+
+    let back: Abc<Current> = ab.inverse_clarke().unwrap();
+    close(back.a, abc.a.into_f64());
+    close(back.b, abc.b.into_f64());
+    close(back.c, abc.c.into_f64());
+
+    for raw in (0..=u16::MAX).step_by(997) {
+        let theta = Angle::new(raw).unwrap();
+        let angle = theta.into_f64() * std::f64::consts::TAU;
+        let (alpha, beta) = (ab.alpha.into_f64(), ab.beta.into_f64());
+        let dq: Dq<Current> = ab.park(theta).unwrap();
+        close(dq.d, alpha * angle.cos() + beta * angle.sin());
+        close(dq.q, beta * angle.cos() - alpha * angle.sin());
+        let round_trip: AlphaBeta<Current> = dq.inverse_park(theta).unwrap();
+        assert!(round_trip.alpha.raw().abs_diff(ab.alpha.raw()) <= 1);
+        assert!(round_trip.beta.raw().abs_diff(ab.beta.raw()) <= 1);
+    }
+    let dq = Dq {
+        d: amps(0.),
+        q: amps(100.),
+    };
+    assert_eq!(
+        dq.inverse_park::<Amps, _>(Angle::new(1 << 15).unwrap()),
+        Ok(AlphaBeta {
+            alpha: amps(0.),
+            beta: amps(-100.)
+        })
+    );
+    assert_eq!(
+        dq.inverse_park::<I8<8, 1>, _>(Angle::new(1 << 15).unwrap()),
+        Err(RangeError::TooSmall)
+    );
+}
+
+#[test]
+fn svm() {
+    type Volts = I32<24, 20>;
+    type DutyCycle = U16<16, 15>;
+    let one = 1 << 15;
+    let zero = AlphaBeta {
+        alpha: Volts::new(0).unwrap(),
+        beta: Volts::new(0).unwrap(),
+    };
+    let half = DutyCycle::new(one / 2).unwrap();
+    assert_eq!(
+        zero.svm::<DutyCycle>(),
+        Abc {
+            a: half,
+            b: half,
+            c: half
+        }
+    );
+
+    let limit = 1. / 3f64.sqrt();
+    for raw in (0..1 << 16).step_by(1009) {
+        let angle = raw as f64 / 65536. * std::f64::consts::TAU;
+        let (alpha, beta) = (limit * angle.cos(), limit * angle.sin());
+        let v = AlphaBeta {
+            alpha: Volts::from_f64(alpha).unwrap(),
+            beta: Volts::from_f64(beta).unwrap(),
+        };
+        let duty = v.svm::<DutyCycle>();
+        let d = [duty.a, duty.b, duty.c].map(|d| d.into_f64());
+        // The line-to-line voltages are reproduced.
+        let phases = v.inverse_clarke::<Current>().unwrap();
+        let p = [phases.a, phases.b, phases.c].map(|p| p.into_f64());
+        for (i, j) in [(0, 1), (1, 2), (2, 0)] {
+            assert!(((d[i] - d[j]) - (p[i] - p[j])).abs() <= 2. / one as f64);
+        }
+        // The duty cycles are centered, and stay within the rails at the limit of the
+        // linear range.
+        let max = d.iter().cloned().fold(0., f64::max);
+        let min = d.iter().cloned().fold(1., f64::min);
+        assert!(((max + min) / 2. - 0.5).abs() <= 1. / one as f64);
+        assert!(max - min <= 1. + 2. / one as f64);
+    }
+
+    // Overmodulation clamps.
+    let big = AlphaBeta {
+        alpha: Volts::from_f64(7.).unwrap(),
+        beta: Volts::from_f64(0.).unwrap(),
+    };
+    let duty = big.svm::<DutyCycle>();
+    assert_eq!(duty.a.raw(), one);
+    assert_eq!(duty.b.raw(), 0);
+    assert_eq!(duty.c.raw(), 0);
+}
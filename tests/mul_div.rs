@@ -20,4 +20,501 @@ fn mul_limits() {
     validate_mul::<I32<4, 0>, U32<5, 0>, I32<9, 0>>();
     validate_mul::<U32<4, 0>, I32<5, 0>, I32<9, 0>>();
     validate_mul::<U32<4, 0>, U32<5, 0>, U32<9, 0>>();
+    validate_mul::<I16<16, 8>, I32<16, 8>, I32<32, 16>>();
+    validate_mul::<I64<40, 0>, I8<8, 4>, I64<48, 4>>();
+    validate_mul::<U8<8, 0>, U128<100, 0>, U128<108, 0>>();
+}
+
+#[test]
+fn mul_div_cross_width() {
+    let a = I16::<12, 8>::from_f64(-1.5).unwrap();
+    let b = I32::<18, 4>::from_f64(2.25).unwrap();
+    let product: I32<30, 12> = a * b;
+    assert_eq!(product.into_f64(), -3.375);
+    let product: I32<30, 12> = b * a;
+    assert_eq!(product.into_f64(), -3.375);
+    let quotient: I32<13, 4> = a / b;
+    assert_eq!(quotient.raw(), -384 / 36);
+    let quotient: I32<19, -4> = b / a;
+    assert_eq!(quotient.raw(), 36 / -384);
+    let quotient: U64<8, 0> = U8::<8, 0>::MAX / U64::<40, 0>::new(5).unwrap();
+    assert_eq!(quotient.raw(), 51);
+}
+
+#[test]
+fn div_rem_matches_primitive() {
+    for a in -8..8 {
+        for b in (-4..4).filter(|&b| b != 0) {
+            let x = I32::<4, 2>::new(a).unwrap();
+            let y = I32::<3, 1>::new(b).unwrap();
+            let (q, r): (I32<5, 1>, I32<3, 2>) = x.div_rem(y);
+            assert_eq!((q.raw(), r.raw()), (a / b, a % b));
+            let (q, r): (I32<5, 1>, I32<3, 2>) = x.div_rem_euclid(y);
+            assert_eq!((q.raw(), r.raw()), (a.div_euclid(b), a.rem_euclid(b)));
+            assert_eq!(x.div_euclid(y), q);
+            assert_eq!(x.rem_euclid(y), r);
+        }
+        for b in 1..16 {
+            let x = I16::<4, 0>::new(a as i16).unwrap();
+            let y = U16::<4, 0>::new(b).unwrap();
+            let (q, r): (I16<4, 0>, I16<5, 0>) = x.div_rem_euclid(y);
+            let b = b as i16;
+            assert_eq!((q.raw(), r.raw()), ((a as i16).div_euclid(b), (a as i16).rem_euclid(b)));
+            let (q, r): (I16<4, 0>, I16<4, 0>) = x.div_rem(y);
+            assert_eq!((q.raw(), r.raw()), (a as i16 / b, a as i16 % b));
+        }
+    }
+    for a in 0..16 {
+        for b in (-8..8).filter(|&b| b != 0) {
+            let x = U8::<4, 0>::new(a).unwrap();
+            let y = I8::<4, 0>::new(b).unwrap();
+            let (q, r): (I8<5, 0>, U8<3, 0>) = x.div_rem_euclid(y);
+            assert_eq!((q.raw(), r.raw() as i8), ((a as i8).div_euclid(b), (a as i8).rem_euclid(b)));
+        }
+    }
+    // A full-width unsigned divisor does not fit in the signed raw type.
+    for a in i8::MIN..=i8::MAX {
+        let x = I8::<8, 0>::new(a).unwrap();
+        for b in 1..=u8::MAX {
+            let (q, r): (I8<8, 0>, I8<8, 0>) = x.div_rem(U8::<8, 0>::new(b).unwrap());
+            assert_eq!(q, x / U8::<8, 0>::new(b).unwrap());
+            let (a, b) = (i16::from(a), i16::from(b));
+            assert_eq!((i16::from(q.raw()), i16::from(r.raw())), (a / b, a % b), "{a} / {b}");
+            if b < 128 {
+                let y = U8::<7, 0>::new(b as u8).unwrap();
+                let (q, r): (I8<8, 0>, I8<8, 0>) = x.div_rem_euclid(y);
+                let euclid = (a.div_euclid(b), a.rem_euclid(b));
+                assert_eq!((i16::from(q.raw()), i16::from(r.raw())), euclid, "{a} / {b}");
+            }
+        }
+    }
+    let x = I16::<4, 0>::new(-5).unwrap();
+    let (q, r): (I16<4, 0>, I16<4, 0>) = x.div_rem(U16::<16, 0>::new(65535).unwrap());
+    assert_eq!((q.raw(), r.raw()), (0, -5));
+    let q: I8<8, 0> = I8::<8, 0>::new(-100).unwrap() / U8::<8, 0>::new(200).unwrap();
+    assert_eq!(q.raw(), 0);
+    let q: I8<8, 0> = I8::<8, 0>::MIN / U8::<8, 0>::new(128).unwrap();
+    assert_eq!(q.raw(), -1);
+}
+
+#[test]
+fn rem_matches_primitive() {
+    for a in i8::MIN..=i8::MAX {
+        let x = I8::<8, 3>::new(a).unwrap();
+        for b in (i8::MIN..=i8::MAX).filter(|&b| b != 0) {
+            let r: I8<8, 3> = x % I8::<8, 0>::new(b).unwrap();
+            assert_eq!(r.raw(), a.wrapping_rem(b));
+            let r: U8<7, 0> = U8::<8, 0>::new(a as u8).unwrap() % I8::<8, 2>::new(b).unwrap();
+            assert_eq!(r.raw() as u32, a as u8 as u32 % b.unsigned_abs() as u32);
+        }
+        for b in 1..=u8::MAX {
+            let r: I8<8, 3> = x % U8::<8, 0>::new(b).unwrap();
+            assert_eq!(r.raw() as i32, a as i32 % b as i32);
+        }
+    }
+    // Phase wrapping, with an integer quotient since both operands have the same shift.
+    let phase = I32::<24, 12>::new(-1000 << 12).unwrap();
+    let turn = I32::<22, 12>::new(360 << 12).unwrap();
+    let r: I32<22, 12> = phase % turn;
+    assert_eq!(r.raw(), -280 << 12);
+    let (q, expected): (I32<25, 0>, I32<22, 12>) = phase.div_rem(turn);
+    assert_eq!((q.raw(), r), (-2, expected));
+}
+
+#[test]
+fn widening_mul() {
+    let a = I32::<32, 16>::MIN;
+    let b = I32::<20, 4>::MIN;
+    let product: I64<52, 20> = a.widening_mul(b);
+    assert_eq!(product.raw(), i32::MIN as i64 * (-1 << 19));
+    let product: U16<16, 0> = U8::<8, 0>::MAX.widening_mul(U8::<8, 0>::MAX);
+    assert_eq!(product.raw(), 255 * 255);
+    let a = I64::<64, 32>::MAX;
+    let product: I128<128, 64> = a.widening_mul(I64::<64, 32>::MIN);
+    assert_eq!(product.raw(), i64::MAX as i128 * i64::MIN as i128);
+    let a = I128::<128, 0>::MIN;
+    let product: I256<256, 0> = a.widening_mul(a);
+    assert_eq!(product.raw(), i256::from_limbs(1 << 126, 0));
+    let a = U128::<128, 0>::MAX;
+    let product: U256<256, 0> = a.widening_mul(a);
+    assert_eq!(product.raw(), u256::from_limbs(u128::MAX - 1, 1));
+}
+
+#[test]
+fn mul_round_ties_to_even() {
+    for a in -8..8 {
+        for b in -8..8 {
+            let x = I8::<4, 2>::new(a).unwrap();
+            let y = I8::<4, 1>::new(b).unwrap();
+            let product: I8<7, 1> = x.mul_round::<2, _>(y);
+            let expected = (f64::from(a) * f64::from(b) / 4.0).round_ties_even();
+            assert_eq!(f64::from(product.raw()), expected, "{a} * {b}");
+        }
+    }
+    let x = U16::<15, 15>::MAX;
+    let product: U32<18, 15> = U32::<16, 15>::MAX.mul_round::<15, _>(U32::<16, 15>::MAX);
+    assert_eq!(product.raw(), (65535 * 65535 + (1 << 14)) >> 15);
+    assert_eq!(x.mul_round::<0, _>(U16::<0, 0>::new(0).unwrap()).raw(), 0);
+    // Rounding up may reach the next power of 2, which needs the extra bit.
+    let x = U32::<16, 0>::MAX;
+    let product: U32<2, -31> = x.mul_round::<31, _>(x);
+    assert_eq!(product.raw(), 2);
+    // Discarding all but the sign bit of the raw type.
+    let x = I8::<4, 0>::MIN;
+    assert_eq!(x.mul_round::<7, _>(x).raw(), 0);
+    assert_eq!(x.mul_round::<7, _>(I8::<4, 0>::new(7).unwrap()).raw(), 0);
+    assert_eq!(I8::<4, 0>::new(-7).unwrap().mul_round::<7, _>(x).raw(), 0);
+    let x = I32::<16, 0>::MIN;
+    assert_eq!(x.mul_round::<30, _>(x).raw(), 1);
+    assert_eq!(x.mul_round::<31, _>(x).raw(), 0);
+    let half = I32::<8, 1>::new(1).unwrap();
+    let odd = I32::<8, 0>::new(3).unwrap();
+    assert_eq!(odd.mul_round::<1, _>(half).raw(), 2);
+    assert_eq!((-odd).mul_round::<1, _>(half).raw(), -2);
+}
+
+#[test]
+fn mul_shr_truncates() {
+    let a = I32::<32, 31>::MIN;
+    let b = I32::<32, 31>::new(-0x4000_0001).unwrap();
+    let product: I32<32, 30> = a.mul_shr::<32, _>(b);
+    assert_eq!(product.raw(), ((i32::MIN as i64 * -0x4000_0001) >> 32) as i32);
+    let product: I32<32, 30> = b.mul_shr::<32, _>(I32::<32, 31>::new(3).unwrap());
+    assert_eq!(product.raw(), -1);
+    let a = U16::<16, 16>::MAX;
+    let product: U16<16, 16> = a.mul_shr::<16, _>(a);
+    assert_eq!(product.raw(), 65534);
+    let a = I64::<64, 40>::new(-(1 << 62) - 7).unwrap();
+    let b = I64::<40, 20>::new((1 << 39) - 3).unwrap();
+    let product: I64<64, 20> = a.mul_shr::<40, _>(b);
+    assert_eq!(product.raw(), ((a.raw() as i128 * b.raw() as i128) >> 40) as i64);
+    let a = I128::<128, 64>::new(i128::MIN + 5).unwrap();
+    let b = I128::<100, 64>::new(-(1 << 99) + 11).unwrap();
+    let product: I128<128, 28> = a.mul_shr::<100, _>(b);
+    let expected = (i256::from(a.raw()) * i256::from(b.raw())) >> 100;
+    assert_eq!(product.raw(), expected.limbs().1 as i128);
+}
+
+#[test]
+fn mla_dot_product() {
+    let x = [0.5, -0.25, 0.75].map(|v| I32::<8, 6>::from_f64(v).unwrap());
+    let h = [-1.5, 2.0, 0.25].map(|v| I32::<7, 4>::from_f64(v).unwrap());
+    let acc = I32::<0, 10>::new(0).unwrap();
+    let acc: I32<16, 10> = acc.mla(x[0], h[0]);
+    let acc: I32<17, 10> = acc.mla(x[1], h[1]);
+    assert_eq!(acc.into_f64(), -1.25);
+    let acc: I32<18, 10> = acc.mla(x[2], h[2]);
+    assert_eq!(acc.into_f64(), -1.0625);
+    for a in [U8::<4, 0>::MIN, U8::<4, 0>::MAX] {
+        for b in [U8::<3, 2>::MIN, U8::<3, 2>::MAX] {
+            let acc: U8<8, 2> = U8::<7, 2>::MAX.mla(a, b);
+            assert_eq!(acc.raw(), 127 + a.raw() * b.raw());
+        }
+    }
+}
+
+#[test]
+fn div_round_matches_float() {
+    for a in -64..64 {
+        for b in (-8..8).filter(|&b| b != 0) {
+            let x = I8::<7, 4>::new(a).unwrap();
+            let y = I8::<4, 1>::new(b).unwrap();
+            let exact = f64::from(a) / f64::from(b);
+            assert_eq!(f64::from(x.div_round(y).raw()), exact.round(), "{a} / {b}");
+            let even = x.div_round_half_even(y);
+            assert_eq!(f64::from(even.raw()), exact.round_ties_even(), "{a} / {b}");
+        }
+    }
+    for a in 0..128 {
+        let x = U8::<7, 0>::new(a).unwrap();
+        let y = I8::<3, 0>::new(-2).unwrap();
+        let exact = -f64::from(a) / 2.0;
+        assert_eq!(f64::from(x.div_round(y).raw()), exact.round(), "{a}");
+        assert_eq!(f64::from(x.div_round_half_even(y).raw()), exact.round_ties_even(), "{a}");
+        let z = U8::<2, 0>::new(3).unwrap();
+        let exact = f64::from(a) / 3.0;
+        assert_eq!(f64::from(x.div_round(z).raw()), exact.round(), "{a}");
+    }
+}
+
+#[test]
+fn rem_euclid_wraps_angle() {
+    // An angle in turns, wrapped into [0, 1).
+    let turn = U32::<17, 16>::new(1 << 16).unwrap();
+    for raw in [-(5 << 16) - 3, -1, 0, 1 << 15, (1 << 16) + 7] {
+        let angle = I32::<20, 16>::new(raw).unwrap();
+        let wrapped: I32<18, 16> = angle.rem_euclid(turn);
+        assert_eq!(wrapped.raw(), raw.rem_euclid(1 << 16));
+        let turns: I32<20, 0> = angle.div_euclid(turn);
+        assert_eq!(turns.raw(), raw.div_euclid(1 << 16));
+    }
+}
+
+#[test]
+fn div_to_keeps_fraction() {
+    for a in -32..32 {
+        for b in (-16..16).filter(|&b| b != 0) {
+            let x = I8::<6, 4>::new(a).unwrap();
+            let y = I8::<5, 4>::new(b).unwrap();
+            let q: I16<11, 4> = x.div_to(y);
+            assert_eq!(q.raw(), (i16::from(a) << 4) / i16::from(b), "{a} / {b}");
+            let q: I8<5, -2> = x.div_to(y);
+            assert_eq!(q.raw(), a / (b << 2), "{a} / {b}");
+        }
+    }
+    let x = I32::<32, 20>::MIN;
+    let y = I32::<32, 20>::new(-1).unwrap();
+    let q: I64<53, 20> = x.div_to(y);
+    assert_eq!(q.raw(), 1 << 51);
+    let x = U32::<32, 20>::MAX;
+    let y = U32::<32, 20>::new(3 << 20).unwrap();
+    let q: U64<52, 20> = x.div_to(y);
+    assert_eq!(q.raw(), (u64::from(u32::MAX) << 20) / (3 << 20));
+    let x = I64::<64, 0>::MIN;
+    let q: I128<125, 60> = x.div_to(I8::<8, 0>::new(-3).unwrap());
+    assert_eq!(q.raw(), (i128::from(i64::MIN) << 60) / -3);
+    // `MIN / -1` is positive, so it needs 1 more bit than `MIN`.
+    let x = I32::<8, 0>::new(-128).unwrap();
+    let q: I32<9, 0> = x.div_to(I32::<8, 0>::new(-1).unwrap());
+    assert_eq!(q.raw(), 128);
+}
+
+#[test]
+#[should_panic]
+fn div_to_by_zero() {
+    let x = I16::<16, 8>::MAX;
+    let _: I32<25, 8> = x.div_to(I16::<16, 8>::new(0).unwrap());
+}
+
+#[test]
+fn recip_matches_division() {
+    for a in (i8::MIN..=i8::MAX).filter(|&a| a != 0) {
+        let x = I8::<8, 4>::new(a).unwrap();
+        let r: I16<14, 8> = x.recip();
+        assert_eq!(r.raw(), (1 << 12) / i16::from(a), "{a}");
+        assert_eq!(x.checked_recip::<I16<14, 8>>(), Some(r));
+    }
+    assert_eq!(I8::<8, 4>::new(0).unwrap().checked_recip::<I16<14, 8>>(), None);
+    let x = U32::<32, 16>::MAX;
+    let r: U64<49, 32> = x.recip();
+    assert_eq!(r.raw(), (1 << 48) / u64::from(u32::MAX));
+    let x = I64::<64, 60>::new(-3).unwrap();
+    let r: I128<124, 62> = x.recip();
+    assert_eq!(r.raw(), (1 << 122) / -3);
+    let r: I8<8, -70> = x.recip();
+    assert_eq!(r.raw(), 0);
+}
+
+#[test]
+#[should_panic(expected = "attempt to take the reciprocal of zero")]
+fn recip_of_zero() {
+    let _: I32<20, 8> = I16::<16, 8>::new(0).unwrap().recip();
+}
+
+#[test]
+fn pow_limits() {
+    for raw in -8..8 {
+        let x = I32::<4, 2>::new(raw).unwrap();
+        let square: I32<8, 4> = x.pow::<2>();
+        assert_eq!(square.raw(), raw * raw);
+        let cube: I32<12, 6> = x.pow::<3>();
+        assert_eq!(cube.raw(), raw * raw * raw);
+        assert_eq!(x.pow::<1>(), x);
+    }
+    let fifth: U64<60, -10> = U64::<12, -2>::MAX.pow::<5>();
+    assert_eq!(fifth.raw(), 4095u64.pow(5));
+}
+
+#[test]
+fn square_is_unsigned() {
+    for raw in i8::MIN..=i8::MAX {
+        let x = I16::<8, 3>::new(raw.into()).unwrap();
+        let square: U16<15, 6> = x.square();
+        assert_eq!(u32::from(square.raw()), (i32::from(raw) * i32::from(raw)) as u32);
+    }
+    let square: U32<32, 0> = U32::<16, 0>::MAX.square();
+    assert_eq!(square.raw(), 65535 * 65535);
+    let square: U64<63, 2> = I64::<32, 1>::MIN.square();
+    assert_eq!(square.raw(), 1 << 62);
+}
+
+#[test]
+fn isqrt_matches_float() {
+    for raw in 0..=u16::MAX {
+        let x = U16::<16, 4>::new(raw).unwrap();
+        let root: U16<9, 2> = x.isqrt();
+        assert_eq!(root.raw(), f64::from(raw).sqrt().floor() as u16, "{raw}");
+    }
+    for raw in 0..=i8::MAX {
+        let x = I8::<8, -2>::new(raw).unwrap();
+        let root: U8<5, -1> = x.isqrt();
+        assert_eq!(root.raw(), f64::from(raw).sqrt().floor() as u8, "{raw}");
+    }
+    let root: U128<65, 0> = U128::<128, 0>::MAX.isqrt();
+    assert_eq!(root.raw(), u128::from(u64::MAX));
+    let root: U64<33, 16> = U64::<64, 32>::new((1 << 40) + 1).unwrap().isqrt();
+    assert_eq!(root.raw(), 1 << 20);
+}
+
+#[test]
+#[should_panic(expected = "argument of isqrt is negative")]
+fn isqrt_negative() {
+    I32::<16, 0>::new(-1).unwrap().isqrt();
+}
+
+#[test]
+fn sqrt_to_matches_float() {
+    for raw in 0..=u16::MAX {
+        let x = U16::<16, 4>::new(raw).unwrap();
+        let root: U32<14, 8> = x.sqrt_to();
+        let expected = (f64::from(raw) * 4096.0).sqrt().floor();
+        assert_eq!(f64::from(root.raw()), expected, "{raw}");
+        let root: U8<7, 1> = x.sqrt_to();
+        assert_eq!(f64::from(root.raw()), (f64::from(raw) / 4.0).sqrt().floor(), "{raw}");
+    }
+    let x = I64::<64, 32>::MAX;
+    let root: I64<64, 46> = x.sqrt_to();
+    let (n, root) = ((i64::MAX as u128) << 60, root.raw() as u128);
+    assert!(root * root <= n && n < (root + 1) * (root + 1));
+    let root: U64<64, 0> = I128::<128, 0>::new(i128::from(i64::MAX).pow(2)).unwrap().sqrt_to();
+    assert_eq!(root.raw(), i64::MAX as u64);
+}
+
+#[test]
+#[should_panic(expected = "argument of sqrt_to is negative")]
+fn sqrt_to_negative() {
+    let _: I32<25, 16> = I32::<16, 0>::new(-1).unwrap().sqrt_to();
+}
+
+#[test]
+fn cbrt_matches_float() {
+    for raw in 0..=u16::MAX {
+        let x = U16::<16, 6>::new(raw).unwrap();
+        let root: U16<6, 2> = x.cbrt();
+        let expected = f64::from(raw).cbrt().floor();
+        // Allow for the rounding of `f64::cbrt` at exact cubes.
+        let exact = (expected + 1.0).powi(3) <= f64::from(raw);
+        assert_eq!(f64::from(root.raw()), expected + f64::from(exact), "{raw}");
+    }
+    for raw in i8::MIN..=i8::MAX {
+        let x = I8::<8, -3>::new(raw).unwrap();
+        let root: I8<4, -1> = x.cbrt();
+        let magnitude = (0..=5).rev().find(|r| r * r * r <= raw.unsigned_abs()).unwrap();
+        assert_eq!(root.raw(), raw.signum() * magnitude as i8, "{raw}");
+    }
+    let root: U128<43, 0> = U128::<128, 0>::MAX.cbrt();
+    assert_eq!(root.raw(), 6_981_463_658_331);
+    let root: I64<23, 0> = I64::<64, 0>::MIN.cbrt();
+    assert_eq!(root.raw(), -2_097_152);
+}
+
+#[test]
+fn hypot_matches_float() {
+    for a in i8::MIN..=i8::MAX {
+        for b in i8::MIN..=i8::MAX {
+            let (x, y) = (I16::<8, 4>::new(a.into()).unwrap(), I16::<8, 4>::new(b.into()).unwrap());
+            let h: I16<9, 4> = x.hypot(y);
+            let expected = f64::from(a).hypot(f64::from(b)).floor();
+            assert_eq!(f64::from(h.raw()), expected, "{a} {b}");
+        }
+    }
+    let max = U8::<7, 0>::MAX;
+    let h: U8<8, 0> = max.hypot(max);
+    assert_eq!(h.raw(), 179);
+    let min = I64::<63, 32>::MIN;
+    let h: I64<64, 32> = min.hypot(min);
+    assert_eq!(h.raw(), 6_521_908_912_666_391_106);
+}
+
+#[test]
+fn mul_const_bit_growth() {
+    for raw in -32..32 {
+        let x = I8::<6, 2>::new(raw).unwrap();
+        let y: I8<8, 2> = x.mul_const::<3>();
+        assert_eq!(y.raw(), raw * 3);
+        let y: I8<8, 2> = x.mul_const::<-3>();
+        assert_eq!(y.raw(), raw * -3);
+        let y: I8<7, 2> = x.mul_const::<-1>();
+        assert_eq!(y.raw(), -raw);
+        let y: I8<6, 2> = x.mul_const::<0>();
+        assert_eq!(y.raw(), 0);
+        // Powers of 2 grow by their exponent, and negated powers of 2 by one more bit.
+        let y: I8<6, 2> = x.mul_const::<1>();
+        assert_eq!(y.raw(), raw);
+        let y: I8<7, 2> = x.mul_const::<2>();
+        assert_eq!(y.raw(), raw * 2);
+        let y: I8<8, 2> = x.mul_const::<-2>();
+        assert_eq!(y.raw(), raw * -2);
+    }
+    for raw in 0..64 {
+        let x = U8::<6, 2>::new(raw).unwrap();
+        let y: U8<6, 2> = x.mul_const::<1>();
+        assert_eq!(y.raw(), raw);
+        let y: U8<7, 2> = x.mul_const::<2>();
+        assert_eq!(y.raw(), raw * 2);
+        let y: U8<8, 2> = x.mul_const::<3>();
+        assert_eq!(y.raw(), raw * 3);
+    }
+    assert_eq!(I8::<6, 2>::MIN.mul_const::<-1>().raw(), 32);
+    assert_eq!(I8::<6, 2>::MIN.mul_const::<-2>().raw(), 64);
+    let y: I64<64, 0> = I64::<1, 0>::MIN.mul_const::<{ i64::MIN + 1 }>();
+    assert_eq!(y.raw(), i64::MAX);
+    let y: U32<30, 0> = U32::<26, 0>::MAX.mul_const::<10>();
+    assert_eq!(y.raw(), ((1 << 26) - 1) * 10);
+    let y: I64<64, 8> = I64::<1, 8>::MIN.mul_const::<{ i64::MAX }>();
+    assert_eq!(y.raw(), -i64::MAX);
+}
+
+#[test]
+fn mul_ratio_exact_bits() {
+    for raw in -128..128 {
+        let fahrenheit = I16::<8, 0>::new(raw).unwrap();
+        let celsius: I16<8, 0> = fahrenheit.mul_ratio::<5, 9>();
+        assert_eq!(celsius.raw(), raw * 5 / 9);
+        let negated: I16<9, 0> = fahrenheit.mul_ratio::<-9, 5>();
+        assert_eq!(negated.raw(), raw * -9 / 5);
+    }
+    let kib = U32::<32, 0>::MAX;
+    let kb: U32<32, 0> = kib.mul_ratio::<1000, 1024>();
+    assert_eq!(kb.raw(), (u64::from(u32::MAX) * 1000 / 1024) as u32);
+    let x = I128::<100, 10>::MIN;
+    let y: I128<102, 10> = x.mul_ratio::<{ 1 << 26 }, { 1 << 25 }>();
+    assert_eq!(y.raw(), x.raw() * 2);
+}
+
+#[test]
+fn dot_widens() {
+    let a = [I16::<8, 2>::MIN, I16::<8, 2>::MAX, I16::<8, 2>::MIN];
+    let b = [I16::<4, 1>::MIN, I16::<4, 1>::MIN, I16::<4, 1>::MIN];
+    let dot: I16<14, 3> = I16::dot(a, b);
+    assert_eq!(dot.raw(), -128 * -8 + 127 * -8 + -128 * -8);
+    let dot: U32<32, 0> = U32::dot([U32::<15, 0>::MAX; 4], [U32::<15, 0>::MAX; 4]);
+    assert_eq!(dot.raw(), 4 * 32767 * 32767);
+    let dot: I8<8, -2> = I8::dot([I8::<3, 0>::MAX], [I8::<5, -2>::MIN]);
+    assert_eq!(dot.raw(), 3 * -16);
+    let dot: U8<8, 2> = U8::<4, 1>::dot::<4, 1, 0>([], []);
+    assert_eq!(dot.raw(), 0);
+    let dot: I128<128, 0> = I128::dot([I128::<63, 0>::MIN; 2], [I128::<64, 0>::MIN; 2]);
+    assert_eq!(dot.raw(), 2 * (i128::from(i64::MIN) / 2) * i128::from(i64::MIN));
+}
+
+#[test]
+fn ilog2_and_next_power_of_two() {
+    for raw in 1..=u16::from(u8::MAX) {
+        let x = U16::<8, 3>::new(raw).unwrap();
+        assert_eq!(x.ilog2(), (f64::from(raw) / 8.).log2().floor() as i32);
+        let next: U16<9, 3> = x.next_power_of_two();
+        assert_eq!(next.raw(), raw.next_power_of_two());
+    }
+    assert_eq!(U8::<7, 3>::ZERO.next_power_of_two().raw(), 1);
+    assert_eq!(I8::<8, 3>::ZERO.checked_ilog2(), None);
+    assert_eq!(I8::<8, 3>::MIN.checked_ilog2(), None);
+    assert_eq!(I8::<8, -3>::MAX.ilog2(), 9);
+    assert_eq!(I128::<128, 200>::MAX.ilog2(), -74);
+}
+
+#[test]
+#[should_panic(expected = "argument of integer logarithm must be positive")]
+fn ilog2_of_zero() {
+    U32::<32, 0>::ZERO.ilog2();
 }
@@ -0,0 +1,93 @@
+#![allow(incomplete_features)]
+#![feature(generic_const_exprs)]
+
+use fp::nav::*;
+use fp::*;
+
+type Deg = I32<32, 23>;
+type Meters = U32<32, 6>;
+type Bearing = U32<32, 22>;
+
+const RADIUS: f64 = 6_371_008.8;
+
+fn pos(lat: f64, lon: f64) -> LatLon<Deg> {
+    LatLon {
+        lat: Deg::from_f64(lat).unwrap(),
+        lon: Deg::from_f64(lon).unwrap(),
+    }
+}
+
+fn haversine(p: LatLon<Deg>, q: LatLon<Deg>) -> (f64, f64) {
+    let (lat1, lon1) = (p.lat.into_f64().to_radians(), p.lon.into_f64().to_radians());
+    let (lat2, lon2) = (q.lat.into_f64().to_radians(), q.lon.into_f64().to_radians());
+    let a = ((lat2 - lat1) / 2.).sin().powi(2)
+        + lat1.cos() * lat2.cos() * ((lon2 - lon1) / 2.).sin().powi(2);
+    let distance = 2. * RADIUS * a.sqrt().atan2((1. - a).sqrt());
+    let y = (lon2 - lon1).sin() * lat2.cos();
+    let x = lat1.cos() * lat2.sin() - lat1.sin() * lat2.cos() * (lon2 - lon1).cos();
+    (distance, y.atan2(x).to_degrees().rem_euclid(360.))
+}
+
+#[test]
+fn known_routes() {
+    // One degree of longitude along the equator, and one degree of latitude anywhere.
+    let one_degree = RADIUS * std::f64::consts::PI / 180.;
+    let d: Meters = pos(0., 10.).distance_to(pos(0., 11.)).unwrap();
+    assert!((d.into_f64() - one_degree).abs() <= 0.5f64.powi(6));
+    let d: Meters = pos(45., -120.).distance_to(pos(46., -120.)).unwrap();
+    assert!((d.into_f64() - one_degree).abs() <= 0.5f64.powi(6));
+    assert_eq!(
+        pos(12., 34.)
+            .distance_to::<Meters>(pos(12., 34.))
+            .unwrap()
+            .raw(),
+        0
+    );
+    // Half way around the globe, across the antimeridian.
+    let d: Meters = pos(0., 179.).distance_to(pos(0., -1.)).unwrap();
+    assert!((d.into_f64() - 180. * one_degree).abs() <= 0.02);
+    assert_eq!(
+        pos(0., 0.).distance_to::<U16<16, 0>>(pos(1., 0.)),
+        Err(RangeError::TooLarge)
+    );
+
+    let east: Bearing = pos(0., 0.).bearing_to(pos(0., 1.)).unwrap();
+    assert_eq!(east.into_f64(), 90.);
+    let north: Bearing = pos(10., 20.).bearing_to(pos(11., 20.)).unwrap();
+    assert_eq!(north.raw(), 0);
+    let south: Bearing = pos(10., 20.).bearing_to(pos(-11., 20.)).unwrap();
+    assert_eq!(south.into_f64(), 180.);
+    let west: Bearing = pos(0., 0.).bearing_to(pos(0., -1.)).unwrap();
+    assert_eq!(west.into_f64(), 270.);
+    assert_eq!(
+        pos(5., 5.)
+            .bearing_to::<Bearing>(pos(5., 5.))
+            .unwrap()
+            .raw(),
+        0
+    );
+}
+
+#[test]
+fn random_routes() {
+    let mut rng = Xoshiro128::new(1487);
+    for _ in 0..2000 {
+        let lat = |rng: &mut Xoshiro128| (rng.next_unit::<U32<32, 32>>().into_f64() - 0.5) * 180.;
+        let lon = |rng: &mut Xoshiro128| (rng.next_unit::<U32<32, 32>>().into_f64() - 0.5) * 360.;
+        let p = pos(lat(&mut rng), lon(&mut rng));
+        let q = pos(lat(&mut rng), lon(&mut rng));
+        let (distance, bearing) = haversine(p, q);
+        let d: Meters = p.distance_to(q).unwrap();
+        // Away from antipodal points, the result is correctly rounded (up to the error of
+        // the f64 reference).
+        let tolerance = if distance < 15_000_000. {
+            0.5f64.powi(6) + 1e-6
+        } else {
+            0.02
+        };
+        assert!((d.into_f64() - distance).abs() <= tolerance, "{p:?} {q:?}");
+        let b: Bearing = p.bearing_to(q).unwrap();
+        let error = (b.into_f64() - bearing).abs();
+        assert!(error.min(360. - error) <= 1e-6, "{p:?} {q:?}");
+    }
+}
@@ -0,0 +1,111 @@
+#![allow(incomplete_features)]
+#![feature(generic_const_exprs)]
+
+use fp::*;
+
+type Sample = I8<8, 4>;
+
+fn sample(val: f64) -> Sample {
+    Sample::from_f64(val).unwrap()
+}
+
+#[test]
+fn accumulate() {
+    let mut total = Strict(sample(0.0));
+    for _ in 0..7 {
+        total += Strict(sample(1.0));
+    }
+    total -= Strict(sample(0.5));
+    total *= Strict(sample(0.5));
+    assert_eq!(total.0, sample(3.25));
+}
+
+#[test]
+#[should_panic(expected = "attempt to add with overflow")]
+fn strict_overflow() {
+    let mut total = Strict(sample(7.0));
+    total += Strict(sample(1.0));
+}
+
+#[test]
+fn saturating() {
+    let mut total = Saturating(sample(7.0));
+    total += Saturating(sample(1.0));
+    assert_eq!(total.0, Sample::MAX);
+    total -= Saturating(sample(-1.0));
+    assert_eq!(total.0, Sample::MAX);
+    for _ in 0..3 {
+        total -= Saturating(sample(7.5));
+    }
+    assert_eq!(total.0, Sample::MIN);
+    total *= Saturating(sample(-1.0));
+    assert_eq!(total.0, Sample::MAX);
+    total *= Saturating(sample(-2.0));
+    assert_eq!(total.0, Sample::MIN);
+    let mut count = Saturating(U8::<4, 0>::new(3).unwrap());
+    count -= Saturating(U8::<4, 0>::new(5).unwrap());
+    assert_eq!(count.0.raw(), 0);
+}
+
+#[test]
+fn wrapping() {
+    let mut total = Wrapping(sample(7.0));
+    total += Wrapping(sample(1.0));
+    assert_eq!(total.0, sample(-8.0));
+    total -= Wrapping(sample(1.0));
+    assert_eq!(total.0, sample(7.0));
+    total *= Wrapping(sample(2.5));
+    assert_eq!(total.0, sample(1.5));
+    let mut count = Wrapping(U8::<4, 0>::new(3).unwrap());
+    count -= Wrapping(U8::<4, 0>::new(5).unwrap());
+    assert_eq!(count.0.raw(), 14);
+    assert_eq!((-Wrapping(sample(-8.0))).0, sample(-8.0));
+    assert_eq!((-Wrapping(sample(1.5))).0, sample(-1.5));
+    assert_eq!((-count).0.raw(), 2);
+    let mut phase = Wrapping(U32::<32, 32>::new(0xc000_0000).unwrap());
+    phase += Wrapping(U32::<32, 32>::new(0x6000_0000).unwrap());
+    assert_eq!(phase.0.raw(), 0x2000_0000);
+}
+
+#[test]
+fn sum_product() {
+    let buf = [1.5, -0.25, 3.0, 2.0].map(sample);
+    assert_eq!(buf.iter().copied().sum::<Sample>(), sample(6.25));
+    assert_eq!(buf.iter().sum::<Sample>(), sample(6.25));
+    assert_eq!(buf.iter().product::<Sample>(), sample(-2.25));
+    assert_eq!(buf[..0].iter().sum::<Sample>(), sample(0.0));
+    assert_eq!(buf[..0].iter().product::<Sample>(), sample(1.0));
+    let saturated: Saturating<Sample> = buf.iter().chain(&buf).copied().map(Saturating).sum();
+    assert_eq!(saturated.0, Sample::MAX);
+    let wrapped: Wrapping<Sample> = buf.iter().chain(&buf).copied().map(Wrapping).sum();
+    assert_eq!(wrapped.0, sample(-3.5));
+    let product: Strict<Sample> = [Strict(sample(2.0)); 2].iter().product();
+    assert_eq!(product.0, sample(4.0));
+}
+
+#[test]
+#[should_panic(expected = "attempt to add with overflow")]
+fn sum_overflow() {
+    let _: Sample = [sample(7.0), sample(1.0)].into_iter().sum();
+}
+
+#[test]
+#[should_panic(expected = "1 is not representable for the empty product")]
+fn empty_product_without_one() {
+    let empty: [I8<4, 4>; 0] = [];
+    let _: I8<4, 4> = empty.into_iter().product();
+}
+
+#[test]
+fn truncate_bits() {
+    for raw in i16::MIN..=i16::MAX {
+        let x = I16::<16, 4>::new(raw).unwrap();
+        let y: I16<5, 4> = x.truncate_bits();
+        assert_eq!(i32::from(y.raw()), (i32::from(raw) + 16).rem_euclid(32) - 16);
+    }
+    let phase = U32::<32, 32>::MAX;
+    let wrapped: U32<24, 32> = phase.truncate_bits();
+    assert_eq!(wrapped.raw(), 0xff_ffff);
+    assert_eq!(I128::<128, 0>::MIN.truncate_bits::<0>().raw(), 0);
+    assert_eq!(I8::<8, 0>::new(-1).unwrap().truncate_bits::<1>().raw(), -1);
+}
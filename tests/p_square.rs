@@ -0,0 +1,39 @@
+use fp::*;
+
+// Deterministic permutation of 0..n (n must be a power of two).
+fn scrambled(n: u32) -> impl Iterator<Item = u32> {
+    (0..n).map(move |i| i.wrapping_mul(2654435761) % n)
+}
+
+fn estimate(p: f32, samples: impl Iterator<Item = u32>) -> i32 {
+    let mut est = P2Quantile::<I32<16, 0>>::new(U32::<17, 16>::from_f32(p).unwrap());
+    for x in samples {
+        est.record(I32::new(x as i32).unwrap());
+    }
+    est.estimate().unwrap().raw()
+}
+
+#[test]
+fn streaming_quantiles() {
+    for (p, expected) in [(0.5, 512), (0.9, 922), (0.1, 102), (0.99, 1014)] {
+        let actual = estimate(p, scrambled(1024));
+        assert!((actual - expected).abs() <= 10, "p={p}: {actual} vs {expected}");
+    }
+}
+
+#[test]
+fn extremes() {
+    assert_eq!(estimate(0.0, scrambled(256)), 0);
+    assert_eq!(estimate(1.0, scrambled(256)), 255);
+}
+
+#[test]
+fn few_samples() {
+    let mut est = P2Quantile::<U8<8, 4>>::new(U32::<2, 1>::new(1).unwrap());
+    assert_eq!(est.estimate(), None);
+    for x in [7, 3, 5] {
+        est.record(U8::new(x).unwrap());
+    }
+    assert_eq!(est.count(), 3);
+    assert_eq!(est.estimate().unwrap().raw(), 5);
+}
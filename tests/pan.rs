@@ -0,0 +1,63 @@
+#![allow(incomplete_features)]
+#![feature(generic_const_exprs)]
+
+use fp::*;
+
+type Q15 = U16<16, 15>;
+type Q31 = U32<32, 31>;
+
+const SINE: PanTable<Q15, 129> = PanTable::<Q15, 129>::new(PanLaw::Sine);
+const SQRT: PanTable<Q31, 65> = PanTable::<Q31, 65>::new(PanLaw::Sqrt);
+
+#[test]
+fn tables() {
+    for i in 0..129 {
+        let angle = i as f64 / 128. * std::f64::consts::FRAC_PI_2;
+        let (left, right) = SINE.gains(i);
+        assert!((left.into_f64() - angle.cos()).abs() <= 0.5f64.powi(16));
+        assert!((right.into_f64() - angle.sin()).abs() <= 0.5f64.powi(16));
+        let power = left.into_f64().powi(2) + right.into_f64().powi(2);
+        assert!((power - 1.).abs() <= 0.5f64.powi(14));
+    }
+    assert_eq!(
+        SINE.gains(0),
+        (Q15::new(1 << 15).unwrap(), Q15::new(0).unwrap())
+    );
+    assert_eq!(SINE.gains(64).0, SINE.gains(64).1);
+    assert_eq!(SINE.gains(64).0.raw(), 23170);
+
+    for i in 0..65 {
+        let p = i as f64 / 64.;
+        let (left, right) = SQRT.gains(i);
+        assert!((left.into_f64() - (1. - p).sqrt()).abs() <= 0.5f64.powi(32));
+        assert!((right.into_f64() - p.sqrt()).abs() <= 0.5f64.powi(32));
+    }
+    assert_eq!(SQRT.gains(16).1.raw(), 1 << 30);
+    assert_eq!(SQRT.gains(64).1.raw(), 1 << 31);
+    // A single position is the center.
+    let center = PanTable::<U8<8, 7>, 1>::new(PanLaw::Sine).gains(0);
+    assert_eq!((center.0.raw(), center.1.raw()), (91, 91));
+}
+
+#[test]
+fn apply() {
+    let sample = I16::<16, 15>::new(-20000).unwrap();
+    let (left, right) = SINE.pan(sample, 64);
+    assert_eq!(left, right);
+    assert_eq!(left.raw(), (-20000. * 23170. / 32768f64).round() as i16);
+    assert_eq!(SINE.pan(sample, 0), (sample, I16::new(0).unwrap()));
+    assert_eq!(SINE.pan(I16::<16, 15>::MIN, 128).1, I16::<16, 15>::MIN);
+
+    // Crossfading a signal into itself peaks at sqrt(2) at the center.
+    let full = I16::<16, 15>::MAX;
+    let mid: I32<17, 15> = SINE.crossfade(full, full, 64).unwrap();
+    assert!((mid.into_f64() - full.into_f64() * 2f64.sqrt()).abs() < 0.5f64.powi(14));
+    assert_eq!(
+        SINE.crossfade::<_, I16<16, 15>>(full, full, 64),
+        Err(RangeError::TooLarge)
+    );
+    let end: I16<16, 15> = SINE.crossfade(full, sample, 128).unwrap();
+    assert_eq!(end, sample);
+    let quiet: I16<16, 15> = SQRT.crossfade(sample, I16::new(0).unwrap(), 48).unwrap();
+    assert_eq!(quiet.raw(), -10000);
+}
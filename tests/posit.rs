@@ -0,0 +1,71 @@
+#![allow(incomplete_features)]
+#![feature(generic_const_exprs)]
+#![cfg(feature = "posit")]
+
+use fp::posit::*;
+use fp::*;
+
+#[test]
+fn known_values() {
+    let one = I32::<8, 4>::new(16).unwrap();
+    assert_eq!(Posit16::from_fp(one).to_bits(), 0x4000);
+    assert_eq!(Posit32::from_fp(one).to_bits(), 0x4000_0000);
+    let half = U8::<1, 1>::new(1).unwrap();
+    assert_eq!(Posit16::from_fp(half).to_bits(), 0x3000);
+    assert_eq!(Posit32::from_fp(half).to_bits(), 0x3800_0000);
+    assert_eq!(Posit16::from_fp(-2i8).to_bits(), 0xb000);
+    assert_eq!(Posit16::from_fp(0u8), Posit16::ZERO);
+    // Saturation instead of overflow or underflow.
+    assert_eq!(Posit16::from_fp(i64::MAX), Posit16::MAXPOS);
+    assert_eq!(
+        Posit16::from_fp(I64::<1, 60>::new(-1).unwrap()).to_bits(),
+        0xffff
+    );
+    assert_eq!(
+        Posit16::MAXPOS.into_fp::<I64<30, 0>>(),
+        Ok(I64::new(1 << 28).unwrap())
+    );
+    assert_eq!(
+        Posit32::MINPOS.into_fp::<I128<3, 121>>(),
+        Ok(I128::new(2).unwrap())
+    );
+    assert_eq!(Posit32::MAXPOS.into_fp::<i64>(), Err(RangeError::TooLarge));
+    assert_eq!(Posit32::from_bits(0xc000_0000).into_fp::<i8>(), Ok(-1));
+}
+
+#[test]
+fn round_trip() {
+    // Every posit with enough precision round-trips through a wide fixed-point type.
+    for bits in 0..=u16::MAX {
+        let p = Posit16::from_bits(bits);
+        if p == Posit16::NAR {
+            continue;
+        }
+        let x: I64<58, 28> = p.into_fp().unwrap();
+        assert_eq!(Posit16::from_fp(x), p);
+    }
+    for raw in -1000..1000 {
+        let x = I32::<16, 6>::new(raw).unwrap();
+        let p = Posit32::from_fp(x);
+        assert_eq!(p.into_fp::<I32<16, 6>>(), Ok(x));
+    }
+}
+
+#[test]
+fn rounding() {
+    // 1 + 2^-13 is exactly halfway between two posit16 values near 1 (which have 12
+    // fraction bits), so it rounds to even.
+    let x = I32::<16, 13>::new((1 << 13) + 1).unwrap();
+    assert_eq!(Posit16::from_fp(x).to_bits(), 0x4000);
+    let y = I32::<16, 13>::new((1 << 13) + 3).unwrap();
+    assert_eq!(Posit16::from_fp(y).to_bits(), 0x4002);
+    // Rounding to the output type.
+    assert_eq!(Posit16::from_bits(0x3000).into_fp::<i8>(), Ok(1));
+    assert_eq!(Posit16::from_bits(0xd000).into_fp::<i8>(), Ok(-1));
+}
+
+#[test]
+#[should_panic(expected = "posit is NaR")]
+fn nar() {
+    let _: Result<i32, _> = Posit32::NAR.into_fp();
+}
@@ -0,0 +1,59 @@
+#![allow(incomplete_features)]
+#![feature(generic_const_exprs)]
+
+use fp::*;
+
+/// Check that `samples` draws of `T` cover its range evenly, by counting them in 8 bins.
+fn check_uniform<T: Num>(rng: &mut impl Prng, samples: usize)
+where
+    T::Raw: Into<i128> + TryFrom<i128>,
+{
+    let min: i128 = T::MIN.raw().into();
+    let max: i128 = T::MAX.raw().into();
+    let mut bins = [0usize; 8];
+    for _ in 0..samples {
+        let raw: i128 = rng.next::<T>().raw().into();
+        assert!(raw >= min && raw <= max);
+        bins[(raw.abs_diff(min) / (max.abs_diff(min) / 8 + 1)) as usize] += 1;
+    }
+    let expected = samples / 8;
+    for bin in bins {
+        assert!(bin.abs_diff(expected) < expected / 10, "{bins:?}");
+    }
+}
+
+#[test]
+fn xoshiro() {
+    let mut rng = Xoshiro128::new(1);
+    assert_ne!(rng.next_u32(), rng.clone().next_u32());
+    assert_eq!(Xoshiro128::new(7), Xoshiro128::new(7));
+    assert_ne!(Xoshiro128::new(7), Xoshiro128::new(8));
+    check_uniform::<I8<8, 0>>(&mut rng, 20000);
+    check_uniform::<U16<10, 4>>(&mut rng, 20000);
+    check_uniform::<I32<3, -2>>(&mut rng, 20000);
+    check_uniform::<I64<40, 20>>(&mut rng, 20000);
+    check_uniform::<I128<128, 0>>(&mut rng, 20000);
+    // A type with a single value.
+    assert_eq!(rng.next::<U8<0, 0>>().raw(), 0);
+
+    let mut sum = 0.;
+    for _ in 0..20000 {
+        let x = rng.next_unit::<U32<24, 24>>().into_f64();
+        assert!((0. ..1.).contains(&x));
+        sum += x;
+    }
+    assert!((sum / 20000. - 0.5).abs() < 0.01);
+    assert!(rng.next_unit::<I8<8, 7>>().raw() >= 0);
+}
+
+#[test]
+fn lfsr() {
+    let mut rng = Lfsr32::new(0);
+    assert_eq!(rng, Lfsr32::new(u32::MAX));
+    // The register shifts right, and the taps are XORed in when a one is shifted out.
+    assert!(rng.next_bit());
+    assert_eq!(rng, Lfsr32::new(0x7fff_ffff ^ 0x8020_0003));
+    let mut rng = Lfsr32::new(1);
+    check_uniform::<U8<8, 0>>(&mut rng, 20000);
+    check_uniform::<I16<12, 3>>(&mut rng, 20000);
+}
@@ -0,0 +1,97 @@
+#![allow(incomplete_features)]
+#![feature(generic_const_exprs)]
+
+use fp::*;
+
+type P16 = U16<16, 15>;
+type P32 = U32<32, 30>;
+
+fn prob(val: f64) -> Prob<P16> {
+    Prob::new(P16::from_f64(val).unwrap()).unwrap()
+}
+
+#[test]
+fn combine() {
+    assert_eq!(Prob::<P16>::zero().get().raw(), 0);
+    assert_eq!(Prob::<P16>::one().get().raw(), 1 << 15);
+    assert_eq!(Prob::new(P16::new(1 << 15).unwrap()), Ok(Prob::one()));
+    assert_eq!(
+        Prob::new(P16::new((1 << 15) + 1).unwrap()),
+        Err(RangeError::TooLarge)
+    );
+    assert_eq!(
+        Prob::new(I8::<8, 6>::new(-1).unwrap()),
+        Err(RangeError::TooSmall)
+    );
+
+    let p = prob(0.75);
+    let q = prob(0.5);
+    assert_eq!(p.complement(), prob(0.25));
+    assert_eq!(p.and::<_, P16>(q), prob(0.375));
+    assert_eq!(p.or::<_, P16>(q), prob(0.875));
+    assert_eq!(p.requantize::<U8<8, 2>>().get().raw(), 3);
+    // 0.7 * 0.3 rounded to 2^-30.
+    let r: Prob<P32> = prob(0.7).and(prob(0.3));
+    let exact = P16::from_f64(0.7).unwrap().into_f64() * P16::from_f64(0.3).unwrap().into_f64();
+    assert!((r.get().into_f64() - exact).abs() <= 0.5f64.powi(31));
+}
+
+#[test]
+fn bayes() {
+    // Two independent observations, each 3:1 in favor: 9:1 = 0.9 = 29491 / 2^15.
+    let prior = prob(0.5);
+    let sensor = prob(0.75);
+    let posterior: Prob<P16> = prior.update(sensor).unwrap();
+    assert_eq!(posterior, prob(0.75));
+    let posterior: Prob<P16> = posterior.update(sensor).unwrap();
+    assert_eq!(posterior.get().raw(), 29491);
+    // Uninformative observations leave the prior unchanged.
+    assert_eq!(prob(0.3).update::<_, P16>(prob(0.5)), Some(prob(0.3)));
+    assert_eq!(
+        Prob::<P16>::one().update::<_, P16>(Prob::<P16>::zero()),
+        None
+    );
+}
+
+#[test]
+fn log_odds() {
+    type L = I32<24, 16>;
+    assert_eq!(prob(0.5).log_odds::<L>().unwrap().raw(), 0);
+    let l = prob(0.75).log_odds::<L>().unwrap();
+    assert_eq!(l.raw(), (3f64.log2() * 65536.).round() as i32);
+    assert_eq!(prob(0.25).log_odds::<L>().unwrap().raw(), -l.raw());
+    assert_eq!(
+        Prob::<P16>::zero().log_odds::<L>(),
+        Err(RangeError::TooSmall)
+    );
+    assert_eq!(
+        Prob::<P16>::one().log_odds::<L>(),
+        Err(RangeError::TooLarge)
+    );
+    assert_eq!(prob(0.8).log_odds::<I8<3, 2>>(), Err(RangeError::TooLarge));
+
+    for raw in (1..(1 << 15)).step_by(331) {
+        let p = Prob::new(P16::new(raw).unwrap()).unwrap();
+        let l: L = p.log_odds().unwrap();
+        let pf = p.get().into_f64();
+        assert!((l.into_f64() - (pf / (1. - pf)).log2()).abs() <= 0.5f64.powi(16));
+        assert!(Prob::<P16>::from_log_odds(l).get().raw().abs_diff(raw) <= 1);
+    }
+    assert_eq!(Prob::<P16>::from_log_odds(i16::MAX), Prob::one());
+    assert_eq!(Prob::<P16>::from_log_odds(i16::MIN), Prob::zero());
+    assert_eq!(Prob::<P16>::from_log_odds(2i8), prob(0.8));
+}
+
+#[test]
+fn lerp() {
+    let a = I16::<12, 4>::new(-160).unwrap();
+    let b = I16::<12, 4>::new(80).unwrap();
+    assert_eq!(Prob::<P16>::zero().lerp(a, b), a);
+    assert_eq!(Prob::<P16>::one().lerp(a, b), b);
+    assert_eq!(prob(0.25).lerp(a, b).raw(), -100);
+    assert_eq!(prob(0.25).lerp(b, a).raw(), 20);
+    // -160 + 240 * 2^-15 rounds to -160, and -160 + 240 * 2^-8 = -159.0625 rounds to -159.
+    assert_eq!(prob(0.5f64.powi(15)).lerp(a, b).raw(), -160);
+    assert_eq!(prob(0.5f64.powi(8)).lerp(a, b).raw(), -159);
+    assert_eq!(prob(0.5).lerp(u64::MAX, 0).raw(), 1 << 63);
+}
@@ -0,0 +1,95 @@
+#![allow(incomplete_features)]
+#![feature(generic_const_exprs)]
+#![cfg(feature = "python")]
+
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+
+use fp::cordic;
+use fp::*;
+
+fn run(code: &str) {
+    Python::with_gil(|py| {
+        let module = pyo3::wrap_pymodule!(fp::python::fp)(py);
+        let globals = PyDict::new(py);
+        globals.set_item("fp", module).unwrap();
+        let code = std::ffi::CString::new(code).unwrap();
+        if let Err(err) = py.run(&code, Some(&globals), None) {
+            err.display(py);
+            panic!("Python code failed");
+        }
+    });
+}
+
+#[test]
+fn fixed() {
+    pyo3::prepare_freethreaded_python();
+    run(r#"
+x = fp.Fixed(-13, 8, 2)
+assert (x.raw, x.bits, x.shift, x.signed) == (-13, 8, 2, True)
+assert str(x) == "-3.25" and float(x) == -3.25
+assert repr(x) == "Fixed(raw=-13, bits=8, shift=2, signed=True)"
+assert fp.Fixed.from_float(-3.3, 8, 2) == x
+assert str(fp.Fixed(1, 1, 70, False)) == "0.0000000000000000000008470329472543003390683225006796419620513916015625"
+assert str(fp.Fixed(3, 2, -70, False)) == "3541774862152233910272"
+y = x * x
+assert (y.raw, y.bits, y.shift) == (169, 16, 4)
+z = fp.Fixed(3, 2, 2, False) - fp.Fixed(1, 1, 2, False)
+assert (z.raw, z.bits, z.shift, z.signed) == (2, 3, 2, True)
+for bad in [lambda: fp.Fixed(128, 8, 0), lambda: fp.Fixed.from_float(127.5, 8, 0)]:
+    try:
+        bad()
+        assert False
+    except OverflowError:
+        pass
+try:
+    float(fp.Fixed(0, 54, 0))
+    assert False
+except ValueError:
+    pass
+# Extreme shifts are errors or exact results, never panics.
+assert fp.Fixed.from_float(0.0, 8, -2**31).raw == 0
+try:
+    fp.Fixed.from_float(1.0, 8, 2**31 - 1)
+    assert False
+except OverflowError:
+    pass
+try:
+    float(fp.Fixed(0, 8, -2**31))
+    assert False
+except ValueError:
+    pass
+assert str(fp.Fixed(0, 8, -2**31)) == "0"
+try:
+    fp.Fixed(1, 8, -2**31) * fp.Fixed(1, 8, -1)
+    assert False
+except OverflowError:
+    pass
+"#);
+}
+
+#[test]
+fn kernels() {
+    pyo3::prepare_freethreaded_python();
+    // The Python functions are bit-exact with the Rust functions.
+    let x = I32::<16, 15>::new(-12345).unwrap();
+    let e: I64<40, 32> = cordic::exp(x).unwrap();
+    let s: I32<17, 15> = cordic::sinh(x).unwrap();
+    let l: I64<40, 32> = cordic::ln(I32::<20, 8>::new(777).unwrap()).unwrap();
+    run(&format!(
+        r#"
+x = fp.Fixed(-12345, 16, 15)
+assert fp.exp(x, 40, 32).raw == {}
+assert fp.sinh(x, 17, 15).raw == {}
+assert fp.ln(fp.Fixed(777, 20, 8), 40, 32).raw == {}
+try:
+    fp.sinh(fp.Fixed(0, 17, 15), 17, 15)
+    assert False
+except ValueError:
+    pass
+"#,
+        e.raw(),
+        s.raw(),
+        l.raw()
+    ));
+}
@@ -0,0 +1,151 @@
+#![allow(incomplete_features)]
+#![feature(generic_const_exprs)]
+
+use fp::*;
+
+type Q30 = I32<32, 30>;
+
+fn q(w: i32, x: i32, y: i32, z: i32) -> Quaternion<I32<8, 4>> {
+    let c = |v| I32::new(v).unwrap();
+    Quaternion::new(c(w), c(x), c(y), c(z))
+}
+
+fn raw<N: Num>(q: Quaternion<N>) -> [N::Raw; 4] {
+    [q.w().raw(), q.x().raw(), q.y().raw(), q.z().raw()]
+}
+
+fn to_f64<N: Num>(q: Quaternion<N>) -> [f64; 4] {
+    [q.w(), q.x(), q.y(), q.z()].map(|c| c.into_f64())
+}
+
+#[test]
+fn arithmetic() {
+    let sum: Quaternion<I32<9, 4>> = q(1, 2, 3, 4) + q(127, -128, 0, 1);
+    assert_eq!(raw(sum), [128, -126, 3, 5]);
+    let diff: Quaternion<I32<9, 4>> = q(1, 2, 3, 4) - q(127, -128, 0, 1);
+    assert_eq!(raw(diff), [-126, 130, 3, 3]);
+    assert_eq!(raw(-q(1, -128, 3, 0)), [-1, 128, -3, 0]);
+    let conj: Quaternion<I32<9, 4>> = q(1, -128, 3, 0).conj();
+    assert_eq!(raw(conj), [1, 128, -3, 0]);
+    // i j = k, j i = -k, and i^2 = -1.
+    let (i, j) = (q(0, 1, 0, 0), q(0, 0, 1, 0));
+    let product: Quaternion<I32<18, 8>> = i * j;
+    assert_eq!(raw(product), [0, 0, 0, 1]);
+    assert_eq!(raw(j * i), [0, 0, 0, -1]);
+    assert_eq!(raw(i * i), [-1, 0, 0, 0]);
+    let product = q(1, 2, 3, 4) * q(5, -6, 7, -8);
+    assert_eq!(raw(product), [28, -48, 14, 44]);
+    let product = q(-128, -128, -128, -128) * q(-128, 127, 127, 127);
+    assert_eq!(raw(product)[0], 16384 + 3 * 16256);
+}
+
+#[test]
+fn normalize() {
+    let unit: Quaternion<Q30> = q(3, 0, -4, 0).normalize();
+    // 0.6 and -0.8, rounded to nearest.
+    assert_eq!(raw(unit), [644245094, 0, -858993459, 0]);
+    let unit: Quaternion<Q30> = q(-128, 0, 0, 0).normalize();
+    assert_eq!(raw(unit), [-1 << 30, 0, 0, 0]);
+    let unit: Quaternion<I16<16, 14>> = q(1, 1, 1, 1).normalize();
+    assert_eq!(raw(unit), [1 << 13; 4]);
+    for (w, x, y, z) in [
+        (1, 2, 3, 4),
+        (-127, 5, 0, 99),
+        (0, 0, 0, 1),
+        (7, -7, 8, -128),
+    ] {
+        let unit: Quaternion<Q30> = q(w, x, y, z).normalize();
+        let norm = f64::from(w * w + x * x + y * y + z * z).sqrt();
+        let expected = [w, x, y, z].map(|c| f64::from(c) / norm);
+        for (c, e) in to_f64(unit).into_iter().zip(expected) {
+            assert!((c - e).abs() <= 0.5 / f64::from(1 << 30), "{c} {e}");
+        }
+    }
+}
+
+#[test]
+fn rotate() {
+    // A quarter turn about z takes x to y.
+    let h = (0.5f64.sqrt() * f64::from(1 << 30)).round() as i32;
+    let quarter_z = Quaternion::new(
+        Q30::new(h).unwrap(),
+        Q30::ZERO,
+        Q30::ZERO,
+        Q30::new(h).unwrap(),
+    );
+    let v = Vec3::new(
+        I32::<16, 8>::new(1000).unwrap(),
+        I32::new(0).unwrap(),
+        I32::new(-77).unwrap(),
+    );
+    let r: Vec3<I32<17, 8>> = quarter_z.rotate(v);
+    assert_eq!([r.x().raw(), r.y().raw(), r.z().raw()], [0, 1000, -77]);
+    // Rotating by the conjugate undoes the rotation.
+    let inverse = Quaternion::new(
+        Q30::new(h).unwrap(),
+        Q30::ZERO,
+        Q30::ZERO,
+        Q30::new(-h).unwrap(),
+    );
+    let back: Vec3<I32<18, 8>> = inverse.rotate(r);
+    assert_eq!(
+        [back.x().raw(), back.y().raw(), back.z().raw()],
+        [1000, 0, -77]
+    );
+    // A third of a turn about (1, 1, 1) takes x to y, y to z and z to x.
+    let axis: Quaternion<Q30> = q(1, 1, 1, 1).normalize();
+    let v = Vec3::new(
+        I32::<16, 8>::new(100).unwrap(),
+        I32::new(-20).unwrap(),
+        I32::new(3).unwrap(),
+    );
+    let r = axis.rotate(v);
+    assert_eq!([r.x().raw(), r.y().raw(), r.z().raw()], [3, 100, -20]);
+}
+
+#[test]
+fn slerp() {
+    let identity: Quaternion<Q30> = q(1, 0, 0, 0).normalize();
+    let half_z = q(0, 0, 0, 1);
+    let t = |x: f64| Ratio::<u32>::new((x * f64::from(1u32 << 31)).round() as u32).unwrap();
+    // Interpolating halfway between no rotation and a half turn about z is a quarter turn.
+    let mid: Quaternion<Q30> = q(1, 0, 0, 0).slerp(half_z, t(0.5));
+    let expected = [0.5f64.sqrt(), 0.0, 0.0, 0.5f64.sqrt()];
+    for (c, e) in to_f64(mid).into_iter().zip(expected) {
+        assert!((c - e).abs() < 1e-8, "{c} {e}");
+    }
+    assert_eq!(
+        q(1, 0, 0, 0).slerp::<Q30>(half_z, Ratio::<u32>::ZERO),
+        identity
+    );
+    assert_eq!(
+        raw(q(1, 0, 0, 0).slerp::<Q30>(half_z, Ratio::<u32>::ONE)),
+        [0, 0, 0, 1 << 30]
+    );
+    // The angle changes at a constant rate, along the shorter path (the second input is
+    // negated, since it is more than a quarter turn from the first).
+    for i in 0..=8 {
+        let x = f64::from(i) / 8.0;
+        let r: Quaternion<Q30> = q(16, 0, 0, 0).slerp(q(-3, 0, -4, 0), t(x));
+        let angle = (4f64).atan2(3.0) * x;
+        let expected = [angle.cos(), 0.0, angle.sin(), 0.0];
+        for (c, e) in to_f64(r).into_iter().zip(expected) {
+            assert!((c - e).abs() < 1e-8, "{x} {c} {e}");
+        }
+    }
+    // Nearly equal inputs are interpolated linearly.
+    let a = Quaternion::new(
+        I32::<32, 0>::new(1 << 30).unwrap(),
+        I32::ZERO,
+        I32::ZERO,
+        I32::ZERO,
+    );
+    let b = Quaternion::new(
+        I32::<32, 0>::new(1 << 30).unwrap(),
+        I32::ZERO,
+        I32::new(2).unwrap(),
+        I32::ZERO,
+    );
+    let r: Quaternion<Q30> = a.slerp(b, t(0.5));
+    assert_eq!(raw(r), [1 << 30, 0, 1, 0]);
+}
@@ -0,0 +1,108 @@
+use fp::*;
+
+#[test]
+fn constants() {
+    assert_eq!(Ratio::<u8>::ONE.raw(), 0x80);
+    assert_eq!(Ratio::<u16>::HALF.raw(), 0x4000);
+    assert_eq!(Ratio::<u64>::ONE.raw(), 1 << 63);
+    assert_eq!(Ratio::<u8>::new(0x81), Err(RangeError::TooLarge));
+    assert_eq!(Ratio::<u8>::new(0x80), Ok(Ratio::<u8>::ONE));
+    assert_eq!(
+        Ratio::<u16>::ONE.value(),
+        U16::<16, 15>::new(0x8000).unwrap()
+    );
+    assert_eq!(Ratio::<u32>::ZERO.complement(), Ratio::<u32>::ONE);
+    assert_eq!(Ratio::<u32>::HALF.complement(), Ratio::<u32>::HALF);
+    assert_eq!(
+        Ratio::<u16>::new(0x1234).unwrap().complement().raw(),
+        0x8000 - 0x1234
+    );
+}
+
+#[test]
+fn from_fp() {
+    let ratio = Ratio::<u16>::from_fp(U8::<8, 8>::new(0x40).unwrap()).unwrap();
+    assert_eq!(ratio.raw(), 0x2000);
+    assert_eq!(
+        Ratio::<u8>::from_fp(I32::<16, 0>::new(1).unwrap()),
+        Ok(Ratio::<u8>::ONE)
+    );
+    assert_eq!(
+        Ratio::<u8>::from_fp(I32::<16, 0>::new(2).unwrap()),
+        Err(RangeError::TooLarge)
+    );
+    assert_eq!(
+        Ratio::<u8>::from_fp(I32::<16, 0>::new(-1).unwrap()),
+        Err(RangeError::TooSmall)
+    );
+    assert_eq!(
+        Ratio::<u8>::from_fp(I32::<32, -20>::MIN),
+        Err(RangeError::TooSmall)
+    );
+    // -2^-9 rounds to zero, and 1 + 2^-9 rounds to one.
+    assert_eq!(
+        Ratio::<u8>::from_fp(I16::<16, 9>::new(-1).unwrap()),
+        Ok(Ratio::<u8>::ZERO)
+    );
+    assert_eq!(
+        Ratio::<u8>::from_fp(I16::<16, 9>::new(513).unwrap()),
+        Ok(Ratio::<u8>::ONE)
+    );
+}
+
+#[test]
+fn multiply() {
+    let three_quarters = Ratio::<u8>::new(0x60).unwrap();
+    assert_eq!(
+        three_quarters * I8::<8, 0>::MIN,
+        I8::<8, 0>::new(-96).unwrap()
+    );
+    assert_eq!(
+        three_quarters * U8::<8, 3>::MAX,
+        U8::<8, 3>::new(191).unwrap()
+    );
+    assert_eq!(three_quarters * 10i32, 8);
+    assert_eq!(three_quarters * -10i32, -7);
+    assert_eq!(Ratio::<u64>::ONE * u64::MAX, u64::MAX);
+    assert_eq!(Ratio::<u64>::ONE * i64::MIN, i64::MIN);
+    assert_eq!(Ratio::<u64>::HALF * u64::MAX, 1 << 63);
+    assert_eq!(
+        Ratio::<u32>::new(0x2000_0000)
+            .unwrap()
+            .scale_to(U16::<12, 0>::new(4000).unwrap())
+            .raw(),
+        1000
+    );
+    for raw in 0..=0x80 {
+        let ratio = Ratio::<u8>::new(raw).unwrap();
+        for x in i8::MIN..=i8::MAX {
+            let expected = (f64::from(x) * f64::from(raw) / 128. + 0.5).floor();
+            assert_eq!(
+                f64::from(ratio * I8::<8, 0>::new(x).unwrap().raw()),
+                expected
+            );
+        }
+    }
+}
+
+#[test]
+fn lerp() {
+    let quarter = Ratio::<u16>::new(0x2000).unwrap();
+    let (a, b) = (
+        I16::<10, 2>::new(-512).unwrap(),
+        I16::<10, 2>::new(511).unwrap(),
+    );
+    assert_eq!(quarter.lerp(a, b).raw(), -256);
+    assert_eq!(quarter.lerp(b, a).raw(), 255);
+    assert_eq!(Ratio::<u16>::ZERO.lerp(a, b), a);
+    assert_eq!(Ratio::<u16>::ONE.lerp(a, b), b);
+    assert_eq!(
+        Ratio::<u64>::HALF.lerp(u64::MAX, u64::MAX - 2),
+        u64::MAX - 1
+    );
+    assert_eq!(
+        Ratio::<u64>::new(1).unwrap().lerp(i64::MIN, i64::MAX),
+        i64::MIN + 2
+    );
+    assert_eq!(Ratio::<u64>::ONE.lerp(i64::MIN, i64::MAX), i64::MAX);
+}
@@ -0,0 +1,52 @@
+use fp::*;
+
+#[test]
+fn exact_fit() {
+    // y = 1.5 x - 2.25
+    let mut fit = LinearRegression::<I16<12, 4>, I32<20, 8>, 10>::new();
+    for i in -20..20 {
+        let x = I16::new(i * 8).unwrap();
+        let y = I32::from_f32(1.5 * x.into_f32() - 2.25).unwrap();
+        fit.record(x, y).unwrap();
+    }
+    assert_eq!(fit.count(), 40);
+    assert_eq!(fit.slope::<I32<16, 12>>().unwrap().into_f32(), 1.5);
+    assert_eq!(fit.intercept::<I16<8, 4>>().unwrap().into_f32(), -2.25);
+    assert_eq!(fit.sum_x().raw(), -160);
+    assert_eq!(fit.sum_xx().raw(), (-20..20).map(|i: i128| 64 * i * i).sum::<i128>());
+}
+
+#[test]
+fn noisy_fit() {
+    // y = -0.25 x + 100, plus +/-1 alternating noise
+    let mut fit = LinearRegression::<U8<8, 0>, I32<12, 0>, 8>::new();
+    for x in 0..=255u8 {
+        let noise = if x % 2 == 0 { 1 } else { -1 };
+        let y = 100 - x as i32 / 4 + noise;
+        fit.record(U8::new(x).unwrap(), I32::new(y).unwrap()).unwrap();
+    }
+    let slope = fit.slope::<I32<16, 12>>().unwrap().into_f64();
+    let intercept = fit.intercept::<I32<16, 8>>().unwrap().into_f64();
+    assert!((slope + 0.25).abs() < 0.01, "{slope}");
+    assert!((intercept - 100.).abs() < 1., "{intercept}");
+}
+
+#[test]
+fn degenerate_and_out_of_range() {
+    let mut fit = LinearRegression::<I8<8, 0>, I8<8, 0>, 2>::new();
+    assert!(fit.slope::<I32<32, 0>>().is_none());
+    for _ in 0..4 {
+        fit.record(I8::new(3).unwrap(), I8::new(1).unwrap()).unwrap();
+    }
+    assert!(fit.slope::<I32<32, 0>>().is_none());
+    assert_eq!(
+        fit.record(I8::new(3).unwrap(), I8::new(1).unwrap()),
+        Err(RangeError::TooLarge)
+    );
+
+    let mut fit = LinearRegression::<I8<8, 0>, I8<8, 0>, 2>::new();
+    fit.record(I8::new(0).unwrap(), I8::new(0).unwrap()).unwrap();
+    fit.record(I8::new(1).unwrap(), I8::new(100).unwrap()).unwrap();
+    assert_eq!(fit.slope::<I32<8, 0>>().unwrap().raw(), 100);
+    assert!(fit.slope::<I32<7, 0>>().is_none());
+}
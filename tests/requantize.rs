@@ -0,0 +1,314 @@
+#![allow(incomplete_features)]
+#![feature(generic_const_exprs)]
+
+use fp::*;
+
+const MODES: [Rounding; 5] = [
+    Rounding::Floor,
+    Rounding::Ceil,
+    Rounding::TowardZero,
+    Rounding::Nearest,
+    Rounding::NearestEven,
+];
+
+fn round(val: f64, rounding: Rounding) -> f64 {
+    match rounding {
+        Rounding::Floor => val.floor(),
+        Rounding::Ceil => val.ceil(),
+        Rounding::TowardZero => val.trunc(),
+        Rounding::Nearest => (val + 0.5).floor(),
+        Rounding::NearestEven => val.round_ties_even(),
+    }
+}
+
+/// Check `requantize` against a floating-point reference for every value of `T`.
+fn check<T: Num, Out: Num>()
+where
+    T::Raw: Into<i128> + TryFrom<i128>,
+    Out::Raw: Into<i128> + TryFrom<i128>,
+{
+    let (min, max) = (T::MIN.raw().into(), T::MAX.raw().into());
+    let (out_min, out_max) = (Out::MIN.raw().into() as f64, Out::MAX.raw().into() as f64);
+    for raw in min..=max {
+        let val = T::new(raw.try_into().ok().unwrap()).unwrap();
+        let scaled = raw as f64 * 2f64.powi(Out::SHIFT - T::SHIFT);
+        for rounding in MODES {
+            let expected = round(scaled, rounding).clamp(out_min, out_max);
+            let out: Out = val.requantize(rounding);
+            assert_eq!(out.raw().into() as f64, expected, "{raw} {rounding:?}");
+        }
+    }
+}
+
+#[test]
+fn exhaustive() {
+    check::<I16<12, 6>, I8<8, 3>>();
+    check::<I16<12, 6>, I8<6, 0>>();
+    check::<I16<12, 6>, U8<8, 1>>();
+    check::<U16<16, 8>, I8<8, 7>>();
+    check::<I8<8, 0>, I16<16, 6>>();
+    check::<I8<8, 0>, I8<8, 3>>();
+    check::<I8<8, 7>, I8<8, -1>>();
+    check::<I8<8, 4>, I32<32, -40>>();
+    check::<I16<16, -30>, I16<16, 15>>();
+    check::<U8<8, 0>, U64<64, 0>>();
+    check::<I8<8, 0>, U64<64, 56>>();
+}
+
+#[test]
+fn accumulator() {
+    // 40-bit accumulator of Q15 × Q15 products to a Q15 sample.
+    type Acc = I64<40, 30>;
+    type Q15 = I16<16, 15>;
+    let acc = Acc::new(0x0000_2000_4000).unwrap();
+    assert_eq!(acc.requantize::<Q15>(Rounding::Nearest).raw(), 0x4001);
+    assert_eq!(acc.requantize::<Q15>(Rounding::NearestEven).raw(), 0x4000);
+    assert_eq!(acc.requantize::<Q15>(Rounding::Floor).raw(), 0x4000);
+    assert_eq!(Acc::MAX.requantize::<Q15>(Rounding::Nearest), Q15::MAX);
+    assert_eq!(Acc::MIN.requantize::<Q15>(Rounding::Ceil), Q15::MIN);
+    let acc = Acc::new(-0x0000_2000_4000).unwrap();
+    assert_eq!(acc.requantize::<Q15>(Rounding::Nearest).raw(), -0x4000);
+    assert_eq!(acc.requantize::<Q15>(Rounding::TowardZero).raw(), -0x4000);
+    assert_eq!(acc.requantize::<Q15>(Rounding::Floor).raw(), -0x4001);
+    // Wide types and primitives take the `i128` path.
+    let wide = I128::<100, 90>::new(-3 << 88).unwrap();
+    assert_eq!(wide.requantize::<i8>(Rounding::NearestEven), -1);
+    assert_eq!(
+        wide.requantize::<I128<100, 98>>(Rounding::Nearest).raw(),
+        -3 << 96
+    );
+    assert_eq!(
+        I128::<100, 0>::MAX.requantize::<u64>(Rounding::Nearest),
+        u64::MAX
+    );
+    assert_eq!(
+        u64::MAX.requantize::<I128<128, 60>>(Rounding::Floor).raw(),
+        (u64::MAX as i128) << 60
+    );
+    assert_eq!(
+        i64::MIN.requantize::<I128<128, 64>>(Rounding::Floor).raw(),
+        i64::MIN as i128 * (1 << 64)
+    );
+}
+
+#[test]
+fn integer_rounding() {
+    for raw in i8::MIN..=i8::MAX {
+        let val = I8::<8, 3>::new(raw).unwrap();
+        let x = f64::from(raw) / 8.;
+        let (floor, trunc): (I8<5, 0>, I8<5, 0>) = (val.floor(), val.trunc());
+        let (ceil, nearest): (I8<6, 0>, I8<6, 0>) = (val.ceil(), val.round());
+        assert_eq!(f64::from(floor.raw()), x.floor());
+        assert_eq!(f64::from(ceil.raw()), x.ceil());
+        assert_eq!(f64::from(nearest.raw()), round(x, Rounding::Nearest));
+        assert_eq!(f64::from(trunc.raw()), x.trunc());
+    }
+    for raw in u8::MIN..=u8::MAX {
+        let val = U8::<8, 3>::new(raw).unwrap();
+        let x = f64::from(raw) / 8.;
+        assert_eq!(f64::from(val.floor().raw()), x.floor());
+        assert_eq!(f64::from(val.ceil().raw()), x.ceil());
+        assert_eq!(f64::from(val.round().raw()), round(x, Rounding::Nearest));
+        assert_eq!(f64::from(val.trunc().raw()), x.trunc());
+    }
+    let (min, max) = (I128::<128, 64>::MIN, I128::<128, 64>::MAX);
+    assert_eq!(min.floor().raw(), i128::from(i64::MIN));
+    assert_eq!(min.ceil().raw(), i128::from(i64::MIN));
+    assert_eq!(max.ceil().raw(), 1 << 63);
+    assert_eq!(max.trunc().raw(), i128::from(i64::MAX));
+    assert_eq!(I128::<128, 64>::new(-1).unwrap().trunc().raw(), 0);
+
+    // All bits but the sign are fractional.
+    for raw in i8::MIN..=i8::MAX {
+        let val = I8::<8, 7>::new(raw).unwrap();
+        let x = f64::from(raw) / 128.;
+        assert_eq!(f64::from(val.floor().raw()), x.floor());
+        assert_eq!(f64::from(val.ceil().raw()), x.ceil());
+        assert_eq!(f64::from(val.round().raw()), round(x, Rounding::Nearest));
+        assert_eq!(f64::from(val.trunc().raw()), x.trunc());
+    }
+    let val = I16::<16, 15>::new(-0x4000).unwrap();
+    assert_eq!((val.ceil().raw(), val.round().raw(), val.trunc().raw()), (0, 0, 0));
+    let val = I32::<32, 31>::MAX;
+    assert_eq!((val.ceil().raw(), val.round().raw(), val.trunc().raw()), (1, 1, 0));
+    let val = I128::<128, 127>::MIN;
+    assert_eq!((val.ceil().raw(), val.round().raw(), val.trunc().raw()), (-1, -1, -1));
+}
+
+#[test]
+fn fract_and_split() {
+    for raw in i8::MIN..=i8::MAX {
+        let val = I8::<8, 3>::new(raw).unwrap();
+        let x = f64::from(raw) / 8.;
+        let fract: U8<3, 3> = val.fract();
+        assert_eq!(f64::from(fract.raw()) / 8., x - x.floor());
+        let (int, fract) = val.split();
+        assert_eq!((int, fract), (val.floor(), val.fract()));
+    }
+    let val = U16::<16, 4>::new(0x1234).unwrap();
+    assert_eq!((val.split().0.raw(), val.split().1.raw()), (0x123, 4));
+    let (int, fract) = I128::<128, 100>::MIN.split();
+    assert_eq!((int.raw(), fract.raw()), (-1 << 27, 0));
+
+    // All bits but the sign are fractional, as in a Q-format interpolation weight.
+    for raw in i8::MIN..=i8::MAX {
+        let val = I8::<8, 7>::new(raw).unwrap();
+        let x = f64::from(raw) / 128.;
+        let fract: U8<7, 7> = val.fract();
+        assert_eq!(f64::from(fract.raw()) / 128., x - x.floor());
+        assert_eq!(val.split(), (val.floor(), fract));
+    }
+    let (int, fract) = I32::<32, 31>::new(-1).unwrap().split();
+    assert_eq!((int.raw(), fract.raw()), (-1, (1 << 31) - 1));
+    let (int, fract) = I128::<128, 127>::MAX.split();
+    assert_eq!((int.raw(), fract.raw()), (0, i128::MAX as u128));
+}
+
+#[test]
+fn rescale() {
+    let val = I32::<24, 16>::new(0x3_4580).unwrap();
+    let out: I32<12, 8> = val.rescale();
+    assert_eq!(out.raw(), 0x346);
+    assert_eq!(
+        I32::<24, 16>::new(-0x80)
+            .unwrap()
+            .rescale::<I32<12, 8>>()
+            .raw(),
+        0
+    );
+    assert_eq!(val.try_rescale::<I32<12, 10>>(), Err(RangeError::TooLarge));
+    assert_eq!(
+        I32::<24, 16>::MIN.try_rescale::<I32<12, 8>>(),
+        Err(RangeError::TooSmall)
+    );
+    let saturated: I32<12, 10> = val.rescale_saturating();
+    assert_eq!(saturated, I32::<12, 10>::MAX);
+    let saturated: I32<12, 8> = I32::<24, 16>::MIN.rescale_saturating();
+    assert_eq!(saturated, I32::<12, 8>::MIN);
+    let wide: I32<32, 20> = out.rescale();
+    assert_eq!(wide.raw(), 0x346 << 12);
+    assert_eq!(wide.try_rescale::<I32<32, 31>>(), Err(RangeError::TooLarge));
+    let narrow: U8<4, 0> = U8::<8, 4>::new(0xf7).unwrap().rescale();
+    assert_eq!(narrow.raw(), 15);
+    assert_eq!(
+        U8::<8, 4>::new(0xf8).unwrap().try_rescale::<U8<4, 0>>(),
+        Err(RangeError::TooLarge)
+    );
+}
+
+#[test]
+#[should_panic(expected = "rescaled value out of range")]
+fn rescale_out_of_range() {
+    I32::<24, 16>::MAX.rescale::<I32<12, 8>>();
+}
+
+#[test]
+fn raw_shr_round() {
+    for raw in i8::MIN..=i8::MAX {
+        let val = I8::<8, 0>::new(raw).unwrap();
+        for rounding in MODES {
+            let out: I8<6, -3> = val.raw_shr_round::<3>(rounding);
+            assert_eq!(f64::from(out.raw()), round(f64::from(raw) / 8., rounding));
+        }
+    }
+    let max = U8::<8, 4>::MAX.raw_shr_round::<4>(Rounding::Nearest);
+    assert_eq!(max.raw(), 16);
+}
+
+#[test]
+fn raw_shr_stochastic() {
+    let mut rng = Xoshiro128::new(1);
+    for raw in [-7, -4, 0, 3, 5, 8] {
+        let val = I32::<8, 2>::new(raw).unwrap();
+        let mut sum = 0;
+        for _ in 0..4096 {
+            let out: I32<6, -1> = val.raw_shr_stochastic::<3, _>(&mut rng);
+            let floor = raw.div_euclid(8);
+            assert!(out.raw() == floor || out.raw() == floor + 1);
+            sum += out.raw();
+        }
+        // The mean is `raw / 8` to within a few standard deviations.
+        assert!(
+            (f64::from(sum) / 512. - f64::from(raw)).abs() < 0.3,
+            "{raw} {sum}"
+        );
+    }
+    let min = I128::<128, 0>::MIN.raw_shr_stochastic::<128, _>(&mut rng);
+    assert!(min.raw() == -1 || min.raw() == 0);
+    let one = I128::<128, 0>::new(1).unwrap();
+    assert_eq!(
+        one.raw_shr_stochastic::<128, _>(&mut Lfsr32::new(1)).raw(),
+        0
+    );
+}
+
+#[test]
+fn dither_shr() {
+    let mut rng = Xoshiro128::new(2);
+    for raw in [-0x1_2345, -0x100, 0, 0x80, 0x7f_ffff] {
+        let val = I32::<24, 0>::new(raw).unwrap();
+        let (mut sum, mut min, mut max) = (0, i32::MAX, i32::MIN);
+        for _ in 0..4096 {
+            let out: I32<16, -8> = val.dither_shr::<8, _>(&mut rng);
+            (min, max) = (min.min(out.raw()), max.max(out.raw()));
+            sum += out.raw();
+        }
+        // TPDF dither spreads the output over up to 3 values, with the mean of the input
+        // (except where it saturates).
+        let expected = f64::from(raw) / 256.;
+        assert!(f64::from(min) >= expected.floor() - 1. && f64::from(max) <= expected.ceil() + 1.);
+        assert!(max <= I32::<16, -8>::MAX.raw());
+        if raw < 0x7f_ff00 {
+            assert!(max > min);
+            assert!(
+                (f64::from(sum) / 4096. - expected).abs() < 0.05,
+                "{raw} {sum}"
+            );
+        }
+    }
+    let out = U8::<8, 0>::MAX.dither_shr::<0, _>(&mut rng);
+    assert_eq!(out, U8::<8, 0>::MAX);
+}
+
+#[test]
+fn cmp_logical() {
+    use core::cmp::Ordering;
+    let threshold = I32::<32, 16>::new(0x1_8000).unwrap();
+    assert_eq!(
+        threshold.cmp_logical(I32::<32, 8>::new(0x180).unwrap()),
+        Ordering::Equal
+    );
+    assert!(threshold.eq_logical(U8::<8, 7>::new(0xc0).unwrap()));
+    assert_eq!(
+        threshold.cmp_logical(I16::<16, 8>::new(0x17f).unwrap()),
+        Ordering::Greater
+    );
+    assert_eq!(
+        I16::<16, -4>::MIN.cmp_logical(I32::<32, 16>::MIN),
+        Ordering::Less
+    );
+    for a in i8::MIN..=i8::MAX {
+        for b in i8::MIN..=i8::MAX {
+            let (x, y) = (I8::<8, 3>::new(a).unwrap(), I8::<8, -2>::new(b).unwrap());
+            assert_eq!(
+                x.cmp_logical(y),
+                (f64::from(a) / 8.).total_cmp(&(f64::from(b) * 4.))
+            );
+        }
+    }
+    let max = I128::<128, 0>::MAX;
+    assert!(max.eq_logical(max) && U64::<62, -64>::MAX.cmp_logical(max).is_lt());
+}
+
+#[test]
+fn resize() {
+    let x = I32::<12, 4>::MIN;
+    let y: I32<20, 8> = x.resize();
+    assert_eq!(y.raw(), x.raw() << 4);
+    assert!(y.eq_logical(x));
+    let z: U8<8, 0> = U8::<5, -3>::MAX.resize();
+    assert_eq!(z.raw(), 31 << 3);
+    let same: I128<128, 7> = I128::<128, 7>::MIN.resize();
+    assert_eq!(same, I128::<128, 7>::MIN);
+    assert_eq!(I16::<3, 0>::new(-4).unwrap().resize::<I16<16, 13>>().raw(), i16::MIN);
+}
@@ -0,0 +1,78 @@
+#![allow(incomplete_features)]
+#![feature(generic_const_exprs)]
+
+use fp::*;
+
+fn pixels<P: Num>(raw: &[i64]) -> Vec<P>
+where
+    P::Raw: TryFrom<i128>,
+{
+    raw.iter()
+        .map(|&p| P::new((p as i128).try_into().ok().unwrap()).unwrap())
+        .collect()
+}
+
+fn raw<P: Num>(pixels: &[P]) -> Vec<i64>
+where
+    P::Raw: Into<i128>,
+{
+    pixels.iter().map(|p| p.raw().into() as i64).collect()
+}
+
+#[test]
+fn positions() {
+    let position = |i, from, to| {
+        let (index, frac) = sample_position(i, from, to);
+        (index, frac.get().into_f64())
+    };
+    assert_eq!(position(0, 2, 4), (0, 0.));
+    assert_eq!(position(1, 2, 4), (0, 0.25));
+    assert_eq!(position(2, 2, 4), (0, 0.75));
+    assert_eq!(position(3, 2, 4), (1, 0.));
+    assert_eq!(position(0, 4, 2), (0, 0.5));
+    assert_eq!(position(1, 4, 2), (2, 0.5));
+    assert_eq!(position(5, 7, 7), (5, 0.));
+    // 1/3 is rounded to 16 bits.
+    assert_eq!(sample_position(2, 3, 9).1.get().raw(), 21845);
+}
+
+#[test]
+fn upscale() {
+    let input = pixels::<U8<8, 0>>(&[0, 100, 200, 255]);
+    let mut output = pixels::<U8<8, 0>>(&[0; 16]);
+    resize_bilinear(&input, 2, &mut output, 4);
+    // Interpolate exactly, and round once.
+    let t: [f64; 4] = [0., 0.25, 0.75, 1.];
+    let expected: Vec<i64> = t
+        .iter()
+        .flat_map(|&ty| {
+            t.map(|tx| {
+                let top = 100. * tx;
+                let bottom = 200. + 55. * tx;
+                (top + ty * (bottom - top) + 0.5).floor() as i64
+            })
+        })
+        .collect();
+    assert_eq!(raw(&output), expected);
+    assert_eq!(&raw(&output)[4..8], [50, 72, 117, 139]);
+}
+
+#[test]
+fn downscale() {
+    let input = pixels::<U16<16, 0>>(&[
+        0, 2, 4, 6, //
+        10, 12, 14, 65535, //
+    ]);
+    let mut output = pixels::<U16<16, 0>>(&[0; 2]);
+    resize_bilinear(&input, 4, &mut output, 2);
+    // Each output pixel averages a 2x2 block (rounding ties up).
+    assert_eq!(raw(&output), [6, (4 + 6 + 14 + 65535 + 2) / 4]);
+
+    let mut same = pixels::<U16<16, 0>>(&[0; 8]);
+    resize_bilinear(&input, 4, &mut same, 4);
+    assert_eq!(same, input);
+
+    let mut column = pixels::<U16<16, 0>>(&[0; 3]);
+    resize_bilinear(&input[..1], 1, &mut column, 1);
+    assert_eq!(raw(&column), [0, 0, 0]);
+}
@@ -0,0 +1,128 @@
+#![allow(incomplete_features)]
+#![feature(generic_const_exprs)]
+
+use fp::sensor::*;
+use fp::*;
+
+type Counts = U16<12, 0>;
+type Celsius = I32<32, 16>;
+
+const BETA: u32 = 3950;
+
+// 10 kΩ NTC to ground with a 10 kΩ fixed resistor, on a 12-bit ADC.
+const NTC: NtcTable<Counts, Celsius, 65> =
+    NtcTable::new(BETA, 10_000, Divider::NtcToGround, 10_000, 200, 3900);
+
+fn beta_model(counts: f64, to_ground: bool) -> f64 {
+    let ratio = counts / (4096. - counts);
+    let r = 10_000. * if to_ground { ratio } else { 1. / ratio };
+    1. / (1. / 298.15 + (r / 10_000.).ln() / BETA as f64) - 273.15
+}
+
+#[test]
+fn ntc() {
+    let bound = NTC.error_bound().into_f64();
+    assert!(bound < 0.5, "{bound}");
+    let mut worst = 0f64;
+    for raw in 200..=3900 {
+        let t = NTC.apply(Counts::new(raw).unwrap()).unwrap().into_f64();
+        let err = (t - beta_model(raw as f64, true)).abs();
+        assert!(err <= bound, "{raw}: {err} > {bound}");
+        worst = worst.max(err);
+    }
+    // The bound is tight.
+    assert!(worst > bound / 2., "{worst} {bound}");
+    // 25 °C at the midpoint, and clamping outside the table.
+    let mid = NTC.apply(Counts::new(2048).unwrap()).unwrap().into_f64();
+    assert!((mid - 25.).abs() <= bound);
+    assert_eq!(
+        NTC.apply(Counts::new(0).unwrap()),
+        NTC.apply(Counts::new(200).unwrap())
+    );
+
+    let reversed: NtcTable<Counts, Celsius, 65> =
+        NtcTable::new(BETA, 10_000, Divider::NtcToReference, 10_000, 100, 4000);
+    let bound = reversed.error_bound().into_f64();
+    for raw in (100..=4000).step_by(7) {
+        let t = reversed
+            .apply(Counts::new(raw).unwrap())
+            .unwrap()
+            .into_f64();
+        assert!((t - beta_model(raw as f64, false)).abs() <= bound);
+    }
+}
+
+type Fine = U32<20, 8>;
+type Narrow = I32<24, 16>;
+type Coef = I32<32, 24>;
+
+// Two-point calibration of the ADC, the NTC table (in units of 1/256 count), and a
+// quadratic correction of the Beta model.
+type Calibration = Affine<Counts, U16<16, 15>, I16<16, 4>, Fine>;
+type Correction = Polynomial<Narrow, Coef, Celsius, 3>;
+
+const SENSOR: Chain<Chain<Calibration, NtcTable<Fine, Narrow, 65>>, Correction> = Chain::new(
+    Chain::new(
+        Affine::new(U16::new_const(32833), I16::new_const(-52)),
+        NtcTable::new(
+            BETA,
+            10_000,
+            Divider::NtcToGround,
+            10_000,
+            200 << 8,
+            3900 << 8,
+        ),
+    ),
+    Polynomial::new([
+        I32::new_const(1 << 22),
+        I32::new_const(16_793_993),
+        I32::new_const(-168),
+    ]),
+);
+
+#[test]
+fn pipeline() {
+    let gain = 32833. / 32768.;
+    let offset = -52. / 16.;
+    let [c0, c1, c2] = [1 << 22, 16_793_993, -168].map(|c| c as f64 / (1 << 24) as f64);
+    let bound = SENSOR.error_bound().into_f64();
+    assert!(bound < 0.5, "{bound}");
+    for raw in 210..=3890 {
+        let counts = raw as f64 * gain + offset;
+        let t = beta_model(counts, true);
+        let expected = c0 + c1 * t + c2 * t * t;
+        let actual = SENSOR.apply(Counts::new(raw).unwrap()).unwrap().into_f64();
+        assert!((actual - expected).abs() <= bound, "{raw}");
+    }
+}
+
+#[test]
+fn stages() {
+    type X = I16<16, 4>;
+    let x = |val: f64| X::from_f64(val).unwrap();
+    let pwl = Pwl::new(
+        [x(-10.), x(0.), x(0.), x(5.)],
+        [x(3.), x(1.), x(2.), x(-2.)],
+    );
+    assert_eq!(pwl.apply(x(-20.)), Ok(x(3.)));
+    assert_eq!(pwl.apply(x(-5.)), Ok(x(2.)));
+    assert_eq!(pwl.apply(x(0.)), Ok(x(1.)));
+    assert_eq!(pwl.apply(x(2.5)), Ok(x(0.)));
+    assert_eq!(pwl.apply(x(100.)), Ok(x(-2.)));
+    // The steepest segment has a slope of 0.8.
+    assert_eq!(pwl.sensitivity_raw(10), 8);
+
+    let affine = Affine::<X, X, X, I8<8, 0>>::new(x(2.), x(0.5));
+    assert_eq!(affine.apply(x(10.)), Ok(I8::new(21).unwrap()));
+    assert_eq!(affine.apply(x(100.)), Err(RangeError::TooLarge));
+    assert_eq!(affine.error_raw(), 1);
+    assert_eq!(affine.sensitivity_raw(8), 1);
+
+    // x^3 - 2 overflows for large inputs.
+    let cubic =
+        Polynomial::<X, I8<8, 0>, I32<32, 0>, 4>::new([-2, 0, 0, 1].map(|c| I8::new(c).unwrap()));
+    assert_eq!(cubic.apply(x(3.)), Ok(I32::new(25).unwrap()));
+    assert_eq!(cubic.apply(x(-3.)), Ok(I32::new(-29).unwrap()));
+    assert_eq!(cubic.apply(x(2047.)), Err(RangeError::TooLarge));
+    assert_eq!(cubic.apply(x(-2047.)), Err(RangeError::TooSmall));
+}
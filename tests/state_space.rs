@@ -0,0 +1,120 @@
+#![allow(incomplete_features)]
+#![feature(generic_const_exprs)]
+
+use fp::*;
+
+type Coef = I16<16, 14>;
+type Input = I16<16, 8>;
+type State = I32<32, 16>;
+
+fn coef(val: f64) -> Coef {
+    Coef::from_f64(val).unwrap()
+}
+
+#[test]
+fn lowpass() {
+    let mut model = StateSpace::<_, _, _, _, _, Input, 1, 1, 1>::new(
+        [[coef(0.75)]],
+        [[coef(0.25)]],
+        [[coef(1.)]],
+        [[coef(0.)]],
+        [State::new(0).unwrap()],
+    )
+    .unwrap();
+    let u = [Input::from_f64(-100.).unwrap()];
+    let mut expected = 0f64;
+    for _ in 0..100 {
+        let [y]: [I32<32, 16>; 1] = model.update(&u).unwrap();
+        assert!((y.into_f64() - expected).abs() <= 1e-4);
+        expected = 0.75 * expected - 25.;
+    }
+    assert!((model.state()[0].into_f64() + 100.).abs() <= 0.5f64.powi(14));
+    model.set_state([State::new(1 << 16).unwrap()]);
+    let [y]: [I8<8, 0>; 1] = model.output(&u).unwrap();
+    assert_eq!(y.raw(), 1);
+}
+
+#[test]
+fn oscillator() {
+    // A rotation by 30° damped by 0.6, with one input, and the outputs x and x + u.
+    let (cos, sin) = (
+        coef(0.6 * 3f64.sqrt() / 2.).into_f64(),
+        coef(0.6 * 0.5).into_f64(),
+    );
+    let gain = coef(0.09).into_f64();
+    let mut model = StateSpace::<_, _, _, _, _, Input, 2, 1, 2>::new(
+        [[coef(cos), coef(-sin)], [coef(sin), coef(cos)]],
+        [[coef(0.)], [coef(0.09)]],
+        [[coef(1.), coef(0.)], [coef(1.), coef(0.)]],
+        [[coef(0.)], [coef(1.)]],
+        [State::from_f64(1000.).unwrap(), State::new(0).unwrap()],
+    )
+    .unwrap();
+    let (mut x, mut y) = (1000f64, 0f64);
+    for k in 0..50 {
+        let u = if k % 7 == 0 { 127.5 } else { -3.25 };
+        let input = [Input::from_f64(u).unwrap()];
+        let [first, second]: [I32<24, 12>; 2] = model.update(&input).unwrap();
+        assert!((first.into_f64() - x).abs() <= 1e-3, "{k}");
+        assert!((second.into_f64() - (x + u)).abs() <= 1e-3, "{k}");
+        (x, y) = (cos * x - sin * y, sin * x + cos * y + gain * u);
+    }
+    // The output overflows a narrow type, which leaves the state unchanged.
+    model.set_state([State::from_f64(200.).unwrap(), State::new(0).unwrap()]);
+    let input = [Input::from_f64(0.).unwrap()];
+    assert_eq!(model.update::<I8<8, 0>>(&input), Err(RangeError::TooLarge));
+    assert_eq!(model.state()[0].into_f64(), 200.);
+}
+
+#[test]
+fn headroom() {
+    let zero = [State::new(0).unwrap()];
+    let model = |a: f64, b: f64| {
+        StateSpace::<_, _, _, _, _, Input, 1, 1, 1>::new(
+            [[coef(a)]],
+            [[coef(b)]],
+            [[coef(1.)]],
+            [[coef(0.)]],
+            zero,
+        )
+        .is_ok()
+    };
+    assert!(model(0.75, 0.25));
+    assert!(model(-0.5, 1.));
+    assert!(model(0.99, 1.));
+    // A pure integrator, and a leaky one whose state can grow beyond the range of `State`.
+    assert!(!model(1., 0.5));
+    assert!(!model(0.999, 1.));
+    // The state has exactly enough headroom for a unit-gain lowpass at the full range of
+    // `Input`, but not for a lowpass with a gain of 1.0001.
+    assert!(StateSpace::<_, _, _, _, I16<16, 8>, Input, 1, 1, 1>::new(
+        [[coef(0.75)]],
+        [[coef(0.25)]],
+        [[coef(1.)]],
+        [[coef(0.)]],
+        [I16::new(0).unwrap()],
+    )
+    .is_err());
+    // An unsigned state must not be driven negative, by `A` or by a signed input.
+    let unsigned = |a: f64, b: f64| {
+        StateSpace::<_, _, _, _, U16<8, 0>, U16<4, 0>, 1, 1, 1>::new(
+            [[coef(a)]],
+            [[coef(b)]],
+            [[coef(1.)]],
+            [[coef(0.)]],
+            [U16::new(0).unwrap()],
+        )
+        .err()
+    };
+    assert_eq!(unsigned(0.5, 1.), None);
+    assert_eq!(unsigned(-0.5, 1.), Some(RangeError::TooSmall));
+    assert_eq!(unsigned(0.5, -0.25), Some(RangeError::TooSmall));
+    let signed_input = StateSpace::<_, _, _, _, U16<8, 0>, I16<4, 0>, 1, 1, 1>::new(
+        [[coef(0.5)]],
+        [[coef(1.)]],
+        [[coef(1.)]],
+        [[coef(0.)]],
+        [U16::new(0).unwrap()],
+    );
+    assert_eq!(signed_input.err(), Some(RangeError::TooSmall));
+}
@@ -0,0 +1,108 @@
+#![allow(incomplete_features)]
+#![feature(generic_const_exprs)]
+
+use fp::*;
+
+type Q8 = I64<24, 8>;
+
+fn q8(raw: i64) -> Tracked<Q8> {
+    Tracked::new(Q8::new(raw).unwrap())
+}
+
+#[test]
+fn values() {
+    let (a, b) = (q8(300), q8(-44));
+    let sum: Tracked<I64<25, 8>> = a + b;
+    assert_eq!(sum.get().raw(), 256);
+    assert_eq!((a - b).get().raw(), 344);
+    assert_eq!((-b).get().raw(), 44);
+    let product: Tracked<I64<48, 16>> = a * b;
+    assert_eq!(product.get().raw(), -13200);
+    let quotient: Tracked<I64<33, 8>> = a.div_to(b);
+    assert_eq!(quotient.get().raw(), -1745);
+    let shifted: Tracked<I64<20, 4>> = a.raw_shr::<4>();
+    assert_eq!(shifted.get().raw(), 18);
+    let rounded: Tracked<I64<21, 4>> = a.raw_shr_round::<4>(Rounding::Nearest);
+    assert_eq!(rounded.get().raw(), 19);
+    let wide: Tracked<I64<26, 10>> = a.raw_shl::<2>();
+    assert_eq!(wide.get().raw(), 1200);
+    assert_eq!(
+        a.try_requantize::<I64<5, 4>>(Rounding::Nearest),
+        Err(RangeError::TooLarge)
+    );
+}
+
+#[cfg(not(feature = "tracked"))]
+#[test]
+fn zero_cost() {
+    assert_eq!(
+        core::mem::size_of::<Tracked<Q8>>(),
+        core::mem::size_of::<Q8>()
+    );
+}
+
+#[cfg(feature = "tracked")]
+#[test]
+fn error_bounds() {
+    let (a, b) = (q8(300), q8(-44));
+    // Exact operations have no error.
+    assert_eq!((a * b + a * a).error_bound(), 0.0);
+    assert_eq!(a.raw_shl::<2>().error_bound(), 0.0);
+    // Rounding adds its error, in LSBs of the result.
+    assert_eq!(a.raw_shr::<4>().error_bound(), 15.0 / 16.0);
+    let rounded = a.raw_shr_round::<4>(Rounding::NearestEven);
+    assert_eq!(rounded.error_bound(), 0.5);
+    assert_eq!(a.div_to::<I64<33, 8>, _>(b).error_bound(), 1.0);
+    let rounded: Tracked<I64<24, 6>> = a.try_requantize(Rounding::Floor).unwrap();
+    assert_eq!(rounded.error_bound(), 0.75);
+    // Errors propagate through exact operations.
+    let x = Tracked::with_error(Q8::new(768).unwrap(), 0.5);
+    assert_eq!(x.error_bound(), 0.5);
+    assert_eq!((x + x - a).error_bound(), 1.0);
+    assert_eq!((-x).error_bound(), 0.5);
+    // |b| * 0.5 LSB of x = 44 / 256 * 0.5 / 256 = 44 * 128 LSBs of the product.
+    assert_eq!((x * b).error_bound(), 44.0 * 128.0 / 256.0);
+    assert_eq!(
+        (x * x).error_bound(),
+        (3.0 * 0.5 + (3.0 + 0.5 / 256.0) * 0.5) * 256.0
+    );
+    // Division by a value whose bound includes zero has no bound.
+    let tiny = Tracked::with_error(Q8::new(1).unwrap(), 1.0);
+    assert_eq!(a.div_to::<I64<33, 8>, _>(tiny).error_bound(), f64::INFINITY);
+}
+
+#[cfg(feature = "tracked")]
+#[test]
+fn filter_meets_bound() {
+    // A first-order low-pass filter `y += g * (x - y)`, rounded back to Q8 at each step.
+    let g = Tracked::new(I64::<16, 15>::new(3277).unwrap());
+    let mut y = q8(0);
+    let mut exact = 0.0;
+    for n in 0..200 {
+        let x = q8(((n * 7919) % 2000) - 1000);
+        let step: Tracked<Q8> = (g * (x - y)).try_requantize(Rounding::Nearest).unwrap();
+        y = (y + step).try_requantize(Rounding::Nearest).unwrap();
+        exact += 3277.0 / 32768.0 * (x.get().into_f64() - exact);
+        let error = (y.get().into_f64() - exact).abs() * 256.0;
+        assert!(error <= y.error_bound(), "{n} {error} {}", y.error_bound());
+    }
+    // The bound grows much faster than the actual error, since it treats the errors of `y`
+    // in both terms of the update as independent, but it is still a bound.
+    assert!(y.error_bound() >= 100.0);
+}
+
+#[test]
+fn wide_values() {
+    // Values wider than the mantissa of `f64` are fine, with or without the bound.
+    let a = Tracked::with_error(I64::<60, 30>::MAX, 0.5);
+    let b = Tracked::new(I64::<2, 0>::new(-2).unwrap());
+    let product: Tracked<I64<62, 30>> = a * b;
+    assert_eq!(product.get().raw(), -2 * I64::<60, 30>::MAX.raw());
+    let quotient: Tracked<I64<62, 30>> = a.div_to(b);
+    assert_eq!(quotient.get().raw(), -(I64::<60, 30>::MAX.raw() / 2));
+    #[cfg(feature = "tracked")]
+    {
+        assert_eq!(product.error_bound(), 1.0);
+        assert_eq!(quotient.error_bound(), 1.25);
+    }
+}
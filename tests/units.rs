@@ -0,0 +1,52 @@
+#![allow(incomplete_features)]
+#![feature(generic_const_exprs)]
+
+use fp::*;
+
+#[test]
+fn multiply_and_divide_compose_dimensions() {
+    let current = Quantity::<I32<12, 8>, Amperes>::new(I32::new(0x180).unwrap());
+    let resistance = Quantity::<I32<12, 4>, Ohms>::new(I32::new(0x40).unwrap());
+    let voltage: Quantity<I32<24, 12>, Volts> = current * resistance;
+    assert_eq!(voltage.get().raw(), 0x180 * 0x40);
+    let power: Quantity<I64<36, 20>, Watts> =
+        Quantity::<_, Volts>::new(I64::<24, 12>::from(voltage.get()))
+            * Quantity::<_, Amperes>::new(I64::<12, 8>::from(current.get()));
+    assert_eq!(power.get().raw(), 0x180 * 0x40 * 0x180);
+
+    let distance = Quantity::<U16<10, 2>, Metres>::new(U16::new(400).unwrap());
+    let time = Quantity::<U16<8, 0>, Seconds>::new(U16::new(20).unwrap());
+    let velocity: Quantity<U16<10, 2>, MetresPerSecond> = distance / time;
+    assert_eq!(velocity.get().raw(), 20);
+    let ratio: Quantity<U16<10, 0>, Dimensionless> = distance / distance;
+    assert_eq!(ratio.get().raw(), 1);
+    let frequency: Quantity<U16<8, 0>, Hertz> =
+        Quantity::<U16<8, 0>, Dimensionless>::new(U16::new(1).unwrap()) / time;
+    assert_eq!(frequency.get().raw(), 0);
+}
+
+#[test]
+fn add_and_subtract_same_dimension() {
+    let a = Quantity::<I16<8, 4>, Volts>::new(I16::new(100).unwrap());
+    let b = Quantity::<I16<10, 4>, Volts>::new(I16::new(-300).unwrap());
+    let sum: Quantity<I16<11, 4>, Volts> = a + b;
+    assert_eq!(sum.get().raw(), -200);
+    let difference: Quantity<I16<11, 4>, Volts> = a - b;
+    assert_eq!(difference.get().raw(), 400);
+    let negated: Quantity<I16<9, 4>, Volts> = -a;
+    assert_eq!(negated.get().raw(), -100);
+    assert!(a < Quantity::new(I16::new(101).unwrap()));
+    assert_eq!(Quantity::<I16<8, 4>, Volts>::default().get().raw(), 0);
+}
+
+#[test]
+fn plain_primitives() {
+    let work: Quantity<f64, Joules> =
+        Quantity::<f64, Newtons>::new(2.5) * Quantity::<f64, Metres>::new(4.);
+    assert_eq!(work.get(), 10.);
+    let charge: Quantity<i32, Coulombs> =
+        Quantity::<i32, Amperes>::new(3) * Quantity::<i32, Seconds>::new(7);
+    assert_eq!(charge.get(), 21);
+    let mass: Quantity<i32, Kilograms> = Quantity::new(5);
+    assert_eq!((mass + mass).get(), 10);
+}
@@ -0,0 +1,34 @@
+#![allow(incomplete_features)]
+#![feature(generic_const_exprs)]
+#![cfg(feature = "uom")]
+
+use fp::*;
+use uom::si::electric_potential::millivolt;
+use uom::si::f64::{ElectricPotential, Length};
+use uom::si::length::{kilometer, meter};
+
+#[test]
+fn into_uom() {
+    let voltage = Quantity::<I32<20, 8>, Volts>::new(I32::new(-0x380).unwrap());
+    let uom: ElectricPotential = voltage.into_uom();
+    assert_eq!(uom.get::<millivolt>(), -3500.);
+    let distance = Quantity::<U32<32, -10>, Metres>::new(U32::new(3).unwrap());
+    assert_eq!(distance.into_uom().get::<meter>(), 3072.);
+}
+
+#[test]
+fn try_from_uom() {
+    let uom = ElectricPotential::new::<millivolt>(-3500.);
+    let voltage = Quantity::<I32<20, 8>, Volts>::try_from_uom(uom).unwrap();
+    assert_eq!(voltage.get().raw(), -0x380);
+    let distance = Quantity::<U16<16, 2>, Metres>::try_from_uom(Length::new::<kilometer>(20.));
+    assert_eq!(distance, Err(RangeError::TooLarge));
+    let distance = Quantity::<U16<16, 2>, Metres>::try_from_uom(Length::new::<meter>(0.3));
+    assert_eq!(distance.unwrap().get().raw(), 1);
+    let distance = Quantity::<U16<16, 2>, Metres>::try_from_uom(Length::new::<meter>(-1.));
+    assert_eq!(distance, Err(RangeError::TooSmall));
+    for raw in i8::MIN..=i8::MAX {
+        let ratio = Quantity::<I8<8, 3>, Dimensionless>::new(I8::new(raw).unwrap());
+        assert_eq!(Quantity::try_from_uom(ratio.into_uom()), Ok(ratio));
+    }
+}
@@ -0,0 +1,74 @@
+#![allow(incomplete_features)]
+#![feature(generic_const_exprs)]
+
+use fp::*;
+
+fn v2(x: i32, y: i32) -> Vec2<I32<8, 4>> {
+    Vec2::new(I32::new(x).unwrap(), I32::new(y).unwrap())
+}
+
+fn v3(x: i32, y: i32, z: i32) -> Vec3<I32<8, 4>> {
+    Vec3::new(
+        I32::new(x).unwrap(),
+        I32::new(y).unwrap(),
+        I32::new(z).unwrap(),
+    )
+}
+
+#[test]
+fn elementwise() {
+    let sum: Vec2<I32<9, 4>> = v2(127, -128) + v2(1, -1);
+    assert_eq!((sum.x().raw(), sum.y().raw()), (128, -129));
+    let diff: Vec3<I32<9, 4>> = v3(-128, 0, 5) - v3(127, 1, 5);
+    assert_eq!(
+        (diff.x().raw(), diff.y().raw(), diff.z().raw()),
+        (-255, -1, 0)
+    );
+    let neg: Vec3<I32<9, 4>> = -v3(-128, 3, 0);
+    assert_eq!((neg.x().raw(), neg.y().raw(), neg.z().raw()), (128, -3, 0));
+    let scaled: Vec2<I32<12, 6>> = v2(-128, 7) * I32::<4, 2>::new(-8).unwrap();
+    assert_eq!((scaled.x().raw(), scaled.y().raw()), (1024, -56));
+}
+
+#[test]
+fn dot_cross() {
+    let dot: I32<17, 8> = v2(3, -4).dot(v2(5, 6));
+    assert_eq!(dot.raw(), -9);
+    let cross: I32<17, 8> = v2(3, -4).cross(v2(5, 6));
+    assert_eq!(cross.raw(), 38);
+    // The largest results need every bit.
+    assert_eq!(v2(-128, -128).dot(v2(-128, -128)).raw(), 1 << 15);
+    let dot: I32<18, 8> = v3(-128, -128, -128).dot(v3(-128, -128, -128));
+    assert_eq!(dot.raw(), 3 << 14);
+    // x cross y = z
+    let z: Vec3<I32<17, 8>> = v3(16, 0, 0).cross(v3(0, 16, 0));
+    assert_eq!((z.x().raw(), z.y().raw(), z.z().raw()), (0, 0, 256));
+    let a = v3(1, -2, 3);
+    let b = v3(-4, 5, 6);
+    let c = a.cross(b);
+    assert_eq!((c.x().raw(), c.y().raw(), c.z().raw()), (-27, -18, -3));
+    // The cross product is orthogonal to both inputs.
+    let dot: I32<27, 12> = a.dot(c);
+    assert_eq!(dot.raw(), 0);
+    assert_eq!(b.dot(c).raw(), 0);
+}
+
+#[test]
+fn norm() {
+    let norm_sqr: U32<16, 8> = v2(-128, -128).norm_sqr();
+    assert_eq!(norm_sqr.raw(), 1 << 15);
+    let norm: I32<9, 4> = v2(3, -4).norm();
+    assert_eq!(norm.raw(), 5);
+    assert_eq!(v3(2, -3, 6).norm().raw(), 7);
+    assert_eq!(v3(-128, -128, -128).norm_sqr().raw(), 3 << 14);
+    // sqrt(3) * 128 = 221.7
+    assert_eq!(v3(-128, -128, -128).norm().raw(), 221);
+    for x in -128..=127 {
+        for y in [-128, -37, 0, 99, 127] {
+            let z = 3 * x / 4;
+            let expected = (f64::from(x * x + y * y + z * z)).sqrt().floor();
+            assert_eq!(f64::from(v3(x, y, z).norm().raw()), expected);
+            assert_eq!(v3(x, y, z).norm_sqr().raw(), (x * x + y * y + z * z) as u32);
+        }
+    }
+}
@@ -0,0 +1,116 @@
+#![allow(incomplete_features)]
+#![feature(generic_const_exprs)]
+
+use fp::*;
+
+#[test]
+fn limits() {
+    assert_eq!(I256::<256, 0>::MIN.raw(), i256::MIN);
+    assert_eq!(U256::<256, 0>::MAX.raw(), u256::MAX);
+    assert_eq!(
+        I256::<200, 0>::MAX.raw().limbs(),
+        ((1 << 71) - 1, u128::MAX)
+    );
+    assert_eq!(I256::<200, 0>::MIN.raw().limbs(), (-1 << 71, 0));
+    assert_eq!(U256::<100, 0>::MAX.raw().limbs(), (0, (1 << 100) - 1));
+    assert!(I256::<200, 0>::new(i256::MAX).is_err());
+}
+
+#[test]
+fn arithmetic() {
+    let a = I256::<128, 64>::from(I128::<128, 64>::MAX);
+    let b = I256::<128, 64>::from(I128::<128, 64>::MIN);
+    // The full product of two 128-bit numbers.
+    let product: I256<256, 128> = a * b;
+    let expected = i256::from(i128::MAX) * i256::from(i128::MIN);
+    assert_eq!(product.raw(), expected);
+    assert_eq!(expected.limbs(), (-1 << 126, 1 << 127));
+    let sum: I256<129, 64> = a + a;
+    assert_eq!(sum.raw().limbs(), (0, u128::MAX - 1));
+    let diff: I256<129, 64> = b - a;
+    assert_eq!(diff.raw().limbs(), (-1, 1));
+    let neg: I256<129, 64> = -b;
+    assert_eq!(neg.raw().limbs(), (0, 1 << 127));
+    let u = U256::<128, 0>::from(U128::<128, 0>::MAX);
+    let square: U256<256, 0> = u * u;
+    assert_eq!(square.raw().limbs(), (u128::MAX - 1, 1));
+    let diff: I256<129, 0> = U256::<128, 0>::ZERO - u;
+    assert_eq!(diff.raw(), i256::ZERO - i256::from_limbs(0, u128::MAX));
+    // The high limbs overflow in an intermediate step, but not in the result.
+    let sum = i256::from_limbs(i128::MIN, 1) + i256::from_limbs(-1, u128::MAX);
+    assert_eq!(sum, i256::MIN);
+    let diff = i256::from_limbs(i128::MAX, 0) - i256::from_limbs(-1, 1);
+    assert_eq!(diff, i256::MAX);
+    assert_eq!(u256::MAX >> 200, u256::from_limbs(0, (1 << 56) - 1));
+    assert_eq!(i256::MIN >> 255, i256::from(-1));
+    assert_eq!(i256::from(1) << 255, i256::MIN);
+}
+
+#[test]
+fn float() {
+    let x = I256::<50, -150>::from_f64(-3.25 * 2f64.powi(160)).unwrap();
+    assert_eq!(x.raw(), i256::from(-13 << 8));
+    assert!(I256::<50, -150>::from_f64(1e70).is_err());
+    let y = I256::<40, 20>::from_f64(-1234.5).unwrap();
+    assert_eq!(y.into_f64(), -1234.5);
+    assert_eq!(U256::<24, 8>::from_f32(100.5).unwrap().into_f32(), 100.5);
+
+    // Types wider than the mantissa of the float.
+    assert_eq!(I256::<200, 0>::from_f64(1.0).unwrap().raw(), i256::from(1));
+    let x = I256::<200, 10>::from_f64(-3.75 * 2f64.powi(150)).unwrap();
+    assert_eq!(x.raw(), i256::from(-15) << 158);
+    assert_eq!(U256::<256, 0>::from_f32(-0.5), Err(RangeError::TooSmall));
+    assert_eq!(I256::<256, 0>::from_f32(-0.5).unwrap().raw(), i256::from(0));
+    let x = U256::<256, 0>::from_f64(2f64.powi(255)).unwrap();
+    assert_eq!(x.raw(), u256::from(1) << 255);
+    let x = I256::<256, 0>::from_f64(-(2f64.powi(255))).unwrap();
+    assert_eq!(x.raw(), i256::MIN);
+    assert_eq!(
+        I256::<256, 0>::from_f64(2f64.powi(255)),
+        Err(RangeError::TooLarge)
+    );
+    assert_eq!(
+        U256::<256, 0>::from_f64(2f64.powi(256)),
+        Err(RangeError::TooLarge)
+    );
+    assert_eq!(U256::<200, 0>::from_f64(-1.0), Err(RangeError::TooSmall));
+    assert_eq!(
+        I256::<200, 0>::from_f64(2f64.powi(199)),
+        Err(RangeError::TooLarge)
+    );
+    // A value which truncates to `MAX` but lies beyond it is out of range.
+    let max = 2f64.powi(199) - 2f64.powi(146);
+    assert_eq!(
+        I256::<200, 0>::from_f64(max).unwrap().raw(),
+        (i256::from(1) << 199) - (i256::from(1) << 146)
+    );
+    assert_eq!(I256::<2, 0>::from_f64(1.5), Err(RangeError::TooLarge));
+    assert_eq!(I256::<2, 0>::from_f64(-1.5).unwrap().raw(), i256::from(-1));
+    assert_eq!(i256::from_f64(-1e300), Err(RangeError::TooSmall));
+    assert_eq!(u256::from_f32(3.9).unwrap(), u256::from(3));
+    assert_eq!(
+        I256::<100, 0>::from_f64(1.0),
+        I256::<100, 0>::new(i256::from(1))
+    );
+    assert_eq!(
+        I256::<100, { i32::MIN }>::from_f64(1.0).unwrap().raw(),
+        i256::from(0)
+    );
+}
+
+/// Return the bit counts of `val` through `Num`, as generic code sees them.
+fn bit_counts<T: Num>(val: T) -> (u32, u32, u32) {
+    (val.leading_zeros(), val.trailing_zeros(), val.count_ones())
+}
+
+#[test]
+fn bit_inspection() {
+    assert_eq!(bit_counts(I16::<12, 4>::new(0x28).unwrap()), (10, 3, 2));
+    assert_eq!(bit_counts(I16::<12, 4>::new(-1).unwrap()), (0, 0, 16));
+    assert_eq!(bit_counts(U8::<3, 0>::ZERO), (8, 8, 0));
+    assert_eq!(bit_counts(0x8000_0000_0000_0000_0000_0000_0000_0000u128), (0, 127, 1));
+    assert_eq!(bit_counts(u256::from_limbs(0, 0)), (256, 256, 0));
+    assert_eq!(bit_counts(u256::from_limbs(6, 0)), (125, 129, 2));
+    assert_eq!(bit_counts(i256::from_limbs(-1, 1 << 40)), (0, 40, 129));
+    assert_eq!(bit_counts(I256::<200, 0>::new(i256::from_limbs(0, 5)).unwrap()), (253, 0, 2));
+}